@@ -0,0 +1,66 @@
+//! 构建时从 Cargo.lock 和本地 crates.io 缓存里整理参与构建的第三方库的许可证，
+//! 生成 `$OUT_DIR/licenses.txt`（每行“名称\t版本\t许可证”），供“关于”页面显示。
+
+use std::path::{Path, PathBuf};
+
+fn main() {
+    println!("cargo:rerun-if-changed=Cargo.lock");
+    let lock = std::fs::read_to_string("Cargo.lock").unwrap_or_default();
+    let sources = registry_sources();
+    let mut lines = Vec::new();
+    for block in lock.split("[[package]]").skip(1) {
+        let field = |key: &str| {
+            block.lines().find_map(|line| {
+                let value = line.strip_prefix(key)?.trim_start().strip_prefix('=')?;
+                Some(value.trim().trim_matches('"').to_string())
+            })
+        };
+        let (Some(name), Some(version)) = (field("name"), field("version")) else {
+            continue;
+        };
+        // 没有 source 的是本仓库自己的包
+        if field("source").is_none() {
+            continue;
+        }
+        // 缓存里没有源码的库没参与这次构建（别的平台或没开的特性），不列出
+        let Some(manifest) = sources
+            .iter()
+            .map(|dir| dir.join(format!("{}-{}", name, version)).join("Cargo.toml"))
+            .find(|manifest| manifest.exists())
+        else {
+            continue;
+        };
+        let license = manifest_license(&manifest).unwrap_or_else(|| "见随附许可证文件".to_string());
+        lines.push(format!("{}\t{}\t{}", name, version, license));
+    }
+    let out = PathBuf::from(std::env::var("OUT_DIR").expect("构建脚本需要 OUT_DIR"));
+    std::fs::write(out.join("licenses.txt"), lines.join("\n")).expect("写入许可证清单失败");
+}
+
+/// crates.io 源码缓存目录（$CARGO_HOME/registry/src/*）
+fn registry_sources() -> Vec<PathBuf> {
+    let home = std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".cargo")));
+    let Some(Ok(entries)) = home.map(|home| std::fs::read_dir(home.join("registry").join("src"))) else {
+        return Vec::new();
+    };
+    entries.flatten().map(|entry| entry.path()).collect()
+}
+
+/// 读出清单里 [package] 段的 license 字段
+fn manifest_license(manifest: &Path) -> Option<String> {
+    let text = std::fs::read_to_string(manifest).ok()?;
+    let mut in_package = false;
+    for line in text.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_package = line == "[package]";
+        } else if in_package {
+            if let Some(value) = line.strip_prefix("license").and_then(|rest| rest.trim_start().strip_prefix('=')) {
+                return Some(value.trim().trim_matches('"').to_string());
+            }
+        }
+    }
+    None
+}
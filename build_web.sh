@@ -28,6 +28,11 @@ wasm-bindgen target/wasm32-unknown-unknown/release/xiuxian_game.wasm \
     --target web \
     --no-typescript
 
+# 字体与内容数据不编进 wasm，作为单独的资源在启动后下载
+echo "📚 复制内容数据..."
+mkdir -p web/content
+cp content/*.json web/content/
+
 # 优化 WASM 大小（可选，需要安装 wasm-opt）
 if command -v wasm-opt &> /dev/null; then
     echo "⚡ 优化 WASM 体积..."
@@ -40,6 +45,8 @@ echo ""
 echo "📁 输出文件在 web/ 目录:"
 ls -lh web/
 echo ""
+echo "📏 查看体积报告: ./size_report.sh"
+echo ""
 echo "🚀 本地测试方法:"
 echo "   cd web && python3 -m http.server 8080"
 echo "   然后打开浏览器访问 http://localhost:8080"
@@ -0,0 +1,278 @@
+//! 成就：跨局累计的里程碑（连续高压存活、屡败屡战终于晋升、技能破千……）。
+//! 条件都从对局状态里算，前端每次从总线取到事件就检查一遍，解锁后记在档案里，不随对局重置。
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::companion::COMPANION_SLOTS;
+use crate::finance::Housing;
+use crate::game::{GameState, HistoryKind, PlayerState, Realm};
+
+/// 存储键
+pub const ACHIEVEMENTS_KEY: &str = "xiuxian_achievements";
+
+/// “满压力”的门槛（压力上限 100，真到 100 基本活不过当晚）
+pub const FULL_PRESSURE: i32 = 90;
+
+/// 全部成就
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Achievement {
+    初入仙途,
+    引气入体,
+    筑基有成,
+    金丹大道,
+    化神飞升,
+    百日筑基,
+    技能破千,
+    刀尖舔血,
+    屡败屡战,
+    佛系修仙,
+    猝死于安逸,
+    压力山大,
+    小有积蓄,
+    有房一族,
+    良师益友,
+    同道中人,
+    有求必应,
+    铁面无私,
+    人缘极佳,
+    一身两用,
+    学无止境,
+    开源之星,
+    周周有戏,
+    灰色地带,
+    功德圆满,
+    飞升再修,
+}
+
+impl fmt::Display for Achievement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 26] = [
+        Achievement::初入仙途,
+        Achievement::引气入体,
+        Achievement::筑基有成,
+        Achievement::金丹大道,
+        Achievement::化神飞升,
+        Achievement::百日筑基,
+        Achievement::技能破千,
+        Achievement::刀尖舔血,
+        Achievement::屡败屡战,
+        Achievement::佛系修仙,
+        Achievement::猝死于安逸,
+        Achievement::压力山大,
+        Achievement::小有积蓄,
+        Achievement::有房一族,
+        Achievement::良师益友,
+        Achievement::同道中人,
+        Achievement::有求必应,
+        Achievement::铁面无私,
+        Achievement::人缘极佳,
+        Achievement::一身两用,
+        Achievement::学无止境,
+        Achievement::开源之星,
+        Achievement::周周有戏,
+        Achievement::灰色地带,
+        Achievement::功德圆满,
+        Achievement::飞升再修,
+    ];
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Achievement::初入仙途 => "🐣",
+            Achievement::引气入体 | Achievement::筑基有成 | Achievement::金丹大道 => "⬆",
+            Achievement::化神飞升 => "🌈",
+            Achievement::百日筑基 => "📅",
+            Achievement::技能破千 => "💻",
+            Achievement::刀尖舔血 => "🔥",
+            Achievement::屡败屡战 => "💪",
+            Achievement::佛系修仙 => "🍵",
+            Achievement::猝死于安逸 => "💤",
+            Achievement::压力山大 => "💀",
+            Achievement::小有积蓄 => "💰",
+            Achievement::有房一族 => "🏠",
+            Achievement::良师益友 => "🎁",
+            Achievement::同道中人 => "🤝",
+            Achievement::有求必应 => "🙆",
+            Achievement::铁面无私 => "🙅",
+            Achievement::人缘极佳 => "💬",
+            Achievement::一身两用 => "👥",
+            Achievement::学无止境 => "🎓",
+            Achievement::开源之星 => "⭐",
+            Achievement::周周有戏 => "📰",
+            Achievement::灰色地带 => "🕶",
+            Achievement::功德圆满 => "🏆",
+            Achievement::飞升再修 => "☯",
+        }
+    }
+
+    /// 解锁条件
+    pub fn description(&self) -> &'static str {
+        match self {
+            Achievement::初入仙途 => "做出第一次选择",
+            Achievement::引气入体 => "晋升到炼气期",
+            Achievement::筑基有成 => "晋升到筑基期",
+            Achievement::金丹大道 => "晋升到结丹期",
+            Achievement::化神飞升 => "晋升到化神期",
+            Achievement::百日筑基 => "一局活过第 100 天",
+            Achievement::技能破千 => "技能点达到 1000",
+            Achievement::刀尖舔血 => "连续 7 天压力在 90 以上还活着",
+            Achievement::屡败屡战 => "晋升失败 5 次之后终于晋升成功",
+            Achievement::佛系修仙 => "连续 5 天零压力",
+            Achievement::猝死于安逸 => "因为压力长期为零而猝死",
+            Achievement::压力山大 => "压力满 100",
+            Achievement::小有积蓄 => "存款达到 20000 元",
+            Achievement::有房一族 => "买房",
+            Achievement::良师益友 => "走完一位同事的专属剧情，拿到信物",
+            Achievement::同道中人 => "同伴栏位全部坐满",
+            Achievement::有求必应 => "一局内答应同事的请求 20 次",
+            Achievement::铁面无私 => "一局内拒绝同事的请求 20 次",
+            Achievement::人缘极佳 => "一局内和同事打交道 30 次",
+            Achievement::一身两用 => "修出分身",
+            Achievement::学无止境 => "进修结业，获得永久加成",
+            Achievement::开源之星 => "开源项目收获 1000 颗星",
+            Achievement::周周有戏 => "一局内经历 10 次周事件",
+            Achievement::灰色地带 => "做了灰色操作被内审",
+            Achievement::功德圆满 => "活着通关",
+            Achievement::飞升再修 => "在飞升等级 1 以上通关",
+        }
+    }
+
+    /// 当前对局是否满足条件（`run_over` 为本局已经结束）
+    pub fn is_met(&self, state: &GameState, run_over: bool) -> bool {
+        let player = state.main_player();
+        let realm = Realm::from_level(player.realm_level);
+        let count = |kind: HistoryKind| player.history.iter().filter(|e| e.kind == kind).count();
+        let dealings = player.npc_dealings();
+        match self {
+            Achievement::初入仙途 => player.history.iter().any(|e| e.kind.is_choice()),
+            Achievement::引气入体 => realm >= Realm::炼气期,
+            Achievement::筑基有成 => realm >= Realm::筑基期,
+            Achievement::金丹大道 => realm >= Realm::结丹期,
+            Achievement::化神飞升 => realm >= Realm::化神期,
+            Achievement::百日筑基 => player.is_alive && state.current_day > 100,
+            Achievement::技能破千 => player.skills >= 1000,
+            Achievement::刀尖舔血 => player.is_alive && full_pressure_streak(player) >= 7,
+            Achievement::屡败屡战 => comeback(player),
+            Achievement::佛系修仙 => player.zero_pressure_streak >= 5,
+            Achievement::猝死于安逸 => player.died_from_zero_pressure,
+            Achievement::压力山大 => player.pressure >= 100,
+            Achievement::小有积蓄 => player.savings >= 20_000,
+            Achievement::有房一族 => player.housing == Housing::买房,
+            Achievement::良师益友 => !player.keepsakes.is_empty(),
+            Achievement::同道中人 => state.companions.len() >= COMPANION_SLOTS,
+            Achievement::有求必应 => dealings.iter().map(|(_, accepts, _)| accepts).sum::<u32>() >= 20,
+            Achievement::铁面无私 => dealings.iter().map(|(_, _, rejects)| rejects).sum::<u32>() >= 20,
+            Achievement::人缘极佳 => count(HistoryKind::Npc) >= 30,
+            Achievement::一身两用 => state.alt.is_some(),
+            Achievement::学无止境 => !player.perks.is_empty(),
+            Achievement::开源之星 => player.open_source.stars >= 1000,
+            Achievement::周周有戏 => count(HistoryKind::周事件) >= 10,
+            Achievement::灰色地带 => state.audit.audits > 0,
+            Achievement::功德圆满 => state.is_victory(run_over),
+            Achievement::飞升再修 => state.ascension > 0 && state.is_victory(run_over),
+        }
+    }
+}
+
+/// 最近连续多少天（按每日快照）压力在满压力门槛以上
+fn full_pressure_streak(player: &PlayerState) -> usize {
+    player
+        .snapshots
+        .iter()
+        .rev()
+        .take_while(|snapshot| snapshot.pressure >= FULL_PRESSURE)
+        .count()
+}
+
+/// 某次晋升成功之前已经失败过至少 5 次
+fn comeback(player: &PlayerState) -> bool {
+    let mut failures = 0;
+    for entry in player.history.iter().filter(|e| e.kind == HistoryKind::晋升) {
+        if entry.choice.starts_with("晋升成功") {
+            if failures >= 5 {
+                return true;
+            }
+        } else {
+            failures += 1;
+        }
+    }
+    false
+}
+
+/// 解锁记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Unlock {
+    pub achievement: Achievement,
+    pub date: String,  // 解锁日期（本地日期，如 2024-05-01）
+}
+
+/// 档案里的成就进度（随档案落盘）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Achievements {
+    unlocked: Vec<Unlock>,
+}
+
+impl Achievements {
+    /// 解锁日期（还没解锁为 None）
+    pub fn unlocked_on(&self, achievement: Achievement) -> Option<&str> {
+        self.unlocked
+            .iter()
+            .find(|unlock| unlock.achievement == achievement)
+            .map(|unlock| unlock.date.as_str())
+    }
+
+    pub fn is_unlocked(&self, achievement: Achievement) -> bool {
+        self.unlocked_on(achievement).is_some()
+    }
+
+    pub fn count(&self) -> usize {
+        self.unlocked.len()
+    }
+
+    /// 按当前对局检查一遍，把新达成的记为 `date` 解锁，返回新解锁的成就
+    pub fn check(&mut self, state: &GameState, run_over: bool, date: &str) -> Vec<Achievement> {
+        let fresh: Vec<Achievement> = Achievement::ALL
+            .into_iter()
+            .filter(|a| !self.is_unlocked(*a) && a.is_met(state, run_over))
+            .collect();
+        self.unlocked.extend(fresh.iter().map(|a| Unlock { achievement: *a, date: date.to_string() }));
+        fresh
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{DailySnapshot, HistoryEntry};
+
+    #[test]
+    fn unlocks_once_from_run_state() {
+        let mut state = GameState::with_seed("测试".to_string(), 7);
+        let mut achievements = Achievements::default();
+        assert!(achievements.check(&state, false, "2024-05-01").is_empty());
+
+        let player = &mut state.player;
+        player.skills = 1000;
+        for day in 1..=7 {
+            player.snapshots.push(DailySnapshot { day, skills: 0, pressure: 95, experience: 0, pressure_momentum: 0 });
+        }
+        for _ in 0..5 {
+            player.history.push(HistoryEntry::promotion(false, Realm::凡人境, 0, ""));
+        }
+        player.history.push(HistoryEntry::promotion(true, Realm::炼气期, 0, ""));
+        player.realm_level = 2;
+        let fresh = achievements.check(&state, false, "2024-05-01");
+        for expected in [Achievement::技能破千, Achievement::刀尖舔血, Achievement::屡败屡战, Achievement::引气入体] {
+            assert!(fresh.contains(&expected), "{} 应该解锁", expected);
+        }
+        assert_eq!(achievements.unlocked_on(Achievement::技能破千), Some("2024-05-01"));
+        assert!(achievements.check(&state, false, "2024-05-02").is_empty());
+    }
+}
@@ -0,0 +1,106 @@
+//! 修仙黄历：每天一条“宜/忌”，由种子和天数决定。
+//! 当天日常事件选了沾边的选项会有一点点加成或惩罚，图个彩头。
+
+use std::fmt;
+
+use crate::mood::stable_hash;
+
+/// 黄历上的事项，用选项说明里的关键词判断是否沾边
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Activity {
+    重构,
+    上线,
+    加班,
+    摸鱼,
+    甩锅,
+    优化,
+    改需求,
+    修bug,
+}
+
+impl fmt::Display for Activity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Activity::重构 => "重构",
+            Activity::上线 => "上线",
+            Activity::加班 => "加班",
+            Activity::摸鱼 => "摸鱼",
+            Activity::甩锅 => "甩锅",
+            Activity::优化 => "优化",
+            Activity::改需求 => "改需求",
+            Activity::修bug => "修bug",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl Activity {
+    pub const ALL: [Activity; 8] = [
+        Activity::重构,
+        Activity::上线,
+        Activity::加班,
+        Activity::摸鱼,
+        Activity::甩锅,
+        Activity::优化,
+        Activity::改需求,
+        Activity::修bug,
+    ];
+
+    fn keywords(&self) -> &'static [&'static str] {
+        match self {
+            Activity::重构 => &["重构", "重写"],
+            Activity::上线 => &["上线", "发布", "部署"],
+            Activity::加班 => &["加班", "通宵", "熬夜"],
+            Activity::摸鱼 => &["摸鱼", "划水"],
+            Activity::甩锅 => &["甩锅", "推给"],
+            Activity::优化 => &["优化", "性能"],
+            Activity::改需求 => &["需求"],
+            Activity::修bug => &["bug", "Bug", "调试"],
+        }
+    }
+
+    fn matches(&self, desc: &str) -> bool {
+        self.keywords().iter().any(|k| desc.contains(k))
+    }
+}
+
+/// 某一天的黄历
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Almanac {
+    pub good: Activity,  // 宜
+    pub bad: Activity,   // 忌
+}
+
+impl Almanac {
+    /// 由种子和天数决定，宜忌不会是同一件事
+    pub fn on(seed: u64, day: u32) -> Self {
+        let hash = stable_hash(&[b"almanac", &seed.to_le_bytes(), &day.to_le_bytes()]);
+        let count = Activity::ALL.len() as u64;
+        let good = (hash % count) as usize;
+        let bad = (good + 1 + ((hash / count) % (count - 1)) as usize) % count as usize;
+        Almanac {
+            good: Activity::ALL[good],
+            bad: Activity::ALL[bad],
+        }
+    }
+
+    pub fn label(&self) -> String {
+        format!("📅 黄历 宜: {} / 忌: {}", self.good, self.bad)
+    }
+
+    /// 按黄历微调选项数值：顺应“宜”技能 +1、压力 -1，犯了“忌”压力 +2；
+    /// 有调整时一并返回说明
+    pub fn adjust(&self, desc: &str, (skill, pressure): (i32, i32)) -> ((i32, i32), Option<String>) {
+        let mut value = (skill, pressure);
+        let mut notes = Vec::new();
+        if self.good.matches(desc) {
+            value = (value.0 + 1, value.1 - 1);
+            notes.push(format!("黄历宜{}，顺风顺水（技能 +1，压力 -1）", self.good));
+        }
+        if self.bad.matches(desc) {
+            value.1 += 2;
+            notes.push(format!("黄历忌{}，偏偏犯了忌讳（压力 +2）", self.bad));
+        }
+        (value, (!notes.is_empty()).then(|| notes.join("；")))
+    }
+}
@@ -0,0 +1,113 @@
+//! 多步剧情线：由某个选择触发，之后每天按顺序出场一个专属日常事件，
+//! 每一步的选择累积“准备度”，走完最后一步按准备度结算
+
+use serde::{Deserialize, Serialize};
+
+/// 剧情线中的一步：出场的事件，以及各选项（按原始顺序）带来的准备度
+#[derive(Debug, Clone, Copy)]
+pub struct ArcStep {
+    pub event_id: usize,
+    pub preparation: [i32; 3],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StoryArc {
+    /// 遇见熟人劫里答应下来的技术分享
+    技术分享,
+}
+
+/// 技术分享的准备步骤：选题 → 做PPT → 彩排 → 上台
+const TALK_STEPS: [ArcStep; 4] = [
+    ArcStep { event_id: 95, preparation: [3, 2, 0] },
+    ArcStep { event_id: 96, preparation: [3, 1, 0] },
+    ArcStep { event_id: 97, preparation: [3, 2, 0] },
+    ArcStep { event_id: 98, preparation: [2, 1, 0] },
+];
+
+impl StoryArc {
+    /// 某个日常选择是否会开启剧情线（事件 id + 选项原始序号）
+    pub fn triggered_by(event_id: usize, original_index: u32) -> Option<StoryArc> {
+        match (event_id, original_index) {
+            (37, 0) => Some(StoryArc::技术分享),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StoryArc::技术分享 => "技术分享",
+        }
+    }
+
+    pub fn steps(&self) -> &'static [ArcStep] {
+        match self {
+            StoryArc::技术分享 => &TALK_STEPS,
+        }
+    }
+
+    /// 按准备度结算
+    pub fn conclude(&self, preparation: i32) -> ArcOutcome {
+        match self {
+            StoryArc::技术分享 => match preparation {
+                10.. => ArcOutcome {
+                    reputation: 30,
+                    gains_fan: true,
+                    message: "🎤 分享大获成功！台下掌声雷动，会后还有人追着你加微信。".to_string(),
+                },
+                7..=9 => ArcOutcome {
+                    reputation: 15,
+                    gains_fan: true,
+                    message: "🎤 分享顺利结束，有位听众说'讲得真清楚'，还关注了你。".to_string(),
+                },
+                _ => ArcOutcome {
+                    reputation: 3,
+                    gains_fan: false,
+                    message: "🎤 分享磕磕绊绊，提问环节冷场了五分钟。好歹露了个脸。".to_string(),
+                },
+            },
+        }
+    }
+}
+
+/// 剧情线结算结果
+#[derive(Debug, Clone)]
+pub struct ArcOutcome {
+    pub reputation: u32,
+    pub gains_fan: bool,
+    pub message: String,
+}
+
+/// 进行中的剧情线
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArcProgress {
+    pub arc: StoryArc,
+    pub step: usize,
+    pub preparation: i32,
+}
+
+impl ArcProgress {
+    pub fn new(arc: StoryArc) -> Self {
+        ArcProgress {
+            arc,
+            step: 0,
+            preparation: 0,
+        }
+    }
+
+    /// 当前这一步要出场的事件
+    pub fn current_event_id(&self) -> Option<usize> {
+        self.arc.steps().get(self.step).map(|step| step.event_id)
+    }
+
+    /// 记录这一步的选择；若这是当前步骤的事件就前进一步，返回是否已走完
+    pub fn record(&mut self, event_id: usize, original_index: u32) -> bool {
+        let Some(step) = self.arc.steps().get(self.step) else {
+            return true;
+        };
+        if step.event_id == event_id {
+            self.preparation += step.preparation.get(original_index as usize).copied().unwrap_or(0);
+            self.step += 1;
+        }
+        self.step >= self.arc.steps().len()
+    }
+}
@@ -0,0 +1,324 @@
+//! 代打擂台：把"怎么打"抽象成 `Agent`，每一步只看 `StateView` 给出一个 `Action`；
+//! 擂台让若干代理在同一批种子上各打一遍，按平均得分排名。
+//! 内置的几种代打策略本身就是代理；想写机器人的道友实现 `Agent` 交给 `Arena::run` 即可。
+
+use std::fmt;
+
+use crate::balance::BalanceConfig;
+use crate::director::Director;
+use crate::engine::{GameCommand, GameEngine, GamePhase};
+use crate::game::{GameState, NpcDecision, OptionInfo, Realm};
+use crate::policy::AutoPolicy;
+use crate::specialty::CareerPath;
+use crate::tone::NarrativeTone;
+
+/// 不限天数的预设也最多打这么多天
+pub const MAX_DAYS: u32 = 365;
+
+/// 单局最多推进多少步（防止代理卡住时死循环）
+pub(crate) const MAX_STEPS: u32 = 20_000;
+
+/// 代打时压力低于这个值才接深夜来电
+const NIGHT_CALL_PRESSURE: i32 = 60;
+
+/// 代打对局里的道号（代理的名字长短不一，不拿来当道号）
+const RUN_NAME: &str = "代打道友";
+
+/// 代理每一步能做的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// 选当前事件的第几个选项，从 0 开始
+    Choose(usize),
+    OpenWeeklyEvent,
+    NextDay,
+    Promote,
+    DeclinePromotion,
+    /// 深夜来电：true 接，false 不接
+    AnswerNightCall(bool),
+    CloseRecap,
+}
+
+impl From<Action> for GameCommand {
+    fn from(action: Action) -> Self {
+        match action {
+            Action::Choose(idx) => GameCommand::Choose(idx.saturating_add(1).min(u8::MAX as usize) as u8),
+            Action::OpenWeeklyEvent => GameCommand::OpenWeeklyEvent,
+            Action::NextDay => GameCommand::NextDay,
+            Action::Promote => GameCommand::Promote,
+            Action::DeclinePromotion => GameCommand::DeclinePromotion,
+            Action::AnswerNightCall(accept) => {
+                GameCommand::AnswerNightCall(if accept { NpcDecision::Accept } else { NpcDecision::Reject })
+            }
+            Action::CloseRecap => GameCommand::CloseRecap,
+        }
+    }
+}
+
+/// 代理做决定时看到的局面
+pub struct StateView<'a> {
+    pub phase: GamePhase,
+    pub state: &'a GameState,
+    /// 现在可以选的事件选项（已打乱，没有可选的事件时为空）
+    pub options: &'a [OptionInfo],
+    pub weekly_event_pending: bool,
+    /// 今天的事都做完了，可以过夜
+    pub can_advance: bool,
+}
+
+impl<'a> StateView<'a> {
+    /// 对局进行中时取得局面；未开局或已结束时为 None
+    pub fn of(engine: &'a GameEngine) -> Option<Self> {
+        let state = engine.game_state.as_ref().filter(|_| engine.in_run())?;
+        let options: &[OptionInfo] = match engine.phase {
+            GamePhase::EventDisplay if engine.can_make_daily_choice() => &state.get_today_event().shuffled_options,
+            GamePhase::WeeklyEventDisplay => state.get_weekly_event().map_or(&[], |weekly| &weekly.shuffled_options),
+            _ => &[],
+        };
+        Some(StateView {
+            phase: engine.phase,
+            state,
+            options,
+            weekly_event_pending: engine.weekly_event_pending(),
+            can_advance: engine.can_advance(),
+        })
+    }
+
+    /// 这个操作现在是否可用（代理给出不可用的操作时引擎不执行）
+    pub fn allows(&self, action: Action) -> bool {
+        match action {
+            Action::Choose(idx) => idx < self.options.len(),
+            Action::OpenWeeklyEvent => self.weekly_event_pending,
+            Action::NextDay => self.can_advance,
+            Action::Promote => self.phase == GamePhase::PromotionConfirm && self.state.player.can_promote(),
+            Action::DeclinePromotion => self.phase == GamePhase::PromotionConfirm,
+            Action::AnswerNightCall(_) => self.phase == GamePhase::NightCall,
+            Action::CloseRecap => self.phase == GamePhase::WeeklyRecap,
+        }
+    }
+
+    /// 常规操作：有选项时用 `pick` 挑（挑不出就选第一个），其余按部就班——
+    /// 做完日常开周事件、再进入下一天，压力扛得住才接深夜来电，能晋升就晋升
+    pub fn routine(&self, pick: impl FnOnce(&[OptionInfo]) -> Option<usize>) -> Action {
+        let player = &self.state.player;
+        match self.phase {
+            _ if !self.options.is_empty() => Action::Choose(pick(self.options).unwrap_or(0)),
+            GamePhase::EventDisplay if self.weekly_event_pending => Action::OpenWeeklyEvent,
+            GamePhase::NightCall => Action::AnswerNightCall(player.pressure < NIGHT_CALL_PRESSURE),
+            GamePhase::WeeklyRecap => Action::CloseRecap,
+            GamePhase::PromotionConfirm if player.can_promote() => Action::Promote,
+            GamePhase::PromotionConfirm => Action::DeclinePromotion,
+            _ => Action::NextDay,
+        }
+    }
+}
+
+/// 代打的代理：每一步看局面给出一个操作
+pub trait Agent {
+    /// 排行榜上显示的名字
+    fn name(&self) -> String;
+
+    fn choose(&mut self, view: &StateView) -> Action;
+}
+
+/// 内置的代打策略也是代理
+impl Agent for AutoPolicy {
+    fn name(&self) -> String {
+        self.label().to_string()
+    }
+
+    fn choose(&mut self, view: &StateView) -> Action {
+        let player = &view.state.player;
+        match view.phase {
+            GamePhase::PromotionConfirm if player.can_promote() && !self.should_promote(player) => {
+                Action::DeclinePromotion
+            }
+            _ => view.routine(|options| AutoPolicy::choose(self, player, options)),
+        }
+    }
+}
+
+/// 避险：每次都选当晚出局风险最低的选项（和师傅提示同一套算法），失败率高时不晋升
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Cautious;
+
+impl Agent for Cautious {
+    fn name(&self) -> String {
+        "避险大师".to_string()
+    }
+
+    fn choose(&mut self, view: &StateView) -> Action {
+        let player = &view.state.player;
+        match view.phase {
+            GamePhase::PromotionConfirm if player.promotion_failure_rate() > 0.3 => Action::DeclinePromotion,
+            _ => view.routine(|options| {
+                options
+                    .iter()
+                    .enumerate()
+                    .min_by(|(_, a), (_, b)| {
+                        player
+                            .option_risk(player.scale_outcome(a.value))
+                            .total_cmp(&player.option_risk(player.scale_outcome(b.value)))
+                            .then(b.value.0.cmp(&a.value.0))
+                    })
+                    .map(|(idx, _)| idx)
+            }),
+        }
+    }
+}
+
+/// 内置代理：三种代打策略加避险大师
+pub fn builtin_agents() -> Vec<Box<dyn Agent>> {
+    let mut agents: Vec<Box<dyn Agent>> = AutoPolicy::ALL
+        .into_iter()
+        .map(|policy| Box::new(policy) as Box<dyn Agent>)
+        .collect();
+    agents.push(Box::new(Cautious));
+    agents
+}
+
+/// 一局的结果
+#[derive(Debug, Clone, Copy)]
+pub struct Outcome {
+    pub days: u32,
+    pub survived: bool,
+    pub victory: bool,
+    pub realm: Realm,
+    pub score: u32,
+}
+
+/// 一个代理在擂台上的战绩
+#[derive(Debug, Clone, Default)]
+pub struct Standing {
+    pub agent: String,
+    pub runs: u32,
+    pub survived: u32,
+    pub victories: u32,
+    pub total_days: u64,
+    pub total_score: u64,
+    pub best_realm: Option<Realm>,
+}
+
+impl Standing {
+    fn add(&mut self, outcome: Outcome) {
+        self.runs += 1;
+        self.survived += u32::from(outcome.survived);
+        self.victories += u32::from(outcome.victory);
+        self.total_days += u64::from(outcome.days);
+        self.total_score += u64::from(outcome.score);
+        self.best_realm = self.best_realm.max(Some(outcome.realm));
+    }
+
+    pub fn avg_score(&self) -> f32 {
+        self.total_score as f32 / self.runs.max(1) as f32
+    }
+
+    pub fn survival_rate(&self) -> f32 {
+        self.survived as f32 / self.runs.max(1) as f32
+    }
+
+    pub fn avg_days(&self) -> f32 {
+        self.total_days as f32 / self.runs.max(1) as f32
+    }
+}
+
+/// 擂台：所有代理在同一批种子上各打一遍
+#[derive(Debug, Clone)]
+pub struct Arena {
+    pub balance: BalanceConfig,
+    pub director: Director,
+    pub seeds: Vec<u64>,
+}
+
+impl Arena {
+    /// 用 `first_seed` 起连续 `runs` 个种子
+    pub fn new(balance: BalanceConfig, runs: u32, first_seed: u64) -> Self {
+        Arena {
+            balance,
+            director: Director::default(),
+            seeds: (0..u64::from(runs)).map(|i| first_seed.wrapping_add(i)).collect(),
+        }
+    }
+
+    /// 让一个代理用指定种子打完一局
+    pub fn play(&self, agent: &mut dyn Agent, seed: u64) -> Outcome {
+        let mut engine = start_run(&self.balance, self.director, seed);
+        let mut steps = 0;
+        while steps < MAX_STEPS && engine.agent_step(agent) {
+            // 擂台不需要订阅引擎事件，及时清掉免得越攒越多
+            engine.bus.drain();
+            steps += 1;
+        }
+        let state = engine.game_state.as_ref().expect("已开局");
+        let survived = state.player.is_alive && engine.phase == GamePhase::GameOver;
+        Outcome {
+            days: state.player.days_played,
+            survived,
+            victory: state.is_victory(survived),
+            realm: Realm::from_level(state.player.realm_level),
+            score: state.score(),
+        }
+    }
+
+    /// 打完所有对局，按平均得分（相同时按存活率）从高到低排名
+    pub fn run(&self, agents: &mut [Box<dyn Agent>]) -> Vec<Standing> {
+        let mut standings: Vec<Standing> = agents
+            .iter_mut()
+            .map(|agent| {
+                let mut standing = Standing { agent: agent.name(), ..Standing::default() };
+                for &seed in &self.seeds {
+                    standing.add(self.play(agent.as_mut(), seed));
+                }
+                standing
+            })
+            .collect();
+        standings.sort_by(|a, b| {
+            b.avg_score()
+                .total_cmp(&a.avg_score())
+                .then(b.survival_rate().total_cmp(&a.survival_rate()))
+        });
+        standings
+    }
+}
+
+/// 用指定种子开一局代打用的对局（天数封顶 `MAX_DAYS`，同一种子每次开出来都一样）
+pub fn start_run(balance: &BalanceConfig, director: Director, seed: u64) -> GameEngine {
+    let mut balance = balance.clone();
+    balance.max_days = Some(balance.max_days.map_or(MAX_DAYS, |days| days.min(MAX_DAYS)));
+    let mut engine = GameEngine::new();
+    engine.player_name = RUN_NAME.to_string();
+    engine.start_game(
+        Vec::new(),
+        balance,
+        NarrativeTone::沙雕,
+        director,
+        CareerPath::default(),
+        Vec::new(),
+        Some(seed),
+    )
+    .expect("代打的道号合规");
+    engine
+}
+
+/// 排行榜（纯文本）
+pub struct Leaderboard<'a>(pub &'a [Standing]);
+
+impl fmt::Display for Leaderboard<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // 代理名字长短不一（还夹着中文），放在最后一列免得表格错位
+        writeln!(f, "名次  平均得分  存活率  通关  平均天数  最高境界  代理")?;
+        for (rank, standing) in self.0.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>4}  {:>8.1}  {:>5.0}%  {:>4}  {:>8.1}  {}    {}",
+                rank + 1,
+                standing.avg_score(),
+                standing.survival_rate() * 100.0,
+                standing.victories,
+                standing.avg_days(),
+                standing.best_realm.map_or("  - ".to_string(), |realm| realm.to_string()),
+                standing.agent
+            )?;
+        }
+        Ok(())
+    }
+}
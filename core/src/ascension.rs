@@ -0,0 +1,59 @@
+//! 飞升等级：通关（活着走完天数上限，或晋升到化神期）后解锁下一级。
+//! 每一级在前一级的基础上再叠加一条固定的不利条件，得分按等级加成，给老玩家一条天梯。
+
+/// 每级飞升的得分加成
+const SCORE_BONUS_PER_LEVEL: f32 = 0.25;
+/// “门槛上调”：晋升所需技能点的倍率（百分比）
+pub const RAISED_REQUIREMENT_PERCENT: i32 = 125;
+/// “开局负债”：开局存款的变化
+pub const STARTING_DEBT: i32 = -5000;
+/// “开局带压”：开局压力
+pub const STARTING_PRESSURE: i32 = 20;
+/// “每晚加压”：每晚额外增加的压力
+pub const NIGHTLY_PRESSURE: i32 = 1;
+
+/// 飞升带来的不利条件，按解锁顺序排列：第 n 级包含前 n 条
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Handicap {
+    门槛上调,
+    开局带压,
+    师傅失联,
+    开局负债,
+    每晚加压,
+    取消宽限,
+}
+
+impl Handicap {
+    pub const ALL: [Handicap; 6] = [
+        Handicap::门槛上调,
+        Handicap::开局带压,
+        Handicap::师傅失联,
+        Handicap::开局负债,
+        Handicap::每晚加压,
+        Handicap::取消宽限,
+    ];
+
+    pub fn description(&self) -> String {
+        match self {
+            Handicap::门槛上调 => format!("晋升所需技能点提高到 {}%", RAISED_REQUIREMENT_PERCENT),
+            Handicap::开局带压 => format!("开局压力 {}", STARTING_PRESSURE),
+            Handicap::师傅失联 => "没有师傅提示".to_string(),
+            Handicap::开局负债 => format!("开局存款 {} 元", STARTING_DEBT),
+            Handicap::每晚加压 => format!("每晚压力 +{}", NIGHTLY_PRESSURE),
+            Handicap::取消宽限 => "技能转负直接开除，没有绩效改进计划".to_string(),
+        }
+    }
+}
+
+/// 最高飞升等级
+pub const MAX_LEVEL: u32 = Handicap::ALL.len() as u32;
+
+/// 某个飞升等级叠加的全部不利条件
+pub fn handicaps(level: u32) -> &'static [Handicap] {
+    &Handicap::ALL[..level.min(MAX_LEVEL) as usize]
+}
+
+/// 某个飞升等级的得分倍率
+pub fn score_multiplier(level: u32) -> f32 {
+    1.0 + SCORE_BONUS_PER_LEVEL * level.min(MAX_LEVEL) as f32
+}
@@ -0,0 +1,84 @@
+//! 灰色操作与内审：收红包、虚报发票这类选项来钱快，但会悄悄累积审计风险，
+//! 风险越高越容易撞上内审，罚得也越狠
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::game::GameRng;
+
+/// 每晚被内审的概率上限
+const MAX_AUDIT_CHANCE: f32 = 0.4;
+
+/// 内审的处罚
+#[derive(Debug, Clone, Copy)]
+pub struct AuditPenalty {
+    pub skill: i32,
+    pub pressure: i32,
+    pub money: i32,
+    pub reputation: u32,
+}
+
+impl AuditPenalty {
+    /// 处罚与累积的风险成正比
+    fn for_risk(risk: i32) -> Self {
+        AuditPenalty {
+            skill: -risk / 3,
+            pressure: risk / 2,
+            money: -risk * 100,
+            reputation: (risk / 5) as u32,
+        }
+    }
+
+    pub fn message(&self) -> String {
+        format!(
+            "🔍 内审组连夜调取了你的报销单和聊天记录，灰色操作一笔笔摆在桌上。\n技能 {:+} | 压力 {:+} | 罚款 {} 元 | 声望 -{}",
+            self.skill, self.pressure, -self.money, self.reputation
+        )
+    }
+}
+
+/// 审计风险：玩家看不到具体数值，只能从“心虚程度”里猜
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Audit {
+    risk: i32,
+    pub gray_ops: u32,  // 做过多少次灰色操作
+    pub audits: u32,    // 被内审过几次
+}
+
+impl Audit {
+    /// 做了一次灰色操作（风险为 0 的选项不算）
+    pub fn record(&mut self, risk: i32) {
+        if risk > 0 {
+            self.risk += risk;
+            self.gray_ops += 1;
+        }
+    }
+
+    /// 过夜时掷骰：没有风险时不掷，不影响原有的随机序列；
+    /// 没被查到的话风头慢慢过去
+    pub fn roll(&mut self, rng: &mut GameRng) -> Option<AuditPenalty> {
+        if self.risk <= 0 {
+            return None;
+        }
+        let chance = (self.risk as f32 / 250.0).min(MAX_AUDIT_CHANCE);
+        if rng.gen::<f32>() >= chance {
+            self.risk -= 1;
+            return None;
+        }
+        let penalty = AuditPenalty::for_risk(self.risk);
+        self.risk = 0;
+        self.audits += 1;
+        Some(penalty)
+    }
+
+    /// 给玩家看的模糊提示
+    pub fn unease(&self) -> &'static str {
+        match self.risk {
+            ..=0 => "风头已过",
+            1..=19 => "有点心虚",
+            20..=49 => "风声渐紧",
+            _ => "如坐针毡",
+        }
+    }
+}
@@ -0,0 +1,311 @@
+//! 数值平衡配置：境界门槛、晋升要求、单局天数上限、绩效改进计划的宽限、各境界的事件难度，
+//! 事件去重的间隔、技能软上限的折算、压力变化的结算方式，以及随难度变化的猝死概率、晋升失败率、
+//! 咸鱼猝死与周事件频率；转世加成也折算到这里。
+//! 不同玩法（如轻量模式、地狱难度）是同一套规则下的不同预设，而不是另写一套逻辑。
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::rebirth::{self, Boon, MetaProgress};
+
+/// 一局游戏使用的数值配置（旧存档缺的字段按标准模式、正常难度补齐）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BalanceConfig {
+    /// 生成该配置的预设（速通成绩按预设分开记录）
+    pub preset: BalancePreset,
+    /// 各境界经验上限：凡人境 … 大乘期（超过最后一档即飞升）
+    #[serde(deserialize_with = "padded_thresholds")]
+    pub realm_thresholds: [u32; 6],
+    /// 各等级晋升所需技能点：凡人境→炼气期 … 大乘期→飞升
+    #[serde(deserialize_with = "padded_promotions")]
+    pub promotion_skills: [i32; 6],
+    /// 单局最多天数（None 为不限）
+    pub max_days: Option<u32>,
+    /// 技能第一次转负时绩效改进计划的天数（None 为直接开除）
+    pub probation_days: Option<u32>,
+    /// 各境界（凡人境 … 飞升）事件代价的倍率（百分比）：
+    /// 只放大扣技能和加压力的部分，收益不变，免得后期技能攒多了每天都稳如泰山
+    #[serde(deserialize_with = "padded_costs")]
+    pub cost_percent: [i32; 7],
+    /// 本局难度（决定下面几项）
+    pub difficulty: Difficulty,
+    /// 猝死概率表：(压力下限, 每晚概率)，按下限从低到高；低于第一档不会猝死
+    pub death_table: [(i32, f32); 4],
+    /// 每失败一次晋升，失败率增加多少（第一次冲击也按一档计）
+    pub promotion_failure_step: f32,
+    /// 连续零压力多少晚之后开始有咸鱼猝死的风险
+    pub zero_pressure_grace: u32,
+    /// 咸鱼猝死的每晚概率
+    pub zero_pressure_death_chance: f32,
+    /// 每隔几天来一次周事件
+    pub weekly_event_interval: u32,
+    /// 同一事件出现后至少隔几天才会再次抽到（0 为不限）
+    pub repeat_cooldown_days: u32,
+    /// 开局自带的技能点（转世加成“宿慧”）
+    pub starting_skills: i32,
+    /// 隐藏事件是否进入抽取池（转世加成“前世记忆”）
+    pub hidden_events: bool,
+    /// 技能软上限：技能点超出下一次晋升要求这么多（百分比）之后，超出的部分每晚折成存款（None 为不折算）
+    pub overflow_cap_percent: Option<i32>,
+    /// 溢出的每点技能折多少元存款
+    pub overflow_rate: i32,
+    /// 压力变化是当场生效还是带惯性分几天落地
+    pub pressure_model: PressureModel,
+}
+
+impl BalanceConfig {
+    /// 换成另一个难度（只改随难度变化的几项，境界门槛等保持预设；绩效改进计划按预设的天数加减）
+    pub fn with_difficulty(self, difficulty: Difficulty) -> Self {
+        let (
+            death_table,
+            promotion_failure_step,
+            zero_pressure_grace,
+            zero_pressure_death_chance,
+            weekly_event_interval,
+            probation_percent,
+        ) = match difficulty {
+            Difficulty::轻松 => ([(20, 0.03), (30, 0.05), (50, 0.12), (70, 0.25)], 0.03, 3, 0.10, 7, 150),
+            Difficulty::正常 => ([(20, 0.05), (30, 0.08), (50, 0.20), (70, 0.40)], 0.05, 2, 0.15, 7, 100),
+            Difficulty::地狱996 => ([(20, 0.08), (30, 0.12), (50, 0.28), (70, 0.55)], 0.08, 1, 0.25, 5, 50),
+        };
+        let probation_days =
+            self.preset.config().probation_days.map(|days| (days * probation_percent / 100).max(1));
+        BalanceConfig {
+            difficulty,
+            death_table,
+            promotion_failure_step,
+            zero_pressure_grace,
+            zero_pressure_death_chance,
+            weekly_event_interval,
+            probation_days,
+            ..self
+        }
+    }
+
+    /// 换一种压力结算方式
+    pub fn with_pressure_model(self, pressure_model: PressureModel) -> Self {
+        BalanceConfig { pressure_model, ..self }
+    }
+
+    /// 叠加转世兑换的永久加成（在 `with_difficulty` 之后调用，猝死概率按难度表打折）
+    pub fn with_boons(self, progress: &MetaProgress) -> Self {
+        let discount = 1.0 - (progress.level(Boon::护体) as i32 * rebirth::DEATH_REDUCTION_PERCENT) as f32 / 100.0;
+        BalanceConfig {
+            starting_skills: progress.level(Boon::宿慧) as i32 * rebirth::SKILLS_PER_LEVEL,
+            hidden_events: progress.level(Boon::前世记忆) > 0,
+            death_table: self.death_table.map(|(floor, chance)| (floor, chance * discount.max(0.0))),
+            ..self
+        }
+    }
+
+    /// 某个压力值下每晚的猝死概率
+    pub fn death_chance(&self, pressure: i32) -> f32 {
+        self.death_table
+            .iter()
+            .rev()
+            .find(|(floor, _)| pressure >= *floor)
+            .map_or(0.0, |(_, chance)| *chance)
+    }
+
+    /// 技能点超出软上限的部分（没有软上限或没超出时为 0）
+    pub fn overflow(&self, skills: i32, requirement: i32) -> i32 {
+        self.overflow_cap_percent
+            .map_or(0, |percent| (skills - requirement.saturating_mul(100 + percent) / 100).max(0))
+    }
+
+    /// 按境界放大事件结果中的代价；`realm` 为境界序号（0=凡人境 … 6=飞升）
+    pub fn scale_outcome(&self, realm: usize, (skill, pressure): (i32, i32)) -> (i32, i32) {
+        let percent = self.cost_percent[realm.min(self.cost_percent.len() - 1)];
+        let scale = |cost: i32| (cost * percent + 50) / 100;
+        (
+            if skill < 0 { -scale(-skill) } else { skill },
+            if pressure > 0 { scale(pressure) } else { pressure },
+        )
+    }
+}
+
+/// 旧存档的境界数组只到化神期，缺的后几档按标准预设补齐
+fn padded<'de, D, T, const N: usize>(deserializer: D, standard: [T; N]) -> Result<[T; N], D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Copy,
+{
+    let values = Vec::<T>::deserialize(deserializer)?;
+    if values.len() > N {
+        return Err(D::Error::invalid_length(values.len(), &"不超过境界数的数组"));
+    }
+    let mut padded = standard;
+    padded[..values.len()].copy_from_slice(&values);
+    Ok(padded)
+}
+
+fn padded_thresholds<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u32; 6], D::Error> {
+    padded(deserializer, BalancePreset::标准.config().realm_thresholds)
+}
+
+fn padded_promotions<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[i32; 6], D::Error> {
+    padded(deserializer, BalancePreset::标准.config().promotion_skills)
+}
+
+fn padded_costs<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[i32; 7], D::Error> {
+    padded(deserializer, BalancePreset::标准.config().cost_percent)
+}
+
+impl Default for BalanceConfig {
+    fn default() -> Self {
+        BalancePreset::标准.config()
+    }
+}
+
+/// 可选的数值预设
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BalancePreset {
+    标准,
+    /// 咖啡时间一局：门槛除以5，最多30天
+    轻量,
+}
+
+impl BalancePreset {
+    pub const ALL: [BalancePreset; 2] = [BalancePreset::标准, BalancePreset::轻量];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BalancePreset::标准 => "标准模式",
+            BalancePreset::轻量 => "☕ 轻量模式",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            BalancePreset::标准 => "完整的修仙之路，不限天数",
+            BalancePreset::轻量 => "境界门槛降为1/5，最多30天，绩效改进计划只有3天，适合喝杯咖啡的工夫玩一局",
+        }
+    }
+
+    pub fn config(&self) -> BalanceConfig {
+        let standard = BalanceConfig {
+            preset: BalancePreset::标准,
+            realm_thresholds: [50, 150, 300, 500, 800, 1200],
+            promotion_skills: [50, 150, 300, 500, 800, 1200],
+            max_days: None,
+            probation_days: Some(7),
+            cost_percent: [100, 110, 125, 150, 175, 200, 225],
+            difficulty: Difficulty::正常,
+            death_table: [(20, 0.05), (30, 0.08), (50, 0.20), (70, 0.40)],
+            promotion_failure_step: 0.05,
+            zero_pressure_grace: 2,
+            zero_pressure_death_chance: 0.15,
+            weekly_event_interval: 7,
+            repeat_cooldown_days: 5,
+            starting_skills: 0,
+            hidden_events: false,
+            overflow_cap_percent: Some(100),
+            overflow_rate: 20,
+            pressure_model: PressureModel::即时,
+        };
+        match self {
+            BalancePreset::标准 => standard,
+            BalancePreset::轻量 => BalanceConfig {
+                preset: BalancePreset::轻量,
+                realm_thresholds: standard.realm_thresholds.map(|t| t / 5),
+                promotion_skills: standard.promotion_skills.map(|s| s / 5),
+                max_days: Some(30),
+                probation_days: Some(3),
+                ..standard
+            },
+        }
+    }
+}
+
+/// 压力变化的结算方式：与难度、预设独立，开局时选
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PressureModel {
+    /// 加多少压力当场就是多少
+    #[default]
+    即时,
+    /// 压力变化只有一半当场生效，剩下的攒成惯性，之后每晚再落地一半：
+    /// 管压力要看趋势，一次放松救不了连续的加班，一次加班也不会当场压垮
+    惯性,
+}
+
+impl PressureModel {
+    pub const ALL: [PressureModel; 2] = [PressureModel::即时, PressureModel::惯性];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PressureModel::即时 => "⚡ 即时",
+            PressureModel::惯性 => "🌊 惯性",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            PressureModel::即时 => "压力变化当场生效",
+            PressureModel::惯性 => "压力变化当场只生效一半，其余在之后几晚陆续落地，要看趋势管压力",
+        }
+    }
+
+    /// 把一次压力变化拆成 (当场生效, 攒进惯性) 两部分
+    pub fn split(&self, change: i32) -> (i32, i32) {
+        match self {
+            PressureModel::即时 => (change, 0),
+            PressureModel::惯性 => (change / 2, change - change / 2),
+        }
+    }
+
+    /// 每晚从攒着的惯性里落地多少（剩一点时一次落完）
+    pub fn release(&self, pending: i32) -> i32 {
+        match self {
+            PressureModel::即时 => pending,
+            PressureModel::惯性 if pending.abs() <= 1 => pending,
+            PressureModel::惯性 => pending / 2,
+        }
+    }
+}
+
+/// 难度：与预设（标准/轻量）独立，只影响猝死、晋升失败、咸鱼猝死与周事件频率
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Difficulty {
+    轻松,
+    #[default]
+    正常,
+    地狱996,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::轻松, Difficulty::正常, Difficulty::地狱996];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Difficulty::轻松 => "🍵 轻松",
+            Difficulty::正常 => "⚖ 正常",
+            Difficulty::地狱996 => "🔥 地狱 996",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Difficulty::轻松 => "猝死率约减半，晋升失败涨得慢，多躺一晚也不会咸鱼猝死，绩效改进计划多给一半时间",
+            Difficulty::正常 => "原汁原味的修仙职场",
+            Difficulty::地狱996 => "猝死率翻倍有余，晋升失败涨得快，躺一晚就可能咸鱼猝死，周事件每5天一次，绩效改进计划减半",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn difficulty_sets_probation_length() {
+        for preset in BalancePreset::ALL {
+            let days = Difficulty::ALL.map(|difficulty| preset.config().with_difficulty(difficulty).probation_days.unwrap());
+            assert!(days[0] > days[1] && days[1] > days[2], "{:?}: {:?}", preset, days);
+            assert_eq!(days[1], preset.config().probation_days.unwrap(), "正常难度保持预设的天数");
+        }
+        // 反复换难度不会越换越短
+        let config = BalancePreset::标准.config().with_difficulty(Difficulty::地狱996).with_difficulty(Difficulty::正常);
+        assert_eq!(config.probation_days, Some(7));
+    }
+}
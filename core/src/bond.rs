@@ -0,0 +1,75 @@
+//! 羁绊与结怨：同意/拒绝同事的请求会累积好感或厌恶（与送礼共用 `Relations` 里的好感，负数即厌恶）。
+//! 好感到门槛后安排一次这位同事的专属剧情，走完送一件信物（永久加成）；
+//! 厌恶太深的同事会在周事件里给你穿小鞋。
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// 同意请求增加的好感
+pub const ACCEPT_AFFINITY: i32 = 4;
+/// 拒绝请求减少的好感
+pub const REJECT_AFFINITY: i32 = -6;
+/// 好感达到多少安排专属剧情
+pub const BOND_AFFINITY: i32 = 60;
+/// 好感低于多少（厌恶）会被穿小鞋
+pub const GRUDGE_AFFINITY: i32 = -40;
+/// 穿完小鞋出了口气，厌恶减轻多少
+pub const GRUDGE_RELIEF: i32 = 20;
+/// 穿小鞋的周事件 id
+pub const GRUDGE_EVENT: usize = 10;
+/// 穿小鞋事件文案里代指对方的词，出场时换成具体的同事
+pub const GRUDGE_STAND_IN: &str = "某同事";
+
+/// 专属剧情送的信物（永久加成）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Keepsake {
+    自愈脚本,
+    卷王笔记,
+    摸鱼秘籍,
+}
+
+impl fmt::Display for Keepsake {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Keepsake::自愈脚本 => "自愈脚本",
+            Keepsake::卷王笔记 => "卷王笔记",
+            Keepsake::摸鱼秘籍 => "摸鱼秘籍",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl Keepsake {
+    /// “自愈脚本”每晚减少的压力
+    pub const NIGHTLY_RELIEF: i32 = 2;
+    /// “摸鱼秘籍”降低的晋升失败率
+    pub const PROMOTION_EDGE: f32 = 0.05;
+
+    pub fn description(&self) -> String {
+        match self {
+            Keepsake::自愈脚本 => format!("线上小毛病自己好了：每晚压力 -{}", Self::NIGHTLY_RELIEF),
+            Keepsake::卷王笔记 => "日常事件的技能收益 +1".to_string(),
+            Keepsake::摸鱼秘籍 => format!("答辩时看起来很懂：晋升失败率 -{:.0}%", Self::PROMOTION_EDGE * 100.0),
+        }
+    }
+}
+
+/// 一位同事的专属剧情：出场的日常事件与走完后送的信物
+#[derive(Debug, Clone, Copy)]
+pub struct BondStory {
+    pub npc: &'static str,
+    pub event_id: usize,
+    pub keepsake: Keepsake,
+}
+
+pub const STORIES: [BondStory; 3] = [
+    BondStory { npc: "运维老李", event_id: 114, keepsake: Keepsake::自愈脚本 },
+    BondStory { npc: "内卷仙子阿卷", event_id: 115, keepsake: Keepsake::卷王笔记 },
+    BondStory { npc: "摸鱼王大壮", event_id: 116, keepsake: Keepsake::摸鱼秘籍 },
+];
+
+/// 某个日常事件是不是专属剧情
+pub fn story_for_event(event_id: usize) -> Option<&'static BondStory> {
+    STORIES.iter().find(|story| story.event_id == event_id)
+}
@@ -0,0 +1,88 @@
+//! 引擎事件总线：GameEngine 推进对局时只管发布带类型的事件，
+//! 高光录制、挑战成绩、飞升进度、本次运行统计等子系统在每帧取出后各自响应，
+//! 不再由 GameEngine 为每个子系统单独留一个一次性标记。
+
+use crate::game::{HistoryKind, Realm};
+
+/// 引擎发布的事件
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameEvent {
+    /// 做出了一次事件选择
+    ChoiceMade { kind: HistoryKind },
+    /// 日常/周事件选了哪个选项：`position` 是打乱后的位置，`original` 是原始位置，`offered` 是选项数
+    OptionPicked { kind: HistoryKind, event_id: usize, name: String, position: usize, original: u32, offered: usize },
+    /// 做完日常后触发了本周的周事件
+    WeeklyEventTriggered,
+    /// 进入了新的一天
+    DayAdvanced { day: u32 },
+    /// 过夜的生死判定
+    DeathRolled { survived: bool },
+    /// 晋升有了结果（`realm` 为判定后的职级对应的境界）
+    PromotionResolved { success: bool, realm: Realm },
+    /// 本局结束
+    RunEnded,
+}
+
+/// 待分发的事件队列
+#[derive(Debug, Default)]
+pub struct EventBus {
+    pending: Vec<GameEvent>,
+}
+
+impl EventBus {
+    pub fn publish(&mut self, event: GameEvent) {
+        self.pending.push(event);
+    }
+
+    /// 取出本帧之前发布的全部事件，按发布顺序分发
+    pub fn drain(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.pending)
+    }
+}
+
+/// 本次运行的简单统计（订阅总线，不落盘）
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SessionStats {
+    pub daily_choices: u32,
+    pub weekly_choices: u32,
+    pub npc_choices: u32,
+    pub days: u32,
+    pub furthest_day: u32,
+    pub promotions: u32,
+    pub deaths: u32,
+    pub runs: u32,
+}
+
+impl SessionStats {
+    pub fn on_event(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::ChoiceMade { kind: HistoryKind::日常 } => self.daily_choices += 1,
+            GameEvent::ChoiceMade { kind: HistoryKind::周事件 } => self.weekly_choices += 1,
+            GameEvent::ChoiceMade { kind: HistoryKind::Npc } => self.npc_choices += 1,
+            // 项目结算、晋升结果不是玩家的选择，引擎不会发布
+            GameEvent::ChoiceMade { kind: HistoryKind::项目 | HistoryKind::晋升 } => {}
+            GameEvent::DayAdvanced { day } => {
+                self.days += 1;
+                self.furthest_day = self.furthest_day.max(*day);
+            }
+            GameEvent::DeathRolled { survived } => self.deaths += u32::from(!survived),
+            GameEvent::PromotionResolved { success, .. } => self.promotions += u32::from(*success),
+            GameEvent::RunEnded => self.runs += 1,
+            GameEvent::OptionPicked { .. } | GameEvent::WeeklyEventTriggered => {}
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "本次运行：{} 局，{} 天（最远第 {} 天），日常选择 {} 次，周事件 {} 次，同事请求 {} 次，晋升 {} 次，出局 {} 次",
+            self.runs,
+            self.days,
+            self.furthest_day,
+            self.daily_choices,
+            self.weekly_choices,
+            self.npc_choices,
+            self.promotions,
+            self.deaths
+        )
+    }
+}
@@ -0,0 +1,53 @@
+//! 通勤方式：决定会遇到哪些通勤劫，并带来每天的开销与压力变化；只能在周报时更换
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Commute {
+    #[default]
+    地铁,
+    电动车,
+    打车,
+    步行,
+}
+
+impl Commute {
+    pub const ALL: [Commute; 4] = [Commute::地铁, Commute::电动车, Commute::打车, Commute::步行];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Commute::地铁 => "🚇 地铁",
+            Commute::电动车 => "🛵 电动车",
+            Commute::打车 => "🚕 打车",
+            Commute::步行 => "🚶 步行",
+        }
+    }
+
+    /// 每天的通勤花费（元）
+    pub fn daily_cost(&self) -> i32 {
+        match self {
+            Commute::地铁 => 6,
+            Commute::电动车 => 1,
+            Commute::打车 => 50,
+            Commute::步行 => 0,
+        }
+    }
+
+    /// 每天通勤带来的压力变化
+    pub fn daily_pressure(&self) -> i32 {
+        match self {
+            Commute::地铁 => 1,
+            Commute::电动车 => 0,
+            Commute::打车 => -2,
+            Commute::步行 => -1,
+        }
+    }
+
+    pub fn description(&self) -> String {
+        format!(
+            "每天 {} 元，压力 {:+}",
+            self.daily_cost(),
+            self.daily_pressure()
+        )
+    }
+}
@@ -0,0 +1,91 @@
+//! 同伴：好感拉满的同事可以邀请进自己的小团队，每天给被动加成，
+//! 另有一个每周可用一次的专属技能（如老李的免死回滚）
+
+use serde::{Deserialize, Serialize};
+
+/// 同伴栏位数
+pub const COMPANION_SLOTS: usize = 1;
+
+/// 同伴的专属技能
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Ability {
+    免死回滚,
+    带薪摸鱼,
+    通宵冲刺,
+    特调咖啡,
+    帮把手,
+}
+
+impl Ability {
+    /// 各同事的专属技能，没有专门设计的同事都会“帮把手”
+    pub fn of(npc: &str) -> Ability {
+        match npc {
+            "运维老李" => Ability::免死回滚,
+            "摸鱼王大壮" => Ability::带薪摸鱼,
+            "内卷仙子阿卷" => Ability::通宵冲刺,
+            "咖啡机器人007" => Ability::特调咖啡,
+            _ => Ability::帮把手,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Ability::免死回滚 => "🛡 免死回滚",
+            Ability::带薪摸鱼 => "🐟 带薪摸鱼",
+            Ability::通宵冲刺 => "🔥 通宵冲刺",
+            Ability::特调咖啡 => "☕ 特调咖啡",
+            Ability::帮把手 => "🤝 帮把手",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Ability::免死回滚 => "开启后下一次出局时回滚到前一天的状态继续（每周可开启一次）",
+            Ability::带薪摸鱼 => "压力 -15（每周一次）",
+            Ability::通宵冲刺 => "技能 +10，压力 +10（每周一次）",
+            Ability::特调咖啡 => "压力 -10（每周一次）",
+            Ability::帮把手 => "技能 +5（每周一次）",
+        }
+    }
+
+    /// 每天的被动加成（技能, 压力）
+    pub fn passive(&self) -> (i32, i32) {
+        match self {
+            Ability::免死回滚 | Ability::特调咖啡 => (0, -1),
+            Ability::带薪摸鱼 => (0, -2),
+            Ability::通宵冲刺 | Ability::帮把手 => (1, 0),
+        }
+    }
+
+    pub fn passive_label(&self) -> String {
+        let (skill, pressure) = self.passive();
+        if skill != 0 {
+            format!("每天技能 {:+}", skill)
+        } else {
+            format!("每天压力 {:+}", pressure)
+        }
+    }
+}
+
+/// 加入团队的同事
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Companion {
+    pub name: String,
+    pub ability: Ability,
+    pub last_used_week: Option<u32>,
+}
+
+impl Companion {
+    pub fn new(name: &str) -> Self {
+        Companion {
+            name: name.to_string(),
+            ability: Ability::of(name),
+            last_used_week: None,
+        }
+    }
+
+    /// 本周的专属技能是否还能用
+    pub fn ready(&self, week: u32) -> bool {
+        self.last_used_week != Some(week)
+    }
+}
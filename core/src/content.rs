@@ -0,0 +1,87 @@
+//! 内容数据文件（content/*.json）：桌面端直接编译进程序；
+//! 网页端为了控制首次加载体积不编进 wasm，启动后由 webassets 单独下载再装进来，装好之前不能开局。
+
+#[cfg(target_arch = "wasm32")]
+use std::sync::OnceLock;
+
+/// 按需读取的内容数据文件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentFile {
+    Npcs,
+    Gifts,
+    Tones,
+    Events,
+    BannedWords,
+    Weights,
+}
+
+impl ContentFile {
+    #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+    pub const ALL: [ContentFile; 6] = [
+        ContentFile::Npcs,
+        ContentFile::Gifts,
+        ContentFile::Tones,
+        ContentFile::Events,
+        ContentFile::BannedWords,
+        ContentFile::Weights,
+    ];
+
+    /// content/ 目录下的文件名（网页端下载地址为 ./content/<文件名>）
+    #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            ContentFile::Npcs => "npcs.json",
+            ContentFile::Gifts => "gifts.json",
+            ContentFile::Tones => "tones.json",
+            ContentFile::Events => "events.json",
+            ContentFile::BannedWords => "banned_words.json",
+            ContentFile::Weights => "weights.json",
+        }
+    }
+
+    /// 文件内容
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn text(&self) -> &'static str {
+        match self {
+            ContentFile::Npcs => include_str!("../../content/npcs.json"),
+            ContentFile::Gifts => include_str!("../../content/gifts.json"),
+            ContentFile::Tones => include_str!("../../content/tones.json"),
+            ContentFile::Events => include_str!("../../content/events.json"),
+            ContentFile::BannedWords => include_str!("../../content/banned_words.json"),
+            ContentFile::Weights => include_str!("../../content/weights.json"),
+        }
+    }
+
+    /// 文件内容（网页端须先 `install`）
+    #[cfg(target_arch = "wasm32")]
+    pub fn text(&self) -> &'static str {
+        self.slot()
+            .get()
+            .map(String::as_str)
+            .unwrap_or_else(|| panic!("内容文件 {} 还没下载完", self.file_name()))
+    }
+
+    /// 装入下载好的文件内容（只认第一次）
+    #[cfg(target_arch = "wasm32")]
+    pub fn install(&self, text: String) {
+        let _ = self.slot().set(text);
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn slot(&self) -> &'static OnceLock<String> {
+        static NPCS: OnceLock<String> = OnceLock::new();
+        static GIFTS: OnceLock<String> = OnceLock::new();
+        static TONES: OnceLock<String> = OnceLock::new();
+        static EVENTS: OnceLock<String> = OnceLock::new();
+        static BANNED_WORDS: OnceLock<String> = OnceLock::new();
+        static WEIGHTS: OnceLock<String> = OnceLock::new();
+        match self {
+            ContentFile::Npcs => &NPCS,
+            ContentFile::Gifts => &GIFTS,
+            ContentFile::Tones => &TONES,
+            ContentFile::Events => &EVENTS,
+            ContentFile::BannedWords => &BANNED_WORDS,
+            ContentFile::Weights => &WEIGHTS,
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! 进修课程：报名后连续若干天的晚上都得去上课，结业后获得永久加成；
+//! 中途退课按没上的课时退一半学费
+
+use serde::{Deserialize, Serialize};
+
+use crate::specialty::SkillTrack;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Course {
+    K8s入门,
+    面试算法特训,
+}
+
+impl Course {
+    pub const ALL: [Course; 2] = [Course::K8s入门, Course::面试算法特训];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Course::K8s入门 => "☸ K8s 入门",
+            Course::面试算法特训 => "📝 面试算法特训",
+        }
+    }
+
+    /// 课时（占用多少个晚上）
+    pub fn days(&self) -> u32 {
+        match self {
+            Course::K8s入门 => 5,
+            Course::面试算法特训 => 7,
+        }
+    }
+
+    /// 学费（元，报名时一次付清）
+    pub fn fee(&self) -> i32 {
+        match self {
+            Course::K8s入门 => 1999,
+            Course::面试算法特训 => 2999,
+        }
+    }
+
+    /// 上课的晚上额外增加的压力
+    pub fn nightly_pressure(&self) -> i32 {
+        match self {
+            Course::K8s入门 => 2,
+            Course::面试算法特训 => 3,
+        }
+    }
+
+    /// 结业时加到技能线上的点数
+    pub fn graduation_training(&self) -> (SkillTrack, i32) {
+        match self {
+            Course::K8s入门 => (SkillTrack::工程, 10),
+            Course::面试算法特训 => (SkillTrack::算法, 10),
+        }
+    }
+
+    pub fn perk(&self) -> Perk {
+        match self {
+            Course::K8s入门 => Perk::云原生,
+            Course::面试算法特训 => Perk::面霸,
+        }
+    }
+
+    pub fn description(&self) -> String {
+        format!(
+            "{} 元，占用 {} 个晚上（每晚压力 +{}），结业获得「{}」：{}",
+            self.fee(),
+            self.days(),
+            self.nightly_pressure(),
+            self.perk(),
+            self.perk().description()
+        )
+    }
+}
+
+/// 结业获得的永久加成
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Perk {
+    云原生,
+    面霸,
+}
+
+impl std::fmt::Display for Perk {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let name = match self {
+            Perk::云原生 => "云原生",
+            Perk::面霸 => "面霸",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl Perk {
+    pub fn description(&self) -> &'static str {
+        match self {
+            Perk::云原生 => "工程类事件的技能收益 +1",
+            Perk::面霸 => "晋升失败率 -10%",
+        }
+    }
+}
+
+/// 正在上的课
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Enrollment {
+    pub course: Course,
+    pub nights_attended: u32,
+}
+
+impl Enrollment {
+    pub fn new(course: Course) -> Self {
+        Enrollment {
+            course,
+            nights_attended: 0,
+        }
+    }
+
+    pub fn nights_left(&self) -> u32 {
+        self.course.days().saturating_sub(self.nights_attended)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.nights_left() == 0
+    }
+
+    /// 退课时退还的学费：没上的课时按比例退一半
+    pub fn refund(&self) -> i32 {
+        self.course.fee() * self.nights_left() as i32 / self.course.days() as i32 / 2
+    }
+}
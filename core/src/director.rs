@@ -0,0 +1,46 @@
+//! 事件导演：观察最近的压力走势，给接下来的日常事件抽取加权——
+//! 压力高或上升太快时倾向于喘口气的事件，太安逸时倾向于紧张的事件，让节奏有张有弛。
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::DailyEvent;
+use crate::world::WorldView;
+
+/// 观察最近几天的压力走势
+pub const TREND_DAYS: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Director {
+    /// 干预力度：0 为纯随机，1 为最强
+    pub aggressiveness: f32,
+}
+
+impl Default for Director {
+    fn default() -> Self {
+        Director { aggressiveness: 0.5 }
+    }
+}
+
+impl Director {
+    /// 导演此刻想要的节奏：正数想加压，负数想放松，范围 [-1, 1]（已乘以力度）
+    pub fn mood(&self, world: &WorldView) -> f32 {
+        let level = (50 - world.pressure) as f32 / 50.0;
+        let trend = world.pressure_trend as f32 / 25.0;
+        ((level - trend) / 2.0).clamp(-1.0, 1.0) * self.aggressiveness.clamp(0.0, 1.0)
+    }
+
+    /// 按导演的节奏给候选事件加权：事件越紧张（选项平均加压越多），想加压时权重越高
+    pub fn weight(&self, mood: f32, event: &DailyEvent, max_tension: f32) -> f32 {
+        let tension = event.tension() / max_tension.max(1.0);
+        (1.0 + mood * tension).max(0.05)
+    }
+
+    /// 给状态栏看的一句话
+    pub fn describe(mood: f32) -> &'static str {
+        match mood {
+            m if m > 0.25 => "🎬 日子太安逸了，导演准备加点戏",
+            m if m < -0.25 => "🎬 导演看你快撑不住了，安排点轻松的",
+            _ => "🎬 导演按兵不动",
+        }
+    }
+}
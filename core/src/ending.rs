@@ -0,0 +1,226 @@
+//! 结局判定：本局结束时按最终技能点、压力、摸鱼/内卷/甩锅倾向和本局里程碑的完成度，
+//! 从若干结局里挑一个，连同关键数字整理成一张总结卡片，结束画面和终端模式都用它。
+
+use std::fmt;
+
+use crate::game::{ChoiceArchetype, GameState, Realm};
+
+/// 财务自由提前退休需要的存款（元）
+pub const RETIRE_SAVINGS: i32 = 100_000;
+
+/// 某种倾向占本局选择的比例达到多少算“主打”
+const DOMINANT_SHARE: f32 = 0.4;
+
+/// 结局
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ending {
+    /// 一路晋升到飞升
+    飞升成仙,
+    /// 活到最后，存款够多、该见的世面也见过了
+    财务自由,
+    /// 活到最后，靠甩锅混成了中层
+    甩锅上岸,
+    /// 被开除、咸鱼猝死，或者摸鱼摸到最后
+    转行卖烤肠,
+    /// 高压之下倒在工位上
+    卷王猝死,
+    /// 活到最后，平平淡淡
+    平凡打工人,
+    /// 其他死法
+    英年早逝,
+}
+
+impl Ending {
+    pub const ALL: [Ending; 7] = [
+        Ending::飞升成仙,
+        Ending::财务自由,
+        Ending::甩锅上岸,
+        Ending::转行卖烤肠,
+        Ending::卷王猝死,
+        Ending::平凡打工人,
+        Ending::英年早逝,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Ending::飞升成仙 => "飞升成仙",
+            Ending::财务自由 => "财务自由，提前退休",
+            Ending::甩锅上岸 => "甩锅成精，稳坐中层",
+            Ending::转行卖烤肠 => "转行卖烤肠",
+            Ending::卷王猝死 => "卷王陨落",
+            Ending::平凡打工人 => "平凡打工人",
+            Ending::英年早逝 => "英年早逝",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Ending::飞升成仙 => "☁",
+            Ending::财务自由 => "🏝",
+            Ending::甩锅上岸 => "🪑",
+            Ending::转行卖烤肠 => "🌭",
+            Ending::卷王猝死 => "⚰",
+            Ending::平凡打工人 => "💼",
+            Ending::英年早逝 => "🕯",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Ending::飞升成仙 => "渡过最后一道雷劫，你从工位上缓缓升起，工牌化作一道金光。从此公司流传着你的传说：需求永远按期，线上从不出事。",
+            Ending::财务自由 => "存款到位，见识到位。你在周一早上递了辞呈，{boss}挽留了三次。现在你在海边晒太阳，偶尔看看股票，再也不用看告警。",
+            Ending::甩锅上岸 => "锅甩得又准又稳，你成功混进了管理层。周报写得漂亮，会议开得漫长，活儿总有人干。",
+            Ending::转行卖烤肠 => "写代码不如烤肠香。你在公司楼下支了个摊，生意火爆，以前的同事每天下班都来照顾你生意。",
+            Ending::卷王猝死 => "你把自己卷成了传说，也把自己卷没了。工位上还亮着没合上的 IDE，群里还在 @ 你。",
+            Ending::平凡打工人 => "不上不下，不好不坏。日子就这样过去了，明天早上还是要打卡。",
+            Ending::英年早逝 => "修仙路上，你倒在了半途。好在下一世还可以重来。",
+        }
+    }
+
+    /// 死亡相关结局的中性描写（其余结局为 None）
+    pub fn mild_description(&self) -> Option<&'static str> {
+        match self {
+            Ending::卷王猝死 => Some("你把自己逼得太紧，身体先一步喊了停。工位上的 IDE 还开着，群里还在 @ 你。"),
+            Ending::英年早逝 => Some("修仙路上，你在半途停下了脚步。好在下一世还可以重来。"),
+            _ => None,
+        }
+    }
+}
+
+/// 本局里程碑（用来算完成度）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Milestone {
+    晋升化神,
+    技术网红,
+    存款十万,
+    坚持百日,
+    技能千点,
+    心如止水,
+}
+
+impl Milestone {
+    pub const ALL: [Milestone; 6] = [
+        Milestone::晋升化神,
+        Milestone::技术网红,
+        Milestone::存款十万,
+        Milestone::坚持百日,
+        Milestone::技能千点,
+        Milestone::心如止水,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Milestone::晋升化神 => "晋升到化神期",
+            Milestone::技术网红 => "成为技术网红",
+            Milestone::存款十万 => "存款破十万",
+            Milestone::坚持百日 => "坚持一百天",
+            Milestone::技能千点 => "技能点破千",
+            Milestone::心如止水 => "活到最后且压力不超过 20",
+        }
+    }
+
+    fn reached(&self, state: &GameState) -> bool {
+        let player = &state.player;
+        match self {
+            Milestone::晋升化神 => Realm::from_level(player.realm_level) >= Realm::化神期,
+            Milestone::技术网红 => player.title().is_some(),
+            Milestone::存款十万 => player.savings >= RETIRE_SAVINGS,
+            Milestone::坚持百日 => player.days_played >= 100,
+            Milestone::技能千点 => player.skills >= 1000,
+            Milestone::心如止水 => player.is_alive && player.pressure <= 20,
+        }
+    }
+}
+
+/// 结束画面上的总结卡片
+#[derive(Debug, Clone)]
+pub struct EndingCard {
+    pub ending: Ending,
+    pub description: String,  // 占位符已替换
+    pub name: String,
+    pub days: u32,
+    pub realm: Realm,
+    pub skills: i32,
+    pub pressure: i32,
+    pub savings: i32,
+    pub persona: Option<(ChoiceArchetype, f32)>,  // 最主要的选择倾向及其占比
+    pub milestones: Vec<(Milestone, bool)>,
+}
+
+impl EndingCard {
+    /// 里程碑完成度（0~1）
+    pub fn completion(&self) -> f32 {
+        let reached = self.milestones.iter().filter(|(_, reached)| *reached).count();
+        reached as f32 / self.milestones.len() as f32
+    }
+}
+
+impl fmt::Display for EndingCard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} 结局：{}", self.ending.icon(), self.ending.title())?;
+        writeln!(f, "{}", self.description)?;
+        writeln!(
+            f,
+            "{} · 第{}天 · {} · 技能点 {} · 压力 {} · 存款 {} 元",
+            self.name, self.days, self.realm, self.skills, self.pressure, self.savings
+        )?;
+        if let Some((archetype, share)) = self.persona {
+            writeln!(f, "主要倾向：{}（{:.0}%）", archetype.persona(), share * 100.0)?;
+        }
+        write!(f, "里程碑完成度：{:.0}%", self.completion() * 100.0)
+    }
+}
+
+/// 本局结束时的结局
+pub fn judge(state: &GameState) -> Ending {
+    let player = &state.player;
+    let tally = player.archetype_tally();
+    let leaning = |archetype| tally.share(archetype) >= DOMINANT_SHARE;
+    if Realm::from_level(player.realm_level) == Realm::飞升 {
+        return Ending::飞升成仙;
+    }
+    if !player.is_alive {
+        return if player.skills < 0 || player.died_from_zero_pressure || leaning(ChoiceArchetype::摸鱼) {
+            Ending::转行卖烤肠
+        } else if player.pressure >= 70 || leaning(ChoiceArchetype::内卷) {
+            Ending::卷王猝死
+        } else {
+            Ending::英年早逝
+        };
+    }
+    // 财务自由还得见过世面：里程碑至少完成一半
+    let reached = Milestone::ALL.iter().filter(|m| m.reached(state)).count();
+    if player.savings >= RETIRE_SAVINGS && reached * 2 >= Milestone::ALL.len() {
+        Ending::财务自由
+    } else if leaning(ChoiceArchetype::甩锅) {
+        Ending::甩锅上岸
+    } else if leaning(ChoiceArchetype::摸鱼) {
+        Ending::转行卖烤肠
+    } else {
+        Ending::平凡打工人
+    }
+}
+
+/// 本局结束时的总结卡片
+pub fn card(state: &GameState) -> EndingCard {
+    let player = &state.player;
+    let ending = judge(state);
+    let tally = player.archetype_tally();
+    let persona = ChoiceArchetype::ALL
+        .into_iter()
+        .max_by_key(|archetype| tally.count(*archetype))
+        .filter(|_| tally.total() > 0)
+        .map(|archetype| (archetype, tally.share(archetype)));
+    EndingCard {
+        ending,
+        description: state.template_context().render(ending.description()),
+        name: player.name.clone(),
+        days: player.days_played,
+        realm: player.get_realm(),
+        skills: player.skills,
+        pressure: player.pressure,
+        savings: player.savings,
+        persona,
+        milestones: Milestone::ALL.iter().map(|m| (*m, m.reached(state))).collect(),
+    }
+}
@@ -0,0 +1,1509 @@
+//! 对局引擎：阶段切换、事件选择、过夜、晋升等规则都在这里，不依赖界面。
+//! 前端（egui 界面、测试）只把操作翻译成 `GameCommand` 交给 `GameEngine::execute`，
+//! 再从 `GameEngine` 读取阶段与提示文字来渲染，推进中发生的事以 `GameEvent` 发布到总线。
+
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+
+use crate::arena::{Agent, StateView};
+use crate::balance::BalanceConfig;
+use crate::bus::{EventBus, GameEvent};
+use crate::course::Course;
+use crate::director::Director;
+use crate::game::{GameState, HistoryEntry, HistoryKind, NpcDecision, OptionInfo, Realm};
+use crate::items::{self, Item};
+use crate::message::{ResultMessage, Style};
+use crate::name;
+use crate::office::Location;
+use crate::policy::AutoPolicy;
+use crate::portfolio::{self, Instrument};
+use crate::recap::WeeklyRecap;
+use crate::scenario::Scenario;
+use crate::season::ContentPack;
+use crate::shuffle::ShuffleMode;
+use crate::specialty::CareerPath;
+use crate::tag::EventTag;
+use crate::tone::NarrativeTone;
+use crate::warning::DarkContent;
+
+/// 对局所处的阶段（决定界面显示什么、哪些命令有效）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GamePhase {
+    Start,
+    EventDisplay,
+    WeeklyEventDisplay,
+    PromotionConfirm,
+    NightCall,
+    WeeklyRecap,
+    GameOver,
+}
+
+/// 前端发给引擎的命令（按钮、快捷键、代打策略都翻译成这些）
+#[derive(Debug, Clone, Copy)]
+pub enum GameCommand {
+    /// 选当前事件（日常或周事件）的第几个选项，从 1 开始
+    Choose(u8),
+    /// 做完日常后打开今天的周事件
+    OpenWeeklyEvent,
+    /// 请师傅提示当前事件
+    Hint,
+    /// 结束今天（过夜判定、晋升确认、推进到下一天）
+    NextDay,
+    /// 确认晋升
+    Promote,
+    /// 放弃这次晋升，直接过夜
+    DeclinePromotion,
+    /// 答复深夜来电
+    AnswerNightCall(NpcDecision),
+    /// 答复当前同事请求
+    ResolveNpc(NpcDecision),
+    /// 看完周报
+    CloseRecap,
+    /// 搬到更好的住处
+    UpgradeHousing,
+    /// 开一个分身（和本体隔天换班）
+    CreateAlt,
+    /// 使用第几位同伴的专属技能，从 0 开始
+    UseCompanion(usize),
+    /// 报名进修课
+    Enroll(Course),
+    /// 退掉在上的进修课
+    DropCourse,
+    /// 买入一手理财
+    Invest(Instrument),
+    /// 清仓某项理财
+    SellInvestment(Instrument),
+    /// 挂机修炼若干轮
+    IdleTrain(u32),
+    /// 使用背包里的道具
+    UseItem(Item),
+    /// 把同事委托要的道具交给对方
+    DeliverFavor,
+    /// 在办公室地图上挑今天待的地方
+    MoveTo(Location),
+    /// 回到开始界面
+    Restart,
+    /// 同一种子重开
+    Rematch,
+}
+
+/// 对局状态机：持有当前对局与所处阶段，按命令推进
+pub struct GameEngine {
+    pub phase: GamePhase,
+    pub game_state: Option<GameState>,
+    pub player_name: String,
+    pub result_message: ResultMessage,       // 上一次操作的结果提示
+    pub hinted_option: Option<usize>,  // 师傅提示指出的选项（当前事件有效）
+    pub bus: EventBus,                 // 推进对局时发布的引擎事件，由界面每帧取出分发
+    pub weekly_recap: Option<WeeklyRecap>,       // 正在展示的周报
+    pub victory_recorded: bool,                  // 本局通关已计入飞升进度
+    pub launch_seed: Option<u64>,                // 命令行固定的种子（每局都用它开局）
+    pub shuffle: ShuffleMode,                    // 选项排序方式（设置项，每局开局时生效）
+    pub dark_content: DarkContent,               // 黑色段子的处理方式（设置项，随时生效）
+}
+
+impl Default for GameEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameEngine {
+    pub fn new() -> Self {
+        Self {
+            phase: GamePhase::Start,
+            game_state: None,
+            player_name: String::new(),
+            result_message: ResultMessage::default(),
+            hinted_option: None,
+            bus: EventBus::default(),
+            weekly_recap: None,
+            victory_recorded: false,
+            launch_seed: None,
+            shuffle: ShuffleMode::default(),
+            dark_content: DarkContent::default(),
+        }
+    }
+
+    /// 开一局挑战剧本；名号不合规时不开局，返回原因
+    pub fn start_scenario(&mut self, scenario: Scenario, tone: NarrativeTone, career: CareerPath) -> Result<(), String> {
+        let name = name::validate(&self.player_name)?;
+        self.player_name = name.clone();
+        let mut state = GameState::from_scenario(name, scenario);
+        state.tone = tone;
+        state.player.career = career;
+        state.set_shuffle(self.shuffle);
+        self.game_state = Some(state);
+        self.phase = GamePhase::EventDisplay;
+        self.result_message.clear();
+        Ok(())
+    }
+
+    /// 开新的一局；名号不合规时不开局，返回原因
+    #[allow(clippy::too_many_arguments)]
+    pub fn start_game(
+        &mut self,
+        active_packs: Vec<ContentPack>,
+        balance: BalanceConfig,
+        tone: NarrativeTone,
+        director: Director,
+        career: CareerPath,
+        muted_tags: Vec<EventTag>,
+        seed: Option<u64>,
+    ) -> Result<(), String> {
+        let name = name::validate(&self.player_name)?;
+        self.player_name = name.clone();
+        let mut state = match seed.or(self.launch_seed) {
+            Some(seed) => GameState::with_seed(name, seed),
+            None => GameState::new(name),
+        };
+        state.active_packs = active_packs;
+        state.player.skills += balance.starting_skills;
+        state.player.balance = balance;
+        state.tone = tone;
+        state.director = director;
+        state.player.career = career;
+        state.muted_tags = muted_tags;
+        state.set_shuffle(self.shuffle);
+        self.game_state = Some(state);
+        self.phase = GamePhase::EventDisplay;
+        self.result_message.clear();
+        Ok(())
+    }
+
+    /// 执行一条命令；推进中发生的事通过 `bus` 发布
+    pub fn execute(&mut self, command: GameCommand) {
+        match command {
+            GameCommand::Choose(choice) => self.apply_choice(choice),
+            GameCommand::OpenWeeklyEvent => self.reopen_weekly_event(),
+            GameCommand::Hint => self.request_hint(),
+            GameCommand::NextDay => self.next_day(),
+            GameCommand::Promote => self.promote_yes(),
+            GameCommand::DeclinePromotion => self.promote_no(),
+            GameCommand::AnswerNightCall(decision) => self.answer_night_call(decision),
+            GameCommand::ResolveNpc(decision) => self.resolve_npc(decision),
+            GameCommand::CloseRecap => self.close_recap(),
+            GameCommand::UpgradeHousing => self.upgrade_housing(),
+            GameCommand::CreateAlt => self.create_alt(),
+            GameCommand::UseCompanion(index) => self.use_companion(index),
+            GameCommand::Enroll(course) => self.enroll(course),
+            GameCommand::DropCourse => self.drop_course(),
+            GameCommand::Invest(instrument) => self.invest(instrument),
+            GameCommand::SellInvestment(instrument) => self.sell_investment(instrument),
+            GameCommand::IdleTrain(rounds) => self.idle_train(rounds),
+            GameCommand::UseItem(item) => self.use_item(item),
+            GameCommand::DeliverFavor => self.deliver_favor(),
+            GameCommand::MoveTo(place) => self.move_to(place),
+            GameCommand::Restart => self.restart(),
+            GameCommand::Rematch => self.rematch(),
+        }
+    }
+
+    /// 请师傅提示当前事件（已经提示过的不再消耗次数）
+    fn request_hint(&mut self) {
+        if self.hinted_option.is_some() {
+            return;
+        }
+        let Some((_, _, options, _)) = self.current_event_metadata() else {
+            return;
+        };
+        if let Some(game) = &mut self.game_state {
+            self.hinted_option = game.use_hint(&options);
+        }
+    }
+
+    fn apply_choice(&mut self, choice: u8) {
+        use GamePhase::*;
+
+        self.hinted_option = None;
+
+        if let Some(game) = &mut self.game_state {
+            match self.phase {
+                EventDisplay => {
+                    if game.event_chosen_today {
+                        self.result_message = ResultMessage::with(Style::提示, "今天已经选择过了！\n按 \"进入下一天\" 继续");
+                        return;
+                    }
+
+                    let context = game.template_context();
+                    let daily_event = game.get_today_event().clone();
+                    let idx = choice.saturating_sub(1) as usize;
+                    let mut option = match daily_event.shuffled_options.get(idx) {
+                        Some(opt) => opt.render(&context),
+                        None => return,
+                    };
+                    let story = game.narrate(HistoryKind::日常, daily_event.id, &option);
+                    option.story = self.dark_content.story(story, option.mild.as_deref());
+                    let scaled = game.player.scale_outcome(option.value);
+                    let (value, almanac_note) = game.almanac().adjust(&option.desc, scaled);
+                    let (value, grind_note) = game.player.grind.record(option.archetype(), value);
+                    option.value = value;
+
+                    let (skill_reward, pressure_change) = option.value;
+                    let mut story = option.story.clone();
+                    if let Some(note) = almanac_note {
+                        story.push_str(&format!("\n📅 {}", note));
+                    }
+                    if let Some(note) = grind_note {
+                        story.push_str(&format!("\n🕵️ {}", note));
+                    }
+
+                    let skill_reward = skill_reward + game.player.perk_bonus(daily_event.track);
+                    game.player.gain_reward(skill_reward, pressure_change);
+                    game.player.train(daily_event.track, skill_reward);
+                    game.player.savings += option.money;
+                    game.player.add_history(HistoryEntry::event(
+                        HistoryKind::日常,
+                        daily_event.id,
+                        &daily_event.name,
+                        &option,
+                    ));
+                    let outcome = game.record_daily_choice(&daily_event, &option);
+
+                    game.event_chosen_today = true;
+                    self.bus.publish(GameEvent::ChoiceMade { kind: HistoryKind::日常 });
+                    self.bus.publish(GameEvent::OptionPicked {
+                        kind: HistoryKind::日常,
+                        event_id: daily_event.id,
+                        name: daily_event.name.clone(),
+                        position: idx,
+                        original: option.original_index,
+                        offered: daily_event.shuffled_options.len(),
+                    });
+
+                    let mut message = ResultMessage::with(Style::剧情, format!("📖 {}", story));
+                    if let Some(outcome) = outcome {
+                        message.paragraph(Style::喜报, outcome);
+                    }
+                    if let Some(drop) = game.roll_loot(items::EVENT_DROP_CHANCE) {
+                        message.paragraph(Style::喜报, drop);
+                    }
+                    // 周事件可能已经在日常之前先做完了，这时不再切回周事件
+                    if let Some(weekly) = game.get_weekly_event().filter(|_| !game.weekly_event_chosen_today) {
+                        self.phase = WeeklyEventDisplay;
+                        self.bus.publish(GameEvent::WeeklyEventTriggered);
+                        message.paragraph(Style::警告, format!("⚠️ 周事件触发：{}", weekly.name));
+                    } else {
+                        message.paragraph(Style::提示, "点击 \"进入下一天\" 继续");
+                    }
+                    self.result_message = message;
+                }
+                WeeklyEventDisplay => {
+                    if game.weekly_event_chosen_today {
+                        self.result_message = ResultMessage::with(Style::提示, "本周事件已完成！\n点击 \"进入下一天\" 继续");
+                        return;
+                    }
+
+                    if let Some(weekly) = game.get_weekly_event().cloned() {
+                        let context = game.template_context();
+                        let idx = choice.saturating_sub(1) as usize;
+                        let mut option = match weekly.shuffled_options.get(idx) {
+                            Some(opt) => opt.render(&context),
+                            None => return,
+                        };
+                        let story = game.narrate(HistoryKind::周事件, weekly.id, &option);
+                        option.story = self.dark_content.story(story, option.mild.as_deref());
+                        option.value = game.player.scale_outcome(option.value);
+
+                        let (skill_reward, pressure_change) = option.value;
+                        let story = option.story.clone();
+
+                        game.player.gain_reward(skill_reward, pressure_change);
+                        game.player.train(None, skill_reward);
+                        game.player.savings += option.money;
+                        game.player.add_history(HistoryEntry::event(
+                            HistoryKind::周事件,
+                            weekly.id,
+                            &weekly.name,
+                            &option,
+                        ));
+
+                        game.record_weekly_choice(&weekly, &option);
+                        game.weekly_event_chosen_today = true;
+                        self.bus.publish(GameEvent::ChoiceMade { kind: HistoryKind::周事件 });
+                        self.bus.publish(GameEvent::OptionPicked {
+                            kind: HistoryKind::周事件,
+                            event_id: weekly.id,
+                            name: weekly.name.clone(),
+                            position: idx,
+                            original: option.original_index,
+                            offered: weekly.shuffled_options.len(),
+                        });
+
+                        self.phase = EventDisplay;
+                        self.result_message = ResultMessage::with(Style::剧情, format!("📖 {}", story));
+                        if let Some(drop) = game.roll_loot(items::EVENT_DROP_CHANCE) {
+                            self.result_message.paragraph(Style::喜报, drop);
+                        }
+                        self.result_message.paragraph(Style::提示, "周事件完成！点击 \"进入下一天\" 继续");
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// 结束今天：日常（和周事件）都做完才能过夜；晋升确认时等同于暂缓晋升（今晚已经判定过生死）
+    fn next_day(&mut self) {
+        if self.phase == GamePhase::PromotionConfirm {
+            return self.promote_no();
+        }
+        if !self.can_advance() {
+            return;
+        }
+        if let Some(game) = &mut self.game_state {
+            game.check_death();
+            self.bus.publish(GameEvent::DeathRolled { survived: game.player.is_alive });
+
+            if !game.player.is_alive {
+                self.phase = GamePhase::GameOver;
+                self.result_message = ResultMessage::with(Style::标题, format!("【{}】", self.dark_content.death_message(&game.player)));
+                self.result_message.paragraph(
+                    Style::正文,
+                    format!(
+                        "游玩时间: {}\n天数: {}\n技能点: {}\n压力值: {}\n修仙境界: {}",
+                        game.format_time(),
+                        game.player.days_played,
+                        game.player.skills,
+                        game.player.pressure,
+                        game.player.get_realm()
+                    ),
+                );
+            } else if game.player.out_of_days() {
+                self.phase = GamePhase::GameOver;
+                self.result_message = ResultMessage::with(
+                    Style::标题,
+                    format!("【⏰ 时间到！{}天的修仙之旅圆满结束】", game.player.days_played + 1),
+                );
+                self.result_message.paragraph(
+                    Style::正文,
+                    format!(
+                        "游玩时间: {}\n技能点: {}\n压力值: {}\n修仙境界: {}",
+                        game.format_time(),
+                        game.player.skills,
+                        game.player.pressure,
+                        game.player.get_realm()
+                    ),
+                );
+            } else if game.player.can_promote() {
+                self.phase = GamePhase::PromotionConfirm;
+                let failure_percent = (game.player.promotion_failure_rate() * 100.0).round() as i32;
+                self.result_message = ResultMessage::with(Style::喜报, "你已积累足够经验！\n是否选择晋升？\n");
+                self.result_message
+                    .push(Style::警告, format!("(失败率: {}%)", failure_percent))
+                    .push(Style::提示, "\n点击下方按钮进行选择");
+            } else {
+                (self.phase, self.weekly_recap) = Self::advance(game, &mut self.bus);
+                self.result_message = game.take_notice().unwrap_or_default().into();
+            }
+
+            if self.phase == GamePhase::GameOver {
+                if let Some(scenario) = &game.scenario {
+                    self.result_message
+                        .paragraph(Style::喜报, format!("🎯 {} 得分: {}", scenario.name, game.score()));
+                }
+                self.bus.publish(GameEvent::RunEnded);
+            }
+        }
+    }
+
+    fn promote_yes(&mut self) {
+        if self.phase != GamePhase::PromotionConfirm {
+            return;
+        }
+        if let Some(game) = &mut self.game_state {
+            let (success, msg) = match game.attempt_promotion() {
+                Ok(result) => result,
+                Err(err) => {
+                    self.result_message = ResultMessage::with(Style::提示, err);
+                    self.result_message.paragraph(Style::提示, "点击 \"暂缓晋升\" 继续");
+                    return;
+                }
+            };
+            self.result_message = ResultMessage::with(if success { Style::喜报 } else { Style::警告 }, msg);
+            let realm = Realm::from_level(game.player.realm_level);
+            self.bus.publish(GameEvent::PromotionResolved { success, realm });
+            if success && realm == Realm::飞升 {
+                // 最高境界：本局到此圆满结束
+                self.phase = GamePhase::GameOver;
+                self.result_message.paragraph(
+                    Style::标题,
+                    format!("【☁ 第{}天，白日飞升】", game.player.days_played + 1),
+                );
+                self.result_message.paragraph(
+                    Style::正文,
+                    format!(
+                        "游玩时间: {}\n技能点: {}\n压力值: {}\n修仙境界: {}",
+                        game.format_time(),
+                        game.player.skills,
+                        game.player.pressure,
+                        game.player.get_realm()
+                    ),
+                );
+                self.bus.publish(GameEvent::RunEnded);
+            } else if success {
+                (self.phase, self.weekly_recap) = Self::advance(game, &mut self.bus);
+                if let Some(notice) = game.take_notice() {
+                    self.result_message.paragraph(Style::正文, notice);
+                }
+            } else {
+                self.result_message.paragraph(Style::提示, "点击 \"进入下一天\" 继续努力");
+            }
+        }
+    }
+
+    fn promote_no(&mut self) {
+        if self.phase != GamePhase::PromotionConfirm {
+            return;
+        }
+        if let Some(game) = &mut self.game_state {
+            (self.phase, self.weekly_recap) = Self::advance(game, &mut self.bus);
+            self.result_message = game.take_notice().unwrap_or_default().into();
+        }
+    }
+
+    /// 答复当前同事请求
+    fn resolve_npc(&mut self, decision: NpcDecision) {
+        if let Some(game) = &mut self.game_state {
+            let pending = game.player.is_alive && game.npc_active_event.is_some();
+            game.resolve_active_npc_event(decision);
+            if pending {
+                self.bus.publish(GameEvent::ChoiceMade { kind: HistoryKind::Npc });
+            }
+        }
+    }
+
+    fn upgrade_housing(&mut self) {
+        if let Some(game) = self.running_game() {
+            let notice = match game.upgrade_housing() {
+                Ok(housing) => format!("🎉 搬家啦！现在住的是{}", housing.label()),
+                Err(err) => err,
+            };
+            self.push_notice(notice);
+        }
+    }
+
+    fn create_alt(&mut self) {
+        if let Some(game) = self.running_game() {
+            let notice = game.create_alt().unwrap_or_else(|err| err);
+            self.push_notice(notice);
+        }
+    }
+
+    fn use_companion(&mut self, index: usize) {
+        if let Some(game) = self.running_game() {
+            let notice = game.use_companion(index).unwrap_or_else(|err| err);
+            self.push_notice(notice);
+        }
+    }
+
+    fn enroll(&mut self, course: Course) {
+        if let Some(game) = self.running_game() {
+            let notice = match game.enroll(course) {
+                Ok(()) => format!("📚 报名了{}，接下来 {} 个晚上都要去上课", course.label(), course.days()),
+                Err(err) => err,
+            };
+            self.push_notice(notice);
+        }
+    }
+
+    fn invest(&mut self, instrument: Instrument) {
+        if let Some(game) = self.running_game() {
+            let notice = match game.invest(instrument) {
+                Ok(()) => format!("💸 买入{} {} 元", instrument.label(), portfolio::LOT),
+                Err(err) => err,
+            };
+            self.push_notice(notice);
+        }
+    }
+
+    fn idle_train(&mut self, rounds: u32) {
+        if let Some(game) = &mut self.game_state {
+            let notice = game.idle_train(rounds);
+            self.push_notice(notice);
+        }
+    }
+
+    fn use_item(&mut self, item: Item) {
+        if let Some(game) = &mut self.game_state {
+            let notice = game.use_item(item).unwrap_or_else(|err| err);
+            self.push_notice(notice);
+        }
+    }
+
+    fn deliver_favor(&mut self) {
+        if let Some(game) = &mut self.game_state {
+            let notice = game.deliver_favor().unwrap_or_else(|err| err);
+            self.push_notice(notice);
+        }
+    }
+
+    fn move_to(&mut self, place: Location) {
+        if let Some(game) = &mut self.game_state {
+            let notice = game.move_to(place).unwrap_or_else(|err| err);
+            self.push_notice(notice);
+        }
+    }
+
+    fn sell_investment(&mut self, instrument: Instrument) {
+        if let Some(game) = self.running_game() {
+            let cash = game.sell_investment(instrument);
+            self.push_notice(format!("💰 清仓{}，回笼 {} 元", instrument.label(), cash));
+        }
+    }
+
+    fn drop_course(&mut self) {
+        if let Some(game) = self.running_game() {
+            let notice = match game.drop_course() {
+                Ok(refund) => format!("🚪 退课了，退还 {} 元", refund),
+                Err(err) => err,
+            };
+            self.push_notice(notice);
+        }
+    }
+
+    /// 进行中的对局；没开局或已结束时为 None（住处、分身、同伴、进修、理财这些操作只在对局中生效）
+    fn running_game(&mut self) -> Option<&mut GameState> {
+        if !self.in_run() {
+            return None;
+        }
+        self.game_state.as_mut()
+    }
+
+    /// 在当前提示后面追加一条通知
+    pub fn push_notice(&mut self, notice: impl Into<Cow<'static, str>>) {
+        self.result_message.paragraph(Style::正文, notice);
+    }
+
+    /// 推进到下一天；夜里接到来电先接电话，刚跨周时再看周报
+    fn advance(game: &mut GameState, bus: &mut EventBus) -> (GamePhase, Option<WeeklyRecap>) {
+        game.next_day();
+        bus.publish(GameEvent::DayAdvanced { day: game.current_day });
+        let recap = game.take_weekly_recap();
+        let phase = if game.night_call.is_some() {
+            GamePhase::NightCall
+        } else if recap.is_some() {
+            GamePhase::WeeklyRecap
+        } else {
+            GamePhase::EventDisplay
+        };
+        (phase, recap)
+    }
+
+    /// 答复深夜来电，然后继续过夜（有周报就看周报）
+    fn answer_night_call(&mut self, decision: NpcDecision) {
+        if self.phase != GamePhase::NightCall {
+            return;
+        }
+        if let Some(game) = &mut self.game_state {
+            if let Some(message) = game.answer_night_call(decision) {
+                self.push_notice(message);
+            }
+        }
+        self.phase = if self.weekly_recap.is_some() {
+            GamePhase::WeeklyRecap
+        } else {
+            GamePhase::EventDisplay
+        };
+    }
+
+    /// 看完周报，开始新的一周
+    fn close_recap(&mut self) {
+        if self.phase == GamePhase::WeeklyRecap {
+            self.weekly_recap = None;
+            self.phase = GamePhase::EventDisplay;
+        }
+    }
+
+    /// 回到开始界面（保留命令行固定的种子）
+    fn restart(&mut self) {
+        *self = GameEngine {
+            launch_seed: self.launch_seed,
+            shuffle: self.shuffle,
+            dark_content: self.dark_content,
+            ..GameEngine::new()
+        };
+    }
+
+    /// 保留名号、种子与设置项，其余状态全部重置
+    fn rematch(&mut self) {
+        let Some(state) = self.game_state.as_ref().map(GameState::rematch) else {
+            return;
+        };
+        *self = GameEngine {
+            player_name: std::mem::take(&mut self.player_name),
+            launch_seed: self.launch_seed,
+            shuffle: self.shuffle,
+            dark_content: self.dark_content,
+            ..GameEngine::new()
+        };
+        self.game_state = Some(state);
+        self.phase = GamePhase::EventDisplay;
+    }
+
+    /// 让策略代打一步，返回 false 表示无法继续（未开局或已结束）
+    pub fn auto_step(&mut self, mut policy: AutoPolicy) -> bool {
+        self.agent_step(&mut policy)
+    }
+
+    /// 让代理看局面走一步，返回 false 表示无法继续（未开局或已结束）；
+    /// 代理给出当前不可用的操作时这一步白走，局面不动
+    pub fn agent_step(&mut self, agent: &mut dyn Agent) -> bool {
+        let Some(view) = StateView::of(self) else {
+            return false;
+        };
+        let action = agent.choose(&view);
+        if view.allows(action) {
+            self.execute(action.into());
+        }
+        true
+    }
+
+    /// 当前事件的标题、描述与选项（占位符已按玩家上下文替换）
+    pub fn current_event_metadata(&self) -> Option<(String, String, Vec<OptionInfo>, bool)> {
+        let game_state = self.game_state.as_ref()?;
+        let context = game_state.template_context();
+        let render_options =
+            |options: &[OptionInfo]| options.iter().map(|o| o.render(&context)).collect();
+        if matches!(self.phase, GamePhase::WeeklyEventDisplay) {
+            let weekly = game_state.get_weekly_event()?;
+            let tag = if weekly.sequel_only { "【周事件·续】" } else { "【周事件】" };
+            Some((
+                format!("{}{}", tag, context.render(&weekly.name)),
+                context.render(&weekly.description),
+                render_options(&weekly.shuffled_options),
+                true,
+            ))
+        } else {
+            let daily = game_state.get_today_event();
+            Some((
+                format!("【日常事件】{}", context.render(&daily.name)),
+                context.render(&daily.description),
+                render_options(&daily.shuffled_options),
+                false,
+            ))
+        }
+    }
+
+    /// 有进行中的对局（没结束）
+    pub fn in_run(&self) -> bool {
+        self.game_state.is_some() && !matches!(self.phase, GamePhase::Start | GamePhase::GameOver)
+    }
+
+    pub fn can_make_daily_choice(&self) -> bool {
+        self.game_state
+            .as_ref()
+            .map(|g| !g.event_chosen_today)
+            .unwrap_or(false)
+    }
+
+    pub fn can_make_weekly_choice(&self) -> bool {
+        self.game_state
+            .as_ref()
+            .map(|g| g.weekly_event_pending())
+            .unwrap_or(false)
+    }
+
+    pub fn can_advance(&self) -> bool {
+        if let Some(state) = self.game_state.as_ref() {
+            matches!(self.phase, GamePhase::EventDisplay)
+                && state.event_chosen_today
+                && (state.today_weekly_event.is_none() || state.weekly_event_chosen_today)
+        } else {
+            false
+        }
+    }
+
+    pub fn weekly_event_pending(&self) -> bool {
+        matches!(self.phase, GamePhase::EventDisplay) && self.can_make_weekly_choice()
+    }
+
+    fn reopen_weekly_event(&mut self) {
+        if self.weekly_event_pending() {
+            self.phase = GamePhase::WeeklyEventDisplay;
+            self.hinted_option = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::balance::BalancePreset;
+    use crate::specialty::SkillTrack;
+
+    /// 固定种子开一局；`tweak` 在开局前调整数值配置
+    fn engine(seed: u64, tweak: impl FnOnce(&mut BalanceConfig)) -> GameEngine {
+        let mut balance = BalancePreset::标准.config();
+        tweak(&mut balance);
+        let mut engine = GameEngine::new();
+        engine.player_name = "测试道友".to_string();
+        engine.start_game(
+            Vec::new(),
+            balance,
+            NarrativeTone::沙雕,
+            Director::default(),
+            CareerPath::default(),
+            Vec::new(),
+            Some(seed),
+        )
+        .expect("名号合规");
+        engine
+    }
+
+    /// 不会猝死、也攒不够晋升的配置，用来单纯地推进天数
+    fn uneventful(balance: &mut BalanceConfig) {
+        balance.death_table = [(i32::MAX, 0.0); 4];
+        balance.zero_pressure_death_chance = 0.0;
+        balance.promotion_skills = [i32::MAX; 6];
+        balance.probation_days = Some(u32::MAX);
+    }
+
+    fn player(engine: &mut GameEngine) -> &mut crate::game::PlayerState {
+        &mut engine.game_state.as_mut().expect("已开局").player
+    }
+
+    /// 技能点与各技能线都够第一次晋升
+    fn qualify(engine: &mut GameEngine, skills: i32) {
+        let player = player(engine);
+        player.skills = skills;
+        for track in SkillTrack::ALL {
+            player.train(Some(track), skills);
+        }
+    }
+
+    /// 把当前阶段走完，直到又能做今天的日常选择
+    fn finish_day(engine: &mut GameEngine) {
+        engine.execute(GameCommand::NextDay);
+        loop {
+            match engine.phase {
+                GamePhase::NightCall => engine.execute(GameCommand::AnswerNightCall(NpcDecision::Reject)),
+                GamePhase::WeeklyRecap => engine.execute(GameCommand::CloseRecap),
+                _ => break,
+            }
+        }
+    }
+
+    #[test]
+    fn start_requires_a_name() {
+        let mut engine = GameEngine::new();
+        let started = engine.start_game(
+            Vec::new(),
+            BalanceConfig::default(),
+            NarrativeTone::沙雕,
+            Director::default(),
+            CareerPath::default(),
+            Vec::new(),
+            Some(1),
+        );
+        assert_eq!(started, Err("名号不能为空".to_string()));
+        assert_eq!(engine.phase, GamePhase::Start);
+        assert!(engine.game_state.is_none());
+    }
+
+    #[test]
+    fn daily_choice_only_once_per_day() {
+        let mut engine = engine(3, uneventful);
+        assert!(engine.can_make_daily_choice());
+        engine.execute(GameCommand::Choose(1));
+        let events = engine.bus.drain();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0], GameEvent::ChoiceMade { kind: HistoryKind::日常 });
+        assert!(matches!(events[1], GameEvent::OptionPicked { kind: HistoryKind::日常, position: 0, .. }));
+        assert!(!engine.can_make_daily_choice());
+
+        let history = engine.game_state.as_ref().unwrap().player.history.len();
+        engine.execute(GameCommand::Choose(2));
+        assert!(engine.result_message.contains("今天已经选择过了"));
+        assert_eq!(engine.game_state.as_ref().unwrap().player.history.len(), history);
+        assert!(engine.bus.drain().is_empty());
+    }
+
+    #[test]
+    fn next_day_advances_and_publishes() {
+        let mut engine = engine(5, uneventful);
+        engine.execute(GameCommand::Choose(1));
+        engine.bus.drain();
+        let day = engine.game_state.as_ref().unwrap().current_day;
+        engine.execute(GameCommand::NextDay);
+        let events = engine.bus.drain();
+        assert_eq!(events[0], GameEvent::DeathRolled { survived: true });
+        assert!(events.contains(&GameEvent::DayAdvanced { day: day + 1 }));
+        assert_ne!(engine.phase, GamePhase::GameOver);
+        assert!(engine.can_make_daily_choice());
+    }
+
+    #[test]
+    fn certain_death_ends_the_run() {
+        let mut engine = engine(7, |balance| balance.death_table = [(0, 1.0); 4]);
+        engine.execute(GameCommand::Choose(1));
+        engine.bus.drain();
+        engine.execute(GameCommand::NextDay);
+        assert_eq!(engine.phase, GamePhase::GameOver);
+        assert_eq!(
+            engine.bus.drain(),
+            vec![GameEvent::DeathRolled { survived: false }, GameEvent::RunEnded]
+        );
+        assert!(!engine.in_run());
+        assert!(!engine.game_state.as_ref().unwrap().player.is_alive);
+        // 结束后的命令不再推进
+        engine.execute(GameCommand::Choose(1));
+        assert!(engine.bus.drain().is_empty());
+    }
+
+    #[test]
+    fn out_of_days_ends_the_run() {
+        let mut engine = engine(9, |balance| {
+            uneventful(balance);
+            balance.max_days = Some(2);
+        });
+        engine.execute(GameCommand::Choose(1));
+        finish_day(&mut engine);
+        assert_eq!(engine.phase, GamePhase::EventDisplay);
+        engine.execute(GameCommand::Choose(1));
+        engine.execute(GameCommand::NextDay);
+        assert_eq!(engine.phase, GamePhase::GameOver);
+        assert!(engine.result_message.contains("时间到"));
+        assert!(engine.bus.drain().contains(&GameEvent::RunEnded));
+    }
+
+    #[test]
+    fn guaranteed_promotion_succeeds() {
+        let mut engine = engine(11, |balance| {
+            uneventful(balance);
+            balance.promotion_skills = [50, 150, 300, 500, 800, 1200];
+            balance.promotion_failure_step = 0.0;
+        });
+        engine.execute(GameCommand::Choose(1));
+        qualify(&mut engine, 60);
+        engine.execute(GameCommand::NextDay);
+        assert_eq!(engine.phase, GamePhase::PromotionConfirm);
+        engine.bus.drain();
+
+        engine.execute(GameCommand::Promote);
+        let events = engine.bus.drain();
+        assert_eq!(events[0], GameEvent::PromotionResolved { success: true, realm: Realm::炼气期 });
+        assert!(events.iter().any(|e| matches!(e, GameEvent::DayAdvanced { .. })));
+        assert_eq!(player(&mut engine).realm_level, 2);
+        assert!(engine.result_message.contains("恭喜晋升"));
+    }
+
+    #[test]
+    fn ascending_past_the_last_realm_ends_the_run() {
+        let mut engine = engine(12, |balance| {
+            uneventful(balance);
+            balance.promotion_skills = [50, 150, 300, 500, 800, 1200];
+            balance.promotion_failure_step = 0.0;
+        });
+        engine.execute(GameCommand::Choose(1));
+        player(&mut engine).realm_level = 6;
+        qualify(&mut engine, 1300);
+        engine.execute(GameCommand::NextDay);
+        assert_eq!(engine.phase, GamePhase::PromotionConfirm);
+        engine.bus.drain();
+
+        engine.execute(GameCommand::Promote);
+        let events = engine.bus.drain();
+        assert_eq!(events[0], GameEvent::PromotionResolved { success: true, realm: Realm::飞升 });
+        assert!(events.contains(&GameEvent::RunEnded));
+        assert_eq!(engine.phase, GamePhase::GameOver);
+        let state = engine.game_state.as_ref().expect("已开局");
+        assert!(state.is_victory(true));
+        assert_eq!(crate::ending::judge(state), crate::ending::Ending::飞升成仙);
+    }
+
+    #[test]
+    fn weekends_bring_leisure_and_the_weekly_event_lands_on_friday() {
+        let mut engine = engine(21, uneventful);
+        let mut seen = Vec::new();
+        while engine.game_state.as_ref().unwrap().current_day <= 7 {
+            let state = engine.game_state.as_ref().unwrap();
+            if seen.last() != Some(&state.current_day) {
+                seen.push(state.current_day);
+                let weekday = crate::week::Weekday::of(state.current_day);
+                assert_eq!(state.get_today_event().weekend, weekday.is_weekend(), "第{}天", state.current_day);
+                assert_eq!(state.get_weekly_event().is_some(), weekday == crate::week::Weekday::周五);
+            }
+            match engine.phase {
+                GamePhase::NightCall => engine.execute(GameCommand::AnswerNightCall(NpcDecision::Reject)),
+                GamePhase::WeeklyRecap => engine.execute(GameCommand::CloseRecap),
+                GamePhase::WeeklyEventDisplay => engine.execute(GameCommand::Choose(1)),
+                _ if engine.can_make_daily_choice() => engine.execute(GameCommand::Choose(1)),
+                _ if engine.weekly_event_pending() => engine.execute(GameCommand::OpenWeeklyEvent),
+                _ => engine.execute(GameCommand::NextDay),
+            }
+            assert_ne!(engine.phase, GamePhase::GameOver);
+        }
+        assert_eq!(seen, (1..=7).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn arena_replays_the_same_seed_identically() {
+        let arena = crate::arena::Arena::new(BalancePreset::标准.config(), 2, 31);
+        let mut agents = crate::arena::builtin_agents();
+        let standings = arena.run(&mut agents);
+        assert_eq!(standings.len(), agents.len());
+        assert!(standings.iter().all(|standing| standing.runs == 2));
+        assert!(standings.windows(2).all(|pair| pair[0].avg_score() >= pair[1].avg_score()));
+
+        let mut cautious = crate::arena::Cautious;
+        let first = arena.play(&mut cautious, 31);
+        let second = arena.play(&mut cautious, 31);
+        assert_eq!((first.days, first.score), (second.days, second.score));
+    }
+
+    #[test]
+    fn arena_ignores_illegal_actions_and_long_agent_names() {
+        use crate::arena::{Action, Agent, StateView};
+
+        /// 一直点晋升的作弊代理，名字还特别长
+        struct Spammer;
+        impl Agent for Spammer {
+            fn name(&self) -> String {
+                "My reinforcement agent v2".to_string()
+            }
+            fn choose(&mut self, _: &StateView) -> Action {
+                Action::Promote
+            }
+        }
+
+        let arena = crate::arena::Arena::new(BalancePreset::标准.config(), 1, 31);
+        let outcome = arena.play(&mut Spammer, 31);
+        assert_eq!((outcome.days, outcome.realm), (0, Realm::凡人境));
+        assert!(!outcome.victory);
+    }
+
+    #[test]
+    fn failed_promotion_costs_half_the_skills() {
+        // 失败率封顶 95%，换几个种子总能碰上失败
+        for seed in 0..20 {
+            let mut engine = engine(seed, |balance| {
+                uneventful(balance);
+                balance.promotion_skills = [50, 150, 300, 500, 800, 1200];
+                balance.promotion_failure_step = 1.0;
+            });
+            engine.execute(GameCommand::Choose(1));
+            qualify(&mut engine, 101);
+            engine.execute(GameCommand::NextDay);
+            assert_eq!(engine.phase, GamePhase::PromotionConfirm);
+            engine.bus.drain();
+
+            engine.execute(GameCommand::Promote);
+            if engine.bus.drain() != vec![GameEvent::PromotionResolved { success: false, realm: Realm::凡人境 }] {
+                continue;
+            }
+            assert_eq!(player(&mut engine).skills, 51);
+            assert_eq!(player(&mut engine).realm_level, 1);
+            assert_eq!(player(&mut engine).promotion_attempts, 1);
+            // 失败后停在确认阶段，下一步照常过夜
+            assert_eq!(engine.phase, GamePhase::PromotionConfirm);
+            return;
+        }
+        panic!("95% 失败率下 20 个种子都晋升成功了");
+    }
+
+    #[test]
+    fn declining_promotion_just_advances() {
+        let mut engine = engine(17, |balance| {
+            uneventful(balance);
+            balance.promotion_skills = [50, 150, 300, 500, 800, 1200];
+        });
+        engine.execute(GameCommand::Choose(1));
+        qualify(&mut engine, 60);
+        engine.execute(GameCommand::NextDay);
+        assert_eq!(engine.phase, GamePhase::PromotionConfirm);
+        let day = engine.game_state.as_ref().unwrap().current_day;
+        engine.bus.drain();
+
+        engine.execute(GameCommand::DeclinePromotion);
+        let events = engine.bus.drain();
+        assert!(!events.iter().any(|e| matches!(e, GameEvent::PromotionResolved { .. })));
+        assert!(events.contains(&GameEvent::DayAdvanced { day: day + 1 }));
+        assert_eq!(player(&mut engine).realm_level, 1);
+    }
+
+    #[test]
+    fn out_of_phase_commands_are_ignored() {
+        let mut engine = engine(19, |balance| {
+            uneventful(balance);
+            balance.promotion_skills = [50, 150, 300, 500, 800, 1200];
+            balance.promotion_failure_step = 0.0;
+        });
+        // 日常还没选，不能过夜；没到晋升确认，晋升和暂缓都不算数
+        engine.execute(GameCommand::NextDay);
+        for _ in 0..50 {
+            engine.execute(GameCommand::Promote);
+        }
+        engine.execute(GameCommand::DeclinePromotion);
+        assert!(engine.bus.drain().is_empty());
+        assert_eq!(engine.phase, GamePhase::EventDisplay);
+        let state = engine.game_state.as_ref().unwrap();
+        assert_eq!((state.current_day, state.player.realm_level), (1, 1));
+        assert!(state.player.history.is_empty());
+
+        // 到了确认阶段，本事不够也晋升不了
+        engine.execute(GameCommand::Choose(1));
+        qualify(&mut engine, 60);
+        engine.execute(GameCommand::NextDay);
+        assert_eq!(engine.phase, GamePhase::PromotionConfirm);
+        player(&mut engine).skills = 0;
+        engine.bus.drain();
+        engine.execute(GameCommand::Promote);
+        assert!(engine.bus.drain().is_empty());
+        assert_eq!(player(&mut engine).realm_level, 1);
+
+        // 确认阶段点“进入下一天”就是暂缓晋升，不会再判一次生死
+        let day = engine.game_state.as_ref().unwrap().current_day;
+        engine.execute(GameCommand::NextDay);
+        let events = engine.bus.drain();
+        assert!(!events.iter().any(|e| matches!(e, GameEvent::DeathRolled { .. })));
+        assert!(events.contains(&GameEvent::DayAdvanced { day: day + 1 }));
+
+        // 对局结束后，搬家、理财、进修这些操作都不再改动局面
+        engine.phase = GamePhase::GameOver;
+        player(&mut engine).savings = 100_000;
+        for command in [
+            GameCommand::UpgradeHousing,
+            GameCommand::CreateAlt,
+            GameCommand::UseCompanion(0),
+            GameCommand::Enroll(Course::ALL[0]),
+            GameCommand::Invest(Instrument::基金),
+        ] {
+            engine.execute(command);
+        }
+        let player = player(&mut engine);
+        assert_eq!(player.savings, 100_000);
+        assert!(player.enrollment.is_none());
+    }
+
+    #[test]
+    fn weekly_event_follows_the_daily_choice() {
+        let mut engine = engine(19, uneventful);
+        while engine.game_state.as_ref().unwrap().get_weekly_event().is_none() {
+            engine.execute(GameCommand::Choose(1));
+            finish_day(&mut engine);
+            assert!(engine.in_run());
+        }
+        // 周事件当天：做完日常直接进入周事件，没做完不能过夜
+        engine.execute(GameCommand::Choose(1));
+        assert_eq!(engine.phase, GamePhase::WeeklyEventDisplay);
+        assert!(engine.result_message.contains("周事件触发"));
+        assert!(!engine.can_advance());
+        engine.bus.drain();
+
+        engine.execute(GameCommand::Choose(1));
+        assert_eq!(engine.bus.drain()[0], GameEvent::ChoiceMade { kind: HistoryKind::周事件 });
+        assert_eq!(engine.phase, GamePhase::EventDisplay);
+        assert!(engine.can_advance());
+        let history = &engine.game_state.as_ref().unwrap().player.history;
+        assert_eq!(history.last().unwrap().kind, HistoryKind::周事件);
+    }
+
+    #[test]
+    fn weekly_event_can_be_postponed_and_reopened() {
+        let mut engine = engine(23, uneventful);
+        player(&mut engine).skills = 10_000;  // 一路选第一个选项，别在等到周事件之前扣成负数出局
+        while engine.game_state.as_ref().unwrap().get_weekly_event().is_none() {
+            engine.execute(GameCommand::Choose(1));
+            finish_day(&mut engine);
+        }
+        engine.execute(GameCommand::Choose(1));
+        engine.phase = GamePhase::EventDisplay;  // 界面上先关掉周事件
+        assert!(engine.weekly_event_pending());
+        assert!(!engine.can_advance());
+
+        engine.execute(GameCommand::OpenWeeklyEvent);
+        assert_eq!(engine.phase, GamePhase::WeeklyEventDisplay);
+        engine.execute(GameCommand::Choose(1));
+        assert!(!engine.weekly_event_pending());
+        assert!(engine.can_advance());
+    }
+
+    #[test]
+    fn office_map_allows_one_move_per_day() {
+        let mut engine = engine(31, uneventful);
+        let game = engine.game_state.as_mut().unwrap();
+        game.today_npcs.clear();
+        let message = game.move_to(Location::机房).unwrap();
+        assert!(message.contains("运维老李") || message.contains("仓库卢"));
+        assert_eq!(game.today_npcs.len(), 1);
+        assert!(game.move_to(Location::茶水间).is_err());
+
+        engine.execute(GameCommand::Choose(1));
+        finish_day(&mut engine);
+        let game = engine.game_state.as_mut().unwrap();
+        assert!(game.move_to(Location::茶水间).is_ok());
+    }
+
+    #[test]
+    fn auto_play_finishes_a_run() {
+        let mut engine = engine(29, |balance| balance.max_days = Some(30));
+        let mut steps = 0;
+        while engine.auto_step(AutoPolicy::GreedySafe) {
+            steps += 1;
+            assert!(steps < 1000, "代打没能结束对局");
+        }
+        assert_eq!(engine.phase, GamePhase::GameOver);
+        assert!(engine.bus.drain().contains(&GameEvent::RunEnded));
+    }
+
+    #[test]
+    fn daily_draws_respect_triggers_and_cooldown() {
+        let mut engine = engine(37, uneventful);
+        // 直接修到化神期：“老板叫你买烟”之类只在低境界出现的事件不该再抽到
+        player(&mut engine).experience = 10_000;
+        // 一路选第一个选项，技能点得够扣，别在抽满 60 天之前扣成负数出局
+        player(&mut engine).skills = 10_000;
+        let cooldown = engine.game_state.as_ref().unwrap().player.balance.repeat_cooldown_days;
+        let mut seen: Vec<(u32, usize)> = Vec::new();
+        for _ in 0..60 {
+            while !engine.can_advance() {
+                if engine.phase == GamePhase::WeeklyEventDisplay || engine.can_make_daily_choice() {
+                    engine.execute(GameCommand::Choose(1));
+                } else {
+                    engine.execute(GameCommand::OpenWeeklyEvent);
+                }
+            }
+            finish_day(&mut engine);
+            let state = engine.game_state.as_ref().unwrap();
+            seen.push((state.current_day, state.today_event.id));
+        }
+        assert!(seen.iter().all(|(_, id)| *id != 23), "化神期还在抽老板劫：{:?}", seen);
+        // 只看随机抽到的常驻事件（剧情线、理财等强制安排的不参与去重）
+        for (i, (day, id)) in seen.iter().enumerate().filter(|(_, (_, id))| *id < 90) {
+            let repeat = seen[..i].iter().rev().find(|(_, earlier)| earlier == id);
+            if let Some((earlier_day, _)) = repeat {
+                assert!(day - earlier_day >= cooldown, "事件 {} 在第 {} 天和第 {} 天重复", id, earlier_day, day);
+            }
+        }
+    }
+
+    /// 选中今天日常事件里原始位置为 `original_index` 的选项
+    fn choose_original(engine: &mut GameEngine, original_index: u32) {
+        let state = engine.game_state.as_ref().unwrap();
+        let position = state
+            .today_event
+            .shuffled_options
+            .iter()
+            .position(|o| o.original_index == original_index)
+            .unwrap();
+        engine.execute(GameCommand::Choose(position as u8 + 1));
+    }
+
+    #[test]
+    fn quest_runs_through_its_stages_and_pays_out() {
+        let mut engine = engine(41, uneventful);
+        let state = engine.game_state.as_mut().unwrap();
+        state.today_event = state.daily_events.iter().find(|e| e.id == 0).unwrap().clone();
+        choose_original(&mut engine, 0);
+        assert!(engine.result_message.contains("智眼项目上线"));
+
+        // 每个阶段都走捷径：技术债越欠越多，后面的选项越来越累
+        let mut last_pressure = None;
+        for stage in 0..4 {
+            if engine.weekly_event_pending() {
+                engine.execute(GameCommand::OpenWeeklyEvent);
+                engine.execute(GameCommand::Choose(1));
+            }
+            finish_day(&mut engine);
+            let state = engine.game_state.as_ref().unwrap();
+            let quest = state.quest.as_ref().expect("项目进行中");
+            assert_eq!(quest.stage, stage);
+            assert_eq!(state.today_event.id, 110 + stage);
+            let shortcut = state.today_event.shuffled_options.iter().find(|o| o.original_index == 2).unwrap();
+            if let Some(last) = last_pressure {
+                assert!(shortcut.value.1 > last, "技术债没有加压");
+            }
+            last_pressure = Some(shortcut.value.1);
+            choose_original(&mut engine, 2);
+        }
+
+        let state = engine.game_state.as_ref().unwrap();
+        assert!(state.quest.is_none());
+        let entry = state.player.history.last().unwrap();
+        assert_eq!(entry.kind, HistoryKind::项目);
+        assert_eq!(entry.choice, "带病上线");
+    }
+
+    #[test]
+    fn shuffle_setting_fixes_the_order_without_changing_the_events() {
+        // 当前摆出来的事件（周事件优先）各位置上的原始选项
+        let order = |engine: &GameEngine| -> Vec<u32> {
+            let state = engine.game_state.as_ref().unwrap();
+            let options = match state.get_weekly_event().filter(|_| engine.phase == GamePhase::WeeklyEventDisplay) {
+                Some(weekly) => &weekly.shuffled_options,
+                None => &state.today_event.shuffled_options,
+            };
+            options.iter().map(|o| o.original_index).collect()
+        };
+        let choose_a = |engine: &mut GameEngine| {
+            let pick = order(engine).iter().position(|&idx| idx == 0).unwrap();
+            engine.execute(GameCommand::Choose(pick as u8 + 1));
+        };
+        let mut shuffled = engine(17, uneventful);
+        let mut fixed = GameEngine { shuffle: ShuffleMode::固定顺序, ..GameEngine::new() };
+        fixed.player_name = "测试道友".to_string();
+        let mut balance = BalancePreset::标准.config();
+        uneventful(&mut balance);
+        fixed
+            .start_game(
+                Vec::new(),
+                balance,
+                NarrativeTone::沙雕,
+                Director::default(),
+                CareerPath::default(),
+                Vec::new(),
+                Some(17),
+            )
+            .expect("名号合规");
+        for _ in 0..10 {
+            let ids = |engine: &GameEngine| engine.game_state.as_ref().unwrap().today_event.id;
+            assert_eq!(ids(&shuffled), ids(&fixed));
+            let mut sorted = order(&fixed);
+            sorted.sort();
+            assert_eq!(order(&fixed), sorted);
+            // 两边都选原始的 A 选项，保证走的是同一条路
+            for engine in [&mut shuffled, &mut fixed] {
+                choose_a(engine);
+                if engine.phase == GamePhase::WeeklyEventDisplay {
+                    choose_a(engine);
+                }
+                finish_day(engine);
+            }
+        }
+        fixed.execute(GameCommand::Rematch);
+        assert_eq!(fixed.shuffle, ShuffleMode::固定顺序);
+    }
+
+    #[test]
+    fn restart_keeps_launch_seed_and_rematch_keeps_name() {
+        let mut engine = engine(31, uneventful);
+        engine.launch_seed = Some(31);
+        engine.dark_content = DarkContent::略过;
+        engine.execute(GameCommand::Choose(1));
+        engine.execute(GameCommand::Rematch);
+        assert_eq!(engine.player_name, "测试道友");
+        assert_eq!(engine.dark_content, DarkContent::略过, "重开同一种子不能把内容设置改回原样");
+        assert_eq!(engine.phase, GamePhase::EventDisplay);
+        assert!(engine.can_make_daily_choice());
+
+        engine.execute(GameCommand::Restart);
+        assert_eq!(engine.phase, GamePhase::Start);
+        assert!(engine.game_state.is_none());
+        assert_eq!(engine.launch_seed, Some(31));
+        assert_eq!(engine.dark_content, DarkContent::略过);
+    }
+
+    #[test]
+    fn saved_run_resumes_with_the_same_dice() {
+        let mut engine = engine(17, uneventful);
+        for _ in 0..3 {
+            engine.execute(GameCommand::Choose(1));
+            finish_day(&mut engine);
+        }
+        let json = engine.save().unwrap().to_json().unwrap();
+        let mut resumed = GameEngine::new();
+        resumed.resume(crate::save::SaveGame::from_json(&json).unwrap());
+        assert_eq!(resumed.phase, engine.phase);
+
+        // 读档后随机流接着存档时的进度走，和没关过窗口的那局一模一样
+        for _ in 0..5 {
+            for engine in [&mut engine, &mut resumed] {
+                engine.execute(GameCommand::Choose(2));
+                finish_day(engine);
+            }
+        }
+        let (a, b) = (engine.game_state.as_ref().unwrap(), resumed.game_state.as_ref().unwrap());
+        assert_eq!(a.today_event.id, b.today_event.id);
+        assert_eq!((a.player.skills, a.player.pressure), (b.player.skills, b.player.pressure));
+        assert_eq!(a.player.history.len(), b.player.history.len());
+
+        let newer = json.replacen(
+            &format!("\"version\":{}", crate::save::SAVE_VERSION),
+            "\"version\":999",
+            1,
+        );
+        assert!(crate::save::SaveGame::from_json(&newer).is_err());
+
+        // v1 存档还没有掉落流，读档时补上
+        let mut v1: serde_json::Value = serde_json::from_str(&json).unwrap();
+        v1["version"] = 1.into();
+        v1["state"].as_object_mut().unwrap().remove("loot");
+        let v1 = v1.to_string();
+        assert!(crate::save::SaveGame::is_outdated(&v1));
+        let upgraded = crate::save::SaveGame::from_json(&v1).unwrap();
+        assert_eq!(upgraded.version, crate::save::SAVE_VERSION);
+        assert_eq!(upgraded.state.current_day, 4);
+    }
+
+    #[test]
+    fn resume_reconciles_missing_content() {
+        let mut engine = engine(17, uneventful);
+        let state = engine.game_state.as_mut().unwrap();
+        state.banned_events = vec![9999];
+        state.favor = Some(crate::favor::Favor {
+            npc: "早已离职的同事".to_string(),
+            goal: crate::favor::FavorGoal::交付(Item::咖啡),
+            deadline: 5,
+            calm_days: 0,
+        });
+        let mut json: serde_json::Value = serde_json::from_str(&engine.save().unwrap().to_json().unwrap()).unwrap();
+        json["state"]["active_packs"] = serde_json::json!(["端午"]);
+
+        let save = crate::save::SaveGame::from_json(&json.to_string()).unwrap();
+        assert_eq!(save.warnings.len(), 3);
+        let mut resumed = GameEngine::new();
+        resumed.resume(save);
+        let state = resumed.game_state.as_ref().unwrap();
+        assert!(state.banned_events.is_empty() && state.active_packs.is_empty() && state.favor.is_none());
+    }
+
+    #[test]
+    fn empty_event_pools_fall_back() {
+        let mut engine = engine(21, uneventful);
+        let state = engine.game_state.as_mut().unwrap();
+        state.banned_events = state.daily_events.iter().map(|e| e.id).collect();
+        for event in &mut state.weekly_events {
+            event.sequel_only = true;
+        }
+        player(&mut engine).skills = 10_000;
+        while engine.game_state.as_ref().unwrap().get_weekly_event().is_none() {
+            engine.execute(GameCommand::Choose(1));
+            finish_day(&mut engine);
+        }
+        assert!(engine.in_run());
+    }
+
+    #[test]
+    fn resume_checks_against_the_current_library_not_the_embedded_copy() {
+        let mut engine = engine(17, uneventful);
+        let state = engine.game_state.as_mut().unwrap();
+        // 存档时的事件库里还有 9999 号事件，现在的事件库里已经没有了
+        let mut retired = state.daily_events[0].clone();
+        retired.id = 9999;
+        state.daily_events.push(retired);
+        state.banned_events = vec![9999];
+        state.npc_master[0].name = "早已离职的同事".to_string();
+        let save = crate::save::SaveGame::from_json(&engine.save().unwrap().to_json().unwrap()).unwrap();
+        assert_eq!(save.warnings.len(), 2);
+        assert!(save.state.banned_events.is_empty());
+        assert!(save.state.daily_events.iter().all(|e| e.id != 9999));
+        assert_eq!(save.state.daily_events.len(), crate::events::library().daily_events().len());
+        assert!(save.state.npc_master.iter().all(|npc| npc.name != "早已离职的同事"));
+    }
+
+    #[test]
+    fn affinity_unlocks_a_bond_story_and_resentment_brings_a_grudge() {
+        let mut engine = engine(41, uneventful);
+        let state = engine.game_state.as_mut().unwrap();
+        state.relations.adjust("运维老李", crate::bond::BOND_AFFINITY);
+        state.relations.adjust("HR郭", crate::gift::MIN_AFFINITY);
+        engine.execute(GameCommand::Choose(1));
+        finish_day(&mut engine);
+        assert_eq!(engine.game_state.as_ref().unwrap().today_event.id, 114);
+
+        engine.execute(GameCommand::Choose(1));
+        let state = engine.game_state.as_ref().unwrap();
+        assert!(state.player.keepsakes.contains(&crate::bond::Keepsake::自愈脚本));
+        assert!(state.relations.has_bonded("运维老李"));
+
+        let mut grudge = None;
+        for _ in 0..10 {
+            while !engine.can_advance() {
+                if engine.phase == GamePhase::WeeklyEventDisplay || engine.can_make_daily_choice() {
+                    engine.execute(GameCommand::Choose(1));
+                } else {
+                    engine.execute(GameCommand::OpenWeeklyEvent);
+                }
+            }
+            finish_day(&mut engine);
+            let state = engine.game_state.as_ref().unwrap();
+            assert_ne!(state.today_event.id, 114, "专属剧情只出场一次");
+            if let Some(weekly) = &state.today_weekly_event {
+                grudge = Some((weekly.id, weekly.description.clone()));
+                break;
+            }
+        }
+        let (id, description) = grudge.expect("十天内应有周事件");
+        assert_eq!(id, crate::bond::GRUDGE_EVENT);
+        assert!(description.contains("HR郭"));
+        let relations = &engine.game_state.as_ref().unwrap().relations;
+        assert_eq!(relations.affinity("HR郭"), crate::gift::MIN_AFFINITY + crate::bond::GRUDGE_RELIEF);
+    }
+
+    #[test]
+    fn rebirth_boons_carry_into_the_next_run() {
+        use crate::rebirth::{Boon, MetaProgress};
+
+        let mut progress = MetaProgress::default();
+        assert!(progress.buy(Boon::宿慧).is_err(), "没有仙缘点不能兑换");
+        progress.reincarnate(100);
+        progress.buy(Boon::宿慧).unwrap();
+        progress.buy(Boon::护体).unwrap();
+        progress.buy(Boon::前世记忆).unwrap();
+        assert_eq!(progress.karma, 100 - 10 - 15 - 30);
+        assert!(progress.buy(Boon::前世记忆).is_err(), "前世记忆只有一级");
+
+        let plain = BalancePreset::标准.config();
+        let mut engine = engine(43, |balance| {
+            *balance = balance.clone().with_boons(&progress);
+            balance.max_days = Some(60);
+        });
+        let balance = &engine.game_state.as_ref().unwrap().player.balance;
+        assert!(balance.hidden_events);
+        assert!(balance.death_chance(70) < plain.death_chance(70));
+        assert_eq!(player(&mut engine).skills, crate::rebirth::SKILLS_PER_LEVEL);
+
+        // 隐藏事件进了抽取池，打满六十天总该遇到一次；重开同一种子加成照样生效
+        let mut met_hidden = false;
+        while engine.in_run() && !met_hidden {
+            met_hidden = engine.game_state.as_ref().unwrap().today_event.hidden;
+            engine.agent_step(&mut crate::policy::AutoPolicy::GreedySafe);
+        }
+        assert!(met_hidden);
+        engine.execute(GameCommand::Rematch);
+        assert_eq!(player(&mut engine).skills, crate::rebirth::SKILLS_PER_LEVEL);
+    }
+
+    #[test]
+    fn momentum_spreads_pressure_over_nights() {
+        use crate::balance::PressureModel;
+
+        let mut engine = engine(47, |balance| {
+            balance.pressure_model = PressureModel::惯性;
+            balance.death_table = [(i32::MAX, 0.0); 4];
+            balance.zero_pressure_death_chance = 0.0;
+        });
+        let player = player(&mut engine);
+        player.gain_reward(0, 30);
+        assert_eq!((player.pressure, player.pressure_momentum), (15, 15));
+        assert_eq!(player.simulate_option((0, 20)).pressure, 25, "预览只算当场生效的部分");
+
+        // 攒着的惯性每晚落地一半，最后一点一次落完
+        let mut nights = 0;
+        while player.pressure_momentum != 0 {
+            player.settle_pressure();
+            nights += 1;
+        }
+        assert_eq!((player.pressure, nights), (30, 5));
+
+        // 道具的提示按当场实际降了多少报，没落地的另外说
+        let state = engine.game_state.as_mut().unwrap();
+        state.player.inventory.add(Item::咖啡);
+        let message = state.use_item(Item::咖啡).unwrap();
+        let relief = 30 - state.player.pressure;
+        assert!(relief < crate::items::COFFEE_RELIEF && state.player.pressure_momentum < 0);
+        assert!(message.contains(&format!("压力 -{}，", relief)), "{}", message);
+
+        // 压力见底时减压不会攒成之后几晚的负惯性，顶满时加压也不会攒成正惯性
+        let player = &mut state.player;
+        player.pressure = 0;
+        player.pressure_momentum = 0;
+        player.gain_reward(0, -40);
+        assert_eq!((player.pressure, player.pressure_momentum), (0, 0));
+        player.pressure = 100;
+        player.gain_reward(0, 40);
+        assert_eq!((player.pressure, player.pressure_momentum), (100, 0));
+    }
+}
@@ -0,0 +1,329 @@
+//! 事件库：日常事件与周事件的文案和数值放在 content/events.json，开局时从这里构建。
+//! 桌面端还可以在数据目录的 events/ 下放同样格式的文件，按 id 覆盖内置事件或新增事件。
+
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::commute::Commute;
+use crate::content::ContentFile;
+use crate::finance::Housing;
+use crate::game::{DailyEvent, OptionInfo, WeeklyEvent};
+use crate::season::ContentPack;
+use crate::specialty::SkillTrack;
+use crate::tag::EventTag;
+use crate::template;
+use crate::trigger::Trigger;
+
+/// 每个事件的选项数（A/B/C）
+pub const OPTIONS_PER_EVENT: usize = 3;
+
+/// 数据文件中的一个选项（按 A/B/C 的原始顺序排列）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct OptionDef {
+    value: (i32, i32),  // (技能点, 压力值)
+    desc: String,       // 第一行是标题，后面是说明
+    story: String,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    money: i32,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    gray_risk: i32,
+    /// 黑色幽默味太重时的中性替代文案（带这一项即视为需要内容提示）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mild: Option<String>,
+}
+
+/// 数据文件中的一个日常事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DailyDef {
+    id: usize,
+    name: String,
+    description: String,
+    tag: EventTag,
+    options: Vec<OptionDef>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    track: Option<SkillTrack>,
+    #[serde(default, skip_serializing_if = "is_default_trigger")]
+    trigger: Trigger,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pack: Option<ContentPack>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    commutes: Vec<Commute>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    min_housing: Option<Housing>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    arc_only: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    hidden: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    weekend: bool,
+}
+
+/// 数据文件中的一个周事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct WeeklyDef {
+    id: usize,
+    name: String,
+    description: String,
+    tag: EventTag,
+    options: Vec<OptionDef>,
+    #[serde(default, skip_serializing_if = "is_default_trigger")]
+    trigger: Trigger,
+    /// 连续剧：(原始选项位置, 下周续集事件 id)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    follow_ups: Vec<(u32, usize)>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    sequel_only: bool,
+}
+
+fn is_zero(value: &i32) -> bool {
+    *value == 0
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+fn is_default_trigger(trigger: &Trigger) -> bool {
+    *trigger == Trigger::default()
+}
+
+fn build_options(options: &[OptionDef]) -> Vec<OptionInfo> {
+    options
+        .iter()
+        .zip(0u32..)
+        .map(|(option, original_index)| OptionInfo {
+            value: option.value,
+            desc: option.desc.clone(),
+            story: option.story.clone(),
+            original_index,
+            money: option.money,
+            gray_risk: option.gray_risk,
+            mild: option.mild.clone(),
+        })
+        .collect()
+}
+
+/// 一整套日常事件与周事件
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EventLibrary {
+    #[serde(default)]
+    daily: Vec<DailyDef>,
+    #[serde(default)]
+    weekly: Vec<WeeklyDef>,
+}
+
+impl EventLibrary {
+    /// 解析并校验一份事件数据（内置数据或玩家自制的覆盖文件）
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let library: EventLibrary =
+            serde_json::from_str(text).map_err(|e| format!("事件数据格式错误: {}", e))?;
+        library.validate()?;
+        Ok(library)
+    }
+
+    /// 内置事件库
+    pub fn builtin() -> &'static EventLibrary {
+        static BUILTIN: OnceLock<EventLibrary> = OnceLock::new();
+        BUILTIN.get_or_init(|| {
+            EventLibrary::parse(ContentFile::Events.text())
+                .unwrap_or_else(|err| panic!("内置事件数据必须有效: {}", err))
+        })
+    }
+
+    /// 内置事件库叠加目录下所有 .json 覆盖文件（桌面端，按文件名顺序叠加）；
+    /// 读不了或叠加后校验不过的文件跳过，返回各自的原因
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_overrides(dir: &std::path::Path) -> (Self, Vec<String>) {
+        let mut library = Self::builtin().clone();
+        let mut errors = Vec::new();
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return (library, errors);
+        };
+        let mut paths: Vec<_> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        paths.sort();
+        for path in paths {
+            let name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+            // 覆盖文件单独看不完整（续集可能指向内置事件），叠加后再整体校验
+            let applied = std::fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|text| {
+                    serde_json::from_str(&text).map_err(|e| format!("事件数据格式错误: {}", e))
+                })
+                .and_then(|other| library.overlay(other));
+            if let Err(err) = applied {
+                errors.push(format!("{}: {}", name, err));
+            }
+        }
+        (library, errors)
+    }
+
+    /// 用另一份数据覆盖：id 相同的事件整个替换，新 id 追加在后面；合并后重新校验
+    /// （续集要指向存在的周事件），返回被替换与新增的事件数
+    pub fn overlay(&mut self, other: EventLibrary) -> Result<(usize, usize), String> {
+        let mut merged = self.clone();
+        let (mut replaced, mut added) = (0, 0);
+        for def in other.daily {
+            match merged.daily.iter_mut().find(|d| d.id == def.id) {
+                Some(slot) => {
+                    *slot = def;
+                    replaced += 1;
+                }
+                None => {
+                    merged.daily.push(def);
+                    added += 1;
+                }
+            }
+        }
+        for def in other.weekly {
+            match merged.weekly.iter_mut().find(|w| w.id == def.id) {
+                Some(slot) => {
+                    *slot = def;
+                    replaced += 1;
+                }
+                None => {
+                    merged.weekly.push(def);
+                    added += 1;
+                }
+            }
+        }
+        merged.validate()?;
+        *self = merged;
+        Ok((replaced, added))
+    }
+
+    /// 检查数据是否能用：id 不重复、每个事件三个选项、触发条件合理、续集指向存在的周事件、
+    /// 占位符都认识。只报第一处问题
+    pub fn validate(&self) -> Result<(), String> {
+        let mut seen = Vec::new();
+        for def in &self.daily {
+            let label = format!("日常事件 {}「{}」", def.id, def.name);
+            if seen.contains(&def.id) {
+                return Err(format!("{}：id 重复", label));
+            }
+            seen.push(def.id);
+            check_event(&label, &def.name, &def.description, &def.options, &def.trigger)?;
+        }
+        seen.clear();
+        for def in &self.weekly {
+            let label = format!("周事件 {}「{}」", def.id, def.name);
+            if seen.contains(&def.id) {
+                return Err(format!("{}：id 重复", label));
+            }
+            seen.push(def.id);
+            check_event(&label, &def.name, &def.description, &def.options, &def.trigger)?;
+            for (option, next) in &def.follow_ups {
+                if *option as usize >= def.options.len() {
+                    return Err(format!("{}：续集挂在不存在的选项 {} 上", label, option));
+                }
+                if !self.weekly.iter().any(|w| w.id == *next) {
+                    return Err(format!("{}：续集事件 {} 不存在", label, next));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 日常事件数与周事件数
+    pub fn counts(&self) -> (usize, usize) {
+        (self.daily.len(), self.weekly.len())
+    }
+
+    pub fn daily_events(&self) -> Vec<DailyEvent> {
+        self.daily
+            .iter()
+            .map(|def| DailyEvent {
+                id: def.id,
+                name: def.name.clone(),
+                description: def.description.clone(),
+                shuffled_options: build_options(&def.options),
+                pack: def.pack,
+                arc_only: def.arc_only,
+                hidden: def.hidden,
+                min_housing: def.min_housing,
+                commutes: def.commutes.clone(),
+                track: def.track,
+                tag: def.tag,
+                trigger: def.trigger,
+                weekend: def.weekend,
+            })
+            .collect()
+    }
+
+    pub fn weekly_events(&self) -> Vec<WeeklyEvent> {
+        self.weekly
+            .iter()
+            .map(|def| WeeklyEvent {
+                id: def.id,
+                name: def.name.clone(),
+                description: def.description.clone(),
+                shuffled_options: build_options(&def.options),
+                follow_ups: def.follow_ups.clone(),
+                sequel_only: def.sequel_only,
+                tag: def.tag,
+                trigger: def.trigger,
+            })
+            .collect()
+    }
+}
+
+fn check_event(
+    label: &str,
+    name: &str,
+    description: &str,
+    options: &[OptionDef],
+    trigger: &Trigger,
+) -> Result<(), String> {
+    let fail = |problem: String| format!("{}：{}", label, problem);
+    if name.trim().is_empty() {
+        return Err(fail("名称不能为空".to_string()));
+    }
+    if options.len() != OPTIONS_PER_EVENT {
+        return Err(fail(format!("需要 {} 个选项，实际 {} 个", OPTIONS_PER_EVENT, options.len())));
+    }
+    if !(trigger.weight.is_finite() && trigger.weight > 0.0) {
+        return Err(fail("抽取权重必须是正数".to_string()));
+    }
+    if let (Some(min), Some(max)) = (trigger.min_realm, trigger.max_realm) {
+        if min > max {
+            return Err(fail("境界下限高于上限".to_string()));
+        }
+    }
+    if trigger.pressure.is_some_and(|(low, high)| low > high) {
+        return Err(fail("压力区间下限高于上限".to_string()));
+    }
+    if trigger.days.is_some_and(|(first, last)| first > last) {
+        return Err(fail("天数区间下限高于上限".to_string()));
+    }
+    template::validate(name).map_err(fail)?;
+    template::validate(description).map_err(fail)?;
+    for option in options {
+        if option.desc.trim().is_empty() {
+            return Err(fail("选项说明不能为空".to_string()));
+        }
+        template::validate(&option.desc).map_err(fail)?;
+        template::validate(&option.story).map_err(fail)?;
+        if let Some(mild) = &option.mild {
+            template::validate(mild).map_err(fail)?;
+        }
+    }
+    Ok(())
+}
+
+/// 本次运行装入的事件库（桌面端带上了玩家的覆盖文件）
+static INSTALLED: OnceLock<EventLibrary> = OnceLock::new();
+
+/// 装入带覆盖的事件库，之后开的每一局都用它（只认第一次）
+pub fn install(library: EventLibrary) {
+    let _ = INSTALLED.set(library);
+}
+
+/// 开局用的事件库：装入过就用装入的，否则用内置的
+pub fn library() -> &'static EventLibrary {
+    INSTALLED.get().unwrap_or_else(|| EventLibrary::builtin())
+}
+
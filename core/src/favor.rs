@@ -0,0 +1,126 @@
+//! 同事委托：每周开头有一位同事找你帮个忙，限期几天（帮忙找来一件道具，或连续几天把压力稳在线下）。
+//! 按期办成好感大涨，还能拿到只有委托才给的锦囊；过期没办成，对方多少会有点失望。
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{GameRng, NpcEncounter};
+use crate::items::Item;
+
+/// 委托的期限（天）
+pub const FAVOR_DAYS: u32 = 5;
+/// 办成委托的好感加成
+pub const FAVOR_AFFINITY: i32 = 30;
+/// 委托过期的好感惩罚
+pub const FAVOR_LETDOWN: i32 = 10;
+/// 稳压力委托：压力要压在这条线以下
+const CALM_PRESSURE: i32 = 50;
+/// 稳压力委托：需要连续稳住的天数
+const CALM_DAYS: u32 = 3;
+/// 同事可能托你找的道具
+const WANTED: [Item; 2] = [Item::咖啡, Item::晋升符];
+
+/// 委托的内容
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FavorGoal {
+    /// 找来一件道具交给对方
+    交付(Item),
+    /// 连续若干天收工时压力低于某条线（对方也想看你状态好点，好把活交给你）
+    稳住压力 { below: i32, days: u32 },
+}
+
+/// 委托过完一天后的状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FavorStatus {
+    进行中,
+    完成,
+    过期,
+}
+
+/// 进行中的委托
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Favor {
+    pub npc: String,
+    pub goal: FavorGoal,
+    pub deadline: u32,   // 最后一天（含）
+    pub calm_days: u32,  // 稳压力委托已连续稳住的天数
+}
+
+impl Favor {
+    /// 在 `day` 这天随机挑一位同事发布委托（没有同事时不发布）
+    pub fn post(rng: &mut GameRng, npcs: &[NpcEncounter], day: u32) -> Option<Favor> {
+        let npc = npcs.choose(rng)?.name.clone();
+        let goal = if rng.gen_bool(0.5) {
+            FavorGoal::交付(*WANTED.choose(rng).expect("委托道具不能为空"))
+        } else {
+            FavorGoal::稳住压力 { below: CALM_PRESSURE, days: CALM_DAYS }
+        };
+        Some(Favor { npc, goal, deadline: day + FAVOR_DAYS - 1, calm_days: 0 })
+    }
+
+    /// 委托内容，如“帮林玄找一件☕ 咖啡”
+    pub fn describe(&self) -> String {
+        match self.goal {
+            FavorGoal::交付(item) => format!("帮{}找一件{} {}", self.npc, item.icon(), item),
+            FavorGoal::稳住压力 { below, days } => {
+                format!("{}希望你连续 {} 天收工时压力低于 {}", self.npc, days, below)
+            }
+        }
+    }
+
+    /// 进度，如“已稳住 1/3 天 · 还剩 3 天”
+    pub fn progress(&self, day: u32) -> String {
+        let left = self.deadline.saturating_sub(day) + 1;
+        match self.goal {
+            FavorGoal::交付(_) => format!("还剩 {} 天", left),
+            FavorGoal::稳住压力 { days, .. } => {
+                format!("已稳住 {}/{} 天 · 还剩 {} 天", self.calm_days, days, left)
+            }
+        }
+    }
+
+    /// 需要交付的道具（稳压力委托没有）
+    pub fn wanted_item(&self) -> Option<Item> {
+        match self.goal {
+            FavorGoal::交付(item) => Some(item),
+            FavorGoal::稳住压力 { .. } => None,
+        }
+    }
+
+    /// 第 `day` 天收工：按当晚的压力记进度，返回委托状态
+    pub fn end_day(&mut self, day: u32, pressure: i32) -> FavorStatus {
+        if let FavorGoal::稳住压力 { below, days } = self.goal {
+            self.calm_days = if pressure < below { self.calm_days + 1 } else { 0 };
+            if self.calm_days >= days {
+                return FavorStatus::完成;
+            }
+        }
+        if day >= self.deadline {
+            FavorStatus::过期
+        } else {
+            FavorStatus::进行中
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calm_streak_resets_and_expires() {
+        let goal = FavorGoal::稳住压力 { below: 50, days: 3 };
+        let mut favor = Favor { npc: "林玄".to_string(), goal, deadline: 5, calm_days: 0 };
+        assert_eq!(favor.end_day(1, 40), FavorStatus::进行中);
+        assert_eq!(favor.end_day(2, 60), FavorStatus::进行中);
+        assert_eq!(favor.calm_days, 0);
+        assert_eq!(favor.end_day(3, 10), FavorStatus::进行中);
+        assert_eq!(favor.end_day(4, 10), FavorStatus::进行中);
+        assert_eq!(favor.end_day(5, 10), FavorStatus::完成);
+
+        let mut late = Favor { npc: "林玄".to_string(), goal, deadline: 2, calm_days: 0 };
+        late.end_day(1, 10);
+        assert_eq!(late.end_day(2, 10), FavorStatus::过期);
+    }
+}
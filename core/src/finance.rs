@@ -0,0 +1,125 @@
+//! 个人财务：每月发工资、扣房租和奶茶钱；存款见底就进入“破产”剧情线，直到重新上岸。
+//! 存款还可以用来改善居住条件。
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::PlayerState;
+
+/// 一个月的天数（每满这么多天发一次工资）
+pub const DAYS_PER_MONTH: u32 = 30;
+/// 开局存款
+pub const STARTING_SAVINGS: i32 = 1000;
+/// 破产剧情线的日常事件 id，按顺序轮流出现直到存款回正
+pub const DESPERATION_ARC: [usize; 3] = [90, 91, 92];
+
+const BASE_SALARY: i32 = 6000;
+const RAISE_PER_REALM: i32 = 3000;
+
+/// 居住条件：花存款升级，每月住房开销更高，但每天自动减压，并解锁居家事件
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Housing {
+    #[default]
+    合租,
+    整租,
+    买房,
+}
+
+impl Housing {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Housing::合租 => "🏚 合租",
+            Housing::整租 => "🏠 整租",
+            Housing::买房 => "🏡 买房",
+        }
+    }
+
+    /// 每月住房开销（房租或房贷）
+    pub fn monthly_cost(&self) -> i32 {
+        match self {
+            Housing::合租 => 4500,
+            Housing::整租 => 5500,
+            Housing::买房 => 7000,
+        }
+    }
+
+    pub fn cost_label(&self) -> &'static str {
+        match self {
+            Housing::买房 => "房贷",
+            _ => "房租",
+        }
+    }
+
+    /// 每天自动减少的压力
+    pub fn daily_relief(&self) -> i32 {
+        match self {
+            Housing::合租 => 0,
+            Housing::整租 => 1,
+            Housing::买房 => 2,
+        }
+    }
+
+    /// 下一档居住条件与升级所需的一次性花费（押金/首付）
+    pub fn upgrade(&self) -> Option<(Housing, i32)> {
+        match self {
+            Housing::合租 => Some((Housing::整租, 11000)),
+            Housing::整租 => Some((Housing::买房, 80000)),
+            Housing::买房 => None,
+        }
+    }
+}
+
+/// 月度薪资单
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Payslip {
+    pub month: u32,
+    #[serde(default)]
+    pub housing: Housing,
+    pub salary: i32,
+    pub rent: i32,
+    pub milk_tea_cups: i32,
+    pub milk_tea: i32,
+    pub savings_after: i32,
+}
+
+impl Payslip {
+    /// 按最近一个月的每日快照结算（压力越大奶茶喝得越多，单价随行情浮动）
+    pub fn settle(month: u32, player: &PlayerState) -> Self {
+        let salary = Self::salary_for(player.realm_level);
+        let rent = player.housing.monthly_cost();
+        let milk_tea_cups: i32 = player
+            .recent_snapshots(DAYS_PER_MONTH as usize)
+            .iter()
+            .map(|s| cups_for(s.pressure))
+            .sum();
+        let milk_tea = milk_tea_cups * player.market.milk_tea_price;
+        Payslip {
+            month,
+            housing: player.housing,
+            salary,
+            rent,
+            milk_tea_cups,
+            milk_tea,
+            savings_after: player.savings + salary - rent - milk_tea,
+        }
+    }
+
+    /// 月薪：每升一个境界涨一档
+    pub fn salary_for(realm_level: u32) -> i32 {
+        BASE_SALARY + RAISE_PER_REALM * (realm_level as i32 - 1)
+    }
+
+    /// 本月结余
+    pub fn net(&self) -> i32 {
+        self.salary - self.rent - self.milk_tea
+    }
+}
+
+/// 一天喝几杯奶茶：压力每 40 点多一杯
+pub fn cups_for(pressure: i32) -> i32 {
+    1 + pressure / 40
+}
+
+/// 距离下次发工资还有几天
+pub fn days_until_payday(days_played: u32) -> u32 {
+    DAYS_PER_MONTH - days_played % DAYS_PER_MONTH
+}
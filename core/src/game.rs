@@ -0,0 +1,2815 @@
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
+use crate::almanac::Almanac;
+use crate::arc::{ArcProgress, StoryArc};
+use crate::ascension::{self, Handicap};
+use crate::audit::Audit;
+use crate::balance::BalanceConfig;
+use crate::bond::{self, BondStory, Keepsake};
+use crate::commute::Commute;
+use crate::companion::{Ability, Companion, COMPANION_SLOTS};
+use crate::course::{Course, Enrollment, Perk};
+use crate::specialty::{CareerPath, SkillTrack, SkillTracks};
+use crate::tag::{EventTag, TagStats};
+use crate::recap::WeeklyRecap;
+use crate::director::Director;
+use crate::events;
+use crate::favor::{self, Favor, FavorStatus};
+use crate::opensource::{self, EveningPlan, OpenSource};
+use crate::gift::{self, Relations};
+use crate::grind::GrindTracker;
+use crate::idle;
+use crate::items::{self, Inventory, Item};
+use crate::market::Market;
+use crate::portfolio::{self, Instrument, Portfolio};
+use crate::quest::{Quest, QuestOutcome, QuestProgress};
+use crate::mood::{NpcMood, Weather};
+use crate::nightcall::NightCall;
+use crate::onboarding;
+use crate::office::{self, Location, Visit};
+use crate::roster::{self, Appearance};
+use crate::finance::{self, Housing, Payslip};
+use crate::scenario::Scenario;
+use crate::season::ContentPack;
+use crate::shuffle::{ShuffleMode, ShufflePolicy};
+use crate::speedrun::Split;
+use crate::template::{self, TemplateContext};
+use crate::tone::NarrativeTone;
+use crate::trigger::{RecentEvents, Trigger};
+use crate::tuning;
+use crate::week::{self, Weekday};
+use crate::world::WorldView;
+
+/// 对局的随机流：与 rand 的 `StdRng` 同为 ChaCha12（同一种子抽出的数一样），但能随存档序列化
+pub type GameRng = ChaCha12Rng;
+
+/// 修仙境界枚举，基于经验值进度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Realm {
+    凡人境,    // 0~50
+    炼气期,    // 51~150
+    筑基期,    // 151~300
+    结丹期,    // 301~500
+    化神期,    // 501~800
+    大乘期,    // 801~1200
+    飞升,      // 1201+
+}
+
+impl fmt::Display for Realm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Realm::凡人境 => write!(f, "凡人境"),
+            Realm::炼气期 => write!(f, "炼气期"),
+            Realm::筑基期 => write!(f, "筑基期"),
+            Realm::结丹期 => write!(f, "结丹期"),
+            Realm::化神期 => write!(f, "化神期"),
+            Realm::大乘期 => write!(f, "大乘期"),
+            Realm::飞升 => write!(f, "飞升"),
+        }
+    }
+}
+
+impl Realm {
+    /// 该境界下的顶头上司
+    pub fn boss_title(&self) -> &'static str {
+        match self {
+            Realm::凡人境 => "组长",
+            Realm::炼气期 => "经理",
+            Realm::筑基期 => "总监",
+            Realm::结丹期 => "VP",
+            Realm::化神期 => "CEO",
+            Realm::大乘期 => "董事长",
+            Realm::飞升 => "天道",
+        }
+    }
+
+    /// 晋升等级对应的境界（1=凡人境 … 7=飞升）
+    pub fn from_level(level: u32) -> Self {
+        match level {
+            0 | 1 => Realm::凡人境,
+            2 => Realm::炼气期,
+            3 => Realm::筑基期,
+            4 => Realm::结丹期,
+            5 => Realm::化神期,
+            6 => Realm::大乘期,
+            _ => Realm::飞升,
+        }
+    }
+
+    /// 根据经验值获取对应的修仙境界（`thresholds` 为飞升之前各境界的经验上限）
+    pub fn from_experience(exp: u32, thresholds: &[u32; 6]) -> Self {
+        const REALMS: [Realm; 6] = [
+            Realm::凡人境,
+            Realm::炼气期,
+            Realm::筑基期,
+            Realm::结丹期,
+            Realm::化神期,
+            Realm::大乘期,
+        ];
+        thresholds
+            .iter()
+            .zip(REALMS)
+            .find(|(limit, _)| exp <= **limit)
+            .map(|(_, realm)| realm)
+            .unwrap_or(Realm::飞升)
+    }
+}
+
+/// 选项信息（包含数值和描述）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionInfo {
+    pub value: (i32, i32),  // (技能点, 压力值)
+    pub desc: String,
+    pub story: String,      // 选择后触发的剧情
+    pub original_index: u32,  // 原始位置 0=A, 1=B, 2=C
+    pub money: i32,         // 对存款的影响（大多数选项为0）
+    pub gray_risk: i32,     // 灰色操作累积的审计风险（0 为正当选项）
+    #[serde(default)]
+    pub mild: Option<String>,  // 黑色段子的中性替代文案（见内容提示设置）
+}
+
+impl OptionInfo {
+    /// 选项标题（说明文字的第一行）
+    pub fn title(&self) -> &str {
+        self.desc.split('\n').next().unwrap_or("")
+    }
+
+    /// 选项摘要（说明文字标题之后的部分）
+    pub fn summary(&self) -> &str {
+        self.desc.split_once('\n').map(|(_, rest)| rest).unwrap_or("")
+    }
+
+    /// 推断选项的行为倾向
+    pub fn archetype(&self) -> ChoiceArchetype {
+        ChoiceArchetype::classify(&self.desc, self.value)
+    }
+
+    /// 选项结果性质提示
+    pub fn outcome_hint(&self) -> OutcomeHint {
+        OutcomeHint::from_values(self.value)
+    }
+
+    /// 按玩家上下文替换描述与剧情中的占位符
+    pub fn render(&self, context: &TemplateContext) -> OptionInfo {
+        OptionInfo {
+            desc: context.render(&self.desc),
+            story: context.render(&self.story),
+            mild: self.mild.as_deref().map(|mild| context.render(mild)),
+            ..self.clone()
+        }
+    }
+}
+
+/// 选项结果性质（只给方向提示，不透露具体数值）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutcomeHint {
+    冒险,
+    稳妥,
+    赌运,
+}
+
+impl OutcomeHint {
+    /// 压力大涨视为冒险，技能可能倒扣视为赌运，其余视为稳妥
+    pub fn from_values((skill, pressure): (i32, i32)) -> Self {
+        if pressure >= 5 {
+            OutcomeHint::冒险
+        } else if skill < 0 {
+            OutcomeHint::赌运
+        } else {
+            OutcomeHint::稳妥
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            OutcomeHint::冒险 => "⚔️",
+            OutcomeHint::稳妥 => "🛡️",
+            OutcomeHint::赌运 => "🎲",
+        }
+    }
+
+    pub fn tooltip(&self) -> &'static str {
+        match self {
+            OutcomeHint::冒险 => "冒险：收益不小，但压力会明显上涨",
+            OutcomeHint::稳妥 => "稳妥：压力可控，不太会翻车",
+            OutcomeHint::赌运 => "赌运：看似轻松，技能点可能倒扣",
+        }
+    }
+}
+
+/// 选择倾向（用于人物志与统计）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ChoiceArchetype {
+    内卷,
+    甩锅,
+    摸鱼,
+}
+
+impl ChoiceArchetype {
+    pub const ALL: [ChoiceArchetype; 3] = [
+        ChoiceArchetype::内卷,
+        ChoiceArchetype::甩锅,
+        ChoiceArchetype::摸鱼,
+    ];
+
+    /// 根据选项说明和数值推断倾向：先看是否在甩锅，再看是否硬扛压力
+    pub fn classify(desc: &str, value: (i32, i32)) -> Self {
+        const BLAME_KEYWORDS: [&str; 5] = ["甩锅", "推给", "丢给", "拒绝优化", "说'这是"];
+        if BLAME_KEYWORDS.iter().any(|k| desc.contains(k)) {
+            return ChoiceArchetype::甩锅;
+        }
+        Self::from_values(value)
+    }
+
+    /// 只根据数值推断：技能和压力同时上涨视为内卷，否则视为摸鱼
+    pub fn from_values((skill, pressure): (i32, i32)) -> Self {
+        if skill > 0 && pressure > 0 {
+            ChoiceArchetype::内卷
+        } else {
+            ChoiceArchetype::摸鱼
+        }
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            ChoiceArchetype::内卷 => 0,
+            ChoiceArchetype::甩锅 => 1,
+            ChoiceArchetype::摸鱼 => 2,
+        }
+    }
+
+    /// 人物志里使用的性格标签
+    pub fn persona(&self) -> &'static str {
+        match self {
+            ChoiceArchetype::内卷 => "硬刚型",
+            ChoiceArchetype::甩锅 => "甩锅型",
+            ChoiceArchetype::摸鱼 => "养生型",
+        }
+    }
+}
+
+impl fmt::Display for ChoiceArchetype {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChoiceArchetype::内卷 => write!(f, "内卷"),
+            ChoiceArchetype::甩锅 => write!(f, "甩锅"),
+            ChoiceArchetype::摸鱼 => write!(f, "摸鱼"),
+        }
+    }
+}
+
+/// 各类选择倾向的次数统计（本局或跨局累计）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchetypeTally {
+    counts: [u32; 3],  // 按 ChoiceArchetype::ALL 的顺序
+}
+
+impl ArchetypeTally {
+    pub fn from_history(history: &[HistoryEntry]) -> Self {
+        let mut tally = ArchetypeTally::default();
+        for archetype in history.iter().filter_map(|entry| entry.archetype) {
+            tally.counts[archetype.index()] += 1;
+        }
+        tally
+    }
+
+    pub fn count(&self, archetype: ChoiceArchetype) -> u32 {
+        self.counts[archetype.index()]
+    }
+
+    pub fn total(&self) -> u32 {
+        self.counts.iter().sum()
+    }
+
+    /// 某类选择的占比（0~1，没有记录时为 0）
+    pub fn share(&self, archetype: ChoiceArchetype) -> f32 {
+        match self.total() {
+            0 => 0.0,
+            total => self.count(archetype) as f32 / total as f32,
+        }
+    }
+
+    pub fn merged(&self, other: &ArchetypeTally) -> ArchetypeTally {
+        let mut merged = *self;
+        for (mine, theirs) in merged.counts.iter_mut().zip(other.counts) {
+            *mine += theirs;
+        }
+        merged
+    }
+}
+
+/// 历史记录类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HistoryKind {
+    日常,
+    周事件,
+    Npc,
+    /// 项目线走完时的结算
+    项目,
+    /// 晋升的结果
+    晋升,
+}
+
+impl HistoryKind {
+    pub const ALL: [HistoryKind; 5] =
+        [HistoryKind::日常, HistoryKind::周事件, HistoryKind::Npc, HistoryKind::项目, HistoryKind::晋升];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            HistoryKind::日常 => "日常",
+            HistoryKind::周事件 => "周事件",
+            HistoryKind::Npc => "NPC",
+            HistoryKind::项目 => "项目",
+            HistoryKind::晋升 => "晋升",
+        }
+    }
+
+    /// 是不是玩家对事件或同事请求做的抉择（项目结算、晋升结果不算）
+    pub fn is_choice(&self) -> bool {
+        matches!(self, HistoryKind::日常 | HistoryKind::周事件 | HistoryKind::Npc)
+    }
+}
+
+/// 结构化历史记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub day: u32,
+    pub kind: HistoryKind,
+    pub event_id: Option<usize>,
+    pub event_name: String,
+    pub choice: String,
+    pub option_index: Option<u32>,  // 原始选项位置 0=A, 1=B, 2=C
+    pub archetype: Option<ChoiceArchetype>,
+    pub skill_delta: i32,
+    pub pressure_delta: i32,
+    pub story: String,
+    #[serde(default)]
+    pub elapsed: Duration,  // 当天开局到做出这个选择实际花的游玩时间（暂停不计）
+}
+
+impl HistoryEntry {
+    /// 日常/周事件的选择记录
+    pub fn event(kind: HistoryKind, event_id: usize, event_name: &str, option: &OptionInfo) -> Self {
+        HistoryEntry {
+            day: 0,
+            kind,
+            event_id: Some(event_id),
+            event_name: event_name.to_string(),
+            choice: option.title().to_string(),
+            option_index: Some(option.original_index),
+            archetype: Some(option.archetype()),
+            skill_delta: option.value.0,
+            pressure_delta: option.value.1,
+            story: option.story.clone(),
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// NPC 互动记录
+    pub fn npc(npc_name: &str, option: &NpcOption, decision: NpcDecision) -> Self {
+        let (label, archetype) = match decision {
+            NpcDecision::Accept => ("同意", ChoiceArchetype::from_values(option.reward)),
+            NpcDecision::Reject => ("拒绝", ChoiceArchetype::摸鱼),
+            NpcDecision::Negotiate => ("讨价还价", ChoiceArchetype::from_values(option.reward)),
+        };
+        HistoryEntry {
+            day: 0,
+            kind: HistoryKind::Npc,
+            event_id: None,
+            event_name: npc_name.to_string(),
+            choice: format!("{} ({})", option.detail, label),
+            option_index: None,
+            archetype: Some(archetype),
+            skill_delta: option.reward.0,
+            pressure_delta: option.reward.1,
+            story: String::new(),
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// 项目线结算记录
+    pub fn quest(quest: Quest, outcome: &QuestOutcome, story: String) -> Self {
+        HistoryEntry {
+            day: 0,
+            kind: HistoryKind::项目,
+            event_id: None,
+            event_name: quest.label().to_string(),
+            choice: outcome.title.to_string(),
+            option_index: None,
+            archetype: None,
+            skill_delta: outcome.skills,
+            pressure_delta: outcome.pressure,
+            story,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// 晋升结果记录（失败时技能点折半）
+    pub fn promotion(success: bool, realm: Realm, lost_skills: i32, story: &str) -> Self {
+        HistoryEntry {
+            day: 0,
+            kind: HistoryKind::晋升,
+            event_id: None,
+            event_name: "晋升".to_string(),
+            choice: if success { format!("晋升成功（{}）", realm) } else { "晋升失败".to_string() },
+            option_index: None,
+            archetype: None,
+            skill_delta: -lost_skills,
+            pressure_delta: 0,
+            story: story.to_string(),
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// 事件名、选择或剧情里含有关键字（空关键字总是匹配）
+    pub fn matches(&self, keyword: &str) -> bool {
+        let keyword = keyword.trim();
+        keyword.is_empty()
+            || self.event_name.contains(keyword)
+            || self.choice.contains(keyword)
+            || self.story.contains(keyword)
+    }
+
+    /// 净收益：技能收益减去压力代价
+    pub fn merit(&self) -> i32 {
+        self.skill_delta - self.pressure_delta
+    }
+}
+
+impl fmt::Display for HistoryEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let prefix = match self.kind {
+            HistoryKind::日常 => "",
+            HistoryKind::周事件 => "【周事件】",
+            HistoryKind::Npc => "【NPC】",
+            HistoryKind::项目 => "【项目】",
+            HistoryKind::晋升 => "【晋升】",
+        };
+        write!(f, "第{}天: {}{} - {}", self.day, prefix, self.event_name, self.choice)?;
+        if !self.story.is_empty() {
+            write!(f, "\n💬 {}", self.story)?;
+        }
+        write!(
+            f,
+            " [技能{}|压力{}]",
+            format_delta(self.skill_delta),
+            format_delta(self.pressure_delta)
+        )
+    }
+}
+
+/// 每日事件结构（定义在 content/events.json，见 `events` 模块）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyEvent {
+    pub id: usize,
+    pub name: String,
+    pub description: String,
+    pub shuffled_options: Vec<OptionInfo>,  // 打乱后的选项（1,2,3为显示位置）
+    pub pack: Option<ContentPack>,          // 所属限时内容包（None 为常驻内容）
+    pub arc_only: bool,                     // 只在剧情线中出现（如破产），不参与随机抽取
+    pub hidden: bool,                       // 隐藏事件：转世兑换“前世记忆”后才进入抽取池
+    pub min_housing: Option<Housing>,       // 需要至少这样的居住条件才会出现（居家事件）
+    pub commutes: Vec<Commute>,             // 通勤劫：只在这些通勤方式下出现（空为不限）
+    pub track: Option<SkillTrack>,          // 技能成长记到哪条技能线（None 为泛泛成长）
+    pub tag: EventTag,                      // 题材分类
+    pub trigger: Trigger,                   // 触发条件与抽取权重
+    pub weekend: bool,                      // 周末休闲事件：只在周六周日出现，工作日不抽
+}
+
+impl DailyEvent {
+    /// 紧张程度：各选项压力变化的平均值（负数表示偏轻松）
+    pub fn tension(&self) -> f32 {
+        let total: i32 = self.shuffled_options.iter().map(|o| o.value.1).sum();
+        total as f32 / self.shuffled_options.len().max(1) as f32
+    }
+
+    /// 是否算“奇遇”：有一个既涨技能又降压力的选项
+    pub fn is_fortune(&self) -> bool {
+        self.shuffled_options
+            .iter()
+            .any(|o| o.value.0 > 0 && o.value.1 < 0)
+    }
+
+    /// 按本局的排序策略重排选项（每次事件触发时调用）
+    pub fn reshuffle(&mut self, policy: &ShufflePolicy, rng: &mut GameRng) {
+        policy.arrange(&mut self.shuffled_options, rng);
+    }
+}
+
+/// 周事件结构（定义在 content/events.json，见 `events` 模块）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeeklyEvent {
+    pub id: usize,
+    pub name: String,
+    pub description: String,
+    pub shuffled_options: Vec<OptionInfo>,  // 打乱后的选项
+    pub follow_ups: Vec<(u32, usize)>,      // 连续剧：(原始选项位置, 下周续集事件id)
+    pub sequel_only: bool,                  // 只作为续集出现，不参与随机抽取
+    pub tag: EventTag,                      // 题材分类
+    pub trigger: Trigger,                   // 触发条件与抽取权重（续集不受限）
+}
+
+/// 连续多少天没遇到奇遇后强制安排一次
+pub const FORTUNE_PITY_DAYS: u32 = 5;
+/// 某位 NPC 连续多少天没出现后强制安排其出场
+pub const NPC_PITY_DAYS: u32 = 7;
+
+/// 保底计数器：运气太差时强制安排奇遇或久未露面的 NPC
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PityTimers {
+    pub days_since_fortune: u32,
+    pub npc_absence: Vec<(String, u32)>,  // (NPC 名字, 连续缺席天数)
+}
+
+impl PityTimers {
+    pub fn fortune_due(&self) -> bool {
+        self.days_since_fortune >= FORTUNE_PITY_DAYS
+    }
+
+    pub fn record_event(&mut self, event: &DailyEvent) {
+        if event.is_fortune() {
+            self.days_since_fortune = 0;
+        } else {
+            self.days_since_fortune += 1;
+        }
+    }
+
+    /// 缺席最久且已到保底天数的 NPC
+    pub fn overdue_npc(&self) -> Option<&str> {
+        self.npc_absence
+            .iter()
+            .filter(|(_, days)| *days >= NPC_PITY_DAYS)
+            .max_by_key(|(_, days)| *days)
+            .map(|(name, _)| name.as_str())
+    }
+
+    pub fn record_npcs(&mut self, all: &[NpcEncounter], present: &[NpcEncounter]) {
+        for npc in all {
+            let seen = present.iter().any(|p| p.name == npc.name);
+            match self.npc_absence.iter_mut().find(|(name, _)| *name == npc.name) {
+                Some((_, days)) => *days = if seen { 0 } else { *days + 1 },
+                None => self.npc_absence.push((npc.name.clone(), if seen { 0 } else { 1 })),
+            }
+        }
+    }
+}
+
+/// 连续剧状态：上周的选择决定了下周的周事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SagaState {
+    pub next_event_id: usize,
+    pub previous_event: String,
+}
+
+/// NPC 互动信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NpcEncounter {
+    pub name: String,
+    pub description: String,
+    pub ai_model: String,
+    pub prompt_templates: Vec<String>,
+    pub accept_option: NpcOption,
+    pub reject_option: NpcOption,
+    pub recruit_affinity: i32,  // 邀请加入团队所需的好感
+    pub haggle: Option<Haggle>,  // 可以讨价还价的请求
+    pub mood_prompts: HashMap<NpcMood, Vec<String>>,  // 特定心情下的对话模板
+    pub oracle: Vec<String>,  // 卦辞模板（会算命的 NPC 才有）
+    #[serde(default)]
+    pub appears: Appearance,  // 出场条件
+    pub mood: NpcMood,
+    pub interacted: bool,
+}
+
+/// NPC 选项结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NpcOption {
+    pub summary: String,
+    pub detail: String,
+    pub reward: (i32, i32), // (技能点, 压力值)
+    #[serde(default)]
+    pub gray_risk: i32,     // 灰色操作累积的审计风险
+}
+
+/// 讨价还价：沟通线加临场发挥达到门槛就能谈下更好的条件，谈崩了比直接拒绝还糟
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Haggle {
+    pub threshold: i32,
+    pub summary: String,  // 玩家开出的条件
+    pub win: NpcOption,
+    pub lose: NpcOption,
+}
+
+/// 当前激活的 NPC 事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NpcActiveEvent {
+    pub npc_index: usize,
+    pub prompt: String,
+    pub line: String,  // NPC 说的那句台词（不含卦辞、喜好提示），可以换成 AI 生成的
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum NpcDecision {
+    Accept,
+    Reject,
+    Negotiate,
+}
+
+impl NpcEncounter {
+    /// 随机挑一句对话，有符合今天心情的模板时优先用
+    fn random_dialogue(&self, rng: &mut GameRng) -> String {
+        let templates = match self.mood_prompts.get(&self.mood) {
+            Some(prompts) if !prompts.is_empty() => prompts,
+            _ => &self.prompt_templates,
+        };
+        templates
+            .choose(rng)
+            .cloned()
+            .unwrap_or_else(|| self.description.clone())
+    }
+
+    /// 算一卦：把真实的出局概率和晋升成功率套进卦辞
+    fn divine(&self, player: &PlayerState, rng: &mut GameRng) -> Option<String> {
+        let template = self.oracle.choose(rng)?;
+        let promotion = match player.promotion_odds() {
+            Some(odds) => format!("{:.0}%", odds * 100.0),
+            None => "全无（天门未开）".to_string(),
+        };
+        Some(
+            template
+                .replace("{death}", &format!("{:.1}%", player.tonight_risk() * 100.0))
+                .replace("{promotion}", &promotion),
+        )
+    }
+}
+
+impl WeeklyEvent {
+    /// 某个选项对应的续集事件id
+    pub fn follow_up_for(&self, option_index: u32) -> Option<usize> {
+        self.follow_ups
+            .iter()
+            .find(|(idx, _)| *idx == option_index)
+            .map(|(_, next)| *next)
+    }
+
+    /// 按本局的排序策略重排选项（每次事件触发时调用）
+    pub fn reshuffle(&mut self, policy: &ShufflePolicy, rng: &mut GameRng) {
+        policy.arrange(&mut self.shuffled_options, rng);
+    }
+}
+
+/// 选项推演结果
+#[derive(Debug, Clone, Copy)]
+pub struct OptionProjection {
+    pub skills: i32,
+    pub pressure: i32,
+    pub pressure_band: &'static str,
+    pub risk_tonight: f32,                // 今晚出局概率
+    pub days_to_promotion: Option<u32>,   // 按最近节奏估算的晋升天数
+}
+
+/// 每日结束时的属性快照
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct DailySnapshot {
+    pub day: u32,
+    pub skills: i32,
+    pub pressure: i32,
+    pub experience: u32,
+    #[serde(default)]
+    pub pressure_momentum: i32,  // 当晚还没落地的压力惯性
+}
+
+/// 玩家状态
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerState {
+    pub name: String,
+    pub experience: u32,   // 经验值（决定修仙境界）
+    pub skills: i32,       // 技能点
+    pub pressure: i32,     // 压力值 (0-100)
+    pub days_played: u32,  // 玩了多少天
+    pub is_alive: bool,    // 是否存活
+    pub realm_level: u32,  // 当前晋升等级（1=凡人境，2=炼气期，等）
+    pub promotion_attempts: u32,  // 晋升尝试次数（用于计算失败率）
+    pub history: Vec<HistoryEntry>,  // 历史记录
+    pub zero_pressure_streak: u32,  // 连续零压力天数
+    pub died_from_zero_pressure: bool,  // 是否因为零压力猝死
+    #[serde(default)]
+    pub probation: Option<u32>,  // 绩效改进计划剩余天数（技能转负后的宽限期）
+    #[serde(default)]
+    pub probation_used: bool,  // 本局是否已经用掉了绩效改进计划
+    pub snapshots: Vec<DailySnapshot>,  // 每日属性快照
+    #[serde(default)]
+    pub balance: BalanceConfig,  // 本局数值配置（境界门槛、天数上限）
+    #[serde(default)]
+    pub savings: i32,  // 存款（元）
+    #[serde(default)]
+    pub housing: Housing,  // 居住条件
+    #[serde(default)]
+    pub commute: Commute,  // 通勤方式
+    #[serde(default)]
+    pub tracks: SkillTracks,  // 工程/算法/沟通三条技能线
+    #[serde(default)]
+    pub career: CareerPath,  // 职业路线（决定晋升看专精还是均衡）
+    #[serde(default)]
+    pub enrollment: Option<Enrollment>,  // 正在上的进修课（占用晚上）
+    #[serde(default)]
+    pub perks: Vec<Perk>,  // 结业获得的永久加成
+    #[serde(default)]
+    pub reputation: u32,  // 行业声望（技术分享等攒下的名气）
+    #[serde(default)]
+    pub evening: EveningPlan,  // 没课的晚上怎么过
+    #[serde(default)]
+    pub open_source: OpenSource,  // 业余维护的开源项目
+    #[serde(default)]
+    pub market: Market,  // 奶茶价格与股市行情
+    #[serde(default)]
+    pub portfolio: Portfolio,  // 基金、股票、加密货币持仓
+    #[serde(default)]
+    pub grind: GrindTracker,  // 同类安全选择的热度（防刷）
+    #[serde(default)]
+    pub keepsakes: Vec<Keepsake>,  // 同事专属剧情送的信物（永久加成）
+    #[serde(default)]
+    pub inventory: Inventory,  // 背包里的道具
+    #[serde(default)]
+    pub play_time: Duration,  // 本局累计游玩时间（暂停、最小化时不计）
+    #[serde(default)]
+    pub day_time: Duration,   // 今天已经玩了多久
+    #[serde(default)]
+    pub pressure_momentum: i32,  // 还没落地的压力变化（惯性压力模型）
+}
+
+impl PlayerState {
+    /// 创建新玩家
+    pub fn new(name: String) -> Self {
+        PlayerState {
+            name,
+            experience: 0,
+            skills: 0,
+            pressure: 0,
+            days_played: 0,
+            is_alive: true,
+            realm_level: 1,
+            promotion_attempts: 0,
+            history: Vec::new(),
+            zero_pressure_streak: 0,
+            died_from_zero_pressure: false,
+            probation: None,
+            probation_used: false,
+            snapshots: Vec::new(),
+            balance: BalanceConfig::default(),
+            savings: finance::STARTING_SAVINGS,
+            housing: Housing::default(),
+            commute: Commute::default(),
+            tracks: SkillTracks::default(),
+            career: CareerPath::default(),
+            enrollment: None,
+            perks: Vec::new(),
+            reputation: 0,
+            evening: EveningPlan::default(),
+            open_source: OpenSource::default(),
+            market: Market::default(),
+            portfolio: Portfolio::default(),
+            grind: GrindTracker::default(),
+            keepsakes: Vec::new(),
+            inventory: Inventory::default(),
+            play_time: Duration::ZERO,
+            day_time: Duration::ZERO,
+            pressure_momentum: 0,
+        }
+    }
+
+    /// 获取当前修仙境界
+    pub fn get_realm(&self) -> Realm {
+        Realm::from_experience(self.experience, &self.balance.realm_thresholds)
+    }
+
+    /// 基础得分：经验 + 每个已突破境界 100 分，活到最后再加 200 分
+    pub fn score(&self) -> u32 {
+        let survival = if self.is_alive { 200 } else { 0 };
+        self.experience + self.realm_level.saturating_sub(1) * 100 + survival
+    }
+
+    /// 事件选项按当前境界调整后的实际结果（内容文件里的数值与境界无关）
+    pub fn scale_outcome(&self, value: (i32, i32)) -> (i32, i32) {
+        self.balance.scale_outcome(self.get_realm() as usize, value)
+    }
+
+    /// 增加经验值和技能点
+    pub fn gain_reward(&mut self, skill_points: i32, pressure_change: i32) {
+        // 经验值只在获得正向技能点时增长，避免负数溢出
+        if skill_points > 0 {
+            self.experience = self
+                .experience
+                .saturating_add(skill_points as u32);
+        }
+        self.skills = self.skills.saturating_add(skill_points);
+        let (now, deferred) = self.balance.pressure_model.split(pressure_change);
+        self.pressure = (self.pressure + now).clamp(0, 100);
+        self.pressure_momentum += deferred;
+        self.bound_momentum();
+    }
+
+    /// 过夜时让攒着的压力惯性落地一部分
+    pub fn settle_pressure(&mut self) {
+        self.bound_momentum();
+        let released = self.balance.pressure_model.release(self.pressure_momentum);
+        self.pressure_momentum -= released;
+        self.pressure = (self.pressure + released).clamp(0, 100);
+    }
+
+    /// 惯性落地后压力也得在 0~100 之内：压力见底时再减压、顶满时再加压，都不再往惯性里攒
+    fn bound_momentum(&mut self) {
+        self.pressure_momentum = self.pressure_momentum.clamp(-self.pressure, 100 - self.pressure);
+    }
+
+    /// 技能成长记到对应的技能线上（`track` 为 None 表示泛泛成长）
+    pub fn train(&mut self, track: Option<SkillTrack>, skill_points: i32) {
+        self.tracks.train(track, skill_points, self.realm_level);
+    }
+
+    /// 检查猝死（基于压力值或技能点）
+    pub fn check_death(&mut self, rng: &mut GameRng) {
+        self.died_from_zero_pressure = false;
+
+        if self.pressure == 0 {
+            self.zero_pressure_streak = self.zero_pressure_streak.saturating_add(1);
+        } else {
+            self.zero_pressure_streak = 0;
+        }
+
+        if self.zero_pressure_streak >= self.balance.zero_pressure_grace
+            && rng.gen::<f32>() < self.balance.zero_pressure_death_chance
+        {
+            self.is_alive = false;
+            self.died_from_zero_pressure = true;
+            return;
+        }
+
+        // 技能点为负数：第一次进绩效改进计划，计划到期还没转正就被开除
+        if self.skills < 0 {
+            match self.probation {
+                Some(days_left) if days_left > 0 => self.probation = Some(days_left - 1),
+                None if !self.probation_used && self.balance.probation_days.is_some() => {
+                    self.probation = self.balance.probation_days;
+                    self.probation_used = true;
+                }
+                _ => {
+                    self.is_alive = false;
+                    return;
+                }
+            }
+        } else {
+            self.probation = None;
+        }
+
+        if rng.gen::<f32>() < self.balance.death_chance(self.pressure) {
+            self.is_alive = false;
+        }
+    }
+
+    /// 选择某个选项后当晚出局的概率（被开除、咸鱼猝死、过劳猝死）
+    pub fn option_risk(&self, (skill, pressure): (i32, i32)) -> f32 {
+        if self.skills.saturating_add(skill) < 0 && !self.has_probation_left() {
+            return 1.0;
+        }
+        let (pressure, _) = self.balance.pressure_model.split(pressure);
+        let new_pressure = (self.pressure + pressure).clamp(0, 100);
+        let zero_risk = if new_pressure == 0 && self.zero_pressure_streak + 1 >= self.balance.zero_pressure_grace {
+            self.balance.zero_pressure_death_chance
+        } else {
+            0.0
+        };
+        zero_risk + (1.0 - zero_risk) * self.balance.death_chance(new_pressure)
+    }
+
+    /// 什么都不做的话今晚出局的概率
+    pub fn tonight_risk(&self) -> f32 {
+        self.option_risk((0, 0))
+    }
+
+    /// 现在去冲击晋升的成功率（条件不够时为 None）
+    pub fn promotion_odds(&self) -> Option<f32> {
+        self.can_promote().then(|| 1.0 - self.promotion_failure_rate())
+    }
+
+    /// 咸鱼预警：已经连续零压力、再躺一晚就可能咸鱼猝死时，返回提示文案
+    pub fn slacking_warning(&self) -> Option<String> {
+        let at_risk = self.zero_pressure_streak + 1 >= self.balance.zero_pressure_grace;
+        (self.pressure == 0 && at_risk).then(|| {
+            format!(
+                "😶 你已经{}天毫无压力……再躺下去，每晚都有 {:.0}% 的概率咸鱼猝死",
+                self.zero_pressure_streak.max(1),
+                self.balance.zero_pressure_death_chance * 100.0
+            )
+        })
+    }
+
+    /// 技能转负时还有没有绩效改进计划兜底
+    fn has_probation_left(&self) -> bool {
+        match self.probation {
+            Some(days_left) => days_left > 0,
+            None => !self.probation_used && self.balance.probation_days.is_some(),
+        }
+    }
+
+    /// 获取死亡提示文本
+    pub fn get_death_message(&self) -> &'static str {
+        if self.died_from_zero_pressure {
+            return "你这样子天天都没有压力，跟咸鱼有什么分别？？？？";
+        }
+        if self.skills < 0 {
+            return "你小子被开除了，一个技能点都没有还他妈都来应聘，啥也不会";
+        }
+        match self.pressure {
+            20..=29 => "脆弱的弟弟，这就死了",
+            30..=49 => "啊？就这就累死了？还差得远呢，投胎去吧",
+            50..=69 => "辛苦了，但是还不够努力，死的太慢了呢",
+            70..=100 => "该你去死了啊，这么卷不要命了啊",
+            _ => "游戏结束",
+        }
+    }
+
+    /// 死亡提示的中性说法（内容设置里选了替换时用）
+    pub fn mild_death_message(&self) -> &'static str {
+        if self.died_from_zero_pressure {
+            return "连续太久毫无压力，你的修仙路就此停下";
+        }
+        if self.skills < 0 {
+            return "技能跌破零，公司请你另谋高就";
+        }
+        match self.pressure {
+            20..=100 => "身体发出了警报，这一局到此为止",
+            _ => "游戏结束",
+        }
+    }
+
+    /// 技能软上限：远超下一次晋升要求的技能点折成存款，返回提示（没有溢出时为 None）
+    pub fn convert_overflow(&mut self) -> Option<String> {
+        let overflow = self.balance.overflow(self.skills, self.skill_requirement());
+        (overflow > 0).then(|| {
+            let money = overflow * self.balance.overflow_rate;
+            self.skills -= overflow;
+            self.savings += money;
+            format!("💱 技能点远超晋升要求，溢出的 {} 点折成了存款 +{} 元", overflow, money)
+        })
+    }
+
+    /// 当前等级晋升所需技能点
+    pub fn skill_requirement(&self) -> i32 {
+        // 等级1~6 分别对应 凡人境→炼气期 … 大乘期→飞升
+        self.realm_level
+            .checked_sub(1)
+            .and_then(|idx| self.balance.promotion_skills.get(idx as usize))
+            .copied()
+            .unwrap_or(9999)  // 已达最高等级
+    }
+
+    /// 本局天数是否已到上限（轻量模式等预设）
+    pub fn out_of_days(&self) -> bool {
+        self.balance
+            .max_days
+            .is_some_and(|max| self.days_played + 1 >= max)
+    }
+
+    /// 检查是否可以晋升
+    pub fn can_promote(&self) -> bool {
+        let requirement = self.skill_requirement();
+        self.skills >= requirement && self.career.meets(&self.tracks, requirement)
+    }
+
+    /// 压力区间名称（与猝死概率表对应）
+    pub fn pressure_band(pressure: i32) -> &'static str {
+        match pressure {
+            0..=19 => "安全",
+            20..=29 => "轻压",
+            30..=49 => "中压",
+            50..=69 => "高压",
+            _ => "爆表",
+        }
+    }
+
+    /// 最近几次事件的平均技能收益（没有记录时按每天4点估算）
+    fn recent_skill_pace(&self) -> f32 {
+        let recent: Vec<i32> = self
+            .history
+            .iter()
+            .rev()
+            .filter(|entry| entry.kind == HistoryKind::日常)
+            .take(7)
+            .map(|entry| entry.skill_delta)
+            .collect();
+        if recent.is_empty() {
+            4.0
+        } else {
+            recent.iter().sum::<i32>() as f32 / recent.len() as f32
+        }
+    }
+
+    /// 假设选择某个选项，推演之后的状态（不修改当前状态）
+    pub fn simulate_option(&self, value: (i32, i32)) -> OptionProjection {
+        let (skill, pressure) = value;
+        let skills = self.skills.saturating_add(skill);
+        let (pressure, _) = self.balance.pressure_model.split(pressure);
+        let pressure = (self.pressure + pressure).clamp(0, 100);
+        let missing = self.skill_requirement() - skills;
+        let pace = self.recent_skill_pace();
+        let days_to_promotion = if missing <= 0 {
+            Some(0)
+        } else if pace > 0.0 {
+            Some((missing as f32 / pace).ceil() as u32)
+        } else {
+            None
+        };
+        OptionProjection {
+            skills,
+            pressure,
+            pressure_band: Self::pressure_band(pressure),
+            risk_tonight: self.option_risk(value),
+            days_to_promotion,
+        }
+    }
+
+    /// 当前晋升失败率（每失败一次加一档，档位随难度变化，最高95%）
+    pub fn promotion_failure_rate(&self) -> f32 {
+        let mut failure_rate = self.balance.promotion_failure_step * (self.promotion_attempts as f32 + 1.0);
+        if self.perks.contains(&Perk::面霸) {
+            failure_rate -= 0.10;
+        }
+        if self.keepsakes.contains(&Keepsake::摸鱼秘籍) {
+            failure_rate -= Keepsake::PROMOTION_EDGE;
+        }
+        if self.inventory.has(Item::晋升符) {
+            failure_rate -= items::TALISMAN_EDGE;
+        }
+        failure_rate.clamp(0.0, 0.95)  // 最高失败率95%
+    }
+
+    /// 解锁的称号
+    pub fn title(&self) -> Option<&'static str> {
+        self.open_source
+            .is_influencer()
+            .then_some(opensource::INFLUENCER_TITLE)
+    }
+
+    /// 结业加成与信物带来的额外技能收益（日常事件）
+    pub fn perk_bonus(&self, track: Option<SkillTrack>) -> i32 {
+        let cloud_native = track == Some(SkillTrack::工程) && self.perks.contains(&Perk::云原生);
+        let notes = self.keepsakes.contains(&Keepsake::卷王笔记);
+        i32::from(cloud_native) + i32::from(notes)
+    }
+
+    /// 晋升尝试（还不够晋升条件时不掷骰，返回原因）
+    pub fn attempt_promotion(&mut self, rng: &mut GameRng) -> Result<(bool, String), String> {
+        if !self.can_promote() {
+            return Err("还没攒够晋升的本事".to_string());
+        }
+        let failure_rate = self.promotion_failure_rate();
+        // 晋升符不论成败都会烧掉
+        self.inventory.take(Item::晋升符);
+        if rng.gen::<f32>() < failure_rate {
+            // 失败
+            let lost_skills = self.skills / 2;
+            self.skills -= lost_skills;
+            self.promotion_attempts += 1;
+            Ok((false, format!("小垃圾 根本没有这个水平还想晋升\n失去了{}技能点", lost_skills)))
+        } else {
+            // 成功
+            self.realm_level += 1;
+            self.promotion_attempts = 0;
+            Ok((true, format!("恭喜晋升到{}阶！", self.get_realm())))
+        }
+    }
+
+    /// 记录当天结束时的属性快照
+    pub fn record_snapshot(&mut self) {
+        self.snapshots.push(DailySnapshot {
+            day: self.days_played + 1,
+            skills: self.skills,
+            pressure: self.pressure,
+            experience: self.experience,
+            pressure_momentum: self.pressure_momentum,
+        });
+    }
+
+    /// 最近 n 天的快照（不足 n 天时返回全部）
+    pub fn recent_snapshots(&self, n: usize) -> &[DailySnapshot] {
+        &self.snapshots[self.snapshots.len().saturating_sub(n)..]
+    }
+
+    /// 添加历史记录（含具体奖励信息）
+    pub fn add_history(&mut self, mut entry: HistoryEntry) {
+        entry.day = self.days_played + 1;
+        entry.elapsed = self.day_time;
+        self.history.push(entry);
+    }
+
+    /// 本局的选择倾向统计
+    pub fn archetype_tally(&self) -> ArchetypeTally {
+        ArchetypeTally::from_history(&self.history)
+    }
+
+    /// 本局与每位 NPC 打交道的次数：(名字, 同意次数, 拒绝次数)，按首次相遇排序
+    pub fn npc_dealings(&self) -> Vec<(String, u32, u32)> {
+        let mut dealings: Vec<(String, u32, u32)> = Vec::new();
+        for entry in self.history.iter().filter(|e| e.kind == HistoryKind::Npc) {
+            let index = match dealings.iter().position(|(name, _, _)| *name == entry.event_name) {
+                Some(index) => index,
+                None => {
+                    dealings.push((entry.event_name.clone(), 0, 0));
+                    dealings.len() - 1
+                }
+            };
+            if entry.choice.ends_with("(同意)") {
+                dealings[index].1 += 1;
+            } else {
+                dealings[index].2 += 1;
+            }
+        }
+        dealings
+    }
+
+    /// 根据历史选择生成人物志
+    pub fn biography(&self) -> String {
+        let archetypes: Vec<ChoiceArchetype> =
+            self.history.iter().filter_map(|entry| entry.archetype).collect();
+        if archetypes.is_empty() {
+            return format!("{}还没做出任何选择，人物志一片空白。", self.name);
+        }
+
+        let count = |items: &[ChoiceArchetype], target: ChoiceArchetype| {
+            items.iter().filter(|a| **a == target).count()
+        };
+        let mut ranked: Vec<(ChoiceArchetype, usize)> = ChoiceArchetype::ALL
+            .iter()
+            .map(|a| (*a, count(&archetypes, *a)))
+            .collect();
+        ranked.sort_by_key(|(_, n)| std::cmp::Reverse(*n));
+
+        let (primary, primary_count) = ranked[0];
+        let (secondary, secondary_count) = ranked[1];
+        let tag = if secondary_count == primary_count {
+            format!("{}兼{}", primary.persona(), secondary.persona())
+        } else {
+            primary.persona().to_string()
+        };
+
+        let mut lines = vec![
+            format!(
+                "{}，{}修士，入职第{}天。",
+                self.name,
+                self.get_realm(),
+                self.days_played + 1
+            ),
+            format!(
+                "性格标签：{}（内卷{}次 / 甩锅{}次 / 摸鱼{}次）",
+                tag,
+                count(&archetypes, ChoiceArchetype::内卷),
+                count(&archetypes, ChoiceArchetype::甩锅),
+                count(&archetypes, ChoiceArchetype::摸鱼)
+            ),
+            match primary {
+                ChoiceArchetype::内卷 => "遇事从不退缩，键盘敲得比谁都响，工位上常年亮着一盏灯。",
+                ChoiceArchetype::甩锅 => "深谙“锅从天上来，必往别处去”之道，背锅侠名单上从未出现过此人。",
+                ChoiceArchetype::摸鱼 => "坚信身体是革命的本钱，茶水间和厕所都留下了此人的传说。",
+            }
+            .to_string(),
+        ];
+
+        // 最近的选择倾向与总体不同时，记一笔“画风突变”
+        let recent = &archetypes[archetypes.len().saturating_sub(5)..];
+        if recent.len() == 5 {
+            if let Some(recent_main) = ChoiceArchetype::ALL
+                .iter()
+                .copied()
+                .find(|a| count(recent, *a) >= 3)
+            {
+                if recent_main != primary {
+                    lines.push(format!("近来画风突变，开始走{}路线。", recent_main.persona()));
+                }
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GameState {
+    pub player: PlayerState,
+    pub current_day: u32,
+    pub current_week: u32,
+    pub daily_events: Vec<DailyEvent>,
+    pub weekly_events: Vec<WeeklyEvent>,
+    pub today_event: DailyEvent,           // 保存当天事件，避免重复随机
+    pub today_weekly_event: Option<WeeklyEvent>,  // 当周事件（如果有的话）
+    pub event_chosen_today: bool,  // 今天是否已选择
+    pub weekly_event_chosen_today: bool,  // 周事件是否已选择
+    pub npc_master: Vec<NpcEncounter>,
+    pub today_npcs: Vec<NpcEncounter>,
+    pub npc_interaction_message: String,
+    pub npc_active_event: Option<NpcActiveEvent>,
+    pub hint_uses_left: u32,  // 师傅提示剩余次数
+    pub saga: Option<SagaState>,  // 下周待续的连续剧
+    pub active_packs: Vec<ContentPack>,  // 本局开启的限时内容包
+    pub muted_tags: Vec<EventTag>,  // 本局屏蔽的事件题材
+    pub banned_events: Vec<usize>,  // 本局禁用的日常事件 id（最多 MAX_BANNED_EVENTS 个）
+    pub pity: PityTimers,  // 奇遇/NPC 保底计数
+    pub recent: RecentEvents,  // 各事件最近一次出现的天数（去重用）
+    pub splits: Vec<Split>,  // 每次境界突破的分段用时
+    pub seed: u64,  // 本局随机种子（同一种子 + 同样的选择 = 同样的一局）
+    pub scenario: Option<Scenario>,  // 挑战模式：固定事件顺序的剧本
+    #[serde(default)]
+    pub guided: bool,  // 头一周跟着新手引导剧本走
+    pub tone: NarrativeTone,  // 本局的叙事风格（只换结局文案）
+    pub director: Director,  // 事件导演（按压力走势调整日常事件的抽取权重）
+    week_start_day: u32,  // 本周第一天
+    pub payslips: Vec<Payslip>,  // 历月薪资单
+    pub desperation: Option<usize>,  // 破产剧情线进行到第几步（None 为没有破产）
+    pending_recap: Option<WeeklyRecap>,  // 刚结束那一周的周报（待展示）
+    pub arc: Option<ArcProgress>,  // 进行中的剧情线（如技术分享）
+    pub quest: Option<QuestProgress>,  // 进行中的项目线（如智眼上线）
+    pub favor: Option<Favor>,  // 进行中的同事委托
+    pub visit: Option<Visit>,  // 最近一次在办公室地图上挑的去处
+    pub relations: Relations,  // 和同事们的好感与送礼记录
+    pub night_call: Option<NightCall>,  // 过夜时接到、还没答复的深夜来电
+    pub companions: Vec<Companion>,  // 加入团队的同事（栏位有限）
+    pub audit: Audit,  // 灰色操作累积的审计风险
+    pub alt: Option<PlayerState>,  // 修出的分身（与本体隔天轮流上班，存款和投资共用）
+    pub alt_on_duty: bool,  // 今天上班的是分身（player 是分身，alt 是本体）
+    pub ascension: u32,  // 本局的飞升等级（0 为未飞升）
+    rollback_armed: bool,  // 本周已开启免死回滚
+    pending_notice: Option<String>,  // 过夜发生的事（如课程结业），待展示
+    rng: GameRng,   // 事件流：每天的事件、选项顺序、NPC 出场
+    fate: GameRng,  // 命运流：猝死、晋升、NPC 台词（与事件流分开，玩法不同也不会打乱事件顺序）
+    ticker: GameRng,  // 行情流：奶茶价格与股市涨跌
+    loot: GameRng,  // 掉落流：事件和同事给的道具
+    pub shuffle: ShufflePolicy,  // 选项排序策略
+}
+
+/// 咸鱼预警时出场的事件：给玩家一个主动找活干的机会
+const SLACKING_EVENT: usize = 108;
+
+/// 绩效改进计划剧情线的日常事件 id，计划期间轮流出现
+const PROBATION_ARC: [usize; 2] = [106, 107];
+
+/// 命运流种子的扰动值
+const FATE_SALT: u64 = 0x9E37_79B9_7F4A_7C15;
+/// 行情流种子的扰动值
+const TICKER_SALT: u64 = 0xD1B5_4A32_D192_ED03;
+/// 掉落流种子的扰动值
+const LOOT_SALT: u64 = 0x94D0_49BB_1331_11EB;
+
+/// 某个种子的掉落流（旧存档没有掉落流，读档时用它补上）
+pub(crate) fn loot_stream(seed: u64) -> GameRng {
+    GameRng::seed_from_u64(seed ^ LOOT_SALT)
+}
+
+/// 修出分身需要达到的晋升等级（3=筑基期）
+pub const ALT_REALM_LEVEL: u32 = 3;
+/// 修出分身时本体增加的压力
+const ALT_CREATION_PRESSURE: i32 = 10;
+
+/// 每局最多禁用的日常事件数
+pub const MAX_BANNED_EVENTS: usize = 3;
+
+/// 每局可用的师傅提示次数
+pub const HINT_USES_PER_RUN: u32 = 3;
+
+impl GameState {
+    /// 初始化游戏状态（随机种子）
+    pub fn new(name: String) -> Self {
+        Self::with_seed(name, rand::random())
+    }
+
+    /// 用指定种子初始化游戏状态
+    pub fn with_seed(name: String, seed: u64) -> Self {
+        let mut rng = GameRng::seed_from_u64(seed);
+        let library = events::library();
+        let daily_events = library.daily_events();
+        let weekly_events = library.weekly_events();
+        let npc_master = roster::opening_cast();
+        let player = PlayerState::new(name);
+        let opening = WorldView::new(&player, seed, 1, 1, &[]);
+        
+        // 生成第一天的事件（开局时只抽常驻内容）
+        let regular: Vec<&DailyEvent> = daily_events
+            .iter()
+            .filter(|e| {
+                e.pack.is_none()
+                    && !e.arc_only
+                    && !e.hidden
+                    && !e.weekend
+                    && e.min_housing.is_none()
+                    && e.trigger.allows(&opening)
+            })
+            .collect();
+        let mut today_event = (*regular.choose(&mut rng).expect("常驻事件不能为空")).clone();
+        // 第一天也要打乱选项顺序
+        today_event.reshuffle(&ShufflePolicy::default(), &mut rng);
+        let today_weekly_event = None;  // 第一天没有周事件
+        
+        let mut state = GameState {
+            player,
+            current_day: 1,
+            current_week: 1,
+            daily_events,
+            weekly_events,
+            today_event,
+            today_weekly_event,
+            event_chosen_today: false,
+            weekly_event_chosen_today: false,
+            npc_master,
+            today_npcs: Vec::new(),
+            npc_interaction_message: String::new(),
+            npc_active_event: None,
+            hint_uses_left: HINT_USES_PER_RUN,
+            saga: None,
+            active_packs: Vec::new(),
+            muted_tags: Vec::new(),
+            banned_events: Vec::new(),
+            pity: PityTimers::default(),
+            recent: RecentEvents::default(),
+            splits: Vec::new(),
+            seed,
+            scenario: None,
+            guided: false,
+            tone: NarrativeTone::沙雕,
+            director: Director::default(),
+            week_start_day: 1,
+            payslips: Vec::new(),
+            desperation: None,
+            pending_recap: None,
+            arc: None,
+            quest: None,
+            favor: None,
+            visit: None,
+            relations: Relations::default(),
+            night_call: None,
+            companions: Vec::new(),
+            audit: Audit::default(),
+            alt: None,
+            alt_on_duty: false,
+            ascension: 0,
+            rollback_armed: false,
+            pending_notice: None,
+            rng,
+            fate: GameRng::seed_from_u64(seed ^ FATE_SALT),
+            ticker: GameRng::seed_from_u64(seed ^ TICKER_SALT),
+            loot: loot_stream(seed),
+            shuffle: ShufflePolicy::default(),
+        };
+
+        debug_assert_eq!(state.validate_templates(), Ok(()));
+        state.refresh_today_npcs();
+        state
+    }
+
+    /// 按剧本开局：固定种子、固定每天的事件，天数即剧本长度
+    pub fn from_scenario(name: String, scenario: Scenario) -> Self {
+        let mut state = Self::with_seed(name, scenario.seed);
+        state.player.balance = scenario.preset.config();
+        state.player.balance.max_days = Some(scenario.daily.len() as u32);
+        state.scenario = Some(scenario);
+        if let Some(mut event) = state.pinned_daily_event() {
+            event.reshuffle(&state.shuffle, &mut state.rng);
+            state.today_event = event;
+        }
+        state
+    }
+
+    /// 用同一种子重开：事件顺序相同（同样的选择会遇到同样的事），玩家状态全部重置
+    pub fn rematch(&self) -> Self {
+        let name = self.main_player().name.clone();
+        let mut state = match &self.scenario {
+            Some(scenario) => Self::from_scenario(name, scenario.clone()),
+            None => Self::with_seed(name, self.seed),
+        };
+        if self.guided {
+            state.follow_guide();
+        }
+        state.active_packs = self.active_packs.clone();
+        state.player.balance = self.player.balance.clone();
+        state.player.skills += state.player.balance.starting_skills;
+        state.tone = self.tone;
+        state.director = self.director;
+        state.set_shuffle(self.shuffle.mode);
+        state.player.career = self.player.career;
+        state.muted_tags = self.muted_tags.clone();
+        state.ban_events(self.banned_events.clone());
+        state.ascend(self.ascension);
+        state
+    }
+
+    /// 设定本局的选项排序方式，并按新方式重排今天已经摆出来的选项
+    pub fn set_shuffle(&mut self, mode: ShuffleMode) {
+        self.shuffle = ShufflePolicy::new(mode, self.seed);
+        self.shuffle.settle(&mut self.today_event.shuffled_options);
+        if let Some(weekly) = &mut self.today_weekly_event {
+            self.shuffle.settle(&mut weekly.shuffled_options);
+        }
+    }
+
+    /// 按飞升等级叠加不利条件（开局时调用；门槛按预设重新计算，重复调用不会叠加）
+    pub fn ascend(&mut self, level: u32) {
+        self.ascension = level;
+        for handicap in ascension::handicaps(level) {
+            match handicap {
+                Handicap::门槛上调 => {
+                    self.player.balance.promotion_skills = self
+                        .player
+                        .balance
+                        .preset
+                        .config()
+                        .promotion_skills
+                        .map(|s| s * ascension::RAISED_REQUIREMENT_PERCENT / 100);
+                }
+                Handicap::开局带压 => self.player.pressure = ascension::STARTING_PRESSURE,
+                Handicap::师傅失联 => self.hint_uses_left = 0,
+                Handicap::开局负债 => self.player.savings = ascension::STARTING_DEBT,
+                Handicap::每晚加压 => {}
+                Handicap::取消宽限 => self.player.balance.probation_days = None,
+            }
+        }
+    }
+
+    /// 本局是否叠加了某条飞升不利条件
+    fn has_handicap(&self, handicap: Handicap) -> bool {
+        ascension::handicaps(self.ascension).contains(&handicap)
+    }
+
+    /// 本局得分（按飞升等级加成）
+    pub fn score(&self) -> u32 {
+        (self.player.score() as f32 * ascension::score_multiplier(self.ascension)).round() as u32
+    }
+
+    /// 状态检查器用的快照：可序列化的对局状态（随机数流、计时器与事件库不含在内）
+    #[cfg(feature = "debug")]
+    pub fn inspect(&self) -> serde_json::Value {
+        serde_json::json!({
+            "day": self.current_day,
+            "week": self.current_week,
+            "today_event": format!("{} #{}", self.today_event.name, self.today_event.id),
+            "today_weekly_event": self.today_weekly_event.as_ref().map(|e| format!("{} #{}", e.name, e.id)),
+            "event_chosen_today": self.event_chosen_today,
+            "weekly_event_chosen_today": self.weekly_event_chosen_today,
+            "player": self.player,
+            "alt": self.alt,
+            "alt_on_duty": self.alt_on_duty,
+            "hint_uses_left": self.hint_uses_left,
+            "saga": self.saga,
+            "pity": self.pity,
+            "recent": self.recent,
+            "splits": self.splits,
+            "director": self.director,
+            "desperation": self.desperation,
+            "arc": self.arc,
+            "quest": self.quest,
+            "favor": self.favor,
+            "visit": self.visit,
+            "relations": self.relations,
+            "companions": self.companions,
+            "audit": self.audit,
+            "ascension": self.ascension,
+            "banned_events": self.banned_events,
+            "rollback_armed": self.rollback_armed,
+        })
+    }
+
+    /// 通关：晋升到化神期（及以上），或本局结束时还活着（走完了天数上限、飞升）
+    pub fn is_victory(&self, run_over: bool) -> bool {
+        self.player.is_alive && (run_over || Realm::from_level(self.player.realm_level) >= Realm::化神期)
+    }
+
+    /// 按本局叙事风格取选项的结局文案（占位符已替换），该风格没有变体时用原文
+    pub fn narrate(&self, kind: HistoryKind, event_id: usize, option: &OptionInfo) -> String {
+        match self.tone.story(kind, event_id, option.original_index) {
+            Some(story) => self.template_context().render(story),
+            None => option.story.clone(),
+        }
+    }
+
+    /// 换一组随机流（推演用：同一局面的副本各走各的命运），本局种子不变
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = GameRng::seed_from_u64(seed);
+        self.fate = GameRng::seed_from_u64(seed ^ FATE_SALT);
+        self.ticker = GameRng::seed_from_u64(seed ^ TICKER_SALT);
+        self.loot = loot_stream(seed);
+    }
+
+    /// 全部日常事件与周事件的 id（用于校验剧本）
+    pub fn content_ids() -> (Vec<usize>, Vec<usize>) {
+        (
+            events::library().daily_events().iter().map(|e| e.id).collect(),
+            events::library().weekly_events().iter().map(|e| e.id).collect(),
+        )
+    }
+
+    /// 可以禁用的日常事件：(id, 名称)，剧情线专属事件和周末休闲事件不算
+    pub fn bannable_events() -> Vec<(usize, String)> {
+        events::library()
+            .daily_events()
+            .into_iter()
+            .filter(|e| !e.arc_only && !e.hidden && !e.weekend)
+            .map(|e| (e.id, e.name))
+            .collect()
+    }
+
+    /// 本局禁用若干日常事件（超出上限的忽略）；第一天恰好抽到被禁的事件时重抽
+    pub fn ban_events(&mut self, mut ids: Vec<usize>) {
+        ids.truncate(MAX_BANNED_EVENTS);
+        self.banned_events = ids;
+        if self.script().is_some() || !self.banned_events.contains(&self.today_event.id) {
+            return;
+        }
+        let world = WorldView::of(self);
+        let regular: Vec<&DailyEvent> = self
+            .daily_events
+            .iter()
+            .filter(|e| {
+                e.pack.is_none()
+                    && !e.arc_only
+                    && !e.hidden
+                    && !e.weekend
+                    && e.min_housing.is_none()
+                    && !self.banned_events.contains(&e.id)
+                    && e.trigger.allows(&world)
+            })
+            .collect();
+        if let Some(event) = regular.choose(&mut self.rng) {
+            let mut event = (*event).clone();
+            event.reshuffle(&self.shuffle, &mut self.rng);
+            self.today_event = event;
+        }
+    }
+
+    /// 本局禁用的日常事件名称
+    pub fn banned_event_names(&self) -> Vec<String> {
+        self.banned_events
+            .iter()
+            .filter_map(|id| self.daily_events.iter().find(|e| e.id == *id))
+            .map(|e| e.name.clone())
+            .collect()
+    }
+
+    /// 当前生效的事件剧本：挑战剧本，或者新手引导周
+    fn script(&self) -> Option<&Scenario> {
+        self.scenario.as_ref().or_else(|| self.guided.then(onboarding::demo_week))
+    }
+
+    /// 跟着新手引导周走：头几天按引导剧本出事件，之后照常随机
+    pub fn follow_guide(&mut self) {
+        self.guided = true;
+        if let Some(mut event) = self.pinned_daily_event() {
+            event.reshuffle(&self.shuffle, &mut self.rng);
+            self.today_event = event;
+        }
+    }
+
+    /// 剧本给今天的提示（引导周里每天教一样玩法）
+    pub fn script_note(&self) -> Option<&str> {
+        self.script()?.notes.get(self.current_day as usize - 1).map(String::as_str)
+    }
+
+    /// 剧本为今天指定的日常事件
+    fn pinned_daily_event(&self) -> Option<DailyEvent> {
+        let id = *self.script()?.daily.get(self.current_day as usize - 1)?;
+        self.daily_events.iter().find(|e| e.id == id).cloned()
+    }
+
+    /// 更换通勤方式（只在周报时开放）
+    pub fn set_commute(&mut self, commute: Commute) {
+        self.player.commute = commute;
+    }
+
+    /// 花存款升级居住条件
+    pub fn upgrade_housing(&mut self) -> Result<Housing, String> {
+        let (next, cost) = self.player.housing.upgrade().ok_or("已经是最好的居住条件了")?;
+        if self.player.savings < cost {
+            return Err(format!("存款不足，还差 {} 元", cost - self.player.savings));
+        }
+        self.player.savings -= cost;
+        self.player.housing = next;
+        Ok(next)
+    }
+
+    /// 报名进修课，学费一次付清
+    pub fn enroll(&mut self, course: Course) -> Result<(), String> {
+        if let Some(current) = &self.player.enrollment {
+            return Err(format!("正在上{}，先结业或退课", current.course.label()));
+        }
+        if self.player.savings < course.fee() {
+            return Err(format!("存款不足，还差 {} 元", course.fee() - self.player.savings));
+        }
+        self.player.savings -= course.fee();
+        self.player.enrollment = Some(Enrollment::new(course));
+        Ok(())
+    }
+
+    /// 从存款里拿一手买入某个投资品种
+    pub fn invest(&mut self, instrument: Instrument) -> Result<(), String> {
+        if self.player.savings < portfolio::LOT {
+            return Err(format!("存款不足，还差 {} 元", portfolio::LOT - self.player.savings));
+        }
+        self.player.savings -= portfolio::LOT;
+        self.player.portfolio.buy(instrument, portfolio::LOT);
+        Ok(())
+    }
+
+    /// 本体（分身上班的日子里本体在 alt 里休息）
+    pub fn main_player(&self) -> &PlayerState {
+        match (&self.alt, self.alt_on_duty) {
+            (Some(main), true) => main,
+            _ => &self.player,
+        }
+    }
+
+    /// 还没有分身、境界够高时可以修出分身
+    pub fn can_create_alt(&self) -> bool {
+        self.alt.is_none() && self.player.realm_level >= ALT_REALM_LEVEL && self.player.is_alive
+    }
+
+    /// 修出分身：从凡人境起步，明天开始和本体隔天轮流上班
+    pub fn create_alt(&mut self) -> Result<String, String> {
+        if self.alt.is_some() {
+            return Err("已经有一个分身了".to_string());
+        }
+        if !self.can_create_alt() {
+            return Err(format!("境界不够，晋升到第 {} 阶才能修出分身", ALT_REALM_LEVEL));
+        }
+        let mut alt = PlayerState::new(format!("{}·分身", self.player.name));
+        alt.balance = self.player.balance.clone();
+        alt.career = self.player.career;
+        alt.commute = self.player.commute;
+        alt.housing = self.player.housing;
+        self.player.gain_reward(0, ALT_CREATION_PRESSURE);
+        let message = format!(
+            "🪞 神识一分为二，{}诞生了！明天起你们隔天轮流上班，存款和投资共用，任何一个倒下都算出局。\n压力 +{}",
+            alt.name, ALT_CREATION_PRESSURE
+        );
+        self.alt = Some(alt);
+        Ok(message)
+    }
+
+    /// 本体与分身换班；存款、投资和行情是共用的，跟着上班的人走
+    fn switch_persona(&mut self) {
+        let Some(alt) = self.alt.as_mut() else {
+            return;
+        };
+        std::mem::swap(&mut self.player, alt);
+        std::mem::swap(&mut self.player.savings, &mut alt.savings);
+        std::mem::swap(&mut self.player.portfolio, &mut alt.portfolio);
+        std::mem::swap(&mut self.player.market, &mut alt.market);
+        self.alt_on_duty = !self.alt_on_duty;
+        let on_duty = self.player.name.clone();
+        self.push_notice(format!("🪞 换班：今天由{}上班", on_duty));
+    }
+
+    /// 挂机修炼了若干轮：少量技能点换不少压力
+    pub fn idle_train(&mut self, rounds: u32) -> String {
+        let (skill, pressure) = idle::rewards(rounds);
+        self.player.gain_reward(skill, pressure);
+        format!("🧘 挂机修炼 {} 轮：技能 {:+}，压力 {:+}", rounds, skill, pressure)
+    }
+
+    /// 清仓某个投资品种，返回回笼的现金
+    pub fn sell_investment(&mut self, instrument: Instrument) -> i32 {
+        let cash = self.player.portfolio.sell_all(instrument);
+        self.player.savings += cash;
+        cash
+    }
+
+    /// 中途退课，返回退还的学费
+    pub fn drop_course(&mut self) -> Result<i32, String> {
+        let enrollment = self.player.enrollment.take().ok_or("没有在上的课")?;
+        let refund = enrollment.refund();
+        self.player.savings += refund;
+        Ok(refund)
+    }
+
+    /// 设定没课的晚上怎么过
+    pub fn set_evening(&mut self, plan: EveningPlan) {
+        self.player.evening = plan;
+    }
+
+    /// 过完这一晚：有课去上课，否则按晚间安排写开源或休息
+    fn spend_evening(&mut self) {
+        if self.player.enrollment.is_some() {
+            self.player.open_source.neglect();
+            self.attend_class();
+            return;
+        }
+        match self.player.evening {
+            EveningPlan::写开源 => {
+                self.player.gain_reward(1, 2);
+                self.player.train(Some(SkillTrack::工程), 1);
+                let notice = self.player.open_source.contribute(self.player.reputation);
+                if notice.is_some() {
+                    self.pending_notice = notice;
+                }
+            }
+            EveningPlan::休息 => self.player.open_source.neglect(),
+        }
+    }
+
+    /// 开源项目到点该出场的事件（里程碑猎头、issue 积压）
+    fn open_source_event(&mut self) -> Option<DailyEvent> {
+        let id = self.player.open_source.take_due_event()?;
+        self.daily_events.iter().find(|e| e.id == id).cloned()
+    }
+
+    /// 行情到点该出场的事件：持仓暴跌优先，其次是理财推销
+    fn market_event(&mut self) -> Option<DailyEvent> {
+        let id = self
+            .player
+            .portfolio
+            .take_due_event()
+            .or_else(|| self.player.market.take_due_event())?;
+        self.daily_events.iter().find(|e| e.id == id).cloned()
+    }
+
+    /// 晚上去上课：压力上升，上满课时就结业
+    fn attend_class(&mut self) {
+        let Some(enrollment) = &mut self.player.enrollment else {
+            return;
+        };
+        enrollment.nights_attended += 1;
+        let course = enrollment.course;
+        let finished = enrollment.is_finished();
+        self.player.gain_reward(0, course.nightly_pressure());
+        if finished {
+            self.player.enrollment = None;
+            let (track, points) = course.graduation_training();
+            self.player.train(Some(track), points);
+            let perk = course.perk();
+            if !self.player.perks.contains(&perk) {
+                self.player.perks.push(perk);
+            }
+            self.pending_notice = Some(format!(
+                "🎓 {}结业！{}线 +{}，获得「{}」：{}",
+                course.label(),
+                track.label(),
+                points,
+                perk,
+                perk.description()
+            ));
+        }
+    }
+
+    /// 追加一条过夜发生的事（同一晚可能不止一件）
+    fn push_notice(&mut self, notice: String) {
+        self.pending_notice = Some(match self.pending_notice.take() {
+            Some(earlier) => format!("{}\n{}", earlier, notice),
+            None => notice,
+        });
+    }
+
+    /// 取出过夜发生的事的提示，取出后清除
+    pub fn take_notice(&mut self) -> Option<String> {
+        self.pending_notice.take()
+    }
+
+    /// 破产剧情线的下一个事件；存款回正就结束剧情线
+    fn desperation_event(&mut self) -> Option<DailyEvent> {
+        let step = self.desperation?;
+        if self.player.savings >= 0 {
+            self.desperation = None;
+            return None;
+        }
+        self.desperation = Some(step + 1);
+        let id = finance::DESPERATION_ARC[step % finance::DESPERATION_ARC.len()];
+        self.daily_events.iter().find(|e| e.id == id).cloned()
+    }
+
+    /// 绩效改进计划期间每天轮流出现的事件
+    fn probation_event(&self) -> Option<DailyEvent> {
+        let days_left = self.player.probation? as usize;
+        let id = PROBATION_ARC[days_left % PROBATION_ARC.len()];
+        self.daily_events.iter().find(|e| e.id == id).cloned()
+    }
+
+    /// 咸鱼预警期间出场的干预事件
+    fn slacking_event(&self) -> Option<DailyEvent> {
+        self.player.slacking_warning()?;
+        self.daily_events.iter().find(|e| e.id == SLACKING_EVENT).cloned()
+    }
+
+    /// 剧本为本周指定的周事件
+    fn pinned_weekly_event(&self) -> Option<WeeklyEvent> {
+        let index = (week::weekly_event_week(self.current_day) as usize).checked_sub(1)?;
+        let id = *self.script()?.weekly.get(index)?;
+        self.weekly_events.iter().find(|e| e.id == id).cloned()
+    }
+
+    /// 校验所有事件文本中的占位符
+    fn validate_templates(&self) -> Result<(), String> {
+        let daily = self
+            .daily_events
+            .iter()
+            .map(|e| (&e.name, &e.description, &e.shuffled_options));
+        let weekly = self
+            .weekly_events
+            .iter()
+            .map(|e| (&e.name, &e.description, &e.shuffled_options));
+        for (name, description, options) in daily.chain(weekly) {
+            template::validate(name)?;
+            template::validate(description)?;
+            for option in options {
+                template::validate(&option.desc)?;
+                template::validate(&option.story)?;
+            }
+        }
+        for story in NarrativeTone::all_stories() {
+            template::validate(story)?;
+        }
+        Ok(())
+    }
+
+    /// 当前的模板上下文（玩家名、同门卷王、上司、天数）
+    pub fn template_context(&self) -> TemplateContext {
+        let rival = self
+            .today_npcs
+            .first()
+            .or_else(|| self.npc_master.first())
+            .map(|npc| npc.name.clone())
+            .unwrap_or_else(|| "隔壁工位的卷王".to_string());
+        TemplateContext {
+            player: self.player.name.clone(),
+            rival,
+            boss: self.world().boss.to_string(),
+            day: self.current_day,
+        }
+    }
+
+    /// 今天的天气（由种子和天数决定）
+    pub fn weather(&self) -> Weather {
+        Weather::on(self.seed, self.current_day)
+    }
+
+    /// 今天的局面摘要（事件触发、同事出场、事件导演都按它判断）
+    pub fn world(&self) -> WorldView<'_> {
+        WorldView::of(self)
+    }
+
+    /// 今天的黄历（由种子和天数决定）
+    pub fn almanac(&self) -> Almanac {
+        Almanac::on(self.seed, self.current_day)
+    }
+
+    fn refresh_today_npcs(&mut self) {
+        // 按境界、天数、是否临近晋升筛出今天能出场的同事
+        let world = self.world();
+        let day = self.current_day;
+        let eligible: Vec<NpcEncounter> =
+            self.npc_master.iter().filter(|npc| npc.appears.allows(&world)).cloned().collect();
+        // 刚出场过的先歇几天（全都在歇时不管）
+        let mut pool = eligible.clone();
+        let rested = |npc: &NpcEncounter| self.recent.npc_fresh(&npc.name, day, npc.appears.cooldown_days());
+        if pool.iter().any(rested) {
+            pool.retain(rested);
+        }
+        pool.shuffle(&mut self.rng);
+        let max_take = pool.len().min(3);
+        let take = if max_take == 0 {
+            0
+        } else {
+            self.rng.gen_range(1..=max_take)
+        };
+        self.today_npcs = pool
+            .into_iter()
+            .take(take)
+            .map(|mut npc| {
+                npc.interacted = false;
+                npc
+            })
+            .collect();
+        // 缺席太久的 NPC 强制出场（顶替最后一位）
+        if let Some(name) = self.pity.overdue_npc() {
+            if !self.today_npcs.iter().any(|npc| npc.name == name) {
+                if let Some(npc) = eligible.iter().find(|npc| npc.name == name) {
+                    let mut npc = npc.clone();
+                    npc.interacted = false;
+                    if self.today_npcs.len() >= max_take {
+                        self.today_npcs.pop();
+                    }
+                    self.today_npcs.push(npc);
+                }
+            }
+        }
+        // 不满足出场条件的日子不算缺席
+        self.pity.record_npcs(&eligible, &self.today_npcs);
+        for npc in &self.today_npcs {
+            self.recent.record_npc(&npc.name, day);
+        }
+        let world = self.world();
+        let (weather, health) = (world.weather, world.project_health);
+        for npc in &mut self.today_npcs {
+            npc.mood = NpcMood::of(&npc.name, self.seed, self.current_day, weather, health);
+        }
+        self.relations.new_day();
+        self.npc_interaction_message.clear();
+        self.npc_active_event = None;
+    }
+
+    /// 在办公室地图上挑今天待的地方（一天一次）：碰上一位常在那里出没、今天还没露面的同事，
+    /// 之后的日常事件也偏向那里的题材
+    pub fn move_to(&mut self, place: Location) -> Result<String, String> {
+        if !self.player.is_alive {
+            return Err("你已离开公司，哪儿也去不了了。".to_string());
+        }
+        if let Some(visit) = self.visit.filter(|visit| visit.day == self.current_day) {
+            return Err(format!("今天已经待在{}了，明天再换地方吧", visit.place));
+        }
+        self.visit = Some(Visit { place, day: self.current_day });
+        let mut message = format!("🚶 你去了{}：{}", place, place.description());
+
+        let (world, day) = (self.world(), self.current_day);
+        let (weather, health) = (world.weather, world.project_health);
+        let regulars: Vec<&NpcEncounter> = self
+            .npc_master
+            .iter()
+            .filter(|npc| {
+                npc.appears.haunt == Some(place)
+                    && npc.appears.allows(&world)
+                    && !self.today_npcs.iter().any(|present| present.name == npc.name)
+            })
+            .collect();
+        if let Some(npc) = regulars.choose(&mut self.rng) {
+            let mut npc = (*npc).clone();
+            npc.interacted = false;
+            npc.mood = NpcMood::of(&npc.name, self.seed, day, weather, health);
+            message.push_str(&format!("\n👋 正巧碰上了{}", npc.name));
+            self.recent.record_npc(&npc.name, day);
+            self.today_npcs.push(npc);
+        }
+        Ok(message)
+    }
+
+    pub fn trigger_npc_event(&mut self, index: usize) -> Option<String> {
+        if !self.player.is_alive {
+            self.npc_active_event = None;
+            self.npc_interaction_message = "你已离开公司，无法继续和 NPC 交互。".to_string();
+            return Some(self.npc_interaction_message.clone());
+        }
+
+        let npc = self.today_npcs.get(index)?.clone();
+        if npc.interacted {
+            self.npc_interaction_message = format!("{} 今天的请求已经处理完。", npc.name);
+            self.npc_active_event = None;
+            return Some(self.npc_interaction_message.clone());
+        }
+
+        let line = npc.random_dialogue(&mut self.fate);
+        let mut dialogue = line.clone();
+        if let Some(reading) = npc.divine(&self.player, &mut self.fate) {
+            dialogue.push_str(&format!("\n🔮 {}", reading));
+        }
+        // 第一次聊天时对方会不经意透露喜好
+        if let Some(hint) = self.relations.discover(&npc.name) {
+            dialogue.push_str(&format!("\n💭 {}", hint));
+        }
+        self.npc_active_event = Some(NpcActiveEvent {
+            npc_index: index,
+            prompt: dialogue.clone(),
+            line,
+        });
+        self.npc_interaction_message = format!(
+            "{} · {}：{}\n\n同意：{}\n拒绝：{}",
+            npc.name,
+            npc.ai_model,
+            dialogue,
+            npc.accept_option.summary,
+            npc.reject_option.summary
+        );
+        Some(self.npc_interaction_message.clone())
+    }
+
+    /// 把正在进行的 NPC 请求里的台词换成外部生成的（如 AI 台词），卦辞和喜好提示保留；
+    /// 请求已经处理或换了人时返回 false
+    pub fn voice_npc(&mut self, index: usize, line: &str) -> bool {
+        let Some(active) = self.npc_active_event.as_mut().filter(|active| active.npc_index == index) else {
+            return false;
+        };
+        active.prompt = active.prompt.replacen(&active.line, line, 1);
+        self.npc_interaction_message = self.npc_interaction_message.replacen(&active.line, line, 1);
+        active.line = line.to_string();
+        true
+    }
+
+    pub fn resolve_active_npc_event(&mut self, decision: NpcDecision) -> Option<String> {
+        if !self.player.is_alive {
+            self.npc_active_event = None;
+            self.npc_interaction_message = "你已离开公司，无法继续和 NPC 交互。".to_string();
+            return Some(self.npc_interaction_message.clone());
+        }
+
+        let active = self.npc_active_event.clone()?;
+        let npc = self.today_npcs.get_mut(active.npc_index)?;
+        if npc.interacted {
+            self.npc_active_event = None;
+            self.npc_interaction_message = format!("{} 今天已经结束沟通。", npc.name);
+            return Some(self.npc_interaction_message.clone());
+        }
+
+        let option = match decision {
+            NpcDecision::Accept => npc.accept_option.clone(),
+            NpcDecision::Reject => npc.reject_option.clone(),
+            NpcDecision::Negotiate => {
+                let Some(haggle) = npc.haggle.clone() else {
+                    self.npc_interaction_message = format!("{} 的请求没有讨价还价的余地。", npc.name);
+                    return Some(self.npc_interaction_message.clone());
+                };
+                // 心情好的人好说话
+                let mood_bonus = match npc.mood {
+                    NpcMood::开心 => 5,
+                    NpcMood::平静 => 0,
+                    NpcMood::烦躁 => -5,
+                };
+                let roll = self.player.tracks.get(SkillTrack::沟通) + mood_bonus + self.fate.gen_range(0..=20);
+                if roll >= haggle.threshold {
+                    haggle.win
+                } else {
+                    self.relations.adjust(&npc.name, -5);
+                    haggle.lose
+                }
+            }
+        };
+
+        npc.interacted = true;
+        self.audit.record(option.gray_risk);
+        let (skill, pressure) = npc.mood.adjust(option.reward);
+        let skill = skill + self.relations.favor_bonus(&npc.name);
+        self.player.gain_reward(skill, pressure);
+        self.player.train(Some(SkillTrack::沟通), skill);
+        self.player
+            .add_history(HistoryEntry::npc(&npc.name, &option, decision));
+        // 同意攒好感，拒绝结梁子（讨价还价谈崩的已在上面扣过）
+        let affinity_change = match decision {
+            NpcDecision::Accept => bond::ACCEPT_AFFINITY,
+            NpcDecision::Reject => bond::REJECT_AFFINITY,
+            NpcDecision::Negotiate => 0,
+        };
+        self.relations.adjust(&npc.name, affinity_change);
+
+        self.npc_interaction_message = format!(
+            "{}：{} | 技能{} | 压力{}",
+            npc.name,
+            match decision {
+                NpcDecision::Negotiate => format!("{}，{}", option.summary, option.detail),
+                _ => option.summary.clone(),
+            },
+            format_delta(skill),
+            format_delta(pressure)
+        );
+        if affinity_change != 0 {
+            self.npc_interaction_message
+                .push_str(&format!(" | 好感{}", format_delta(affinity_change)));
+        }
+        self.npc_active_event = None;
+        if !matches!(decision, NpcDecision::Reject) {
+            if let Some(drop) = self.roll_loot(items::NPC_DROP_CHANCE) {
+                self.npc_interaction_message.push_str(&format!(" | {}", drop));
+            }
+        }
+        Some(self.npc_interaction_message.clone())
+    }
+
+    /// 答复深夜来电：答应了对方会记住这份情
+    pub fn answer_night_call(&mut self, decision: NpcDecision) -> Option<String> {
+        let call = self.night_call.take()?;
+        let option = match decision {
+            NpcDecision::Accept => {
+                self.relations.adjust(&call.caller, 10);
+                call.accept
+            }
+            // 深夜来电没有讨价还价的余地
+            NpcDecision::Reject | NpcDecision::Negotiate => call.reject,
+        };
+        let (skill, pressure) = option.reward;
+        self.player.gain_reward(skill, pressure);
+        self.player.train(Some(SkillTrack::沟通), skill);
+        self.player
+            .add_history(HistoryEntry::npc(&call.caller, &option, decision));
+        Some(format!(
+            "🌙 {}\n技能{} | 压力{}",
+            option.detail,
+            format_delta(skill),
+            format_delta(pressure)
+        ))
+    }
+
+    /// 给今天遇到的同事送礼，返回提示
+    pub fn give_gift(&mut self, npc_index: usize, gift_id: &str) -> Result<String, String> {
+        if !self.player.is_alive {
+            return Err("你已离开公司，无法送礼。".to_string());
+        }
+        let npc = self.today_npcs.get(npc_index).ok_or("今天没遇到这位同事")?;
+        let gift = gift::catalog()
+            .iter()
+            .find(|g| g.id == gift_id)
+            .ok_or("没有这种礼物")?;
+        if self.relations.gifted_today(&npc.name) {
+            return Err(format!("今天已经给{}送过礼了", npc.name));
+        }
+        if self.player.savings < gift.price {
+            return Err(format!("存款不足，还差 {} 元", gift.price - self.player.savings));
+        }
+        self.player.savings -= gift.price;
+        let change = self.relations.give(&npc.name, gift);
+        let reaction = match change {
+            15.. => "眼睛一亮，正中下怀！",
+            1.. => "客气地收下了。",
+            _ => "表情有点僵硬……",
+        };
+        self.npc_interaction_message = format!(
+            "🎁 你送给{}{}，{}好感{}（现在 {}/{}）",
+            npc.name,
+            gift.label,
+            reaction,
+            format_delta(change),
+            self.relations.affinity(&npc.name),
+            gift::MAX_AFFINITY
+        );
+        Ok(self.npc_interaction_message.clone())
+    }
+
+    /// 生成今天的随机每日事件
+    pub fn get_today_event(&self) -> &DailyEvent {
+        &self.today_event
+    }
+
+    /// 获取周事件（每周五一次）
+    pub fn get_weekly_event(&self) -> Option<&WeeklyEvent> {
+        self.today_weekly_event.as_ref()
+    }
+
+    /// 师傅提示：消耗一次次数，返回当晚出局概率最低的选项下标（同等风险取技能最高）
+    pub fn use_hint(&mut self, options: &[OptionInfo]) -> Option<usize> {
+        if self.hint_uses_left == 0 {
+            return None;
+        }
+        let player = &self.player;
+        let best = options
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| {
+                player
+                    .option_risk(player.scale_outcome(a.value))
+                    .total_cmp(&player.option_risk(player.scale_outcome(b.value)))
+                    .then(b.value.0.cmp(&a.value.0))
+            })
+            .map(|(idx, _)| idx)?;
+        self.hint_uses_left -= 1;
+        Some(best)
+    }
+
+    /// 选出本周的周事件：剧本指定的优先，其次接着演待续的连续剧，否则从非续集事件中随机抽取；
+    /// 事件库里一个周事件都没有时返回 None
+    fn next_weekly_event(&mut self) -> Option<WeeklyEvent> {
+        if let Some(pinned) = self.pinned_weekly_event() {
+            self.saga = None;
+            return Some(pinned);
+        }
+        if let Some(saga) = self.saga.take() {
+            if let Some(sequel) = self.weekly_events.iter().find(|e| e.id == saga.next_event_id) {
+                return Some(sequel.clone());
+            }
+        }
+        if let Some(grudge) = self.grudge_event() {
+            return Some(grudge);
+        }
+        let mut pool: Vec<&WeeklyEvent> = self.weekly_events.iter().filter(|e| !e.sequel_only).collect();
+        // 自制事件包里只有续集时，续集也拿来抽
+        if pool.is_empty() {
+            pool = self.weekly_events.iter().collect();
+        }
+        let (world, day) = (self.world(), self.current_day);
+        if pool.iter().any(|e| e.trigger.allows(&world)) {
+            pool.retain(|e| e.trigger.allows(&world));
+        }
+        let cooldown = self.player.balance.repeat_cooldown_days;
+        if pool.iter().any(|e| self.recent.fresh(true, e.id, day, cooldown)) {
+            pool.retain(|e| self.recent.fresh(true, e.id, day, cooldown));
+        }
+        let tuning = tuning::current();
+        let weights = tuning::normalize(pool.iter().map(|e| tuning.weight(true, e.id, e.trigger.weight)).collect());
+        // 调参表把权重全调成 0 时不按权重，随便抽一个
+        match WeightedIndex::new(&weights) {
+            Ok(dist) => Some(pool[dist.sample(&mut self.rng)].clone()),
+            Err(_) => pool.choose(&mut self.rng).map(|e| (*e).clone()),
+        }
+    }
+
+    /// 记录周事件的选择：若该选项有续集，下周接续
+    pub fn record_weekly_choice(&mut self, event: &WeeklyEvent, option: &OptionInfo) {
+        self.saga = event
+            .follow_up_for(option.original_index)
+            .map(|next_event_id| SagaState {
+                next_event_id,
+                previous_event: event.name.clone(),
+            });
+    }
+
+    /// 记录日常选择对剧情线、开源项目、审计风险与股票的影响；剧情线走完或卖出股票时返回结算文案
+    pub fn record_daily_choice(&mut self, event: &DailyEvent, option: &OptionInfo) -> Option<String> {
+        self.audit.record(option.gray_risk);
+        let cash = self.player.portfolio.resolve(event.id, option.original_index);
+        if cash > 0 {
+            self.player.savings += cash;
+            return Some(format!("💰 全部卖出，回笼 {} 元", cash));
+        }
+        if event.id == opensource::GUILT_EVENT {
+            self.player.open_source.resolve_guilt(option.original_index);
+        }
+        if let Some(story) = bond::story_for_event(event.id) {
+            return Some(self.complete_bond(story));
+        }
+        if let Some(message) = self.record_quest_choice(event, option) {
+            return Some(message);
+        }
+        if self.arc.is_none() {
+            self.arc = StoryArc::triggered_by(event.id, option.original_index).map(ArcProgress::new);
+            return None;
+        }
+        let progress = self.arc.as_mut()?;
+        if !progress.record(event.id, option.original_index) {
+            return None;
+        }
+        let arc = progress.arc;
+        let outcome = arc.conclude(progress.preparation);
+        self.arc = None;
+        self.player.reputation += outcome.reputation;
+        let mut message = format!("{}\n声望 +{}", outcome.message, outcome.reputation);
+        if outcome.gains_fan {
+            for npc in roster::joining_after(arc) {
+                if !self.npc_master.iter().any(|n| n.name == npc.name) {
+                    message.push_str(&format!("\n⭐ {}成了你的粉丝", npc.name));
+                    self.npc_master.push(npc);
+                }
+            }
+        }
+        Some(message)
+    }
+
+    /// 剧情线当前这一步的事件
+    fn arc_event(&self) -> Option<DailyEvent> {
+        let id = self.arc.as_ref()?.current_event_id()?;
+        self.daily_events.iter().find(|e| e.id == id).cloned()
+    }
+
+    /// 好感到门槛、还没走过专属剧情的同事（今天在不在公司都行）安排一次专属剧情
+    fn bond_event(&self) -> Option<DailyEvent> {
+        let story = bond::STORIES.iter().find(|story| {
+            self.npc_master.iter().any(|npc| npc.name == story.npc)
+                && self.relations.affinity(story.npc) >= bond::BOND_AFFINITY
+                && !self.relations.has_bonded(story.npc)
+        })?;
+        self.daily_events.iter().find(|e| e.id == story.event_id).cloned()
+    }
+
+    /// 走完专属剧情：记下羁绊，收下信物
+    fn complete_bond(&mut self, story: &BondStory) -> String {
+        self.relations.bond(story.npc);
+        if !self.player.keepsakes.contains(&story.keepsake) {
+            self.player.keepsakes.push(story.keepsake);
+        }
+        format!(
+            "🔗 和{}结下了羁绊，收到信物「{}」：{}",
+            story.npc,
+            story.keepsake,
+            story.keepsake.description()
+        )
+    }
+
+    /// 厌恶太深的同事这周给你穿小鞋；出了这口气，厌恶减轻一些
+    fn grudge_event(&mut self) -> Option<WeeklyEvent> {
+        let npc = self.relations.most_resentful(bond::GRUDGE_AFFINITY)?.to_string();
+        let mut event = self.weekly_events.iter().find(|e| e.id == bond::GRUDGE_EVENT)?.clone();
+        self.relations.adjust(&npc, bond::GRUDGE_RELIEF);
+        event.description = event.description.replace(bond::GRUDGE_STAND_IN, &npc);
+        for option in &mut event.shuffled_options {
+            option.desc = option.desc.replace(bond::GRUDGE_STAND_IN, &npc);
+            option.story = option.story.replace(bond::GRUDGE_STAND_IN, &npc);
+        }
+        Some(event)
+    }
+
+    /// 项目线当前阶段的事件：前面欠下的技术债让每个选项都多加压力
+    fn quest_event(&self) -> Option<DailyEvent> {
+        let progress = self.quest.as_ref()?;
+        let stage = progress.current_stage()?;
+        let mut event = self.daily_events.iter().find(|e| e.id == stage.event_id)?.clone();
+        let extra = progress.extra_pressure();
+        if extra > 0 {
+            for option in &mut event.shuffled_options {
+                option.value.1 += extra;
+            }
+            event.description.push_str(&format!("\n⚠️ 前面欠下的技术债找上门了：每个选项压力 +{}", extra));
+        }
+        Some(event)
+    }
+
+    /// 记录日常选择对项目线的影响：接下项目、或走完最后一个阶段结算时返回提示
+    fn record_quest_choice(&mut self, event: &DailyEvent, option: &OptionInfo) -> Option<String> {
+        let Some(progress) = self.quest.as_mut() else {
+            let quest = Quest::offered_by(event.id, option.original_index)?;
+            self.quest = Some(QuestProgress::new(quest));
+            let stages: Vec<&str> = quest.stages().iter().map(|stage| stage.name).collect();
+            return Some(self.template_context().render(&format!(
+                "📋 {{boss}}看你排查得漂亮，把「{}」交给了你：{}，每天推进一个阶段。",
+                quest.label(),
+                stages.join(" → ")
+            )));
+        };
+        if !progress.record(event.id, option.original_index) {
+            return None;
+        }
+        let (quest, quality, debt) = (progress.quest, progress.quality, progress.debt);
+        self.quest = None;
+        let outcome = quest.conclude(quality, debt);
+        self.player.gain_reward(outcome.skills, outcome.pressure);
+        self.player.train(None, outcome.skills);
+        self.player.savings += outcome.money;
+        self.player.reputation += outcome.reputation;
+        let story = self.template_context().render(outcome.message);
+        self.player.add_history(HistoryEntry::quest(quest, &outcome, story.clone()));
+        Some(format!(
+            "{}\n【{}】技能 {:+} · 压力 {:+} · 存款 +{} 元 · 声望 +{}",
+            story, outcome.title, outcome.skills, outcome.pressure, outcome.money, outcome.reputation
+        ))
+    }
+
+    /// 按题材汇总本局日常与周事件的表现（只列出遇到过的题材）
+    pub fn tag_stats(&self) -> Vec<TagStats> {
+        let mut stats: Vec<TagStats> = EventTag::ALL.iter().map(|tag| TagStats::new(*tag)).collect();
+        for entry in &self.player.history {
+            let Some(id) = entry.event_id else {
+                continue;
+            };
+            let tag = match entry.kind {
+                HistoryKind::日常 => self.daily_events.iter().find(|e| e.id == id).map(|e| e.tag),
+                HistoryKind::周事件 => self.weekly_events.iter().find(|e| e.id == id).map(|e| e.tag),
+                _ => None,
+            };
+            if let Some(stat) = tag.and_then(|tag| stats.iter_mut().find(|s| s.tag == tag)) {
+                stat.record(entry.skill_delta, entry.pressure_delta);
+            }
+        }
+        stats.retain(|s| s.choices > 0);
+        stats
+    }
+
+    /// 今天有周事件且尚未处理
+    pub fn weekly_event_pending(&self) -> bool {
+        self.today_weekly_event.is_some() && !self.weekly_event_chosen_today
+    }
+
+    /// 推进到下一天
+    pub fn next_day(&mut self) {
+        self.player.record_snapshot();
+        self.player.day_time = Duration::ZERO;
+        self.current_day += 1;
+        self.player.days_played += 1;
+        // 重置当天选择状态
+        self.event_chosen_today = false;
+        self.weekly_event_chosen_today = false;
+        // 同事委托按收工时的压力记进度，到期结算
+        self.settle_favor(self.current_day - 1);
+        
+        // 每7天增加一周，并汇总刚结束的一周；新的一周有同事来托你帮忙
+        if self.current_day.is_multiple_of(7) {
+            self.pending_recap = Some(WeeklyRecap::build(
+                self.current_week,
+                self.week_start_day,
+                self.current_day - 1,
+                &self.player,
+            ));
+            self.current_week += 1;
+            self.week_start_day = self.current_day;
+            self.post_favor();
+        }
+        
+        // 惯性压力模型：之前攒下的压力变化落地一部分
+        self.player.settle_pressure();
+        // 住得好，每天自动减压；通勤每天有开销与压力变化
+        let commute = self.player.commute;
+        self.player.gain_reward(
+            0,
+            commute.daily_pressure() - self.player.housing.daily_relief(),
+        );
+        self.player.savings -= commute.daily_cost();
+        if self.has_handicap(Handicap::每晚加压) {
+            self.player.gain_reward(0, ascension::NIGHTLY_PRESSURE);
+        }
+        if self.player.keepsakes.contains(&Keepsake::自愈脚本) {
+            self.player.gain_reward(0, -Keepsake::NIGHTLY_RELIEF);
+        }
+        // 周末不上班，压力自然回落
+        let weekend = Weekday::of(self.current_day).is_weekend();
+        if weekend {
+            let pressure = self.player.pressure;
+            self.player.gain_reward(0, week::weekend_pressure(pressure) - pressure);
+        }
+        for companion in &self.companions {
+            let (skill, pressure) = companion.ability.passive();
+            self.player.gain_reward(skill, pressure);
+            self.player.train(None, skill);
+        }
+        // 技能点虚涨没有意义，溢出的部分折成存款
+        if let Some(notice) = self.player.convert_overflow() {
+            self.push_notice(notice);
+        }
+        self.spend_evening();
+        self.night_call = NightCall::roll(&mut self.rng, &self.npc_master);
+        // 内审：罚款、降声望，和其他过夜的事一起第二天展示
+        if let Some(penalty) = self.audit.roll(&mut self.fate) {
+            self.player.gain_reward(penalty.skill, penalty.pressure);
+            self.player.savings += penalty.money;
+            self.player.reputation = self.player.reputation.saturating_sub(penalty.reputation);
+            self.push_notice(penalty.message());
+        }
+        if let Some(news) = self.player.market.tick(&mut self.ticker, self.player.savings) {
+            self.push_notice(news);
+        }
+        let market_move = self.player.market.last_move;
+        if let Some(news) = self.player.portfolio.tick(&mut self.ticker, market_move) {
+            self.push_notice(news);
+        }
+
+        // 每满一个月发工资、扣开销；存款为负时开始破产剧情线
+        if self.player.days_played.is_multiple_of(finance::DAYS_PER_MONTH) {
+            let month = self.player.days_played / finance::DAYS_PER_MONTH;
+            let payslip = Payslip::settle(month, &self.player);
+            self.player.savings = payslip.savings_after;
+            self.payslips.push(payslip);
+            if self.player.savings < 0 && self.desperation.is_none() {
+                self.desperation = Some(0);
+            }
+        }
+
+        // 有分身时隔天换班（接下来的事件按上班的人来抽）
+        self.switch_persona();
+
+        // 记下昨天出现过的事件，接下来几天不再抽到
+        self.recent.record(false, self.today_event.id, self.current_day - 1);
+        if let Some(weekly) = &self.today_weekly_event {
+            self.recent.record(true, weekly.id, self.current_day - 1);
+        }
+
+        // 生成下一天的事件：剧本指定的优先，其次是破产剧情线、其他剧情线，否则随机抽（限时内容只在对应内容包开启时出现）；
+        // 周末不走剧情线，只抽休闲事件
+        self.today_event = match self
+            .pinned_daily_event()
+            .or_else(|| if weekend { None } else { self.story_event() })
+        {
+            Some(event) => event,
+            None => {
+                let (world, day) = (self.world(), self.current_day);
+                let mut pool: Vec<&DailyEvent> = self
+                    .daily_events
+                    .iter()
+                    .filter(|e| {
+                        !e.arc_only && e.weekend == weekend && !self.banned_events.contains(&e.id) && world.admits(e)
+                    })
+                    .collect();
+                // 禁用和内容门槛把候选筛光了（自制事件包、禁得太多）时，不看这些再抽
+                if pool.is_empty() {
+                    pool = self.daily_events.iter().filter(|e| !e.arc_only).collect();
+                }
+                // 按局面过滤（都不满足时当作没有条件）
+                if pool.iter().any(|e| e.trigger.allows(&world)) {
+                    pool.retain(|e| e.trigger.allows(&world));
+                }
+                // 屏蔽玩家不想看的题材（全屏蔽了就当没屏蔽）
+                if pool.iter().any(|e| !self.muted_tags.contains(&e.tag)) {
+                    pool.retain(|e| !self.muted_tags.contains(&e.tag));
+                }
+                // 最近几天出现过的不再抽（候选全都出现过时不去重）
+                let cooldown = self.player.balance.repeat_cooldown_days;
+                if pool.iter().any(|e| self.recent.fresh(false, e.id, day, cooldown)) {
+                    pool.retain(|e| self.recent.fresh(false, e.id, day, cooldown));
+                }
+                // 太久没遇到奇遇时，只从奇遇中抽
+                if self.pity.fortune_due() && pool.iter().any(|e| e.is_fortune()) {
+                    pool.retain(|e| e.is_fortune());
+                }
+                // 调参表给出基础权重，导演再按最近的压力走势加权，所在地的题材再加权
+                let mood = self.director.mood(&world);
+                let max_tension = pool.iter().map(|e| e.tension().abs()).fold(0.0, f32::max);
+                let tuning = tuning::current();
+                let local = self.visit.map(|visit| visit.place.tag());
+                let weights = tuning::normalize(
+                    pool.iter()
+                        .map(|e| {
+                            let bias = if local == Some(e.tag) { office::TAG_BIAS } else { 1.0 };
+                            self.director.weight(mood, e, max_tension) * tuning.weight(false, e.id, e.trigger.weight) * bias
+                        })
+                        .collect(),
+                );
+                // 调参表把权重全调成 0 时不按权重；连一个候选都没有时今天的事件照旧
+                match WeightedIndex::new(&weights) {
+                    Ok(dist) => pool[dist.sample(&mut self.rng)].clone(),
+                    Err(_) => pool
+                        .choose(&mut self.rng)
+                        .map_or_else(|| self.today_event.clone(), |e| (*e).clone()),
+                }
+            }
+        };
+        self.pity.record_event(&self.today_event);
+        // 每次触发事件时重新打乱选项顺序
+        self.today_event.reshuffle(&self.shuffle, &mut self.rng);
+        
+        // 按难度的周事件间隔生成周事件（默认每周五）
+        if week::weekly_event_due(self.current_day, self.player.balance.weekly_event_interval) {
+            let mut weekly = self.next_weekly_event();
+            // 每次触发周事件时也重新打乱选项顺序
+            if let Some(weekly) = weekly.as_mut() {
+                weekly.reshuffle(&self.shuffle, &mut self.rng);
+            }
+            self.today_weekly_event = weekly;
+        } else {
+            self.today_weekly_event = None;
+        }
+
+        self.refresh_today_npcs();
+    }
+
+    /// 剧情线安排的日常事件：破产剧情线优先，其次是其他剧情线
+    fn story_event(&mut self) -> Option<DailyEvent> {
+        self.desperation_event()
+            .or_else(|| self.probation_event())
+            .or_else(|| self.slacking_event())
+            .or_else(|| self.arc_event())
+            .or_else(|| self.quest_event())
+            .or_else(|| self.bond_event())
+            .or_else(|| self.open_source_event())
+            .or_else(|| self.market_event())
+    }
+
+    /// 取出刚结束那一周的周报，取出后清除
+    pub fn take_weekly_recap(&mut self) -> Option<WeeklyRecap> {
+        self.pending_recap.take()
+    }
+
+    /// 结束当天时的猝死判定
+    pub fn check_death(&mut self) {
+        let was_on_probation = self.player.probation.is_some();
+        self.player.check_death(&mut self.fate);
+        match (was_on_probation, self.player.probation) {
+            (false, Some(days)) => self.push_notice(format!(
+                "📋 技能跌破零，HR 没有直接开除你，而是发来一份绩效改进计划：{} 天内把技能补回正数，否则走人。",
+                days
+            )),
+            (true, None) if self.player.is_alive => {
+                self.push_notice("✅ 绩效改进计划通过，HR 把你的名字从名单上划掉了。".to_string())
+            }
+            _ => {}
+        }
+        if let Some(warning) = self.player.slacking_warning().filter(|_| self.player.is_alive) {
+            self.push_notice(warning);
+        }
+        if !self.player.is_alive && self.rollback_armed {
+            self.rollback_armed = false;
+            self.roll_back_day();
+        }
+        // 护肝丸只管猝死，被开除（技能为负）救不回来
+        if !self.player.is_alive && self.player.skills >= 0 && self.player.inventory.take(Item::护肝丸) {
+            let player = &mut self.player;
+            player.is_alive = true;
+            player.died_from_zero_pressure = false;
+            player.zero_pressure_streak = 0;
+            player.pressure = player.pressure.min(items::PILL_PRESSURE);
+            // 攒着的惯性不能留着：不然接下来几晚照样把压力推回去
+            player.pressure_momentum = 0;
+            self.push_notice("💊 眼前一黑的瞬间，你摸出护肝丸吞了下去，又活过来了。".to_string());
+        }
+    }
+
+    /// 本周还没有委托时，挑一位今天能出场的同事发布委托
+    fn post_favor(&mut self) {
+        if self.favor.is_some() {
+            return;
+        }
+        let (world, day) = (self.world(), self.current_day);
+        let eligible: Vec<NpcEncounter> =
+            self.npc_master.iter().filter(|npc| npc.appears.allows(&world)).cloned().collect();
+        self.favor = Favor::post(&mut self.loot, &eligible, day);
+        if let Some(favor) = &self.favor {
+            let notice = format!("📨 同事委托：{}（{}天内）", favor.describe(), favor::FAVOR_DAYS);
+            self.push_notice(notice);
+        }
+    }
+
+    /// 第 `day` 天收工时推进委托：办成了发奖励，过期了对方失望
+    fn settle_favor(&mut self, day: u32) {
+        let Some(favor) = self.favor.as_mut() else {
+            return;
+        };
+        match favor.end_day(day, self.player.pressure) {
+            FavorStatus::进行中 => {}
+            FavorStatus::完成 => {
+                let notice = self.complete_favor();
+                self.push_notice(notice);
+            }
+            FavorStatus::过期 => {
+                let npc = favor.npc.clone();
+                self.favor = None;
+                self.relations.adjust(&npc, -favor::FAVOR_LETDOWN);
+                self.push_notice(format!("📭 答应{}的事没办成，好感 -{}", npc, favor::FAVOR_LETDOWN));
+            }
+        }
+    }
+
+    /// 把委托要的道具交给对方
+    pub fn deliver_favor(&mut self) -> Result<String, String> {
+        let item = self.favor.as_ref().and_then(Favor::wanted_item).ok_or("眼下没有要交道具的委托")?;
+        if !self.player.inventory.take(item) {
+            return Err(format!("背包里没有{}，再找找吧", item));
+        }
+        Ok(self.complete_favor())
+    }
+
+    /// 办成委托：好感大涨，再收下一只锦囊
+    fn complete_favor(&mut self) -> String {
+        let Some(favor) = self.favor.take() else {
+            return String::new();
+        };
+        self.relations.adjust(&favor.npc, favor::FAVOR_AFFINITY);
+        self.player.inventory.add(Item::锦囊);
+        format!(
+            "🤝 {}的委托办成了！好感 +{}，对方塞给你一只{} {}",
+            favor.npc,
+            favor::FAVOR_AFFINITY,
+            Item::锦囊.icon(),
+            Item::锦囊
+        )
+    }
+
+    /// 按概率掉落一件道具放进背包，掉了就返回提示
+    pub fn roll_loot(&mut self, chance: f32) -> Option<String> {
+        let item = Item::roll_drop(&mut self.loot, chance)?;
+        self.player.inventory.add(item);
+        Some(format!("🎁 获得道具：{} {}", item.icon(), item))
+    }
+
+    /// 使用背包里的道具（只有需要手动使用的才行）
+    pub fn use_item(&mut self, item: Item) -> Result<String, String> {
+        if !item.is_usable() {
+            return Err(format!("{}会在需要时自动生效", item));
+        }
+        if !self.player.inventory.take(item) {
+            return Err(format!("背包里没有{}", item));
+        }
+        match item {
+            Item::咖啡 => Ok(format!("☕ 一杯咖啡下肚，{}", self.relieve(items::COFFEE_RELIEF))),
+            Item::锦囊 => Ok(format!("🧧 拆开锦囊，里面是一张“别慌”的字条，{}", self.relieve(items::POUCH_RELIEF))),
+            Item::护肝丸 | Item::晋升符 => unreachable!("自动生效的道具不能手动使用"),
+        }
+    }
+
+    /// 道具减压，返回实际的压力变化说明（压力到底了、惯性模式下只落地一部分时都和名义数值不同）
+    fn relieve(&mut self, amount: i32) -> String {
+        let (pressure, momentum) = (self.player.pressure, self.player.pressure_momentum);
+        self.player.gain_reward(0, -amount);
+        let mut text = format!("压力 -{}", pressure - self.player.pressure);
+        let deferred = momentum - self.player.pressure_momentum;
+        if deferred > 0 {
+            text.push_str(&format!("，还有 {} 点之后几晚慢慢消", deferred));
+        }
+        text
+    }
+
+    /// 免死回滚：把技能、压力（连同压力惯性）退回前一天结束时的样子
+    fn roll_back_day(&mut self) {
+        let (skills, pressure, experience, momentum) = self
+            .player
+            .snapshots
+            .last()
+            .map(|s| (s.skills, s.pressure, s.experience, s.pressure_momentum))
+            .unwrap_or((0, 0, 0, 0));
+        let player = &mut self.player;
+        player.skills = skills;
+        player.pressure = pressure;
+        player.experience = experience;
+        player.pressure_momentum = momentum;
+        player.is_alive = true;
+        player.died_from_zero_pressure = false;
+        player.zero_pressure_streak = 0;
+        self.pending_notice = Some("🛡 差点出局！同伴一键回滚，你又回到了昨天。".to_string());
+    }
+
+    /// 好感达到门槛（默认拉满）且还有空栏位时，可以邀请这位同事加入团队
+    pub fn can_recruit(&self, npc: &str) -> bool {
+        let threshold = self
+            .npc_master
+            .iter()
+            .find(|n| n.name == npc)
+            .map_or(gift::MAX_AFFINITY, |n| n.recruit_affinity);
+        self.player.is_alive
+            && self.companions.len() < COMPANION_SLOTS
+            && self.relations.affinity(npc) >= threshold
+            && !self.companions.iter().any(|c| c.name == npc)
+    }
+
+    pub fn recruit(&mut self, npc: &str) -> Result<String, String> {
+        if !self.can_recruit(npc) {
+            return Err(format!("{}现在还不能加入你的团队", npc));
+        }
+        let companion = Companion::new(npc);
+        let notice = format!(
+            "🤝 {}加入了你的团队！{}，专属技能「{}」",
+            npc,
+            companion.ability.passive_label(),
+            companion.ability.label()
+        );
+        self.companions.push(companion);
+        Ok(notice)
+    }
+
+    /// 使用同伴的专属技能（每周一次）
+    pub fn use_companion(&mut self, index: usize) -> Result<String, String> {
+        if !self.player.is_alive {
+            return Err("你已离开公司".to_string());
+        }
+        let week = self.current_week;
+        let companion = self.companions.get_mut(index).ok_or("没有这位同伴")?;
+        if !companion.ready(week) {
+            return Err(format!("{}本周已经帮过你了", companion.name));
+        }
+        companion.last_used_week = Some(week);
+        let ability = companion.ability;
+        let (skill, pressure) = match ability {
+            Ability::免死回滚 => {
+                self.rollback_armed = true;
+                (0, 0)
+            }
+            Ability::带薪摸鱼 => (0, -15),
+            Ability::通宵冲刺 => (10, 10),
+            Ability::特调咖啡 => (0, -10),
+            Ability::帮把手 => (5, 0),
+        };
+        self.player.gain_reward(skill, pressure);
+        self.player.train(None, skill);
+        Ok(format!("{}：{}", ability.label(), ability.description()))
+    }
+
+    /// 本周是否开着免死回滚
+    pub fn rollback_armed(&self) -> bool {
+        self.rollback_armed
+    }
+
+    /// 尝试晋升；成功时立即记下分段用时（在推进到下一天之前，保证计时精确）
+    pub fn attempt_promotion(&mut self) -> Result<(bool, String), String> {
+        let skills = self.player.skills;
+        let result = self.player.attempt_promotion(&mut self.fate)?;
+        let realm = Realm::from_level(self.player.realm_level);
+        let lost = skills - self.player.skills;
+        self.player.add_history(HistoryEntry::promotion(result.0, realm, lost, &result.1));
+        if result.0 {
+            self.splits.push(Split {
+                realm: Realm::from_level(self.player.realm_level),
+                day: self.current_day,
+                elapsed_ms: self.elapsed_millis(),
+            });
+        }
+        Ok(result)
+    }
+
+    /// 累计游玩时间：前端在玩家实际游玩时（没暂停、没最小化）调用
+    pub fn tick(&mut self, elapsed: Duration) {
+        self.player.play_time += elapsed;
+        self.player.day_time += elapsed;
+    }
+
+    /// 本局累计游玩时间（毫秒）
+    pub fn elapsed_millis(&self) -> u64 {
+        self.player.play_time.as_millis() as u64
+    }
+
+    /// 获取游戏进行时间（秒）
+    pub fn get_elapsed_seconds(&self) -> u64 {
+        self.player.play_time.as_secs()
+    }
+
+    /// 格式化时间为"时:分:秒"
+    pub fn format_time(&self) -> String {
+        let seconds = self.get_elapsed_seconds();
+        let hours = seconds / 3600;
+        let minutes = (seconds % 3600) / 60;
+        let secs = seconds % 60;
+        format!("{}:{:02}:{:02}", hours, minutes, secs)
+    }
+}
+
+fn format_delta(value: i32) -> String {
+    if value >= 0 {
+        format!("+{}", value)
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,152 @@
+//! 送礼与好感：花钱给同事送礼物提升好感，好感高的同事办事时多帮一把；好感为负即厌恶。
+//! 礼物目录与每位同事的喜好放在 content/gifts.json，喜好要在对话中摸清。
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::content::ContentFile;
+
+/// 好感上限
+pub const MAX_AFFINITY: i32 = 100;
+/// 好感下限（厌恶到顶）
+pub const MIN_AFFINITY: i32 = -100;
+
+/// 一件可以送的礼物
+#[derive(Debug, Clone, Deserialize)]
+pub struct Gift {
+    pub id: String,
+    pub label: String,
+    pub price: i32,
+}
+
+/// 某位同事的喜好
+#[derive(Debug, Clone, Deserialize)]
+pub struct Taste {
+    pub loves: Vec<String>,
+    pub hates: Vec<String>,
+    /// 对话里透露喜好的台词
+    pub hint: String,
+}
+
+impl Taste {
+    /// 给玩家看的喜好总结
+    pub fn summary(&self) -> String {
+        let join = |ids: &[String]| {
+            if ids.is_empty() {
+                "无".to_string()
+            } else {
+                ids.join("、")
+            }
+        };
+        format!("喜欢：{}；讨厌：{}", join(&self.loves), join(&self.hates))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GiftContent {
+    gifts: Vec<Gift>,
+    preferences: HashMap<String, Taste>,
+}
+
+fn content() -> &'static GiftContent {
+    static CONTENT: OnceLock<GiftContent> = OnceLock::new();
+    CONTENT.get_or_init(|| serde_json::from_str(ContentFile::Gifts.text()).expect("内置礼物数据必须有效"))
+}
+
+/// 全部礼物
+pub fn catalog() -> &'static [Gift] {
+    &content().gifts
+}
+
+/// 某位同事的喜好（没有配置时为 None）
+pub fn taste_of(npc: &str) -> Option<&'static Taste> {
+    content().preferences.get(npc)
+}
+
+/// 和同事们的关系：好感、已摸清的喜好、今天送过礼的人、已走完专属剧情的人
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Relations {
+    affinity: BTreeMap<String, i32>,
+    discovered: Vec<String>,
+    gifted_today: Vec<String>,
+    bonded: Vec<String>,
+}
+
+impl Relations {
+    pub fn affinity(&self, npc: &str) -> i32 {
+        self.affinity.get(npc).copied().unwrap_or(0)
+    }
+
+    pub fn knows_taste(&self, npc: &str) -> bool {
+        self.discovered.iter().any(|name| name == npc)
+    }
+
+    pub fn gifted_today(&self, npc: &str) -> bool {
+        self.gifted_today.iter().any(|name| name == npc)
+    }
+
+    /// 对话中第一次聊到喜好时返回提示台词，并记为已摸清
+    pub fn discover(&mut self, npc: &str) -> Option<&'static str> {
+        if self.knows_taste(npc) {
+            return None;
+        }
+        let taste = taste_of(npc)?;
+        self.discovered.push(npc.to_string());
+        Some(&taste.hint)
+    }
+
+    /// 送出礼物，返回好感变化（喜欢 +15，讨厌 -10，其他 +5）
+    pub fn give(&mut self, npc: &str, gift: &Gift) -> i32 {
+        let change = match taste_of(npc) {
+            Some(taste) if taste.loves.contains(&gift.id) => 15,
+            Some(taste) if taste.hates.contains(&gift.id) => -10,
+            _ => 5,
+        };
+        self.adjust(npc, change);
+        self.gifted_today.push(npc.to_string());
+        change
+    }
+
+    /// 直接调整好感（限制在下限~上限）
+    pub fn adjust(&mut self, npc: &str, change: i32) {
+        let affinity = self.affinity.entry(npc.to_string()).or_insert(0);
+        *affinity = (*affinity + change).clamp(MIN_AFFINITY, MAX_AFFINITY);
+    }
+
+    pub fn has_bonded(&self, npc: &str) -> bool {
+        self.bonded.iter().any(|name| name == npc)
+    }
+
+    /// 记下已走完这位同事的专属剧情
+    pub fn bond(&mut self, npc: &str) {
+        if !self.has_bonded(npc) {
+            self.bonded.push(npc.to_string());
+        }
+    }
+
+    /// 厌恶最深、且好感不高于 `threshold` 的同事
+    pub fn most_resentful(&self, threshold: i32) -> Option<&str> {
+        self.affinity
+            .iter()
+            .filter(|(_, affinity)| **affinity <= threshold)
+            .min_by_key(|(_, affinity)| **affinity)
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// 好感带来的额外技能收益：关系好的同事办事时多帮一把
+    pub fn favor_bonus(&self, npc: &str) -> i32 {
+        match self.affinity(npc) {
+            MAX_AFFINITY.. => 2,
+            50.. => 1,
+            _ => 0,
+        }
+    }
+
+    /// 新的一天，每人又可以收一次礼
+    pub fn new_day(&mut self) {
+        self.gifted_today.clear();
+    }
+}
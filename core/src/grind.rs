@@ -0,0 +1,81 @@
+//! 防刷：同一类“安全”选择（甩锅、摸鱼）反复用，收益逐次递减，
+//! 用到一定程度会被领导识破，吃一次大亏。换着花样选会让热度慢慢降下来。
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::ChoiceArchetype;
+
+/// 热度达到多少开始递减收益
+const DECAY_START: u32 = 4;
+/// 超过起点后，每多一次收益再打的折扣（百分比）
+const DECAY_STEP_PERCENT: i32 = 20;
+/// 收益最低保留的百分比
+const MIN_PERCENT: i32 = 20;
+/// 热度达到多少被识破
+const EXPOSED_AT: u32 = 8;
+/// 选了别的类型时，其余类型热度下降多少
+const COOLDOWN: u32 = 2;
+/// 被识破的代价：(技能点, 压力)
+const EXPOSED_PENALTY: (i32, i32) = (-5, 10);
+
+/// 各类选择的“热度”（随档保存）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GrindTracker {
+    heat: [u32; 3],  // 按 ChoiceArchetype::ALL 的顺序
+}
+
+impl GrindTracker {
+    /// 只有不费力的选择会被盯上；硬刚本身就要付压力，不算刷
+    fn watched(archetype: ChoiceArchetype) -> bool {
+        archetype != ChoiceArchetype::内卷
+    }
+
+    fn slot(archetype: ChoiceArchetype) -> usize {
+        ChoiceArchetype::ALL.iter().position(|a| *a == archetype).unwrap_or(0)
+    }
+
+    /// 某类选择已进入递减区间时，给玩家的提醒
+    pub fn warning(&self) -> Option<String> {
+        ChoiceArchetype::ALL
+            .iter()
+            .find(|a| self.heat[Self::slot(**a)] >= DECAY_START)
+            .map(|a| format!("🕵️ 最近总在{}，领导开始留意了", a))
+    }
+
+    /// 记录一次日常选择并调整结果：热度过高时收益（加技能、减压力）打折，
+    /// 达到识破线时附加惩罚并清零该类热度；有调整时一并返回说明
+    pub fn record(&mut self, archetype: ChoiceArchetype, (skill, pressure): (i32, i32)) -> ((i32, i32), Option<String>) {
+        let slot = Self::slot(archetype);
+        for (i, heat) in self.heat.iter_mut().enumerate() {
+            if i != slot {
+                *heat = heat.saturating_sub(COOLDOWN);
+            }
+        }
+        if !Self::watched(archetype) {
+            return ((skill, pressure), None);
+        }
+        self.heat[slot] += 1;
+        let heat = self.heat[slot];
+        if heat >= EXPOSED_AT {
+            self.heat[slot] = 0;
+            let value = (skill + EXPOSED_PENALTY.0, pressure + EXPOSED_PENALTY.1);
+            let note = format!(
+                "被识破：你这套{}的路数领导早看穿了（技能 {}，压力 +{}）",
+                archetype, EXPOSED_PENALTY.0, EXPOSED_PENALTY.1
+            );
+            return (value, Some(note));
+        }
+        if heat <= DECAY_START {
+            return ((skill, pressure), None);
+        }
+        let percent = (100 - DECAY_STEP_PERCENT * (heat - DECAY_START) as i32).max(MIN_PERCENT);
+        let shrink = |gain: i32| gain * percent / 100;
+        let value = (
+            if skill > 0 { shrink(skill) } else { skill },
+            if pressure < 0 { -shrink(-pressure) } else { pressure },
+        );
+        let note = format!("又是{}，效果只剩 {}%，再这样下去要被识破了", archetype, percent);
+        (value, Some(note))
+    }
+}
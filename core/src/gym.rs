@@ -0,0 +1,181 @@
+//! 训练接口（`gym` 特性）：把对局包装成强化学习常见的 reset/step 环境。
+//! 观测是定长的 f32 向量，操作是 `ACTIONS` 里的下标，奖励是这一步的得分变化；
+//! 同一个种子 reset 出来的对局完全一样，方便复现训练结果。
+//! 对局本身走擂台同一套开局（`arena::start_run`），代理看到的选项数值也和擂台一样。
+
+use crate::arena::{self, Action, StateView, MAX_DAYS, MAX_STEPS};
+use crate::balance::BalanceConfig;
+use crate::director::Director;
+use crate::engine::{GameEngine, GamePhase};
+use crate::week::Weekday;
+
+/// 离散操作表：`step` 收到的下标对应这里的操作
+pub const ACTIONS: [Action; 10] = [
+    Action::Choose(0),
+    Action::Choose(1),
+    Action::Choose(2),
+    Action::OpenWeeklyEvent,
+    Action::NextDay,
+    Action::Promote,
+    Action::DeclinePromotion,
+    Action::AnswerNightCall(true),
+    Action::AnswerNightCall(false),
+    Action::CloseRecap,
+];
+
+/// 观测向量里编码几个选项（多出来的选项看不到，也选不了）
+const OPTION_SLOTS: usize = 3;
+
+/// 每个选项占几位：是否存在、技能点、压力值、是否灰色操作
+const OPTION_FEATURES: usize = 4;
+
+/// 阶段独热编码的顺序
+const PHASES: [GamePhase; 7] = [
+    GamePhase::Start,
+    GamePhase::EventDisplay,
+    GamePhase::WeeklyEventDisplay,
+    GamePhase::PromotionConfirm,
+    GamePhase::NightCall,
+    GamePhase::WeeklyRecap,
+    GamePhase::GameOver,
+];
+
+/// 飞升对应的晋升等级
+const ASCENDED_LEVEL: f32 = 7.0;
+
+/// 玩家属性占几位（见 `observe`）
+const PLAYER_FEATURES: usize = 11;
+
+/// 观测向量的长度
+pub const OBSERVATION_SIZE: usize = PLAYER_FEATURES + PHASES.len() + OPTION_SLOTS * OPTION_FEATURES;
+
+/// 选了当前不可用的操作时扣的分（对局不动）
+pub const INVALID_ACTION_PENALTY: f32 = -1.0;
+
+/// 一步的结果
+#[derive(Debug, Clone)]
+pub struct Step {
+    pub observation: Vec<f32>,
+    pub reward: f32,
+    /// 对局结束（出局、飞升或打满天数）
+    pub done: bool,
+    /// 步数用完被截断
+    pub truncated: bool,
+}
+
+/// 训练环境
+pub struct Env {
+    pub balance: BalanceConfig,
+    pub director: Director,
+    engine: GameEngine,
+    score: u32,
+    steps: u32,
+}
+
+impl Env {
+    pub fn new(balance: BalanceConfig) -> Self {
+        Env {
+            balance,
+            director: Director::default(),
+            engine: GameEngine::new(),
+            score: 0,
+            steps: 0,
+        }
+    }
+
+    /// 用指定种子开新的一局，返回初始观测
+    pub fn reset(&mut self, seed: u64) -> Vec<f32> {
+        self.engine = arena::start_run(&self.balance, self.director, seed);
+        self.engine.bus.drain();
+        self.score = self.current_score();
+        self.steps = 0;
+        self.observe()
+    }
+
+    /// 执行 `ACTIONS[action]`，返回新的观测与这一步的得分变化
+    pub fn step(&mut self, action: usize) -> Step {
+        let done = !self.engine.in_run();
+        let reward = if done {
+            0.0
+        } else if self.action_mask().get(action).copied().unwrap_or(false) {
+            self.engine.execute(ACTIONS[action].into());
+            // 训练不需要订阅引擎事件，及时清掉免得越攒越多
+            self.engine.bus.drain();
+            self.steps += 1;
+            let score = self.current_score();
+            let reward = score as f32 - self.score as f32;
+            self.score = score;
+            reward
+        } else {
+            self.steps += 1;
+            INVALID_ACTION_PENALTY
+        };
+        let done = !self.engine.in_run();
+        Step {
+            observation: self.observe(),
+            reward,
+            done,
+            truncated: !done && self.steps >= MAX_STEPS,
+        }
+    }
+
+    /// 现在哪些操作可用（与 `ACTIONS` 一一对应）
+    pub fn action_mask(&self) -> [bool; ACTIONS.len()] {
+        let mut mask = [false; ACTIONS.len()];
+        let Some(view) = StateView::of(&self.engine) else {
+            return mask;
+        };
+        for (allowed, action) in mask.iter_mut().zip(ACTIONS) {
+            *allowed = view.allows(action);
+        }
+        mask
+    }
+
+    /// 底层引擎（想看剧情文字、结局卡时用）
+    pub fn engine(&self) -> &GameEngine {
+        &self.engine
+    }
+
+    fn current_score(&self) -> u32 {
+        self.engine.game_state.as_ref().map_or(0, |state| state.score())
+    }
+
+    /// 把局面编码成定长向量，各项大致缩放到 0~1 附近
+    pub fn observe(&self) -> Vec<f32> {
+        let mut obs = Vec::with_capacity(OBSERVATION_SIZE);
+        let Some(state) = self.engine.game_state.as_ref() else {
+            obs.resize(OBSERVATION_SIZE, 0.0);
+            return obs;
+        };
+        let player = &state.player;
+        let flag = |on: bool| if on { 1.0 } else { 0.0 };
+        obs.extend([
+            player.days_played as f32 / MAX_DAYS as f32,
+            player.realm_level as f32 / ASCENDED_LEVEL,
+            player.experience as f32 / 1000.0,
+            player.skills as f32 / 100.0,
+            player.pressure as f32 / 100.0,
+            player.savings as f32 / 10_000.0,
+            player.tonight_risk(),
+            player.promotion_failure_rate(),
+            flag(player.can_promote()),
+            flag(Weekday::of(state.current_day).is_weekend()),
+            flag(self.engine.weekly_event_pending()),
+        ]);
+        obs.extend(PHASES.map(|phase| flag(self.engine.phase == phase)));
+        let options = StateView::of(&self.engine).map_or(&[][..], |view| view.options);
+        for slot in 0..OPTION_SLOTS {
+            match options.get(slot) {
+                Some(option) => obs.extend([
+                    1.0,
+                    option.value.0 as f32 / 10.0,
+                    option.value.1 as f32 / 10.0,
+                    flag(option.gray_risk > 0),
+                ]),
+                None => obs.extend([0.0; OPTION_FEATURES]),
+            }
+        }
+        debug_assert_eq!(obs.len(), OBSERVATION_SIZE);
+        obs
+    }
+}
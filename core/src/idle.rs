@@ -0,0 +1,71 @@
+//! 挂机修炼：开着游戏的现实时间、以及两次启动之间的离线时间（有上限），
+//! 按轮折算成少量技能点，代价是不少压力。
+//! 这里单独记账，与速通计时、游戏进行时间互不影响。
+
+use serde::{Deserialize, Serialize};
+
+/// 每修炼一轮需要的现实秒数
+pub const SECONDS_PER_ROUND: f64 = 600.0;
+/// 每轮获得的技能点
+const ROUND_SKILL: i32 = 1;
+/// 每轮增加的压力
+const ROUND_PRESSURE: i32 = 3;
+/// 最多攒下多少秒（离线再久也只算这么多）
+const BANK_CAP: f64 = 8.0 * 3600.0;
+
+/// 挂机修炼的时间账本（随档案设置保存）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IdleClock {
+    pub enabled: bool,
+    banked: f64,             // 攒下、还没折算的秒数
+    last_seen: Option<i64>,  // 上次记账时的 Unix 时间（秒），用来算离线时长
+}
+
+impl IdleClock {
+    /// 启动或切换档案时调用：把离线的时间记入账本，返回记入的秒数
+    pub fn resume(&mut self, now: i64) -> f64 {
+        let offline = match (self.enabled, self.last_seen) {
+            (true, Some(last)) => (now - last).max(0) as f64,
+            _ => 0.0,
+        };
+        self.last_seen = Some(now);
+        self.deposit(offline)
+    }
+
+    /// 程序开着时记账；`now` 为当前 Unix 时间，`elapsed` 为距上次记账的秒数
+    pub fn accrue(&mut self, now: i64, elapsed: f64) {
+        self.last_seen = Some(now);
+        if self.enabled {
+            self.deposit(elapsed.max(0.0));
+        }
+    }
+
+    fn deposit(&mut self, seconds: f64) -> f64 {
+        let before = self.banked;
+        self.banked = (self.banked + seconds).min(BANK_CAP);
+        self.banked - before
+    }
+
+    /// 取出攒够的整轮数，余下的秒数留在账本里
+    pub fn take_rounds(&mut self) -> u32 {
+        let rounds = (self.banked / SECONDS_PER_ROUND).floor();
+        self.banked -= rounds * SECONDS_PER_ROUND;
+        rounds as u32
+    }
+
+    /// 攒下、还没折算的秒数
+    pub fn banked_seconds(&self) -> f64 {
+        self.banked
+    }
+
+    /// 距离下一轮还差多少秒
+    pub fn seconds_to_next_round(&self) -> f64 {
+        (SECONDS_PER_ROUND - self.banked).max(0.0)
+    }
+}
+
+/// 修炼若干轮的收益：(技能点, 压力)
+pub fn rewards(rounds: u32) -> (i32, i32) {
+    (rounds as i32 * ROUND_SKILL, rounds as i32 * ROUND_PRESSURE)
+}
@@ -0,0 +1,167 @@
+//! 道具（法宝）：做事件、帮同事时偶尔掉落，放进背包。
+//! 护肝丸和晋升符在猝死判定、晋升判定时自动生效；咖啡由玩家在背包里手动喝。
+//! 锦囊不会掉落，只有办成同事委托才拿得到。
+
+use std::fmt;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::game::GameRng;
+
+/// 做完一次日常/周事件后掉落道具的概率
+pub const EVENT_DROP_CHANCE: f32 = 0.06;
+/// 答应同事的请求后对方塞给你道具的概率
+pub const NPC_DROP_CHANCE: f32 = 0.12;
+/// 咖啡降低的压力
+pub const COFFEE_RELIEF: i32 = 10;
+/// 晋升符降低的晋升失败率
+pub const TALISMAN_EDGE: f32 = 0.20;
+/// 锦囊降低的压力
+pub const POUCH_RELIEF: i32 = 25;
+/// 护肝丸救回一命后压力降到这个值以下
+pub const PILL_PRESSURE: i32 = 60;
+
+/// 道具
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Item {
+    护肝丸,
+    咖啡,
+    晋升符,
+    锦囊,
+}
+
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Item {
+    pub const ALL: [Item; 4] = [Item::护肝丸, Item::咖啡, Item::晋升符, Item::锦囊];
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Item::护肝丸 => "💊",
+            Item::咖啡 => "☕",
+            Item::晋升符 => "📜",
+            Item::锦囊 => "🧧",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Item::护肝丸 => "猝死时自动服下，抵消一次猝死（被开除救不回来）",
+            Item::咖啡 => "喝一杯，压力 -10",
+            Item::晋升符 => "下次冲击晋升时自动燃起，失败率 -20%",
+            Item::锦囊 => "同事还你人情时塞的锦囊，拆开压力 -25",
+        }
+    }
+
+    /// 需要玩家手动使用（其余道具在对应判定时自动生效）
+    pub fn is_usable(&self) -> bool {
+        matches!(self, Item::咖啡 | Item::锦囊)
+    }
+
+    /// 掉落时各道具的权重：咖啡最常见，护肝丸最稀有，锦囊不掉落
+    fn weight(&self) -> u32 {
+        match self {
+            Item::护肝丸 => 1,
+            Item::咖啡 => 6,
+            Item::晋升符 => 3,
+            Item::锦囊 => 0,
+        }
+    }
+
+    /// 按概率掉落一件道具
+    pub fn roll_drop(rng: &mut GameRng, chance: f32) -> Option<Item> {
+        if rng.gen::<f32>() >= chance {
+            return None;
+        }
+        let total: u32 = Item::ALL.iter().map(Item::weight).sum();
+        let mut roll = rng.gen_range(0..total);
+        for item in Item::ALL {
+            if roll < item.weight() {
+                return Some(item);
+            }
+            roll -= item.weight();
+        }
+        None
+    }
+}
+
+/// 背包：每种道具的数量
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Inventory {
+    items: Vec<(Item, u32)>,
+}
+
+impl Inventory {
+    pub fn count(&self, item: Item) -> u32 {
+        self.items.iter().find(|(i, _)| *i == item).map_or(0, |(_, n)| *n)
+    }
+
+    pub fn has(&self, item: Item) -> bool {
+        self.count(item) > 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn add(&mut self, item: Item) {
+        match self.items.iter_mut().find(|(i, _)| *i == item) {
+            Some((_, n)) => *n += 1,
+            None => self.items.push((item, 1)),
+        }
+    }
+
+    /// 用掉一件，没有时返回 false
+    pub fn take(&mut self, item: Item) -> bool {
+        let Some(index) = self.items.iter().position(|(i, _)| *i == item) else {
+            return false;
+        };
+        self.items[index].1 -= 1;
+        if self.items[index].1 == 0 {
+            self.items.remove(index);
+        }
+        true
+    }
+
+    /// 持有的道具与数量（按获得顺序）
+    pub fn items(&self) -> &[(Item, u32)] {
+        &self.items
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::GameState;
+
+    #[test]
+    fn coffee_is_drunk_by_hand_and_talisman_burns_on_promotion() {
+        let mut state = GameState::with_seed("测试".to_string(), 7);
+        assert!(state.use_item(Item::咖啡).is_err());
+
+        state.player.pressure = 50;
+        state.player.inventory.add(Item::咖啡);
+        state.player.inventory.add(Item::晋升符);
+        assert!(state.use_item(Item::晋升符).is_err());
+        assert!(state.use_item(Item::咖啡).is_ok());
+        assert_eq!(state.player.pressure, 50 - COFFEE_RELIEF);
+        assert!(!state.player.inventory.has(Item::咖啡));
+
+        let with_talisman = state.player.promotion_failure_rate();
+        state.player.skills = state.player.skill_requirement();
+        for track in crate::specialty::SkillTrack::ALL {
+            state.player.train(Some(track), state.player.skills);
+        }
+        assert!(state.attempt_promotion().is_ok());
+        assert!(!state.player.inventory.has(Item::晋升符));
+        assert!(state.player.inventory.is_empty());
+        state.player.promotion_attempts = 0;
+        assert!(state.player.promotion_failure_rate() > with_talisman);
+    }
+}
@@ -0,0 +1,59 @@
+//! 修仙编程游戏的核心逻辑：对局状态机（`engine`）、对局状态与事件（`game`）、数值配置（`balance`）
+//! 以及各玩法模块。不依赖任何界面库，前端只需把操作翻译成 `GameCommand` 交给 `GameEngine`。
+
+pub mod achievements;
+pub mod almanac;
+pub mod arena;
+pub mod arc;
+pub mod ascension;
+pub mod audit;
+pub mod balance;
+pub mod bond;
+pub mod bus;
+pub mod commute;
+pub mod companion;
+pub mod content;
+pub mod course;
+pub mod director;
+pub mod ending;
+pub mod engine;
+pub mod events;
+pub mod favor;
+pub mod finance;
+pub mod game;
+pub mod gift;
+pub mod grind;
+#[cfg(feature = "gym")]
+pub mod gym;
+pub mod idle;
+pub mod items;
+pub mod market;
+pub mod message;
+pub mod mood;
+pub mod name;
+pub mod nightcall;
+pub mod office;
+pub mod onboarding;
+pub mod opensource;
+pub mod pickrate;
+pub mod policy;
+pub mod portfolio;
+pub mod quest;
+pub mod rebirth;
+pub mod recap;
+pub mod roster;
+pub mod save;
+pub mod scenario;
+pub mod season;
+pub mod shuffle;
+pub mod specialty;
+pub mod speedrun;
+pub mod tag;
+pub mod template;
+pub mod tone;
+pub mod trigger;
+pub mod tuning;
+pub mod warning;
+pub mod week;
+pub mod whatif;
+pub mod world;
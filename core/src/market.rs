@@ -0,0 +1,92 @@
+//! 市场行情：奶茶价格和股市每天都在波动。奶茶价格决定月底的奶茶开销，
+//! 大盘涨跌带动投资组合里的股票；存款充裕时偶尔有理财经理上门推销。
+//! 行情用单独的随机流推进，不会打乱每天的事件顺序。
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::game::GameRng;
+
+/// 开局时一杯奶茶的价格
+pub const MILK_TEA_BASE_PRICE: i32 = 22;
+/// 理财经理上门推销时出场的事件
+pub const INVEST_EVENT: usize = 103;
+/// 理财事件各选项（按原始位置 A/B/C）买入的金额
+pub const INVEST_AMOUNTS: [i32; 3] = [3000, 1000, 0];
+
+const OPENING_INDEX: i32 = 3000;
+const PRICE_CHANGE_CHANCE: f32 = 0.03;
+const SHOCK_CHANCE: f32 = 0.02;
+const INVEST_CHANCE: f32 = 0.03;
+
+/// 市场状态：奶茶价格与大盘
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Market {
+    pub milk_tea_price: i32,
+    pub index: i32,      // 大盘点位
+    pub last_move: i32,  // 大盘当天涨跌（千分比）
+    pending_event: Option<usize>,
+}
+
+impl Default for Market {
+    fn default() -> Self {
+        Market {
+            milk_tea_price: MILK_TEA_BASE_PRICE,
+            index: OPENING_INDEX,
+            last_move: 0,
+            pending_event: None,
+        }
+    }
+}
+
+impl Market {
+    /// 过了一天的行情；有值得一提的变动时返回快讯
+    pub fn tick(&mut self, rng: &mut GameRng, savings: i32) -> Option<String> {
+        let mut news = Vec::new();
+
+        if rng.gen::<f32>() < PRICE_CHANGE_CHANCE {
+            let change = if rng.gen_bool(0.6) {
+                rng.gen_range(2..=5)
+            } else {
+                -rng.gen_range(1..=3)
+            };
+            self.milk_tea_price = (self.milk_tea_price + change).clamp(15, 40);
+            news.push(if change > 0 {
+                format!("🧋 奶茶涨价了，一杯 {} 元", self.milk_tea_price)
+            } else {
+                format!("🧋 奶茶店搞促销，一杯 {} 元", self.milk_tea_price)
+            });
+        }
+
+        // 大盘每天在 ±2% 内波动，偶尔暴涨暴跌（千分比）
+        let roll: f32 = rng.gen();
+        let permille = if roll < SHOCK_CHANCE {
+            -rng.gen_range(120..=250)
+        } else if roll < SHOCK_CHANCE * 2.0 {
+            rng.gen_range(60..=120)
+        } else {
+            rng.gen_range(-20..=20)
+        };
+        self.index = (self.index * (1000 + permille) / 1000).max(1);
+        self.last_move = permille;
+
+        if permille <= -120 {
+            news.push(format!("📉 股市暴跌 {:.1}%，大盘 {} 点", permille as f32 / -10.0, self.index));
+        } else if permille >= 60 {
+            news.push(format!("📈 股市暴涨 {:.1}%，大盘 {} 点", permille as f32 / 10.0, self.index));
+        } else if self.pending_event.is_none()
+            && savings >= INVEST_AMOUNTS[0]
+            && rng.gen::<f32>() < INVEST_CHANCE
+        {
+            self.pending_event = Some(INVEST_EVENT);
+        }
+
+        (!news.is_empty()).then(|| news.join("\n"))
+    }
+
+    /// 取出该出场的行情事件
+    pub fn take_due_event(&mut self) -> Option<usize> {
+        self.pending_event.take()
+    }
+}
@@ -0,0 +1,88 @@
+//! 结果提示：每次操作后显示的那段文字。按段落和样式分成若干片段，只在下一次操作时重建，
+//! 界面每帧直接按片段渲染成富文本，不再每帧拼字符串、按行切分。
+
+use std::borrow::Cow;
+use std::fmt;
+
+/// 片段的样式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    正文,
+    /// 选项触发的剧情
+    剧情,
+    /// 结局标题之类的醒目大字
+    标题,
+    /// 操作提示（如“点击进入下一天继续”），淡色显示
+    提示,
+    警告,
+    喜报,
+}
+
+/// 一段同样式的文字
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    pub text: Cow<'static, str>,
+    pub style: Style,
+}
+
+/// 一条结果提示
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResultMessage {
+    segments: Vec<Segment>,
+}
+
+impl ResultMessage {
+    /// 只有一段文字的提示
+    pub fn with(style: Style, text: impl Into<Cow<'static, str>>) -> Self {
+        let mut message = Self::default();
+        message.push(style, text);
+        message
+    }
+
+    /// 紧接着上一段追加文字（不换行）
+    pub fn push(&mut self, style: Style, text: impl Into<Cow<'static, str>>) -> &mut Self {
+        let text = text.into();
+        if !text.is_empty() {
+            self.segments.push(Segment { text, style });
+        }
+        self
+    }
+
+    /// 空一行后另起一段
+    pub fn paragraph(&mut self, style: Style, text: impl Into<Cow<'static, str>>) -> &mut Self {
+        if !self.is_empty() {
+            self.push(Style::正文, "\n\n");
+        }
+        self.push(style, text)
+    }
+
+    pub fn clear(&mut self) {
+        self.segments.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// 某一片段里含有 `pattern`
+    #[cfg(test)]
+    pub fn contains(&self, pattern: &str) -> bool {
+        self.segments.iter().any(|segment| segment.text.contains(pattern))
+    }
+}
+
+impl From<String> for ResultMessage {
+    fn from(text: String) -> Self {
+        Self::with(Style::正文, text)
+    }
+}
+
+impl fmt::Display for ResultMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.segments.iter().try_for_each(|segment| f.write_str(&segment.text))
+    }
+}
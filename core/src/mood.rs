@@ -0,0 +1,123 @@
+//! NPC 心情：每天由天气、项目状况和各人的脾气决定，影响对话内容和奖励大小。
+//! 全部由种子与天数推算，不消耗随机数流。
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// 稳定的字符串哈希（FNV-1a），同样的输入在各平台上结果相同
+pub fn stable_hash(parts: &[&[u8]]) -> u64 {
+    parts
+        .iter()
+        .flat_map(|part| part.iter())
+        .fold(0xcbf2_9ce4_8422_2325_u64, |h, b| (h ^ *b as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Weather {
+    晴,
+    雨,
+    雾霾,
+}
+
+impl Weather {
+    /// 某局某天的天气
+    pub fn on(seed: u64, day: u32) -> Weather {
+        match stable_hash(&[&seed.to_le_bytes(), &day.to_le_bytes()]) % 5 {
+            0 | 1 => Weather::晴,
+            2 | 3 => Weather::雨,
+            _ => Weather::雾霾,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Weather::晴 => "☀ 晴",
+            Weather::雨 => "🌧 雨",
+            Weather::雾霾 => "🌫 雾霾",
+        }
+    }
+
+    fn effect(&self) -> i32 {
+        match self {
+            Weather::晴 => 1,
+            Weather::雨 => 0,
+            Weather::雾霾 => -1,
+        }
+    }
+}
+
+/// 项目状况：玩家压力越大，说明项目越水深火热
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProjectHealth {
+    稳定,
+    一般,
+    告急,
+}
+
+impl ProjectHealth {
+    pub fn from_pressure(pressure: i32) -> ProjectHealth {
+        match pressure {
+            ..=30 => ProjectHealth::稳定,
+            31..=69 => ProjectHealth::一般,
+            _ => ProjectHealth::告急,
+        }
+    }
+
+    fn effect(&self) -> i32 {
+        match self {
+            ProjectHealth::稳定 => 1,
+            ProjectHealth::一般 => 0,
+            ProjectHealth::告急 => -1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NpcMood {
+    开心,
+    #[default]
+    平静,
+    烦躁,
+}
+
+impl fmt::Display for NpcMood {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            NpcMood::开心 => "开心",
+            NpcMood::平静 => "平静",
+            NpcMood::烦躁 => "烦躁",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl NpcMood {
+    /// 某位 NPC 今天的心情：天气 + 项目状况 + 个人脾气
+    pub fn of(npc: &str, seed: u64, day: u32, weather: Weather, health: ProjectHealth) -> NpcMood {
+        let temper = (stable_hash(&[npc.as_bytes(), &seed.to_le_bytes(), &day.to_le_bytes()]) % 3) as i32 - 1;
+        match weather.effect() + health.effect() + temper {
+            1.. => NpcMood::开心,
+            0 => NpcMood::平静,
+            _ => NpcMood::烦躁,
+        }
+    }
+
+    pub fn emoji(&self) -> &'static str {
+        match self {
+            NpcMood::开心 => "😄",
+            NpcMood::平静 => "😐",
+            NpcMood::烦躁 => "😠",
+        }
+    }
+
+    /// 心情对奖励的影响：开心时技能收益翻 1.5 倍，烦躁时减半且多添 2 点压力
+    pub fn adjust(&self, (skill, pressure): (i32, i32)) -> (i32, i32) {
+        match self {
+            NpcMood::开心 if skill > 0 => (skill * 3 / 2, pressure),
+            NpcMood::烦躁 if skill > 0 => (skill / 2, pressure + 2),
+            NpcMood::烦躁 => (skill, pressure + 2),
+            _ => (skill, pressure),
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! 玩家名号的校验：名号会进导出的历史、排行榜和分享图，开局前统一整理一遍。
+//! 首尾空白去掉、中间连续空白并成一个空格，限制长度，再对一遍 content/banned_words.json 里的屏蔽词
+//! （中文词按子串、英文词按整词比对）。
+
+use std::sync::OnceLock;
+
+use crate::content::ContentFile;
+
+/// 名号最多几个字（按字符数，中英文都算一个）
+pub const MAX_NAME_CHARS: usize = 12;
+
+fn banned_words() -> &'static [String] {
+    static WORDS: OnceLock<Vec<String>> = OnceLock::new();
+    WORDS.get_or_init(|| {
+        let words: Vec<String> =
+            serde_json::from_str(ContentFile::BannedWords.text()).expect("内置屏蔽词表必须有效");
+        words.iter().map(|word| fold(word)).filter(|word| !word.is_empty()).collect()
+    })
+}
+
+/// 比对用的形式：小写、去掉所有空白（防止“傻 逼”这样拆开写）
+fn fold(text: &str) -> String {
+    text.chars().filter(|c| !c.is_whitespace()).flat_map(char::to_lowercase).collect()
+}
+
+/// 名号里有没有这个屏蔽词：中文词按子串比对（中文没有词间空格）；
+/// 纯字母数字的词要整词出现，免得“sb”误伤 Isbel 这样的名字（全部拆开写的也算）
+fn contains_word(name: &str, word: &str) -> bool {
+    if !word.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return fold(name).contains(word);
+    }
+    let spelled: String = name.chars().filter(char::is_ascii_alphanumeric).flat_map(|c| c.to_lowercase()).collect();
+    spelled == word
+        || name
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .any(|token| token.eq_ignore_ascii_case(word))
+}
+
+/// 整理名号：去掉控制字符，首尾空白去掉，中间连续空白并成一个空格
+pub fn normalize(raw: &str) -> String {
+    let cleaned: String = raw.chars().map(|c| if c.is_control() { ' ' } else { c }).collect();
+    cleaned.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 校验名号，通过时返回整理后的名号，不通过时返回给玩家看的原因
+pub fn validate(raw: &str) -> Result<String, String> {
+    check(raw, banned_words())
+}
+
+/// 外部来的名字（聊天账号名等）整理成合规名号：太长的截断，仍不合规时用 `fallback`
+pub fn sanitize(raw: &str, fallback: &str) -> String {
+    let name: String = normalize(raw).chars().take(MAX_NAME_CHARS).collect();
+    validate(&name).unwrap_or_else(|_| fallback.to_string())
+}
+
+fn check(raw: &str, banned: &[String]) -> Result<String, String> {
+    let name = normalize(raw);
+    if name.is_empty() {
+        return Err("名号不能为空".to_string());
+    }
+    let len = name.chars().count();
+    if len > MAX_NAME_CHARS {
+        return Err(format!("名号最多 {} 个字（现在 {} 个）", MAX_NAME_CHARS, len));
+    }
+    if banned.iter().any(|word| contains_word(&name, word)) {
+        return Err("名号里有不合适的词，换一个吧".to_string());
+    }
+    Ok(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_and_rejects_long_or_banned_names() {
+        let banned = vec![fold("坏 词")];
+        assert_eq!(check("  凌霄\t 程序侠\n", &banned), Ok("凌霄 程序侠".to_string()));
+        assert!(check(" \n ", &banned).is_err());
+        assert!(check(&"剑".repeat(MAX_NAME_CHARS + 1), &banned).is_err());
+        assert!(check(&"剑".repeat(MAX_NAME_CHARS), &banned).is_ok());
+        assert!(check("大 坏词 侠", &banned).is_err());
+        assert!(check("大坏 词侠", &banned).is_err());
+        assert!(validate("FUCK").is_err());
+        assert!(validate("f u c k").is_err());
+        assert!(validate("Mr SB").is_err());
+        assert!(validate("大sb侠").is_err());
+        assert_eq!(validate("Isbel"), Ok("Isbel".to_string()));
+        assert_eq!(sanitize(" xiuxian_coder_2024 ", "无名"), "xiuxian_code");
+        assert_eq!(sanitize("Mr SB", "无名"), "无名");
+        assert_eq!(sanitize(" \n ", "无名"), "无名");
+    }
+}
@@ -0,0 +1,91 @@
+//! 深夜来电：过夜时偶尔有同事打电话求救，必须当场答应或拒绝，代价和回报都比白天大得多
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{GameRng, NpcEncounter, NpcOption};
+
+/// 每晚接到来电的概率
+const CALL_CHANCE: f32 = 0.08;
+
+/// 一通深夜来电
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NightCall {
+    pub caller: String,
+    pub plea: String,
+    pub accept: NpcOption,
+    pub reject: NpcOption,
+}
+
+type CallScript = (&'static str, &'static str, (&'static str, (i32, i32)), (&'static str, (i32, i32)));
+
+/// 来电剧本：来电人、求救内容、答应的结果、拒绝的结果
+const SCRIPTS: [CallScript; 6] = [
+    (
+        "运维老李",
+        "📞 凌晨三点：'机房着火了！消防在路上，核心库还没备份！'",
+        ("你穿着拖鞋冲进机房抢出了硬盘，第二天全公司都知道了你的名字", (8, 15)),
+        ("第二天复盘会上，你的名字被点了七次", (-6, 6)),
+    ),
+    (
+        "内卷仙子阿卷",
+        "📞 凌晨两点：'明早给大客户演示的环境挂了，全公司就你会修！'",
+        ("你修到天亮，演示大获成功，阿卷在群里@全员感谢你", (6, 12)),
+        ("演示现场白屏了十分钟，客户的脸比屏幕还白", (-3, 4)),
+    ),
+    (
+        "HR郭",
+        "📞 深夜十一点：'你组里有人说撑不下去了，你能过来陪陪他吗？'",
+        ("你陪他聊到天亮，他说'谢谢你，我还想再试试'", (5, 10)),
+        ("第二天他交了辞职信，HR郭看你的眼神意味深长", (-5, 8)),
+    ),
+    (
+        "玄学大师林玄",
+        "📞 子时整：'今夜服务器气运大凶，速来机房烧香！'",
+        ("你半夜在机房点了三炷香，那晚居然一个告警都没有", (3, 8)),
+        ("凌晨四点服务器果然宕机了，林玄发来一个'我早说了'", (-4, 2)),
+    ),
+    (
+        "后勤林",
+        "📞 凌晨一点：'机柜跳闸了，UPS 只能再撑二十分钟！'",
+        ("你们在UPS耗尽前一分钟切好了备用电路", (7, 12)),
+        ("整栋楼断网到天亮，早上的晨会改成了追责会", (-6, 5)),
+    ),
+    (
+        "小迷弟",
+        "📞 凌晨零点半，小迷弟带着哭腔：'前辈！我误删了生产库！'",
+        ("你远程指导他从备份恢复了数据，他说要给你做一辈子的PPT", (6, 10)),
+        ("你关了手机，第二天公司所有订单都不见了", (-2, 3)),
+    ),
+];
+
+impl NightCall {
+    /// 过夜时掷骰：有剧本且在名册里的同事才可能打来
+    pub fn roll(rng: &mut GameRng, roster: &[NpcEncounter]) -> Option<NightCall> {
+        if rng.gen::<f32>() >= CALL_CHANCE {
+            return None;
+        }
+        let candidates: Vec<&CallScript> = SCRIPTS
+            .iter()
+            .filter(|(caller, ..)| roster.iter().any(|npc| npc.name == *caller))
+            .collect();
+        let (caller, plea, (accept, accept_reward), (reject, reject_reward)) = **candidates.choose(rng)?;
+        Some(NightCall {
+            caller: caller.to_string(),
+            plea: plea.to_string(),
+            accept: NpcOption {
+                summary: "马上出门".to_string(),
+                detail: accept.to_string(),
+                reward: accept_reward,
+                gray_risk: 0,
+            },
+            reject: NpcOption {
+                summary: "翻个身继续睡".to_string(),
+                detail: reject.to_string(),
+                reward: reject_reward,
+                gray_risk: 0,
+            },
+        })
+    }
+}
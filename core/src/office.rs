@@ -0,0 +1,66 @@
+//! 办公室地图：每天可以挑一处待着（工位、茶水间、机房、会议室），一天只能挑一次。
+//! 常在那里出没的同事当天更容易碰上；之后抽到的日常事件也偏向那里的题材，直到换个地方。
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::tag::EventTag;
+
+/// 所在地对应题材的日常事件的权重倍数
+pub const TAG_BIAS: f32 = 2.0;
+
+/// 办公室里能待的地方
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Location {
+    工位,
+    茶水间,
+    机房,
+    会议室,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl Location {
+    /// 按地图上的摆放顺序（左上、右上、左下、右下）
+    pub const ALL: [Location; 4] = [Location::工位, Location::茶水间, Location::机房, Location::会议室];
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Location::工位 => "💻",
+            Location::茶水间 => "☕",
+            Location::机房 => "🖥",
+            Location::会议室 => "📊",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Location::工位 => "埋头写代码，编程语言相关的事找上门来",
+            Location::茶水间 => "接杯水摸会儿鱼，生活琐事和八卦多",
+            Location::机房 => "守着服务器，项目上的事一件接一件",
+            Location::会议室 => "开不完的会，职场上的明争暗斗都在这",
+        }
+    }
+
+    /// 在这里更容易遇到的事件题材
+    pub fn tag(&self) -> EventTag {
+        match self {
+            Location::工位 => EventTag::编程语言,
+            Location::茶水间 => EventTag::生活,
+            Location::机房 => EventTag::项目,
+            Location::会议室 => EventTag::职场,
+        }
+    }
+}
+
+/// 最近一次挑的去处
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Visit {
+    pub place: Location,
+    pub day: u32,  // 在第几天挑的（同一天不能再换）
+}
@@ -0,0 +1,31 @@
+//! 新手引导周：玩家第一次开局时，头七天按作者挑好的事件走（每天教一样玩法并附上提示），
+//! 之后照常随机抽取。剧本格式与挑战模式相同，但不限天数、不单独记成绩。
+
+use std::sync::OnceLock;
+
+use crate::scenario::Scenario;
+
+/// 随游戏发布的引导剧本
+const DEMO_WEEK: &str = include_str!("../../scenarios/onboarding.json");
+
+/// 引导周剧本（种子与每天的事件）
+pub fn demo_week() -> &'static Scenario {
+    static SCRIPT: OnceLock<Scenario> = OnceLock::new();
+    SCRIPT.get_or_init(|| serde_json::from_str(DEMO_WEEK).expect("引导剧本格式错误"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events;
+
+    #[test]
+    fn demo_week_references_real_events() {
+        let library = events::library();
+        let daily: Vec<usize> = library.daily_events().iter().map(|e| e.id).collect();
+        let weekly: Vec<usize> = library.weekly_events().iter().map(|e| e.id).collect();
+        let script = demo_week();
+        script.validate(&daily, &weekly).unwrap();
+        assert_eq!(script.notes.len(), script.daily.len());
+    }
+}
@@ -0,0 +1,113 @@
+//! 开源项目：晚上抽空写开源攒 star，达到里程碑会招来猎头，
+//! 长期不管则 issue 越积越多，良心不安
+
+use serde::{Deserialize, Serialize};
+
+/// star 里程碑：每达到一个就招来一次猎头，最后一个解锁“技术网红”称号
+pub const STAR_MILESTONES: [u32; 3] = [100, 500, 1000];
+/// issue 积压太久时出场的事件
+pub const GUILT_EVENT: usize = 99;
+/// 达到 star 里程碑后出场的事件
+pub const RECRUITER_EVENT: usize = 100;
+/// 称号：star 达到最后一个里程碑后获得
+pub const INFLUENCER_TITLE: &str = "技术网红";
+
+/// 下班后的晚上怎么过（报了课时晚上固定去上课）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EveningPlan {
+    #[default]
+    休息,
+    写开源,
+}
+
+impl EveningPlan {
+    pub const ALL: [EveningPlan; 2] = [EveningPlan::休息, EveningPlan::写开源];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            EveningPlan::休息 => "🛋 休息",
+            EveningPlan::写开源 => "💻 写开源",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            EveningPlan::休息 => "下班就是下班",
+            EveningPlan::写开源 => "每晚技能 +1、压力 +2，项目涨 star、清 issue",
+        }
+    }
+}
+
+/// 玩家维护的开源项目
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OpenSource {
+    pub stars: u32,
+    pub commits: u32,
+    pub backlog: u32,       // 积压的 issue
+    pub idle_nights: u32,   // 连续多少个晚上没管项目
+    milestones_reached: usize,
+    pending_event: Option<usize>,
+}
+
+impl OpenSource {
+    /// 写了一晚上开源（声望高的人项目涨得快）；达到里程碑时返回提示
+    pub fn contribute(&mut self, reputation: u32) -> Option<String> {
+        self.stars += 5 + self.commits.min(15) + reputation / 10;
+        self.commits += 1;
+        self.backlog = self.backlog.saturating_sub(2);
+        self.idle_nights = 0;
+
+        let reached = STAR_MILESTONES
+            .iter()
+            .filter(|m| self.stars >= **m)
+            .count();
+        if reached <= self.milestones_reached {
+            return None;
+        }
+        self.milestones_reached = reached;
+        self.pending_event = Some(RECRUITER_EVENT);
+        let milestone = STAR_MILESTONES[reached - 1];
+        let mut notice = format!("⭐ 开源项目突破 {} star！猎头闻着味就来了。", milestone);
+        if self.is_influencer() {
+            notice.push_str(&format!("\n🏷 解锁称号「{}」", INFLUENCER_TITLE));
+        }
+        Some(notice)
+    }
+
+    /// 一晚上没管项目：有人用的项目会不断冒出 issue，积压久了良心不安
+    pub fn neglect(&mut self) {
+        if self.commits == 0 {
+            return;
+        }
+        self.idle_nights += 1;
+        self.backlog += 1 + self.stars / 200;
+        if self.idle_nights >= 4 && self.backlog >= 3 && self.pending_event.is_none() {
+            self.pending_event = Some(GUILT_EVENT);
+            self.idle_nights = 0;
+        }
+    }
+
+    /// 取出该出场的项目事件
+    pub fn take_due_event(&mut self) -> Option<usize> {
+        self.pending_event.take()
+    }
+
+    /// issue 积压事件的后果（按选项原始序号）
+    pub fn resolve_guilt(&mut self, original_index: u32) {
+        match original_index {
+            0 => self.backlog = 0,
+            1 => self.backlog /= 2,
+            _ => self.stars -= self.stars / 10,
+        }
+    }
+
+    pub fn is_influencer(&self) -> bool {
+        self.milestones_reached >= STAR_MILESTONES.len()
+    }
+
+    /// 下一个 star 里程碑
+    pub fn next_milestone(&self) -> Option<u32> {
+        STAR_MILESTONES.get(self.milestones_reached).copied()
+    }
+}
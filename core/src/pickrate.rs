@@ -0,0 +1,103 @@
+//! 选项选择率：记录玩家选的是打乱后第几个位置、原本是哪个选项，跨局在本地累计。
+//! 选项每次都会打乱顺序，理想情况下各位置被选的比例应接近随机期望；
+//! 明显偏离（比如总爱点第一个）说明界面在引导玩家，调整布局时可以参考。
+
+use serde::{Deserialize, Serialize};
+
+use crate::bus::GameEvent;
+use crate::game::HistoryKind;
+
+/// 存储键
+pub const PICK_STATS_KEY: &str = "xiuxian_pick_stats";
+
+/// 某个位置的统计
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PositionPicks {
+    pub offered: u32,   // 这个位置上有选项的次数
+    pub picked: u32,    // 这个位置被选的次数
+    pub expected: f32,  // 随机乱选时期望被选的次数（每次 1/选项数）
+}
+
+impl PositionPicks {
+    /// 实际选择率（0~1）
+    pub fn rate(&self) -> f32 {
+        self.picked as f32 / self.offered.max(1) as f32
+    }
+
+    /// 随机乱选时的期望选择率（0~1）
+    pub fn expected_rate(&self) -> f32 {
+        self.expected / self.offered.max(1) as f32
+    }
+}
+
+/// 某个事件各原始选项被选的次数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventPicks {
+    pub kind: HistoryKind,
+    pub event_id: usize,
+    pub name: String,
+    pub picks: Vec<u32>,  // 按原始位置 0=A, 1=B, 2=C
+}
+
+impl EventPicks {
+    pub fn total(&self) -> u32 {
+        self.picks.iter().sum()
+    }
+}
+
+/// 跨局累计的选项选择率（订阅总线，随档案落盘）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PickStats {
+    pub positions: Vec<PositionPicks>,  // 按打乱后的位置
+    pub events: Vec<EventPicks>,
+}
+
+impl PickStats {
+    pub fn on_event(&mut self, event: &GameEvent) {
+        if let GameEvent::OptionPicked { kind, event_id, name, position, original, offered } = event {
+            self.record(*kind, *event_id, name, *position, *original as usize, *offered);
+        }
+    }
+
+    fn record(&mut self, kind: HistoryKind, event_id: usize, name: &str, position: usize, original: usize, offered: usize) {
+        if offered == 0 || position >= offered {
+            return;
+        }
+        if self.positions.len() < offered {
+            self.positions.resize(offered, PositionPicks::default());
+        }
+        for slot in &mut self.positions[..offered] {
+            slot.offered += 1;
+            slot.expected += 1.0 / offered as f32;
+        }
+        self.positions[position].picked += 1;
+
+        let idx = match self.events.iter().position(|e| e.kind == kind && e.event_id == event_id) {
+            Some(idx) => idx,
+            None => {
+                self.events.push(EventPicks { kind, event_id, name: name.to_string(), picks: Vec::new() });
+                self.events.len() - 1
+            }
+        };
+        let picks = &mut self.events[idx].picks;
+        if picks.len() <= original {
+            picks.resize(original + 1, 0);
+        }
+        picks[original] += 1;
+    }
+
+    /// 一共记录了多少次选择
+    pub fn total(&self) -> u32 {
+        self.positions.iter().map(|slot| slot.picked).sum()
+    }
+
+    /// 被选得最多的几个事件（从多到少）
+    pub fn most_played(&self, n: usize) -> Vec<&EventPicks> {
+        let mut events: Vec<&EventPicks> = self.events.iter().collect();
+        events.sort_by_key(|e| std::cmp::Reverse(e.total()));
+        events.truncate(n);
+        events
+    }
+}
@@ -0,0 +1,64 @@
+use crate::game::{OptionInfo, PlayerState};
+
+/// 自动修仙使用的决策策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutoPolicy {
+    /// 闭眼乱选
+    Random,
+    /// 优先压力最低，绝不让技能点跌成负数
+    GreedySafe,
+    /// 优先技能点最高，压力随缘
+    GreedySkill,
+}
+
+impl AutoPolicy {
+    pub const ALL: [AutoPolicy; 3] = [
+        AutoPolicy::Random,
+        AutoPolicy::GreedySafe,
+        AutoPolicy::GreedySkill,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AutoPolicy::Random => "随缘乱选",
+            AutoPolicy::GreedySafe => "稳健养生",
+            AutoPolicy::GreedySkill => "极限内卷",
+        }
+    }
+
+    /// 从（已打乱的）选项中挑一个，返回下标
+    pub fn choose(&self, player: &PlayerState, options: &[OptionInfo]) -> Option<usize> {
+        if options.is_empty() {
+            return None;
+        }
+        match self {
+            AutoPolicy::Random => Some(rand::random::<usize>() % options.len()),
+            AutoPolicy::GreedySafe => options
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, opt)| {
+                    let (skill, pressure) = opt.value;
+                    let gets_fired = player.skills + skill < 0;
+                    (gets_fired, pressure, -skill)
+                })
+                .map(|(idx, _)| idx),
+            AutoPolicy::GreedySkill => options
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, opt)| {
+                    let (skill, pressure) = opt.value;
+                    (skill, -pressure)
+                })
+                .map(|(idx, _)| idx),
+        }
+    }
+
+    /// 是否接受晋升
+    pub fn should_promote(&self, player: &PlayerState) -> bool {
+        match self {
+            AutoPolicy::Random => rand::random::<bool>(),
+            AutoPolicy::GreedySafe => player.promotion_failure_rate() <= 0.2,
+            AutoPolicy::GreedySkill => true,
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! 投资组合：把存款分配到基金、股票、加密货币，波动一个比一个大。
+//! 每晚按行情流模拟涨跌；持仓遇上暴跌时会逼出割肉还是死扛的抉择。
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::game::GameRng;
+use crate::market;
+
+/// 股票暴跌时出场的事件
+pub const STOCK_CRASH_EVENT: usize = 104;
+/// 加密货币暴跌时出场的事件
+pub const CRYPTO_CRASH_EVENT: usize = 105;
+/// 暴跌事件中“抄底”加仓的金额
+pub const BUY_THE_DIP: i32 = 2000;
+/// 面板上每次买入的金额
+pub const LOT: i32 = 1000;
+/// 单日跌幅达到这个千分比算暴跌
+const CRASH_PERMILLE: i32 = -120;
+/// 走势图保留的天数
+const HISTORY_DAYS: usize = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Instrument {
+    基金,
+    股票,
+    加密货币,
+}
+
+impl Instrument {
+    pub const ALL: [Instrument; 3] = [Instrument::基金, Instrument::股票, Instrument::加密货币];
+
+    fn index(&self) -> usize {
+        match self {
+            Instrument::基金 => 0,
+            Instrument::股票 => 1,
+            Instrument::加密货币 => 2,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Instrument::基金 => "🏦 基金",
+            Instrument::股票 => "📈 股票",
+            Instrument::加密货币 => "🪙 加密货币",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Instrument::基金 => "稳健，每天小涨小跌",
+            Instrument::股票 => "跟着大盘走，偶尔暴跌",
+            Instrument::加密货币 => "一夜暴富或一夜归零",
+        }
+    }
+
+    /// 暴跌时出场的事件（基金不会暴跌）
+    pub fn crash_event(&self) -> Option<usize> {
+        match self {
+            Instrument::基金 => None,
+            Instrument::股票 => Some(STOCK_CRASH_EVENT),
+            Instrument::加密货币 => Some(CRYPTO_CRASH_EVENT),
+        }
+    }
+
+    /// 当天的涨跌（千分比）；股票直接跟随大盘
+    fn daily_move(&self, rng: &mut GameRng, market_move: i32) -> i32 {
+        match self {
+            Instrument::基金 => rng.gen_range(-6..=8),
+            Instrument::股票 => market_move,
+            Instrument::加密货币 => match rng.gen::<f32>() {
+                roll if roll < 0.03 => -rng.gen_range(300..=500),
+                roll if roll < 0.06 => rng.gen_range(200..=400),
+                _ => rng.gen_range(-80..=80),
+            },
+        }
+    }
+}
+
+/// 各品种的持仓市值与投入本金
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Portfolio {
+    values: [i32; 3],
+    costs: [i32; 3],
+    pub history: Vec<i32>,  // 每天收盘后的总市值（画走势图）
+    pending_event: Option<usize>,
+}
+
+impl Portfolio {
+    pub fn value(&self, instrument: Instrument) -> i32 {
+        self.values[instrument.index()]
+    }
+
+    pub fn cost(&self, instrument: Instrument) -> i32 {
+        self.costs[instrument.index()]
+    }
+
+    pub fn total_value(&self) -> i32 {
+        self.values.iter().sum()
+    }
+
+    pub fn total_cost(&self) -> i32 {
+        self.costs.iter().sum()
+    }
+
+    pub fn buy(&mut self, instrument: Instrument, amount: i32) {
+        self.values[instrument.index()] += amount;
+        self.costs[instrument.index()] += amount;
+    }
+
+    /// 清仓，返回回笼的现金
+    pub fn sell_all(&mut self, instrument: Instrument) -> i32 {
+        self.costs[instrument.index()] = 0;
+        std::mem::take(&mut self.values[instrument.index()])
+    }
+
+    /// 过了一晚的涨跌；持仓暴跌时返回快讯，并安排割肉还是死扛的事件
+    pub fn tick(&mut self, rng: &mut GameRng, market_move: i32) -> Option<String> {
+        let mut news = Vec::new();
+        for instrument in Instrument::ALL {
+            let permille = instrument.daily_move(rng, market_move);
+            let value = &mut self.values[instrument.index()];
+            if *value == 0 {
+                continue;
+            }
+            *value = (*value as i64 * (1000 + permille) as i64 / 1000) as i32;
+            if permille <= CRASH_PERMILLE {
+                if let (Some(event), None) = (instrument.crash_event(), self.pending_event) {
+                    self.pending_event = Some(event);
+                    news.push(format!("💥 {}持仓一夜缩水 {:.1}%", instrument.label(), permille as f32 / -10.0));
+                }
+            }
+        }
+        if self.total_cost() > 0 || !self.history.is_empty() {
+            self.history.push(self.total_value());
+            if self.history.len() > HISTORY_DAYS {
+                self.history.remove(0);
+            }
+        }
+        (!news.is_empty()).then(|| news.join("\n"))
+    }
+
+    /// 取出该出场的暴跌事件
+    pub fn take_due_event(&mut self) -> Option<usize> {
+        self.pending_event.take()
+    }
+
+    /// 投资相关事件的后果（按选项原始序号）；返回卖出回笼的现金
+    pub fn resolve(&mut self, event_id: usize, original_index: u32) -> i32 {
+        let crashed = Instrument::ALL
+            .into_iter()
+            .find(|i| i.crash_event() == Some(event_id));
+        match (event_id, crashed, original_index) {
+            (market::INVEST_EVENT, _, idx) => {
+                self.buy(Instrument::股票, market::INVEST_AMOUNTS[idx as usize]);
+                0
+            }
+            (_, Some(instrument), 0) => self.sell_all(instrument),
+            (_, Some(instrument), 1) => {
+                self.buy(instrument, BUY_THE_DIP);
+                0
+            }
+            _ => 0,
+        }
+    }
+}
@@ -0,0 +1,140 @@
+//! 项目线：跨多天的连续剧情链，由某个选择接下项目后，之后每天按阶段出场一个专属日常事件。
+//! 每个阶段的选择会累积“质量”和“技术债”：技术债让后续阶段每个选项都更累，
+//! 质量决定最后发布时的奖励；走完全部阶段按两者结算，并记入历史。
+
+use serde::{Deserialize, Serialize};
+
+/// 每点技术债让后续阶段每个选项多加的压力
+pub const DEBT_PRESSURE: i32 = 2;
+
+/// 项目线中的一个阶段：出场的事件，以及各选项（按原始顺序）带来的质量与技术债
+#[derive(Debug, Clone, Copy)]
+pub struct QuestStage {
+    pub name: &'static str,
+    pub event_id: usize,
+    pub quality: [i32; 3],
+    pub debt: [i32; 3],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Quest {
+    /// 智眼数据劫里排查得漂亮，{boss}把智眼上线交给了你
+    智眼上线,
+}
+
+/// 智眼上线的阶段：立项 → 开发 → 联调 → 发布
+const ZHIYAN_STAGES: [QuestStage; 4] = [
+    QuestStage { name: "立项", event_id: 110, quality: [3, 1, 0], debt: [0, 0, 1] },
+    QuestStage { name: "开发", event_id: 111, quality: [3, 1, 0], debt: [0, 1, 2] },
+    QuestStage { name: "联调", event_id: 112, quality: [2, 1, 0], debt: [-1, 0, 2] },
+    QuestStage { name: "发布", event_id: 113, quality: [2, 1, 0], debt: [0, 0, 1] },
+];
+
+impl Quest {
+    /// 某个日常选择是否会接下项目（事件 id + 选项原始序号）
+    pub fn offered_by(event_id: usize, original_index: u32) -> Option<Quest> {
+        match (event_id, original_index) {
+            (0, 0) => Some(Quest::智眼上线),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Quest::智眼上线 => "智眼项目上线",
+        }
+    }
+
+    pub fn stages(&self) -> &'static [QuestStage] {
+        match self {
+            Quest::智眼上线 => &ZHIYAN_STAGES,
+        }
+    }
+
+    /// 按质量与技术债结算
+    pub fn conclude(&self, quality: i32, debt: i32) -> QuestOutcome {
+        match self {
+            Quest::智眼上线 => match quality - debt {
+                8.. => QuestOutcome {
+                    title: "完美上线",
+                    skills: 40,
+                    pressure: -20,
+                    money: 8000,
+                    reputation: 25,
+                    message: "🚀 智眼零故障上线！客户当场续约三年，{boss}在全员大会上点了你的名，项目奖金到账。",
+                },
+                4..=7 => QuestOutcome {
+                    title: "顺利上线",
+                    skills: 25,
+                    pressure: -10,
+                    money: 4000,
+                    reputation: 10,
+                    message: "🚀 智眼按期上线，小毛病几个，热修复一晚上搞定。项目奖金不多，但够请组里喝一周奶茶。",
+                },
+                _ => QuestOutcome {
+                    title: "带病上线",
+                    skills: 10,
+                    pressure: 10,
+                    money: 1000,
+                    reputation: 0,
+                    message: "🚀 智眼磕磕绊绊上线了，告警群响了一整夜。{boss}说'先上线再说'，你知道这笔债迟早要还。",
+                },
+            },
+        }
+    }
+}
+
+/// 项目线结算结果
+#[derive(Debug, Clone)]
+pub struct QuestOutcome {
+    pub title: &'static str,
+    pub skills: i32,
+    pub pressure: i32,
+    pub money: i32,
+    pub reputation: u32,
+    pub message: &'static str,
+}
+
+/// 进行中的项目线
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuestProgress {
+    pub quest: Quest,
+    pub stage: usize,
+    pub quality: i32,
+    pub debt: i32,
+}
+
+impl QuestProgress {
+    pub fn new(quest: Quest) -> Self {
+        QuestProgress {
+            quest,
+            stage: 0,
+            quality: 0,
+            debt: 0,
+        }
+    }
+
+    /// 当前阶段
+    pub fn current_stage(&self) -> Option<&'static QuestStage> {
+        self.quest.stages().get(self.stage)
+    }
+
+    /// 前面阶段欠下的技术债让这一阶段每个选项多加的压力
+    pub fn extra_pressure(&self) -> i32 {
+        self.debt.max(0) * DEBT_PRESSURE
+    }
+
+    /// 记录这一阶段的选择；若这是当前阶段的事件就进入下一阶段，返回是否已走完
+    pub fn record(&mut self, event_id: usize, original_index: u32) -> bool {
+        let Some(stage) = self.current_stage() else {
+            return true;
+        };
+        if stage.event_id == event_id {
+            let index = original_index as usize;
+            self.quality += stage.quality.get(index).copied().unwrap_or(0);
+            self.debt = (self.debt + stage.debt.get(index).copied().unwrap_or(0)).max(0);
+            self.stage += 1;
+        }
+        self.stage >= self.quest.stages().len()
+    }
+}
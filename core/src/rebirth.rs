@@ -0,0 +1,126 @@
+//! 转世：一局结束（出局或通关）后可以选择转世，把本局修为按比例折成仙缘点带进下一世；
+//! 仙缘点在兑换处换成永久加成（开局技能点、降低猝死率、解锁隐藏事件），
+//! 开局时由 `BalanceConfig::with_boons` 写进数值配置，规则本身不用知道转世的存在。
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::GameState;
+
+/// 存储键
+pub const META_PROGRESS_KEY: &str = "xiuxian_meta_progress";
+
+/// 每级“宿慧”开局多带的技能点
+pub const SKILLS_PER_LEVEL: i32 = 10;
+/// 每级“护体”降低的猝死概率（百分比，按难度表打折）
+pub const DEATH_REDUCTION_PERCENT: i32 = 8;
+
+/// 多少技能点折一点仙缘
+const SKILLS_PER_KARMA: i32 = 10;
+/// 每个晋升等级折多少仙缘
+const KARMA_PER_REALM: u32 = 5;
+/// 通关额外奖励的仙缘
+const VICTORY_KARMA: u32 = 20;
+
+/// 可兑换的永久加成
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Boon {
+    宿慧,
+    护体,
+    前世记忆,
+}
+
+impl fmt::Display for Boon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Boon::宿慧 => "宿慧",
+            Boon::护体 => "护体",
+            Boon::前世记忆 => "前世记忆",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl Boon {
+    pub const ALL: [Boon; 3] = [Boon::宿慧, Boon::护体, Boon::前世记忆];
+
+    /// 最高等级
+    pub fn max_level(&self) -> u32 {
+        match self {
+            Boon::宿慧 | Boon::护体 => 5,
+            Boon::前世记忆 => 1,
+        }
+    }
+
+    /// 从 `level` 级升到下一级要花的仙缘点
+    pub fn cost(&self, level: u32) -> u32 {
+        match self {
+            Boon::宿慧 => 10 * (level + 1),
+            Boon::护体 => 15 * (level + 1),
+            Boon::前世记忆 => 30,
+        }
+    }
+
+    /// 某个等级的效果
+    pub fn description(&self, level: u32) -> String {
+        match self {
+            Boon::宿慧 => format!("开局技能点 +{}", level as i32 * SKILLS_PER_LEVEL),
+            Boon::护体 => format!("猝死概率 -{}%", level as i32 * DEATH_REDUCTION_PERCENT),
+            Boon::前世记忆 if level > 0 => "隐藏事件会出现在日常里".to_string(),
+            Boon::前世记忆 => "解锁只有转世之人才会遇到的隐藏事件".to_string(),
+        }
+    }
+}
+
+/// 跨局保存的转世进度（随档案落盘）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetaProgress {
+    pub karma: u32,           // 手上的仙缘点
+    pub lifetime_karma: u32,  // 历代累计获得的仙缘点
+    pub rebirths: u32,        // 转世次数
+    boons: Vec<(Boon, u32)>,  // 已兑换的加成与等级
+}
+
+impl MetaProgress {
+    /// 一局结束后能折出多少仙缘点：技能点、晋升等级，通关另有奖励
+    pub fn karma_for(state: &GameState) -> u32 {
+        let player = &state.player;
+        let karma = (player.skills.max(0) / SKILLS_PER_KARMA) as u32 + player.realm_level * KARMA_PER_REALM;
+        if state.is_victory(true) {
+            karma + VICTORY_KARMA
+        } else {
+            karma
+        }
+    }
+
+    /// 转世：收下这一世折出的仙缘点
+    pub fn reincarnate(&mut self, karma: u32) {
+        self.karma += karma;
+        self.lifetime_karma += karma;
+        self.rebirths += 1;
+    }
+
+    pub fn level(&self, boon: Boon) -> u32 {
+        self.boons.iter().find(|(b, _)| *b == boon).map_or(0, |(_, level)| *level)
+    }
+
+    /// 升级一项加成；满级或仙缘点不够时返回原因
+    pub fn buy(&mut self, boon: Boon) -> Result<(), String> {
+        let level = self.level(boon);
+        if level >= boon.max_level() {
+            return Err(format!("{}已经满级", boon));
+        }
+        let cost = boon.cost(level);
+        if self.karma < cost {
+            return Err(format!("仙缘点不足：升级{}需要 {} 点", boon, cost));
+        }
+        self.karma -= cost;
+        match self.boons.iter_mut().find(|(b, _)| *b == boon) {
+            Some((_, level)) => *level += 1,
+            None => self.boons.push((boon, 1)),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+//! 周报：每周结束时汇总这一周的属性变化、最好/最差的选择、NPC 往来，并给出评级
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::{HistoryEntry, HistoryKind, PlayerState};
+
+/// 一周的汇总
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeeklyRecap {
+    pub week: u32,
+    pub first_day: u32,
+    pub last_day: u32,
+    pub skill_change: i32,
+    pub pressure_change: i32,
+    pub best: Option<HistoryEntry>,
+    pub worst: Option<HistoryEntry>,
+    pub npc_accepted: u32,
+    pub npc_rejected: u32,
+}
+
+impl WeeklyRecap {
+    /// 汇总 `first_day..=last_day`（调用时这几天的快照都已记录）
+    pub fn build(week: u32, first_day: u32, last_day: u32, player: &PlayerState) -> Self {
+        let value_on = |day: u32| {
+            player
+                .snapshots
+                .iter()
+                .find(|s| s.day == day)
+                .map(|s| (s.skills, s.pressure))
+                .unwrap_or((0, 0))
+        };
+        let (start_skills, start_pressure) = value_on(first_day - 1);
+        let (end_skills, end_pressure) = value_on(last_day);
+
+        let this_week: Vec<&HistoryEntry> = player
+            .history
+            .iter()
+            .filter(|e| (first_day..=last_day).contains(&e.day))
+            .collect();
+        // 技能收益减去压力代价，衡量一次选择划不划算
+        let merit = |e: &&&HistoryEntry| e.merit();
+        let choices = this_week.iter().filter(|e| !matches!(e.kind, HistoryKind::Npc | HistoryKind::晋升));
+        let npc: Vec<&&HistoryEntry> = this_week.iter().filter(|e| e.kind == HistoryKind::Npc).collect();
+        let npc_accepted = npc.iter().filter(|e| e.choice.ends_with("(同意)")).count() as u32;
+
+        WeeklyRecap {
+            week,
+            first_day,
+            last_day,
+            skill_change: end_skills - start_skills,
+            pressure_change: end_pressure - start_pressure,
+            best: choices.clone().max_by_key(merit).map(|e| (*e).clone()),
+            worst: choices.min_by_key(merit).map(|e| (*e).clone()),
+            npc_accepted,
+            npc_rejected: npc.len() as u32 - npc_accepted,
+        }
+    }
+
+    /// 评级：按本周净收益（技能变化减压力变化）
+    pub fn grade(&self) -> (&'static str, &'static str) {
+        match self.skill_change - self.pressure_change {
+            n if n >= 60 => ("S", "卷王本王，这周的你无人能挡"),
+            n if n >= 30 => ("A", "稳中有进，老板看在眼里"),
+            n if n >= 10 => ("B", "中规中矩，至少没出事"),
+            n if n >= -10 => ("C", "原地踏步，下周加油"),
+            _ => ("D", "这周过得很艰难，抱抱"),
+        }
+    }
+}
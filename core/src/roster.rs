@@ -0,0 +1,142 @@
+//! NPC 名册：同事们的设定（对话模板、同意/拒绝的结果、可选的好感门槛与登场剧情线）
+//! 放在 content/npcs.json，扩充阵容只需改数据文件
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::arc::StoryArc;
+use crate::content::ContentFile;
+use crate::game::{Haggle, NpcEncounter, NpcOption, Realm};
+use crate::gift::MAX_AFFINITY;
+use crate::mood::NpcMood;
+use crate::office::Location;
+use crate::world::WorldView;
+
+/// 数据文件中的一位 NPC
+#[derive(Debug, Deserialize)]
+struct NpcDef {
+    name: String,
+    description: String,
+    ai_model: String,
+    #[serde(default)]
+    prompts: Vec<String>,
+    /// 特定心情下的对话模板（可选）
+    #[serde(default)]
+    mood_prompts: HashMap<NpcMood, Vec<String>>,
+    accept: NpcOption,
+    reject: NpcOption,
+    /// 可以讨价还价时的门槛与两种结果
+    #[serde(default)]
+    haggle: Option<Haggle>,
+    /// 卦辞模板：`{death}` 换成今晚出局的概率，`{promotion}` 换成晋升成功率（可选）
+    #[serde(default)]
+    oracle: Vec<String>,
+    /// 走完这条剧情线（且结果够好）才加入名册；None 为开局就在
+    #[serde(default)]
+    joins_after: Option<StoryArc>,
+    /// 邀请加入团队所需的好感（默认好感拉满）
+    #[serde(default)]
+    recruit_affinity: Option<i32>,
+    /// 出场条件（默认不限）
+    #[serde(default)]
+    appears: Appearance,
+}
+
+/// 同事出场后默认要隔这么多天才能再出场（2 即不会连着两天出现）
+pub const NPC_COOLDOWN_DAYS: u32 = 2;
+
+/// NPC 的出场条件（不设的项不限），每天挑当天出场的同事时判断
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Appearance {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_realm: Option<Realm>,       // 至少达到这个境界
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_day: Option<u32>,          // 从第几天起
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub near_promotion: Option<i32>,    // 技能点离晋升要求不超过这么多（够了也算）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cooldown: Option<u32>,          // 出场后隔几天才能再出场（默认 NPC_COOLDOWN_DAYS）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub haunt: Option<Location>,        // 常在办公室哪里出没（玩家去那里时容易碰上）
+}
+
+impl Appearance {
+    /// 今天能否出场
+    pub fn allows(&self, world: &WorldView) -> bool {
+        self.min_realm.is_none_or(|min| world.realm >= min)
+            && self.from_day.is_none_or(|first| world.day >= first)
+            && self.near_promotion.is_none_or(|gap| world.skill_gap <= gap)
+    }
+
+    pub fn cooldown_days(&self) -> u32 {
+        self.cooldown.unwrap_or(NPC_COOLDOWN_DAYS)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RosterContent {
+    npcs: Vec<NpcDef>,
+}
+
+impl NpcDef {
+    fn encounter(&self) -> NpcEncounter {
+        NpcEncounter {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            ai_model: self.ai_model.clone(),
+            prompt_templates: if self.prompts.is_empty() {
+                vec![self.description.clone()]
+            } else {
+                self.prompts.clone()
+            },
+            accept_option: self.accept.clone(),
+            reject_option: self.reject.clone(),
+            recruit_affinity: self.recruit_affinity.unwrap_or(MAX_AFFINITY),
+            haggle: self.haggle.clone(),
+            mood_prompts: self.mood_prompts.clone(),
+            oracle: self.oracle.clone(),
+            appears: self.appears,
+            mood: NpcMood::default(),
+            interacted: false,
+        }
+    }
+}
+
+fn definitions() -> &'static [NpcDef] {
+    static ROSTER: OnceLock<RosterContent> = OnceLock::new();
+    &ROSTER
+        .get_or_init(|| serde_json::from_str(ContentFile::Npcs.text()).expect("内置 NPC 名册必须有效"))
+        .npcs
+}
+
+/// 开局就在公司的同事
+pub fn opening_cast() -> Vec<NpcEncounter> {
+    definitions()
+        .iter()
+        .filter(|def| def.joins_after.is_none())
+        .map(NpcDef::encounter)
+        .collect()
+}
+
+/// 走完某条剧情线后加入的同事
+pub fn joining_after(arc: StoryArc) -> Vec<NpcEncounter> {
+    definitions()
+        .iter()
+        .filter(|def| def.joins_after == Some(arc))
+        .map(NpcDef::encounter)
+        .collect()
+}
+
+/// 按当前名册重建一位同事的设定（保留心情和今天是否打过交道）；名册里已经没有这人时返回 None
+pub fn refresh(npc: &NpcEncounter) -> Option<NpcEncounter> {
+    let def = definitions().iter().find(|def| def.name == npc.name)?;
+    Some(NpcEncounter { mood: npc.mood, interacted: npc.interacted, ..def.encounter() })
+}
+
+/// 名册里的同事总数（含要走完剧情线才加入的）
+pub fn roster_size() -> usize {
+    definitions().len()
+}
@@ -0,0 +1,246 @@
+//! 存档：把进行中的一局（所处阶段、对局状态、三条随机流的进度）序列化成 JSON，
+//! 关掉窗口后下次还能接着修仙。前端决定存在哪里（桌面端写文件，网页端写 localStorage）。
+//! 内容更新或换了自制内容之后，存档里可能引用已经不存在的内容包、事件或同事：读档时逐项核对，
+//! 能补的换成占位，补不了的丢掉，并在读档提示里列出来，而不是拒绝读档或读出半坏的局面。
+//! 核对以当前的事件库和同事名册为准，存档里嵌着的旧副本读档时整份换成当前内容。
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::engine::{GameEngine, GamePhase};
+use crate::events;
+use crate::game::{self, GameState};
+use crate::recap::WeeklyRecap;
+use crate::roster;
+use crate::season::ContentPack;
+use crate::tag::EventTag;
+
+/// 网页端 eframe 存储（localStorage）中续玩存档的键
+pub const SAVE_KEY: &str = "xiuxian_save";
+
+/// 存档格式版本：`GameState` 的结构改得不兼容时加一，并在 `upgrade` 里补上从上一版升级的步骤；
+/// 升级不了的旧存档会被拒绝而不是读出半坏的局面
+pub const SAVE_VERSION: u32 = 2;
+
+/// 还能升级到当前格式的最旧版本
+const OLDEST_SUPPORTED: u32 = 1;
+
+/// 一份存档
+#[derive(Serialize, Deserialize)]
+pub struct SaveGame {
+    pub version: u32,
+    pub saved_at: i64,  // 存档时间（Unix 秒）
+    pub phase: GamePhase,
+    pub player_name: String,
+    pub state: GameState,
+    #[serde(default)]
+    pub weekly_recap: Option<WeeklyRecap>,
+    #[serde(default)]
+    pub hinted_option: Option<usize>,
+    #[serde(default)]
+    pub victory_recorded: bool,
+    /// 读档时发现并修正的内容对不上的地方（不写进存档）
+    #[serde(skip)]
+    pub warnings: Vec<String>,
+}
+
+/// 只读版本号，先判断兼容再完整解析
+#[derive(Deserialize)]
+struct VersionProbe {
+    #[serde(default)]
+    version: u32,
+}
+
+impl SaveGame {
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string(self).map_err(|e| format!("存档失败: {}", e))
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        let probe: VersionProbe =
+            serde_json::from_str(text).map_err(|e| format!("存档已损坏: {}", e))?;
+        if probe.version > SAVE_VERSION {
+            return Err(format!("存档来自更新的版本（格式 v{}），请先升级游戏", probe.version));
+        }
+        if probe.version < OLDEST_SUPPORTED {
+            return Err(format!("存档格式 v{} 太旧，已无法读取", probe.version));
+        }
+        let mut value: Value = serde_json::from_str(text).map_err(|e| format!("存档已损坏: {}", e))?;
+        for from in probe.version..SAVE_VERSION {
+            upgrade(&mut value, from).map_err(|e| format!("存档格式 v{} 升级失败: {}", from, e))?;
+        }
+        value["version"] = SAVE_VERSION.into();
+        let mut warnings = Vec::new();
+        if let Some(state) = value.get_mut("state").and_then(Value::as_object_mut) {
+            warnings.extend(prune_unknown::<ContentPack>(state.get_mut("active_packs"), "内容包"));
+            warnings.extend(prune_unknown::<EventTag>(state.get_mut("muted_tags"), "事件题材"));
+        }
+        let mut save: SaveGame = serde_json::from_value(value).map_err(|e| format!("存档已损坏: {}", e))?;
+        warnings.extend(reconcile(&mut save.state));
+        save.warnings = warnings;
+        Ok(save)
+    }
+
+    /// 是否是旧格式的存档（读出来之后会按新格式重新写）
+    pub fn is_outdated(text: &str) -> bool {
+        serde_json::from_str::<VersionProbe>(text).is_ok_and(|probe| probe.version < SAVE_VERSION)
+    }
+
+    /// 一行概要，如“凌霄程序侠 · 第12天 · 筑基期”
+    pub fn summary(&self) -> String {
+        let player = &self.state.player;
+        format!(
+            "{} · 第{}天 · {}",
+            player.name,
+            self.state.current_day,
+            player.get_realm()
+        )
+    }
+}
+
+/// 把 `from` 版的存档 JSON 升级到下一版
+fn upgrade(value: &mut Value, from: u32) -> Result<(), String> {
+    match from {
+        // v2 加了道具掉落流
+        1 => {
+            let state = value.get_mut("state").and_then(Value::as_object_mut).ok_or("缺少对局状态")?;
+            let seed = state.get("seed").and_then(Value::as_u64).ok_or("缺少种子")?;
+            let loot = serde_json::to_value(game::loot_stream(seed)).map_err(|e| e.to_string())?;
+            state.insert("loot".to_string(), loot);
+            Ok(())
+        }
+        _ => Err("没有对应的升级步骤".to_string()),
+    }
+}
+
+/// 去掉列表里当前版本已经不认识的条目（如下架的限时内容包），返回提示
+fn prune_unknown<T: DeserializeOwned>(list: Option<&mut Value>, kind: &str) -> Vec<String> {
+    let Some(items) = list.and_then(Value::as_array_mut) else {
+        return Vec::new();
+    };
+    let mut warnings = Vec::new();
+    items.retain(|item| {
+        let known = serde_json::from_value::<T>(item.clone()).is_ok();
+        if !known {
+            warnings.push(format!("{} {} 已不存在，已从本局移除", kind, item));
+        }
+        known
+    });
+    warnings
+}
+
+/// 把事件和同事换成当前内容，再核对对局里按 id、名字引用的事件和同事，引用落空的换成占位或丢掉，返回提示
+fn reconcile(state: &mut GameState) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let library = events::library();
+    state.daily_events = library.daily_events();
+    state.weekly_events = library.weekly_events();
+    let retired: Vec<String> =
+        state.npc_master.iter().filter(|npc| roster::refresh(npc).is_none()).map(|npc| npc.name.clone()).collect();
+    state.npc_master = state.npc_master.iter().filter_map(roster::refresh).collect();
+
+    let has_daily = |state: &GameState, id: usize| state.daily_events.iter().any(|e| e.id == id);
+
+    let banned = state.banned_events.len();
+    let kept: Vec<usize> = state.banned_events.iter().copied().filter(|id| has_daily(state, *id)).collect();
+    state.banned_events = kept;
+    if state.banned_events.len() < banned {
+        warnings.push(format!("{} 个禁用的日常事件已不存在，已解除禁用", banned - state.banned_events.len()));
+    }
+    if let Some(saga) = state.saga.take() {
+        if state.weekly_events.iter().any(|e| e.id == saga.next_event_id) {
+            state.saga = Some(saga);
+        } else {
+            warnings.push(format!("连续剧「{}」的下一集已不存在，下周改为随机周事件", saga.previous_event));
+        }
+    }
+    if let Some(id) = state.arc.as_ref().and_then(|arc| arc.current_event_id()).filter(|id| !has_daily(state, *id)) {
+        warnings.push(format!("剧情线的下一步事件 {} 已不存在，剧情线就此中断", id));
+        state.arc = None;
+    }
+    if let Some(stage) = state.quest.as_ref().and_then(|quest| quest.current_stage()).filter(|stage| !has_daily(state, stage.event_id)) {
+        warnings.push(format!("项目线「{}」阶段的事件已不存在，项目就此搁置", stage.name));
+        state.quest = None;
+    }
+    // 剧本里落空的日常事件换成今天的事件占位（今天的也没了就用事件库的第一个），天数保持不变
+    let placeholder = Some(state.today_event.id)
+        .filter(|id| has_daily(state, *id))
+        .or_else(|| state.daily_events.first().map(|e| e.id))
+        .unwrap_or(state.today_event.id);
+    let missing: Vec<usize> = state
+        .scenario
+        .iter()
+        .flat_map(|scenario| scenario.daily.iter().copied())
+        .filter(|id| !has_daily(state, *id))
+        .collect();
+    if let Some(scenario) = state.scenario.as_mut().filter(|_| !missing.is_empty()) {
+        for id in scenario.daily.iter_mut().filter(|id| missing.contains(id)) {
+            *id = placeholder;
+        }
+        warnings.push(format!("剧本里 {} 天的事件已不存在，换成了占位事件", missing.len()));
+    }
+
+    let known = |name: &str, state: &GameState| state.npc_master.iter().any(|npc| npc.name == name);
+    let gone: Vec<String> = state
+        .today_npcs
+        .iter()
+        .map(|npc| npc.name.clone())
+        .chain(state.companions.iter().map(|c| c.name.clone()))
+        .chain(state.favor.iter().map(|f| f.npc.clone()))
+        .filter(|name| !known(name, state))
+        .chain(retired)
+        .collect();
+    if !gone.is_empty() {
+        let before = state.today_npcs.len();
+        state.today_npcs.retain(|npc| !gone.contains(&npc.name));
+        if state.today_npcs.len() < before {
+            // 答复到一半的请求按下标指向今天的同事，名单变了就作废
+            state.npc_active_event = None;
+        }
+        state.companions.retain(|c| !gone.contains(&c.name));
+        if state.favor.as_ref().is_some_and(|f| gone.contains(&f.npc)) {
+            state.favor = None;
+        }
+        let mut names = gone;
+        names.sort();
+        names.dedup();
+        warnings.push(format!("同事 {} 已不在名单里，相关的出场、团队和委托已移除", names.join("、")));
+    }
+    warnings
+}
+
+impl GameEngine {
+    /// 把进行中的对局存成存档（没有对局或已经结束时返回 None）
+    pub fn save(&self) -> Option<SaveGame> {
+        if !self.in_run() {
+            return None;
+        }
+        Some(SaveGame {
+            version: SAVE_VERSION,
+            saved_at: chrono::Utc::now().timestamp(),
+            phase: self.phase,
+            player_name: self.player_name.clone(),
+            state: self.game_state.clone()?,
+            weekly_recap: self.weekly_recap.clone(),
+            hinted_option: self.hinted_option,
+            victory_recorded: self.victory_recorded,
+            warnings: Vec::new(),
+        })
+    }
+
+    /// 读档：接着存档时的阶段继续
+    pub fn resume(&mut self, save: SaveGame) {
+        self.phase = save.phase;
+        self.player_name = save.player_name;
+        self.game_state = Some(save.state);
+        self.weekly_recap = save.weekly_recap;
+        self.hinted_option = save.hinted_option;
+        self.victory_recorded = save.victory_recorded;
+        self.result_message.clear();
+        self.push_notice("📂 已读取存档，继续上次的修仙");
+        for warning in save.warnings {
+            self.push_notice(format!("⚠ {}", warning));
+        }
+    }
+}
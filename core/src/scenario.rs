@@ -0,0 +1,166 @@
+//! 挑战模式：作者编排的剧本，固定随机种子与每天的事件顺序（如“地狱周挑战”），
+//! 成绩单独记录，不计入生涯统计与速通最佳成绩。
+
+use serde::{Deserialize, Serialize};
+
+use crate::balance::BalancePreset;
+
+/// eframe 存储中保存挑战成绩的键（按档案加命名空间）
+pub const SCENARIO_SCORES_KEY: &str = "xiuxian_scenario_scores";
+
+/// 随游戏发布的官方剧本包
+const BUILTIN_PACK: &str = include_str!("../../scenarios/builtin.json");
+
+/// 剧本包文件的大小上限（字节）
+pub const MAX_PACK_BYTES: usize = 1024 * 1024;
+/// 单个剧本最多安排的天数
+pub const MAX_SCENARIO_DAYS: usize = 3650;
+
+/// 一个挑战剧本
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Scenario {
+    /// 剧本 id（成绩按 id 记录，各剧本包之间不能重复）
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub seed: u64,
+    #[serde(default = "standard_preset")]
+    pub preset: BalancePreset,
+    /// 每天的日常事件 id，长度即挑战天数
+    pub daily: Vec<usize>,
+    /// 每周五的周事件 id（不足时随机抽取）
+    #[serde(default)]
+    pub weekly: Vec<usize>,
+    /// 每天开头给玩家看的提示（可选，和 `daily` 按天对应）
+    #[serde(default)]
+    pub notes: Vec<String>,
+}
+
+fn standard_preset() -> BalancePreset {
+    BalancePreset::标准
+}
+
+impl Scenario {
+    /// 检查引用的事件 id 是否都存在
+    pub fn validate(&self, daily_ids: &[usize], weekly_ids: &[usize]) -> Result<(), String> {
+        if self.daily.is_empty() {
+            return Err(format!("剧本“{}”没有安排任何一天", self.name));
+        }
+        if self.daily.len() > MAX_SCENARIO_DAYS {
+            return Err(format!("剧本“{}”超过 {} 天", self.name, MAX_SCENARIO_DAYS));
+        }
+        if let Some(id) = self.daily.iter().find(|id| !daily_ids.contains(id)) {
+            return Err(format!("剧本“{}”引用了不存在的日常事件 {}", self.name, id));
+        }
+        if let Some(id) = self.weekly.iter().find(|id| !weekly_ids.contains(id)) {
+            return Err(format!("剧本“{}”引用了不存在的周事件 {}", self.name, id));
+        }
+        if self.weekly.len() > self.daily.len() / 7 {
+            return Err(format!("剧本“{}”的周事件比周数还多", self.name));
+        }
+        if self.notes.len() > self.daily.len() {
+            return Err(format!("剧本“{}”的提示比天数还多", self.name));
+        }
+        Ok(())
+    }
+}
+
+/// 剧本包：一个 JSON 文件里的一组剧本
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioPack {
+    pub name: String,
+    #[serde(default)]
+    pub author: String,  // 作者（“关于”页面的鸣谢里列出，可不填）
+    pub scenarios: Vec<Scenario>,
+}
+
+impl ScenarioPack {
+    /// 解析并校验剧本包（内容来自玩家自制的文件，出错只返回原因）
+    pub fn parse(json: &str, daily_ids: &[usize], weekly_ids: &[usize]) -> Result<Self, String> {
+        if json.len() > MAX_PACK_BYTES {
+            return Err(format!("剧本包超过 {} KB", MAX_PACK_BYTES / 1024));
+        }
+        let pack: ScenarioPack =
+            serde_json::from_str(json).map_err(|e| format!("剧本包格式错误: {}", e))?;
+        for (i, scenario) in pack.scenarios.iter().enumerate() {
+            scenario.validate(daily_ids, weekly_ids)?;
+            if pack.scenarios[..i].iter().any(|other| other.id == scenario.id) {
+                return Err(format!("剧本 id “{}” 重复", scenario.id));
+            }
+        }
+        Ok(pack)
+    }
+
+    /// 从文件内容解析剧本包（先检查大小和编码）
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub fn parse_bytes(bytes: &[u8], daily_ids: &[usize], weekly_ids: &[usize]) -> Result<Self, String> {
+        if bytes.len() > MAX_PACK_BYTES {
+            return Err(format!("剧本包超过 {} KB", MAX_PACK_BYTES / 1024));
+        }
+        let json = std::str::from_utf8(bytes).map_err(|_| "剧本包不是 UTF-8 编码的文本".to_string())?;
+        Self::parse(json, daily_ids, weekly_ids)
+    }
+
+    pub fn builtin(daily_ids: &[usize], weekly_ids: &[usize]) -> Self {
+        Self::parse(BUILTIN_PACK, daily_ids, weekly_ids).expect("内置剧本包必须有效")
+    }
+
+    /// 读取目录下所有 .json 剧本包（桌面端，放在数据目录的 scenarios 子目录）
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_dir(
+        dir: &std::path::Path,
+        daily_ids: &[usize],
+        weekly_ids: &[usize],
+    ) -> Vec<Result<Self, String>> {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Vec::new();
+        };
+        let mut paths: Vec<_> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        paths.sort();
+        paths
+            .iter()
+            .map(|path| {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                std::fs::read(path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|bytes| Self::parse_bytes(&bytes, daily_ids, weekly_ids))
+                    .map_err(|err| format!("{}: {}", name, err))
+            })
+            .collect()
+    }
+}
+
+/// 各剧本的最高分
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScenarioScores {
+    best: Vec<(String, u32)>,
+}
+
+impl ScenarioScores {
+    pub fn best_for(&self, id: &str) -> Option<u32> {
+        self.best
+            .iter()
+            .find(|(scenario, _)| scenario == id)
+            .map(|(_, score)| *score)
+    }
+
+    /// 记录一次成绩，返回是否刷新了最高分
+    pub fn record(&mut self, id: &str, score: u32) -> bool {
+        match self.best.iter_mut().find(|(scenario, _)| scenario == id) {
+            Some((_, best)) if score > *best => {
+                *best = score;
+                true
+            }
+            Some(_) => false,
+            None => {
+                self.best.push((id.to_string(), score));
+                true
+            }
+        }
+    }
+}
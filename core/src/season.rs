@@ -0,0 +1,115 @@
+//! 季节活动：按现实日期开启/关闭带标签的内容包，玩家可在设置中强制开关
+
+use chrono::Datelike;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// 限时内容包
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ContentPack {
+    春节,
+    中秋,
+    双十一,
+}
+
+impl fmt::Display for ContentPack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContentPack::春节 => write!(f, "春节"),
+            ContentPack::中秋 => write!(f, "中秋"),
+            ContentPack::双十一 => write!(f, "双十一"),
+        }
+    }
+}
+
+impl ContentPack {
+    pub const ALL: [ContentPack; 3] = [ContentPack::春节, ContentPack::中秋, ContentPack::双十一];
+
+    /// 开放月份（含首尾）
+    pub fn months(&self) -> (u32, u32) {
+        match self {
+            ContentPack::春节 => (1, 2),
+            ContentPack::中秋 => (9, 9),
+            ContentPack::双十一 => (11, 11),
+        }
+    }
+
+    pub fn window_label(&self) -> String {
+        match self.months() {
+            (start, end) if start == end => format!("{}月", start),
+            (start, end) => format!("{}~{}月", start, end),
+        }
+    }
+
+    /// 给定月份是否在开放窗口内
+    pub fn in_season(&self, month: u32) -> bool {
+        let (start, end) = self.months();
+        (start..=end).contains(&month)
+    }
+}
+
+/// 单个内容包的开关方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PackOverride {
+    /// 按日期自动开关
+    Auto,
+    AlwaysOn,
+    AlwaysOff,
+}
+
+impl PackOverride {
+    pub const ALL: [PackOverride; 3] = [
+        PackOverride::Auto,
+        PackOverride::AlwaysOn,
+        PackOverride::AlwaysOff,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PackOverride::Auto => "按日期",
+            PackOverride::AlwaysOn => "始终开启",
+            PackOverride::AlwaysOff => "始终关闭",
+        }
+    }
+}
+
+/// 季节活动设置（未列出的内容包按日期自动开关）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SeasonSettings {
+    overrides: Vec<(ContentPack, PackOverride)>,
+}
+
+impl SeasonSettings {
+    pub fn override_for(&self, pack: ContentPack) -> PackOverride {
+        self.overrides
+            .iter()
+            .find(|(p, _)| *p == pack)
+            .map(|(_, o)| *o)
+            .unwrap_or(PackOverride::Auto)
+    }
+
+    pub fn set_override(&mut self, pack: ContentPack, value: PackOverride) {
+        self.overrides.retain(|(p, _)| *p != pack);
+        if value != PackOverride::Auto {
+            self.overrides.push((pack, value));
+        }
+    }
+
+    /// 指定月份下开启的内容包
+    pub fn active_packs_in(&self, month: u32) -> Vec<ContentPack> {
+        ContentPack::ALL
+            .into_iter()
+            .filter(|pack| match self.override_for(*pack) {
+                PackOverride::Auto => pack.in_season(month),
+                PackOverride::AlwaysOn => true,
+                PackOverride::AlwaysOff => false,
+            })
+            .collect()
+    }
+
+    /// 今天开启的内容包
+    pub fn active_packs(&self) -> Vec<ContentPack> {
+        self.active_packs_in(chrono::Local::now().month())
+    }
+}
@@ -0,0 +1,90 @@
+//! 选项排序策略：默认每次事件都打乱选项顺序；不喜欢的玩家可以改为每局只打乱一次
+//! （整局里 A/B/C 的相对顺序都一样），或者完全不打乱（固定 A/B/C）。
+//! 无论哪种模式都照常消耗一次随机数，同一种子遇到的事件不受这个设置影响。
+
+use std::fmt;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{GameRng, OptionInfo};
+
+/// 每局一次的排列用的种子扰动值
+const RUN_ORDER_SALT: u64 = 0xA076_1D64_78BD_642F;
+
+/// 每局一次的排列覆盖几个原始位置（更多的选项排在最后，保持原始顺序）
+const RUN_ORDER_SLOTS: u32 = 6;
+
+/// 选项怎么排
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ShuffleMode {
+    #[default]
+    每次打乱,
+    每局一次,
+    固定顺序,
+}
+
+impl fmt::Display for ShuffleMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ShuffleMode::每次打乱 => "每次打乱",
+            ShuffleMode::每局一次 => "每局一次",
+            ShuffleMode::固定顺序 => "固定顺序",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl ShuffleMode {
+    pub const ALL: [ShuffleMode; 3] = [ShuffleMode::每次打乱, ShuffleMode::每局一次, ShuffleMode::固定顺序];
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            ShuffleMode::每次打乱 => "每个事件的选项都重新洗牌，只能看内容做决定",
+            ShuffleMode::每局一次 => "开局洗一次，整局里选项的相对顺序都一样",
+            ShuffleMode::固定顺序 => "永远按 A/B/C 的原始顺序排列",
+        }
+    }
+}
+
+/// 一局的选项排序策略
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShufflePolicy {
+    pub mode: ShuffleMode,
+    run_order: Vec<u32>,  // 每局一次模式下各原始位置的名次
+}
+
+impl Default for ShufflePolicy {
+    fn default() -> Self {
+        ShufflePolicy { mode: ShuffleMode::default(), run_order: (0..RUN_ORDER_SLOTS).collect() }
+    }
+}
+
+impl ShufflePolicy {
+    /// 按种子定下这一局的策略（每局一次的排列由种子决定，同一种子重来顺序不变）
+    pub fn new(mode: ShuffleMode, seed: u64) -> Self {
+        let mut run_order: Vec<u32> = (0..RUN_ORDER_SLOTS).collect();
+        run_order.shuffle(&mut StdRng::seed_from_u64(seed ^ RUN_ORDER_SALT));
+        ShufflePolicy { mode, run_order }
+    }
+
+    /// 事件触发时排列选项：先恢复原始顺序再洗牌，保证同一种子结果相同，然后按模式定序
+    pub fn arrange(&self, options: &mut [OptionInfo], rng: &mut GameRng) {
+        options.sort_by_key(|o| o.original_index);
+        options.shuffle(rng);
+        self.settle(options);
+    }
+
+    /// 不需要随机数的模式在这里定序（每次打乱的模式保持现状）
+    pub fn settle(&self, options: &mut [OptionInfo]) {
+        match self.mode {
+            ShuffleMode::每次打乱 => {}
+            ShuffleMode::每局一次 => options.sort_by_key(|o| {
+                self.run_order.get(o.original_index as usize).copied().unwrap_or(o.original_index)
+            }),
+            ShuffleMode::固定顺序 => options.sort_by_key(|o| o.original_index),
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//! 技能专精：技能点分成工程/算法/沟通三条线，事件按标签加到对应的线上；
+//! 不带标签的“泛泛成长”会平摊到三条线，且境界越高摊到的越少。
+//! 晋升除了总技能点，还要看所选路线要求的是专精还是均衡。
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SkillTrack {
+    工程,
+    算法,
+    沟通,
+}
+
+impl SkillTrack {
+    pub const ALL: [SkillTrack; 3] = [SkillTrack::工程, SkillTrack::算法, SkillTrack::沟通];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SkillTrack::工程 => "🔧 工程",
+            SkillTrack::算法 => "🧮 算法",
+            SkillTrack::沟通 => "🗣 沟通",
+        }
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            SkillTrack::工程 => 0,
+            SkillTrack::算法 => 1,
+            SkillTrack::沟通 => 2,
+        }
+    }
+}
+
+/// 三条技能线各自积累的点数（只记正向成长）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SkillTracks {
+    points: [i32; 3],
+}
+
+impl SkillTracks {
+    pub fn get(&self, track: SkillTrack) -> i32 {
+        self.points[track.index()]
+    }
+
+    /// 按事件标签成长：有标签全额加到该线；没有标签时除以境界等级后
+    /// 从最弱的一条线开始轮流平摊
+    pub fn train(&mut self, track: Option<SkillTrack>, points: i32, realm_level: u32) {
+        if points <= 0 {
+            return;
+        }
+        match track {
+            Some(track) => self.points[track.index()] += points,
+            None => {
+                let share = points / realm_level.max(1) as i32;
+                let mut order = SkillTrack::ALL;
+                order.sort_by_key(|t| self.get(*t));
+                for i in 0..share as usize {
+                    self.points[order[i % 3].index()] += 1;
+                }
+            }
+        }
+    }
+
+    pub fn best(&self) -> i32 {
+        self.points.iter().copied().max().unwrap_or(0)
+    }
+
+    pub fn weakest(&self) -> i32 {
+        self.points.iter().copied().min().unwrap_or(0)
+    }
+}
+
+/// 职业路线：决定晋升时看专精还是看均衡
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CareerPath {
+    /// 技术专家：最强的一条线要够深
+    #[default]
+    专家,
+    /// 技术管理：三条线都不能太短
+    管理,
+}
+
+impl CareerPath {
+    pub const ALL: [CareerPath; 2] = [CareerPath::专家, CareerPath::管理];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CareerPath::专家 => "技术专家",
+            CareerPath::管理 => "技术管理",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            CareerPath::专家 => "晋升时最强的一条技能线需达到总要求的 30%",
+            CareerPath::管理 => "晋升时最弱的一条技能线也需达到总要求的 1/8",
+        }
+    }
+
+    /// 这条路线对技能线的要求（总要求 `requirement` 下需要的点数）
+    pub fn track_requirement(&self, requirement: i32) -> i32 {
+        match self {
+            CareerPath::专家 => requirement * 3 / 10,
+            CareerPath::管理 => requirement / 8,
+        }
+    }
+
+    /// 当前技能线是否满足路线要求
+    pub fn meets(&self, tracks: &SkillTracks, requirement: i32) -> bool {
+        let needed = self.track_requirement(requirement);
+        match self {
+            CareerPath::专家 => tracks.best() >= needed,
+            CareerPath::管理 => tracks.weakest() >= needed,
+        }
+    }
+
+    /// 给玩家看的进度说明，如“最强技能线 12/15”
+    pub fn progress_line(&self, tracks: &SkillTracks, requirement: i32) -> String {
+        let needed = self.track_requirement(requirement);
+        match self {
+            CareerPath::专家 => format!("最强技能线 {}/{}", tracks.best(), needed),
+            CareerPath::管理 => format!("最弱技能线 {}/{}", tracks.weakest(), needed),
+        }
+    }
+}
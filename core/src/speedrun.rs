@@ -0,0 +1,99 @@
+//! 速通：记录每次境界突破的真实用时与游戏天数，维护本地最佳成绩
+
+use serde::{Deserialize, Serialize};
+
+use crate::balance::BalancePreset;
+use crate::game::{HistoryEntry, Realm};
+
+/// eframe 存储中保存最佳成绩的键（按档案加命名空间）
+pub const BEST_SPLITS_KEY: &str = "xiuxian_best_splits";
+
+/// 一次境界突破的分段成绩
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Split {
+    pub realm: Realm,
+    pub day: u32,
+    pub elapsed_ms: u64,
+}
+
+/// 各预设下每个境界的最快成绩
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BestSplits {
+    entries: Vec<(BalancePreset, Split)>,
+}
+
+impl BestSplits {
+    pub fn best_for(&self, preset: BalancePreset, realm: Realm) -> Option<Split> {
+        self.entries
+            .iter()
+            .find(|(p, split)| *p == preset && split.realm == realm)
+            .map(|(_, split)| *split)
+    }
+
+    /// 用本局的分段刷新最佳成绩（可重复调用），返回是否有新纪录
+    pub fn record(&mut self, preset: BalancePreset, splits: &[Split]) -> bool {
+        let mut improved = false;
+        for split in splits {
+            match self
+                .entries
+                .iter_mut()
+                .find(|(p, best)| *p == preset && best.realm == split.realm)
+            {
+                Some((_, best)) if split.elapsed_ms < best.elapsed_ms => {
+                    *best = *split;
+                    improved = true;
+                }
+                Some(_) => {}
+                None => {
+                    self.entries.push((preset, *split));
+                    improved = true;
+                }
+            }
+        }
+        improved
+    }
+
+    /// 某个预设下的全部最佳成绩（按境界顺序）
+    pub fn table(&self, preset: BalancePreset) -> Vec<Split> {
+        let mut rows: Vec<Split> = self
+            .entries
+            .iter()
+            .filter(|(p, _)| *p == preset)
+            .map(|(_, split)| *split)
+            .collect();
+        rows.sort_by_key(|split| split.realm as u8);
+        rows
+    }
+}
+
+/// 导出用的完整速通记录（附带全部历史选择，便于核验）
+#[derive(Debug, Serialize)]
+pub struct SpeedrunRecord<'a> {
+    pub player: &'a str,
+    pub preset: BalancePreset,
+    pub exported_at: String,
+    pub splits: &'a [Split],
+    pub history: &'a [HistoryEntry],
+}
+
+impl SpeedrunRecord<'_> {
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| format!("JSON 序列化失败: {}", e))
+    }
+}
+
+/// 毫秒格式化为 "分:秒.毫秒"
+pub fn format_millis(ms: u64) -> String {
+    format!("{}:{:02}.{:03}", ms / 60_000, ms / 1000 % 60, ms % 1000)
+}
+
+/// 与最佳成绩的差值，如 "-1.234" / "+0.500"
+pub fn format_delta(ms: u64, best_ms: u64) -> String {
+    let (sign, diff) = if ms <= best_ms {
+        ('-', best_ms - ms)
+    } else {
+        ('+', ms - best_ms)
+    };
+    format!("{}{}.{:03}", sign, diff / 1000, diff % 1000)
+}
@@ -0,0 +1,93 @@
+//! 事件题材标签：所有日常与周事件都归到一类，用来屏蔽不想看的题材，
+//! 也用来统计玩家在各类事件里的表现
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EventTag {
+    项目,
+    职场,
+    通勤,
+    编程语言,
+    生活,
+}
+
+impl fmt::Display for EventTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            EventTag::项目 => "项目",
+            EventTag::职场 => "职场",
+            EventTag::通勤 => "通勤",
+            EventTag::编程语言 => "编程语言",
+            EventTag::生活 => "生活",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl EventTag {
+    pub const ALL: [EventTag; 5] = [
+        EventTag::项目,
+        EventTag::职场,
+        EventTag::通勤,
+        EventTag::编程语言,
+        EventTag::生活,
+    ];
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            EventTag::项目 => "🗂",
+            EventTag::职场 => "💼",
+            EventTag::通勤 => "🚇",
+            EventTag::编程语言 => "⌨",
+            EventTag::生活 => "🏠",
+        }
+    }
+}
+
+/// 某类事件里的累计表现
+#[derive(Debug, Clone, Copy)]
+pub struct TagStats {
+    pub tag: EventTag,
+    pub choices: u32,
+    pub skill: i32,
+    pub pressure: i32,
+}
+
+impl TagStats {
+    pub fn new(tag: EventTag) -> Self {
+        TagStats {
+            tag,
+            choices: 0,
+            skill: 0,
+            pressure: 0,
+        }
+    }
+
+    pub fn record(&mut self, skill: i32, pressure: i32) {
+        self.choices += 1;
+        self.skill += skill;
+        self.pressure += pressure;
+    }
+
+    pub fn average_skill(&self) -> f32 {
+        self.skill as f32 / self.choices.max(1) as f32
+    }
+
+    pub fn average_pressure(&self) -> f32 {
+        self.pressure as f32 / self.choices.max(1) as f32
+    }
+
+    /// 一句话总结，如“你在编程语言类事件平均 +4.2 技能”
+    pub fn summary(&self) -> String {
+        format!(
+            "你在{}类事件平均 {:+.1} 技能、{:+.1} 压力（{} 次）",
+            self.tag,
+            self.average_skill(),
+            self.average_pressure(),
+            self.choices
+        )
+    }
+}
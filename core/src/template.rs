@@ -0,0 +1,82 @@
+//! 事件文本模板：事件描述、选项与剧情中可以写占位符，显示时替换为玩家上下文。
+//!
+//! 支持的占位符：`{player}` 玩家名、`{rival}` 同门卷王、`{boss}` 顶头上司、`{day}` 当前天数。
+//! 用 `{{` 和 `}}` 输出字面量花括号；未知或未闭合的占位符原样保留，不会报错或丢字。
+
+/// 内容中允许使用的占位符
+pub const PLACEHOLDERS: [&str; 4] = ["player", "rival", "boss", "day"];
+
+/// 渲染模板所需的玩家上下文
+#[derive(Debug, Clone)]
+pub struct TemplateContext {
+    pub player: String,
+    pub rival: String,
+    pub boss: String,
+    pub day: u32,
+}
+
+impl TemplateContext {
+    fn lookup(&self, key: &str) -> Option<String> {
+        match key {
+            "player" => Some(self.player.clone()),
+            "rival" => Some(self.rival.clone()),
+            "boss" => Some(self.boss.clone()),
+            "day" => Some(self.day.to_string()),
+            _ => None,
+        }
+    }
+
+    /// 替换文本中的占位符
+    pub fn render(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(pos) = rest.find(['{', '}']) {
+            out.push_str(&rest[..pos]);
+            let tail = &rest[pos..];
+            if let Some(after) = tail.strip_prefix("{{") {
+                out.push('{');
+                rest = after;
+            } else if let Some(after) = tail.strip_prefix("}}") {
+                out.push('}');
+                rest = after;
+            } else if let Some(after) = tail.strip_prefix('}') {
+                out.push('}');
+                rest = after;
+            } else {
+                let after = &tail[1..];
+                match after.find(['{', '}']) {
+                    Some(end) if after[end..].starts_with('}') => {
+                        match self.lookup(&after[..end]) {
+                            Some(value) => out.push_str(&value),
+                            None => out.push_str(&tail[..end + 2]),
+                        }
+                        rest = &after[end + 1..];
+                    }
+                    _ => {
+                        out.push('{');
+                        rest = after;
+                    }
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+/// 检查内容文本中的占位符是否都是已知的（用于校验事件数据）
+pub fn validate(text: &str) -> Result<(), String> {
+    let mut rest = text.replace("{{", "").replace("}}", "");
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        let end = after
+            .find('}')
+            .ok_or_else(|| format!("占位符未闭合: {}", text))?;
+        let key = &after[..end];
+        if !PLACEHOLDERS.contains(&key) {
+            return Err(format!("未知占位符 {{{}}}: {}", key, text));
+        }
+        rest = after[end + 1..].to_string();
+    }
+    Ok(())
+}
@@ -0,0 +1,77 @@
+//! 叙事风格：同一批事件的另一套结局文案（沙雕/热血/写实），开局时选择，只换文字不动数值。
+//! 文案放在 content/tones.json，按事件 id 与原始选项位置查找，缺失时沿用默认（沙雕）文案。
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::content::ContentFile;
+use crate::game::HistoryKind;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NarrativeTone {
+    沙雕,
+    热血,
+    写实,
+}
+
+impl fmt::Display for NarrativeTone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            NarrativeTone::沙雕 => "沙雕",
+            NarrativeTone::热血 => "热血",
+            NarrativeTone::写实 => "写实",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl NarrativeTone {
+    pub const ALL: [NarrativeTone; 3] = [NarrativeTone::沙雕, NarrativeTone::热血, NarrativeTone::写实];
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            NarrativeTone::沙雕 => "原汁原味的职场段子",
+            NarrativeTone::热血 => "每个 bug 都是宿敌，每次加班都是决战",
+            NarrativeTone::写实 => "平铺直叙，像一份真实的工作日志",
+        }
+    }
+
+    /// 某个事件选项在该风格下的结局文案（None 表示沿用默认文案）
+    pub fn story(&self, kind: HistoryKind, event_id: usize, option_index: u32) -> Option<&'static str> {
+        let texts = variants().get(self)?;
+        let table = match kind {
+            HistoryKind::日常 => &texts.daily,
+            HistoryKind::周事件 => &texts.weekly,
+            HistoryKind::Npc | HistoryKind::项目 | HistoryKind::晋升 => return None,
+        };
+        table
+            .get(&event_id)?
+            .get(option_index as usize)
+            .map(String::as_str)
+    }
+
+    /// 全部变体文案（用于模板校验）
+    pub fn all_stories() -> impl Iterator<Item = &'static str> {
+        variants()
+            .values()
+            .flat_map(|t| t.daily.values().chain(t.weekly.values()))
+            .flatten()
+            .map(String::as_str)
+    }
+}
+
+/// 一种风格的全部变体：事件 id → 按原始选项位置排列的文案
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct ToneTexts {
+    daily: HashMap<usize, Vec<String>>,
+    weekly: HashMap<usize, Vec<String>>,
+}
+
+fn variants() -> &'static HashMap<NarrativeTone, ToneTexts> {
+    static VARIANTS: OnceLock<HashMap<NarrativeTone, ToneTexts>> = OnceLock::new();
+    VARIANTS.get_or_init(|| serde_json::from_str(ContentFile::Tones.text()).expect("内置叙事风格文案必须有效"))
+}
@@ -0,0 +1,96 @@
+//! 事件的触发条件与抽取权重：按局面摘要（`world::WorldView`）里的境界、压力、天数、项目状况和天气
+//! 决定事件今天能不能出现，能出现的再按权重抽；
+//! 另外记下每个事件（和每位同事）最近一次出现的天数，同一事件若干天内不重复。日常事件和周事件共用。
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::Realm;
+use crate::mood::{ProjectHealth, Weather};
+use crate::world::WorldView;
+
+/// 触发条件（不设的项不限）与抽取权重，写在事件数据的 `trigger` 字段里
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Trigger {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_realm: Option<Realm>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_realm: Option<Realm>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pressure: Option<(i32, i32)>,  // 压力区间（含两端）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub days: Option<(u32, u32)>,      // 天数区间（含两端）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health: Option<ProjectHealth>, // 项目状况
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weather: Option<Weather>,      // 天气
+    pub weight: f32,                   // 相对权重，默认 1
+}
+
+impl Default for Trigger {
+    fn default() -> Self {
+        Self {
+            min_realm: None,
+            max_realm: None,
+            pressure: None,
+            days: None,
+            health: None,
+            weather: None,
+            weight: 1.0,
+        }
+    }
+}
+
+impl Trigger {
+    /// 当前局面下能否出现
+    pub fn allows(&self, world: &WorldView) -> bool {
+        self.min_realm.is_none_or(|min| world.realm >= min)
+            && self.max_realm.is_none_or(|max| world.realm <= max)
+            && self.pressure.is_none_or(|(min, max)| (min..=max).contains(&world.pressure))
+            && self.days.is_none_or(|(first, last)| (first..=last).contains(&world.day))
+            && self.health.is_none_or(|health| world.project_health == health)
+            && self.weather.is_none_or(|weather| world.weather == weather)
+    }
+}
+
+/// 最近出现过的事件：(事件 id, 最近一次出现的天数)；同事按名字记
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecentEvents {
+    daily: Vec<(usize, u32)>,
+    weekly: Vec<(usize, u32)>,
+    #[serde(default)]
+    npcs: Vec<(String, u32)>,
+}
+
+impl RecentEvents {
+    pub fn record(&mut self, weekly: bool, id: usize, day: u32) {
+        let seen = if weekly { &mut self.weekly } else { &mut self.daily };
+        match seen.iter_mut().find(|(seen_id, _)| *seen_id == id) {
+            Some((_, last)) => *last = day,
+            None => seen.push((id, day)),
+        }
+    }
+
+    /// 距上次出现已满 `cooldown` 天（没出现过也算）
+    pub fn fresh(&self, weekly: bool, id: usize, day: u32, cooldown: u32) -> bool {
+        let seen = if weekly { &self.weekly } else { &self.daily };
+        seen.iter()
+            .find(|(seen_id, _)| *seen_id == id)
+            .is_none_or(|(_, last)| day.saturating_sub(*last) >= cooldown)
+    }
+
+    pub fn record_npc(&mut self, name: &str, day: u32) {
+        match self.npcs.iter_mut().find(|(seen, _)| seen == name) {
+            Some((_, last)) => *last = day,
+            None => self.npcs.push((name.to_string(), day)),
+        }
+    }
+
+    /// 同事距上次出场已满 `cooldown` 天（没出场过也算）
+    pub fn npc_fresh(&self, name: &str, day: u32, cooldown: u32) -> bool {
+        self.npcs
+            .iter()
+            .find(|(seen, _)| seen == name)
+            .is_none_or(|(_, last)| day.saturating_sub(*last) >= cooldown)
+    }
+}
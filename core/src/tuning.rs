@@ -0,0 +1,80 @@
+//! 事件抽取权重的调参表：content/weights.json 按事件 id 给出基础权重，和事件文案分开放，
+//! 调平衡不用碰文案。表里没写的事件用事件自带的 `trigger.weight`（默认 1，玩家自制事件可以自带）。
+//! 抽取时基础权重乘上导演等系统的系数，再归一化成和为 1 的概率。
+//! 调参表可以在运行中整张换掉（开发时的热重载），换掉之后的抽取立即按新表来。
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use serde::Deserialize;
+
+use crate::content::ContentFile;
+
+/// 调参表
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Tuning {
+    daily: HashMap<usize, f32>,   // 日常事件 id → 基础权重
+    weekly: HashMap<usize, f32>,  // 周事件 id → 基础权重
+}
+
+static CURRENT: RwLock<Option<Arc<Tuning>>> = RwLock::new(None);
+
+impl Tuning {
+    /// 解析并校验（权重必须是正数）
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let tuning: Tuning = serde_json::from_str(text).map_err(|e| format!("调参表格式错误: {}", e))?;
+        let all = tuning.daily.iter().map(|entry| ("日常", entry)).chain(tuning.weekly.iter().map(|entry| ("周", entry)));
+        for (kind, (id, weight)) in all {
+            if !(weight.is_finite() && *weight > 0.0) {
+                return Err(format!("{}事件 {} 的权重必须是正数", kind, id));
+            }
+        }
+        Ok(tuning)
+    }
+
+    /// 事件的基础权重（表里没有时用 `fallback`，即事件自带的权重）
+    pub fn weight(&self, weekly: bool, id: usize, fallback: f32) -> f32 {
+        let table = if weekly { &self.weekly } else { &self.daily };
+        table.get(&id).copied().unwrap_or(fallback)
+    }
+}
+
+/// 当前生效的调参表（还没换过时是内置的 content/weights.json）
+pub fn current() -> Arc<Tuning> {
+    if let Some(tuning) = CURRENT.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        return Arc::clone(tuning);
+    }
+    let builtin = Arc::new(Tuning::parse(ContentFile::Weights.text()).unwrap_or_else(|err| panic!("内置调参表必须有效: {}", err)));
+    let mut slot = CURRENT.write().unwrap_or_else(|e| e.into_inner());
+    Arc::clone(slot.get_or_insert(builtin))
+}
+
+/// 换上新的调参表，之后的抽取都用它
+pub fn install(tuning: Tuning) {
+    *CURRENT.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(tuning));
+}
+
+/// 归一化成和为 1（全为零时原样返回）
+pub fn normalize(mut weights: Vec<f32>) -> Vec<f32> {
+    let total: f32 = weights.iter().sum();
+    if total > 0.0 {
+        weights.iter_mut().for_each(|weight| *weight /= total);
+    }
+    weights
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_overrides_event_weight_and_normalizes() {
+        let tuning = Tuning::parse(r#"{"daily": {"30": 0.5}}"#).unwrap();
+        assert_eq!(tuning.weight(false, 30, 1.0), 0.5);
+        assert_eq!(tuning.weight(false, 31, 2.0), 2.0);
+        assert_eq!(tuning.weight(true, 30, 1.0), 1.0);
+        assert!(Tuning::parse(r#"{"weekly": {"1": 0}}"#).is_err());
+        assert_eq!(normalize(vec![1.0, 3.0]), vec![0.25, 0.75]);
+    }
+}
@@ -0,0 +1,83 @@
+//! 内容提示：少数黑色幽默味太重的段子（急救、殡葬、猝死）在数据里带一条中性替代文案（选项的 `mild`），
+//! 玩家可以原样显示、换成中性说法，或者连出局时的死亡描写一起略过。和叙事风格互不相干，单独开关。
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ending::Ending;
+use crate::game::PlayerState;
+
+/// 略过结局描写时显示的占位文字
+const SKIPPED: &str = "（按内容设置略过了这段结局描写）";
+
+/// 黑色段子的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DarkContent {
+    #[default]
+    原样,
+    替换,
+    略过,
+}
+
+impl fmt::Display for DarkContent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl DarkContent {
+    pub const ALL: [DarkContent; 3] = [DarkContent::原样, DarkContent::替换, DarkContent::略过];
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            DarkContent::原样 => "原汁原味，黑色幽默照单全收",
+            DarkContent::替换 => "标记过的黑色段子和死亡描写换成中性说法",
+            DarkContent::略过 => "黑色段子换成中性说法，出局时不显示死亡描写",
+        }
+    }
+
+    /// 选项剧情：带中性替代文案的段子在替换/略过时用替代文案
+    pub fn story(&self, story: String, mild: Option<&str>) -> String {
+        match (self, mild) {
+            (DarkContent::原样, _) | (_, None) => story,
+            (_, Some(mild)) => mild.to_string(),
+        }
+    }
+
+    /// 出局时的标题
+    pub fn death_message(&self, player: &PlayerState) -> &'static str {
+        match self {
+            DarkContent::原样 => player.get_death_message(),
+            DarkContent::替换 => player.mild_death_message(),
+            DarkContent::略过 if player.skills < 0 => player.mild_death_message(),
+            DarkContent::略过 => "游戏结束",
+        }
+    }
+
+    /// 结局描写（`description` 是已经填好占位符的默认文案）
+    pub fn ending_description(&self, ending: Ending, description: String) -> String {
+        match (self, ending.mild_description()) {
+            (DarkContent::原样, _) | (_, None) => description,
+            (DarkContent::替换, Some(mild)) => mild.to_string(),
+            (DarkContent::略过, Some(_)) => SKIPPED.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_tagged_lines_change() {
+        let story = "老板被送急救。".to_string();
+        assert_eq!(DarkContent::原样.story(story.clone(), Some("老板提前离场")), story);
+        assert_eq!(DarkContent::替换.story(story.clone(), Some("老板提前离场")), "老板提前离场");
+        assert_eq!(DarkContent::略过.story(story.clone(), None), story);
+
+        let plain = "不上不下".to_string();
+        assert_eq!(DarkContent::略过.ending_description(Ending::平凡打工人, plain.clone()), plain);
+        assert_eq!(DarkContent::略过.ending_description(Ending::卷王猝死, plain), SKIPPED);
+    }
+}
@@ -0,0 +1,86 @@
+//! 一周的节奏：第 1 天是周一，周六周日休息——不上日常班，改为休闲事件，压力自然回落；
+//! 周事件赶在周末前的周五。
+
+use std::fmt;
+
+/// 周几
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Weekday {
+    周一,
+    周二,
+    周三,
+    周四,
+    周五,
+    周六,
+    周日,
+}
+
+impl fmt::Display for Weekday {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Weekday::周一 => "周一",
+            Weekday::周二 => "周二",
+            Weekday::周三 => "周三",
+            Weekday::周四 => "周四",
+            Weekday::周五 => "周五",
+            Weekday::周六 => "周六",
+            Weekday::周日 => "周日",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl Weekday {
+    pub const ALL: [Weekday; 7] = [
+        Weekday::周一,
+        Weekday::周二,
+        Weekday::周三,
+        Weekday::周四,
+        Weekday::周五,
+        Weekday::周六,
+        Weekday::周日,
+    ];
+
+    /// 第几天是周几（第 1 天是周一）
+    pub fn of(day: u32) -> Weekday {
+        Self::ALL[(day.max(1) - 1) as usize % 7]
+    }
+
+    pub fn is_weekend(self) -> bool {
+        matches!(self, Weekday::周六 | Weekday::周日)
+    }
+}
+
+/// 周末每天压力自然回落多少
+pub const WEEKEND_RELIEF: i32 = 8;
+
+/// 周末回落不会把压力降到这以下（闲出病来也会猝死）
+pub const WEEKEND_FLOOR: i32 = 10;
+
+/// 周事件比按间隔算出的日子提前几天：默认 7 天一次时正好落在周五
+const WEEKLY_EVENT_LEAD: u32 = 2;
+
+/// 这一天有没有周事件
+pub fn weekly_event_due(day: u32, interval: u32) -> bool {
+    (day + WEEKLY_EVENT_LEAD).is_multiple_of(interval.max(1))
+}
+
+/// 离下一场周事件还有几天（当天有周事件时为 0）
+pub fn days_until_weekly_event(day: u32, interval: u32) -> u32 {
+    let interval = interval.max(1);
+    (interval - (day + WEEKLY_EVENT_LEAD) % interval) % interval
+}
+
+/// 这一天的周事件算第几周的（剧本按周指定周事件时用，从 1 开始）
+pub fn weekly_event_week(day: u32) -> u32 {
+    (day + WEEKLY_EVENT_LEAD) / 7
+}
+
+/// 周末压力回落后的压力值
+pub fn weekend_pressure(pressure: i32) -> i32 {
+    if pressure <= WEEKEND_FLOOR {
+        pressure
+    } else {
+        (pressure - WEEKEND_RELIEF).max(WEEKEND_FLOOR)
+    }
+}
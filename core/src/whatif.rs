@@ -0,0 +1,161 @@
+//! 晋升推演：在晋升确认时，从当前局面的副本出发各推演若干遍“现在就晋升”和“先缓几天再晋升”，
+//! 统计之后一段时间的存活率和技能点。每遍推演换一组随机流、用稳健策略代打，不影响正在打的这局。
+
+use std::fmt;
+
+use crate::arena::{Action, Agent, StateView, MAX_STEPS};
+use crate::engine::{GameEngine, GamePhase};
+use crate::game::GameState;
+use crate::policy::AutoPolicy;
+
+/// “缓一缓”要等的天数
+pub const WAIT_DAYS: u32 = 5;
+/// 从现在起推演多少天
+pub const HORIZON_DAYS: u32 = 20;
+/// 每种打算推演的遍数
+pub const ROLLOUTS: u32 = 100;
+
+/// 两种打算
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Plan {
+    立即晋升,
+    缓几天,
+}
+
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Plan::立即晋升 => write!(f, "现在晋升"),
+            Plan::缓几天 => write!(f, "缓 {} 天再晋升", WAIT_DAYS),
+        }
+    }
+}
+
+impl Plan {
+    pub const ALL: [Plan; 2] = [Plan::立即晋升, Plan::缓几天];
+}
+
+/// 一遍推演的结果
+#[derive(Debug, Clone, Copy)]
+pub struct Rollout {
+    pub survived: bool,
+    pub skills: i32,
+    pub promoted: bool,  // 推演期间晋升成功过
+}
+
+/// 一种打算的推演汇总
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Forecast {
+    pub runs: u32,
+    pub survived: u32,
+    pub promoted: u32,
+    pub total_skills: i64,
+}
+
+impl Forecast {
+    pub fn add(&mut self, rollout: Rollout) {
+        self.runs += 1;
+        self.survived += u32::from(rollout.survived);
+        self.promoted += u32::from(rollout.promoted);
+        self.total_skills += i64::from(rollout.skills);
+    }
+
+    pub fn survival_rate(&self) -> f32 {
+        self.survived as f32 / self.runs.max(1) as f32
+    }
+
+    pub fn promotion_rate(&self) -> f32 {
+        self.promoted as f32 / self.runs.max(1) as f32
+    }
+
+    pub fn avg_skills(&self) -> f32 {
+        self.total_skills as f32 / self.runs.max(1) as f32
+    }
+}
+
+/// 推演用的代理：到约定的天数之前一律暂缓晋升，其余照稳健策略走
+struct Patience {
+    promote_from: u32,
+}
+
+impl Agent for Patience {
+    fn name(&self) -> String {
+        "推演".to_string()
+    }
+
+    fn choose(&mut self, view: &StateView) -> Action {
+        match view.phase {
+            GamePhase::PromotionConfirm if view.state.current_day < self.promote_from => Action::DeclinePromotion,
+            _ => view.routine(|options| AutoPolicy::GreedySafe.choose(&view.state.player, options)),
+        }
+    }
+}
+
+/// 从晋升确认时的局面出发按 `plan` 推演一遍；`index` 决定这一遍的随机流
+pub fn rollout(state: &GameState, plan: Plan, index: u32) -> Rollout {
+    let start_day = state.current_day;
+    let realm = state.player.realm_level;
+    let mut copy = state.clone();
+    copy.reseed(state.seed.wrapping_add(u64::from(index) + 1));
+    let mut engine = GameEngine::new();
+    engine.game_state = Some(copy);
+    engine.phase = GamePhase::PromotionConfirm;
+    let mut agent = Patience {
+        promote_from: match plan {
+            Plan::立即晋升 => start_day,
+            Plan::缓几天 => start_day + WAIT_DAYS,
+        },
+    };
+    let mut steps = 0;
+    while steps < MAX_STEPS
+        && engine.game_state.as_ref().is_some_and(|s| s.current_day < start_day + HORIZON_DAYS)
+        && engine.agent_step(&mut agent)
+    {
+        engine.bus.drain();
+        steps += 1;
+    }
+    let state = engine.game_state.as_ref().expect("推演从局面副本开始");
+    Rollout {
+        survived: state.player.is_alive,
+        skills: state.player.skills,
+        promoted: state.player.realm_level > realm,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::balance::BalanceConfig;
+    use crate::director::Director;
+    use crate::engine::GameCommand;
+    use crate::specialty::{CareerPath, SkillTrack};
+    use crate::tone::NarrativeTone;
+
+    #[test]
+    fn guaranteed_promotion_rolls_out_without_touching_the_run() {
+        let balance = BalanceConfig {
+            death_table: [(i32::MAX, 0.0); 4],
+            zero_pressure_death_chance: 0.0,
+            promotion_failure_step: 0.0,
+            promotion_skills: [50, 150, 300, 500, 800, 1200],
+            ..BalanceConfig::default()
+        };
+        let mut engine = GameEngine::new();
+        engine.player_name = "推演道友".to_string();
+        engine.start_game(Vec::new(), balance, NarrativeTone::沙雕, Director::default(), CareerPath::default(), Vec::new(), Some(11))
+            .expect("名号合规");
+        engine.execute(GameCommand::Choose(1));
+        let player = &mut engine.game_state.as_mut().expect("已开局").player;
+        player.skills = 60;
+        for track in SkillTrack::ALL {
+            player.train(Some(track), 60);
+        }
+        engine.execute(GameCommand::NextDay);
+        assert_eq!(engine.phase, GamePhase::PromotionConfirm);
+
+        let state = engine.game_state.as_ref().expect("已开局");
+        let now = rollout(state, Plan::立即晋升, 0);
+        assert!(now.survived && now.promoted);
+        assert_eq!(state.player.realm_level, 1);
+    }
+}
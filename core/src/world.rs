@@ -0,0 +1,114 @@
+//! 局面摘要：判断“今天能不能发生”的各处（日常/周事件的触发条件、日常事件的内容门槛、同事的出场条件、事件导演）
+//! 看到的同一份世界——境界与对应的上司、天数、周几、压力与压力档、项目状况、天气、开着的节日内容包、
+//! 居住与通勤、隐藏事件是否解锁。内容文件里的条件只能引用这里有的字段，判断逻辑也就不用各自去翻玩家状态。
+//!
+//! 上司目前只有按境界定的头衔（`Realm::boss_title`），没有独立的上司类型，所以这里只给头衔；
+//! 以后上司有了自己的类型，要先加进这里，再开放给内容文件的条件。
+
+use crate::commute::Commute;
+use crate::director;
+use crate::finance::Housing;
+use crate::game::{DailyEvent, GameState, PlayerState, Realm};
+use crate::mood::{ProjectHealth, Weather};
+use crate::season::ContentPack;
+use crate::week::Weekday;
+
+/// 某一天的局面摘要（只读，按需现算）
+#[derive(Debug, Clone, Copy)]
+pub struct WorldView<'a> {
+    pub realm: Realm,
+    pub boss: &'static str,  // 顶头上司的头衔（随境界变）
+    pub day: u32,
+    pub week: u32,
+    pub weekday: Weekday,
+    pub pressure: i32,
+    pub pressure_band: &'static str,
+    pub pressure_trend: i32,  // 最近几天（见 `director::TREND_DAYS`）压力涨了多少
+    pub project_health: ProjectHealth,
+    pub weather: Weather,
+    pub festivals: &'a [ContentPack],  // 本局开着的节日内容包
+    pub skill_gap: i32,  // 离晋升要求还差多少技能点（够了为 0 或负数）
+    pub housing: Housing,
+    pub commute: Commute,
+    pub hidden_events: bool,  // 隐藏事件是否解锁（转世加成“前世记忆”）
+}
+
+impl<'a> WorldView<'a> {
+    /// 由玩家状态和日历拼出局面（开局第一天还没有 `GameState` 时也能用）
+    pub fn new(player: &PlayerState, seed: u64, day: u32, week: u32, festivals: &'a [ContentPack]) -> Self {
+        let pressure_trend = player
+            .recent_snapshots(director::TREND_DAYS)
+            .first()
+            .map_or(0, |oldest| player.pressure - oldest.pressure);
+        WorldView {
+            realm: player.get_realm(),
+            boss: player.get_realm().boss_title(),
+            day,
+            week,
+            weekday: Weekday::of(day),
+            pressure: player.pressure,
+            pressure_band: PlayerState::pressure_band(player.pressure),
+            pressure_trend,
+            project_health: ProjectHealth::from_pressure(player.pressure),
+            weather: Weather::on(seed, day),
+            festivals,
+            skill_gap: player.skill_requirement().saturating_sub(player.skills),
+            housing: player.housing,
+            commute: player.commute,
+            hidden_events: player.balance.hidden_events,
+        }
+    }
+
+    /// 当前对局今天的局面
+    pub fn of(state: &'a GameState) -> Self {
+        WorldView::new(&state.player, state.seed, state.current_day, state.current_week, &state.active_packs)
+    }
+
+    pub fn is_festival(&self, pack: ContentPack) -> bool {
+        self.festivals.contains(&pack)
+    }
+
+    /// 日常事件的内容门槛是否满足：隐藏事件要已解锁，限时内容要开着对应的节日，还有居住条件和通勤方式
+    pub fn admits(&self, event: &DailyEvent) -> bool {
+        (!event.hidden || self.hidden_events)
+            && event.pack.is_none_or(|pack| self.is_festival(pack))
+            && event.min_housing.is_none_or(|housing| self.housing >= housing)
+            && (event.commutes.is_empty() || event.commutes.contains(&self.commute))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trigger::Trigger;
+
+    #[test]
+    fn triggers_read_the_world_view() {
+        let mut player = PlayerState::new("局面道友".to_string());
+        player.pressure = 75;
+        let world = WorldView::new(&player, 7, 3, 1, &[ContentPack::中秋]);
+        assert_eq!(world.project_health, ProjectHealth::告急);
+        assert_eq!(world.weekday, Weekday::周三);
+        assert!(world.is_festival(ContentPack::中秋) && !world.is_festival(ContentPack::春节));
+
+        let trigger = |trigger: Trigger| trigger.allows(&world);
+        assert!(trigger(Trigger { pressure: Some((70, 100)), days: Some((1, 5)), ..Trigger::default() }));
+        assert!(trigger(Trigger { health: Some(ProjectHealth::告急), ..Trigger::default() }));
+        assert!(!trigger(Trigger { health: Some(ProjectHealth::稳定), ..Trigger::default() }));
+        assert!(trigger(Trigger { weather: Some(world.weather), ..Trigger::default() }));
+        assert!(!trigger(Trigger { min_realm: Some(Realm::炼气期), ..Trigger::default() }));
+        assert_eq!(world.boss, Realm::凡人境.boss_title());
+    }
+
+    #[test]
+    fn content_gates_read_the_world_view() {
+        let mut player = PlayerState::new("局面道友".to_string());
+        let mut event = crate::events::library().daily_events()[0].clone();
+        event.hidden = true;
+        event.pack = Some(ContentPack::中秋);
+        assert!(!WorldView::new(&player, 7, 3, 1, &[ContentPack::中秋]).admits(&event), "隐藏事件还没解锁");
+        player.balance.hidden_events = true;
+        assert!(!WorldView::new(&player, 7, 3, 1, &[]).admits(&event), "节日没开");
+        assert!(WorldView::new(&player, 7, 3, 1, &[ContentPack::中秋]).admits(&event));
+    }
+}
@@ -0,0 +1,19 @@
+//! 模糊测试：任意字节当作自制剧本包解析，只允许返回错误，不允许 panic。
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+#[path = "../../core/src/balance.rs"]
+#[allow(dead_code)]
+mod balance;
+#[path = "../../core/src/scenario.rs"]
+#[allow(dead_code)]
+mod scenario;
+
+/// 随便给一组存在的事件 id，让校验逻辑也跑到
+const DAILY_IDS: [usize; 4] = [1, 2, 3, 31];
+const WEEKLY_IDS: [usize; 2] = [1, 2];
+
+fuzz_target!(|data: &[u8]| {
+    let _ = scenario::ScenarioPack::parse_bytes(data, &DAILY_IDS, &WEEKLY_IDS);
+});
@@ -0,0 +1,21 @@
+//! 模糊测试：任意字节当作分享码解析，只允许返回错误，不允许 panic；
+//! 另外把任意字节签成合法分享码再解回来，确认内容原样往返。
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+#[path = "../../src/sharecode.rs"]
+#[allow(dead_code)]
+mod sharecode;
+
+const PREFIX: &str = "xiuxian1";
+const KEY: &str = "fuzz";
+
+fuzz_target!(|data: &[u8]| {
+    let text = String::from_utf8_lossy(data);
+    if let Ok(payload) = sharecode::decode(&text, PREFIX, KEY) {
+        let _ = serde_json::from_slice::<serde_json::Value>(&payload);
+    }
+    let code = sharecode::encode(PREFIX, data, KEY);
+    assert_eq!(sharecode::decode(&code, PREFIX, KEY).as_deref(), Ok(data));
+});
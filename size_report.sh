@@ -0,0 +1,54 @@
+#!/bin/bash
+# 网页版体积报告：列出 web/ 下各文件的原始与 gzip 大小，检查首屏体积是否超出预算
+# 先运行 ./build_web.sh；预算可用环境变量调整，例如 FIRST_LOAD_BUDGET_KB=4096 ./size_report.sh
+
+set -e
+
+cd "$(dirname "$0")"
+
+# 首屏（wasm + JS 胶水，gzip 后）预算，单位 KB
+FIRST_LOAD_BUDGET_KB=${FIRST_LOAD_BUDGET_KB:-3072}
+# 启动后下载的资源（字体 + 内容，gzip 后）预算，单位 KB
+ASSETS_BUDGET_KB=${ASSETS_BUDGET_KB:-8192}
+
+if [ ! -f web/xiuxian_game_bg.wasm ]; then
+    echo "❌ 找不到 web/xiuxian_game_bg.wasm，请先运行 ./build_web.sh"
+    exit 1
+fi
+
+raw_kb() { echo $(( $(wc -c < "$1") / 1024 )); }
+gz_kb() { echo $(( $(gzip -9 -c "$1" | wc -c) / 1024 )); }
+
+first_load=0
+assets=0
+
+printf "%-40s %10s %10s\n" "文件" "原始(KB)" "gzip(KB)"
+for file in web/xiuxian_game_bg.wasm web/xiuxian_game.js; do
+    [ -f "$file" ] || continue
+    printf "%-40s %10s %10s\n" "$file" "$(raw_kb "$file")" "$(gz_kb "$file")"
+    first_load=$(( first_load + $(gz_kb "$file") ))
+done
+echo "---- 启动后下载 ----"
+for file in web/fonts/* web/content/*.json; do
+    [ -f "$file" ] || continue
+    printf "%-40s %10s %10s\n" "$file" "$(raw_kb "$file")" "$(gz_kb "$file")"
+    assets=$(( assets + $(gz_kb "$file") ))
+done
+
+echo ""
+echo "首屏：${first_load} KB / 预算 ${FIRST_LOAD_BUDGET_KB} KB"
+echo "资源：${assets} KB / 预算 ${ASSETS_BUDGET_KB} KB"
+
+over=0
+if [ "$first_load" -gt "$FIRST_LOAD_BUDGET_KB" ]; then
+    echo "❌ 首屏超出预算：检查是否又把大文件 include_bytes!/include_str! 进了 wasm"
+    over=1
+fi
+if [ "$assets" -gt "$ASSETS_BUDGET_KB" ]; then
+    echo "❌ 启动资源超出预算"
+    over=1
+fi
+if [ "$over" -eq 0 ]; then
+    echo "✅ 体积在预算内"
+fi
+exit $over
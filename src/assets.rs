@@ -0,0 +1,137 @@
+//! 图片资源：NPC 头像、玩家头像、事件插图。第一次用到时在后台读取并解码成纹理，之后一直缓存；
+//! 还没加载完或者根本没有这张图时返回 None，由界面画占位图（按名字生成的像素头像）。
+//! 桌面端从数据目录下的 assets/ 读取，网页端从页面旁的 ./assets/ 下载；只支持 PNG。
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use eframe::egui::{self, ColorImage, TextureHandle, TextureOptions};
+
+/// 单张图片的大小上限（字节），太大的图当作没有
+const MAX_IMAGE_BYTES: usize = 4 * 1024 * 1024;
+
+/// 一张图片资源
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AssetKey {
+    /// NPC 头像：portraits/<名字>.png
+    Portrait(String),
+    /// 玩家头像：avatar.png
+    Avatar,
+    /// 事件插图：events/daily_<id>.png 或 events/weekly_<id>.png
+    EventArt { weekly: bool, id: usize },
+}
+
+impl AssetKey {
+    /// 相对资源目录的路径
+    fn path(&self) -> String {
+        match self {
+            AssetKey::Portrait(name) => format!("portraits/{}.png", name),
+            AssetKey::Avatar => "avatar.png".to_string(),
+            AssetKey::EventArt { weekly: false, id } => format!("events/daily_{}.png", id),
+            AssetKey::EventArt { weekly: true, id } => format!("events/weekly_{}.png", id),
+        }
+    }
+}
+
+/// 资源目录：桌面端是本地目录，网页端是相对页面的地址前缀
+#[cfg(not(target_arch = "wasm32"))]
+pub type Root = std::path::PathBuf;
+#[cfg(target_arch = "wasm32")]
+pub type Root = String;
+
+enum Slot {
+    Loading,
+    Ready(TextureHandle),
+    Missing,
+}
+
+/// 纹理缓存（界面只读借用时也能发起加载，所以内部用 RefCell）
+pub struct AssetCache {
+    root: Root,
+    slots: RefCell<HashMap<AssetKey, Slot>>,
+    sender: Sender<(AssetKey, Option<ColorImage>)>,
+    receiver: Receiver<(AssetKey, Option<ColorImage>)>,
+}
+
+impl AssetCache {
+    pub fn new(root: Root) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            root,
+            slots: RefCell::default(),
+            sender,
+            receiver,
+        }
+    }
+
+    /// 资源目录（音效与 BGM 也放在这里）
+    pub fn root(&self) -> &Root {
+        &self.root
+    }
+
+    /// 取一张图的纹理；第一次请求时开始后台加载，加载完之前和找不到时返回 None
+    pub fn texture(&self, ctx: &egui::Context, key: &AssetKey) -> Option<TextureHandle> {
+        self.poll(ctx);
+        let mut slots = self.slots.borrow_mut();
+        match slots.get(key) {
+            Some(Slot::Ready(texture)) => Some(texture.clone()),
+            Some(Slot::Loading | Slot::Missing) => None,
+            None => {
+                slots.insert(key.clone(), Slot::Loading);
+                self.load(ctx, key.clone());
+                None
+            }
+        }
+    }
+
+    /// 收下后台加载完的图片，上传成纹理
+    fn poll(&self, ctx: &egui::Context) {
+        let mut slots = self.slots.borrow_mut();
+        for (key, image) in self.receiver.try_iter() {
+            let slot = match image {
+                Some(image) => Slot::Ready(ctx.load_texture(key.path(), image, TextureOptions::LINEAR)),
+                None => Slot::Missing,
+            };
+            slots.insert(key, slot);
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load(&self, ctx: &egui::Context, key: AssetKey) {
+        let path = self.root.join(key.path());
+        let sender = self.sender.clone();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let image = std::fs::read(&path).ok().and_then(|bytes| decode(&bytes));
+            let _ = sender.send((key, image));
+            ctx.request_repaint();
+        });
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn load(&self, ctx: &egui::Context, key: AssetKey) {
+        let url = format!("{}/{}", self.root, key.path());
+        let sender = self.sender.clone();
+        let ctx = ctx.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let image = crate::webassets::fetch(&url, |_, _| {})
+                .await
+                .ok()
+                .and_then(|bytes| decode(&bytes));
+            let _ = sender.send((key, image));
+            ctx.request_repaint();
+        });
+    }
+}
+
+/// 把 PNG 解码成 egui 图片
+fn decode(bytes: &[u8]) -> Option<ColorImage> {
+    if bytes.len() > MAX_IMAGE_BYTES {
+        return None;
+    }
+    let image = image::load_from_memory_with_format(bytes, image::ImageFormat::Png).ok()?;
+    let rgba = image.to_rgba8();
+    let size = [rgba.width() as usize, rgba.height() as usize];
+    Some(ColorImage::from_rgba_unmultiplied(size, rgba.as_flat_samples().as_slice()))
+}
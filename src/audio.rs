@@ -0,0 +1,295 @@
+//! 音效与背景音乐：订阅引擎事件，在选项点击、周事件触发、晋升成败、猝死时放一声，开局后循环播放 BGM。
+//! 音效优先读资源目录下的 audio/<名字>.ogg，没有就用内置的合成音（几个正弦波音符）；
+//! BGM 只读 audio/bgm.ogg，没有这个文件就不放。
+//! 桌面端用 rodio 出声（`audio` 特性，Linux 上需要 ALSA），没开特性时静音；
+//! 网页端用 Web Audio 合成音效（不读音效文件），BGM 用 <audio> 元素循环播放。
+
+use serde::{Deserialize, Serialize};
+
+use crate::assets::Root;
+use crate::bus::GameEvent;
+
+/// BGM 文件（相对资源目录）
+#[cfg_attr(not(any(feature = "audio", target_arch = "wasm32")), allow(dead_code))]
+const MUSIC_FILE: &str = "audio/bgm.ogg";
+
+/// 合成音的基础响度（再乘音效音量）
+#[cfg_attr(not(any(feature = "audio", target_arch = "wasm32")), allow(dead_code))]
+const SYNTH_GAIN: f32 = 0.3;
+
+/// 音量与开关（随设置落盘）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioSettings {
+    pub muted: bool,        // 全部静音
+    pub sfx_volume: f32,    // 音效音量（0~1）
+    pub music: bool,        // 播放背景音乐
+    pub music_volume: f32,  // 背景音乐音量（0~1）
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        AudioSettings {
+            muted: false,
+            sfx_volume: 0.6,
+            music: true,
+            music_volume: 0.4,
+        }
+    }
+}
+
+/// 音效（同一批事件只放最重要的一个，越靠后越重要）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Sound {
+    选项,
+    周事件,
+    晋升成功,
+    晋升失败,
+    猝死,
+}
+
+impl Sound {
+    /// 引擎事件对应的音效
+    pub fn for_event(event: &GameEvent) -> Option<Sound> {
+        match event {
+            GameEvent::ChoiceMade { .. } => Some(Sound::选项),
+            GameEvent::WeeklyEventTriggered => Some(Sound::周事件),
+            GameEvent::PromotionResolved { success: true, .. } => Some(Sound::晋升成功),
+            GameEvent::PromotionResolved { success: false, .. } => Some(Sound::晋升失败),
+            GameEvent::DeathRolled { survived: false } => Some(Sound::猝死),
+            _ => None,
+        }
+    }
+
+    /// 自定义音效文件（相对资源目录）
+    #[cfg_attr(not(all(feature = "audio", not(target_arch = "wasm32"))), allow(dead_code))]
+    fn file(&self) -> &'static str {
+        match self {
+            Sound::选项 => "audio/choice.ogg",
+            Sound::周事件 => "audio/weekly.ogg",
+            Sound::晋升成功 => "audio/promotion.ogg",
+            Sound::晋升失败 => "audio/demotion.ogg",
+            Sound::猝死 => "audio/death.ogg",
+        }
+    }
+
+    /// 内置合成音：依次播放的（频率 Hz，时长秒）
+    #[cfg_attr(not(any(feature = "audio", target_arch = "wasm32")), allow(dead_code))]
+    fn notes(&self) -> &'static [(f32, f32)] {
+        match self {
+            Sound::选项 => &[(880.0, 0.06)],
+            Sound::周事件 => &[(659.25, 0.12), (523.25, 0.2)],
+            Sound::晋升成功 => &[(523.25, 0.1), (659.25, 0.1), (783.99, 0.1), (1046.5, 0.3)],
+            Sound::晋升失败 => &[(392.0, 0.15), (311.13, 0.3)],
+            Sound::猝死 => &[(220.0, 0.25), (185.0, 0.25), (146.83, 0.5)],
+        }
+    }
+}
+
+/// 播放器：第一次出声时才打开音频设备（网页端要等玩家点过页面才允许出声）
+pub struct Audio {
+    root: Root,
+    output: Option<backend::Output>,
+    unavailable: bool,  // 打不开音频设备，之后不再尝试
+}
+
+impl Audio {
+    pub fn new(root: Root) -> Self {
+        Audio { root, output: None, unavailable: false }
+    }
+
+    fn output(&mut self) -> Option<&mut backend::Output> {
+        if self.output.is_none() && !self.unavailable {
+            self.output = backend::Output::open(&self.root);
+            self.unavailable = self.output.is_none();
+        }
+        self.output.as_mut()
+    }
+
+    /// 一批引擎事件里挑最重要的一个放
+    pub fn on_events(&mut self, events: &[GameEvent], settings: &AudioSettings) {
+        if let Some(sound) = events.iter().filter_map(Sound::for_event).max() {
+            self.play(sound, settings);
+        }
+    }
+
+    pub fn play(&mut self, sound: Sound, settings: &AudioSettings) {
+        if settings.muted || settings.sfx_volume <= 0.0 {
+            return;
+        }
+        if let Some(output) = self.output() {
+            output.play(sound, settings.sfx_volume);
+        }
+    }
+
+    /// 每帧调用：对局进行中且开着 BGM 时循环播放，否则停下
+    pub fn update_music(&mut self, settings: &AudioSettings, in_run: bool) {
+        let playing = in_run && settings.music && !settings.muted;
+        if !playing && self.output.is_none() {
+            return;
+        }
+        if let Some(output) = self.output() {
+            output.set_music(playing, settings.music_volume);
+        }
+    }
+}
+
+#[cfg(all(feature = "audio", not(target_arch = "wasm32")))]
+mod backend {
+    use std::fs::File;
+    use std::io::BufReader;
+    use std::path::{Path, PathBuf};
+    use std::time::Duration;
+
+    use rodio::source::SineWave;
+    use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink, Source};
+
+    use super::{Sound, MUSIC_FILE, SYNTH_GAIN};
+
+    pub struct Output {
+        _stream: OutputStream,  // 丢掉就没声音了，一直留着
+        handle: OutputStreamHandle,
+        root: PathBuf,
+        music: Option<Sink>,
+        music_missing: bool,    // 没有 BGM 文件，不再每帧去找
+    }
+
+    impl Output {
+        pub fn open(root: &Path) -> Option<Self> {
+            let (stream, handle) = OutputStream::try_default().ok()?;
+            Some(Output { _stream: stream, handle, root: root.to_path_buf(), music: None, music_missing: false })
+        }
+
+        fn decoder(&self, file: &str) -> Option<Decoder<BufReader<File>>> {
+            let file = File::open(self.root.join(file)).ok()?;
+            Decoder::new(BufReader::new(file)).ok()
+        }
+
+        pub fn play(&mut self, sound: Sound, volume: f32) {
+            let Ok(sink) = Sink::try_new(&self.handle) else {
+                return;
+            };
+            sink.set_volume(volume);
+            match self.decoder(sound.file()) {
+                Some(source) => sink.append(source),
+                None => {
+                    for &(freq, secs) in sound.notes() {
+                        sink.append(
+                            SineWave::new(freq)
+                                .take_duration(Duration::from_secs_f32(secs))
+                                .amplify(SYNTH_GAIN),
+                        );
+                    }
+                }
+            }
+            sink.detach();
+        }
+
+        pub fn set_music(&mut self, playing: bool, volume: f32) {
+            if !playing {
+                if let Some(music) = self.music.take() {
+                    music.stop();
+                }
+                return;
+            }
+            if let Some(music) = &self.music {
+                music.set_volume(volume);
+                return;
+            }
+            if self.music_missing {
+                return;
+            }
+            let looped = File::open(self.root.join(MUSIC_FILE))
+                .ok()
+                .and_then(|file| Decoder::new_looped(BufReader::new(file)).ok());
+            match (looped, Sink::try_new(&self.handle)) {
+                (Some(source), Ok(sink)) => {
+                    sink.set_volume(volume);
+                    sink.append(source);
+                    self.music = Some(sink);
+                }
+                _ => self.music_missing = true,
+            }
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod backend {
+    use web_sys::{AudioContext, HtmlAudioElement, OscillatorType};
+
+    use super::{Sound, MUSIC_FILE, SYNTH_GAIN};
+
+    pub struct Output {
+        context: AudioContext,
+        root: String,
+        music: Option<HtmlAudioElement>,
+    }
+
+    impl Output {
+        pub fn open(root: &str) -> Option<Self> {
+            let context = AudioContext::new().ok()?;
+            Some(Output { context, root: root.to_string(), music: None })
+        }
+
+        pub fn play(&mut self, sound: Sound, volume: f32) {
+            // 页面刚打开时浏览器会把音频挂起，玩家点过之后才能恢复
+            let _ = self.context.resume();
+            let mut at = self.context.current_time();
+            for &(freq, secs) in sound.notes() {
+                let (Ok(oscillator), Ok(gain)) = (self.context.create_oscillator(), self.context.create_gain()) else {
+                    return;
+                };
+                oscillator.set_type(OscillatorType::Sine);
+                oscillator.frequency().set_value(freq);
+                gain.gain().set_value(volume * SYNTH_GAIN);
+                let _ = oscillator.connect_with_audio_node(&gain);
+                let _ = gain.connect_with_audio_node(&self.context.destination());
+                let _ = oscillator.start_with_when(at);
+                let _ = oscillator.stop_with_when(at + f64::from(secs));
+                at += f64::from(secs);
+            }
+        }
+
+        pub fn set_music(&mut self, playing: bool, volume: f32) {
+            if !playing {
+                if let Some(music) = self.music.take() {
+                    let _ = music.pause();
+                }
+                return;
+            }
+            if let Some(music) = &self.music {
+                music.set_volume(f64::from(volume));
+                return;
+            }
+            // 没有 BGM 文件时 play 会失败，元素留着也不会再出声
+            let Ok(music) = HtmlAudioElement::new_with_src(&format!("{}/{}", self.root, MUSIC_FILE)) else {
+                return;
+            };
+            music.set_loop(true);
+            music.set_volume(f64::from(volume));
+            let _ = music.play();
+            self.music = Some(music);
+        }
+    }
+}
+
+/// 没开 `audio` 特性的桌面端：打不开设备，全程静音
+#[cfg(all(not(feature = "audio"), not(target_arch = "wasm32")))]
+mod backend {
+    use std::path::Path;
+
+    use super::Sound;
+
+    pub struct Output;
+
+    impl Output {
+        pub fn open(_root: &Path) -> Option<Self> {
+            None
+        }
+
+        pub fn play(&mut self, _sound: Sound, _volume: f32) {}
+
+        pub fn set_music(&mut self, _playing: bool, _volume: f32) {}
+    }
+}
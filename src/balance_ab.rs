@@ -0,0 +1,161 @@
+//! 平衡 A/B 对比（debug 特性，桌面端）：盯着仓库根目录下的两份数值配置 balance_a.json / balance_b.json，
+//! 任一份改过就重新读入，用同一批种子、同一个代打策略把两份各模拟一遍，并排显示汇总和差值。
+//! 配置文件就是 `BalanceConfig` 的 JSON（缺的字段按标准预设补齐），可以先把当前预设导出一份再改。
+
+use std::path::Path;
+use std::time::SystemTime;
+
+use xiuxian_core::balance::BalanceConfig;
+use xiuxian_core::director::Director;
+use xiuxian_core::game::Realm;
+use xiuxian_core::policy::AutoPolicy;
+
+use crate::simulation::{SimConfig, SimReport, Simulation};
+
+/// 两份配置：(名字, 路径)
+pub const SLOTS: [(&str, &str); 2] = [
+    ("A", concat!(env!("CARGO_MANIFEST_DIR"), "/balance_a.json")),
+    ("B", concat!(env!("CARGO_MANIFEST_DIR"), "/balance_b.json")),
+];
+
+/// 检查间隔（秒）
+const POLL_SECS: f64 = 1.0;
+
+/// 对比表的一行：(指标, A, B, 是否按百分比显示)
+pub type Row = (String, f32, f32, bool);
+
+/// 一份配置的读入状态
+#[derive(Default)]
+pub struct Slot {
+    modified: Option<SystemTime>,
+    pub config: Option<BalanceConfig>,
+    pub error: Option<String>,
+}
+
+impl Slot {
+    fn path(index: usize) -> &'static Path {
+        Path::new(SLOTS[index].1)
+    }
+
+    /// 文件的修改时间变了（包括第一次出现）就重新读入，返回是否读入了新配置
+    fn refresh(&mut self, index: usize) -> bool {
+        let modified = std::fs::metadata(Self::path(index)).and_then(|meta| meta.modified()).ok();
+        if modified.is_none() || modified == self.modified {
+            return false;
+        }
+        self.modified = modified;
+        match std::fs::read_to_string(Self::path(index))
+            .map_err(|e| format!("读取失败: {}", e))
+            .and_then(|text| serde_json::from_str(&text).map_err(|e| format!("格式错误: {}", e)))
+        {
+            Ok(config) => {
+                self.config = Some(config);
+                self.error = None;
+                true
+            }
+            Err(err) => {
+                self.error = Some(err);
+                false
+            }
+        }
+    }
+}
+
+/// A/B 对比的全部状态
+pub struct BalanceAb {
+    pub slots: [Slot; 2],
+    pub policy: AutoPolicy,
+    pub runs: u32,
+    pub auto_rerun: bool,  // 配置改动后自动重跑
+    sims: Option<[Simulation; 2]>,
+    last_poll: f64,
+}
+
+impl Default for BalanceAb {
+    fn default() -> Self {
+        BalanceAb {
+            slots: Default::default(),
+            policy: AutoPolicy::GreedySafe,
+            runs: 200,
+            auto_rerun: true,
+            sims: None,
+            last_poll: f64::NEG_INFINITY,
+        }
+    }
+}
+
+impl BalanceAb {
+    /// 每帧调用：到点检查两份配置，改过就重新读入（开着自动重跑时顺带重跑），再收下模拟结果。
+    /// 返回 (是否还在跑, 这次重新读入了哪些配置的名字)
+    pub fn tick(&mut self, now: f64, director: Director) -> (bool, Vec<&'static str>) {
+        let mut reloaded = Vec::new();
+        if now - self.last_poll >= POLL_SECS {
+            self.last_poll = now;
+            for (index, slot) in self.slots.iter_mut().enumerate() {
+                if slot.refresh(index) {
+                    reloaded.push(SLOTS[index].0);
+                }
+            }
+            if !reloaded.is_empty() && self.auto_rerun && self.sims.is_some() {
+                self.start(director);
+            }
+        }
+        let running = self.sims.as_mut().is_some_and(|sims| sims.iter_mut().fold(false, |any, sim| sim.poll() | any));
+        (running, reloaded)
+    }
+
+    /// 把 `config` 写成第 `index` 份配置（下一次检查时读入）
+    pub fn export(&self, index: usize, config: &BalanceConfig) -> Result<(), String> {
+        let text = serde_json::to_string_pretty(config).map_err(|e| format!("序列化失败: {}", e))?;
+        std::fs::write(Slot::path(index), text).map_err(|e| format!("写入失败: {}", e))
+    }
+
+    /// 两份配置都读入了才能对比
+    pub fn ready(&self) -> bool {
+        self.slots.iter().all(|slot| slot.config.is_some())
+    }
+
+    /// 用同一个种子开始（或重新开始）对比；两份配置没都读入时什么也不做
+    pub fn start(&mut self, director: Director) {
+        self.stop();
+        let seed = rand::random();
+        let [Some(a), Some(b)] = [&self.slots[0].config, &self.slots[1].config] else {
+            return;
+        };
+        let sim = |balance: &BalanceConfig| {
+            Simulation::start(SimConfig { balance: balance.clone(), director, policy: self.policy, runs: self.runs, seed })
+        };
+        self.sims = Some([sim(a), sim(b)]);
+    }
+
+    pub fn stop(&mut self) {
+        for sim in self.sims.iter_mut().flatten() {
+            sim.cancel();
+        }
+    }
+
+    /// 两边的进度：(已完成局数, 总局数)
+    pub fn progress(&self) -> Option<[(u32, u32); 2]> {
+        self.sims.as_ref().map(|sims| sims.each_ref().map(Simulation::progress))
+    }
+
+    /// 并排对比的各行
+    pub fn rows(&self) -> Vec<Row> {
+        let Some([a, b]) = self.sims.as_ref().map(|sims| sims.each_ref().map(Simulation::report)) else {
+            return Vec::new();
+        };
+        compare(a, b)
+    }
+}
+
+/// 两份汇总逐项对比
+fn compare(a: &SimReport, b: &SimReport) -> Vec<Row> {
+    let mut rows = vec![
+        ("存活率".to_string(), a.survival_rate(), b.survival_rate(), true),
+        ("平均天数".to_string(), a.avg_days(), b.avg_days(), false),
+        ("平均技能点".to_string(), a.avg_skills(), b.avg_skills(), false),
+    ];
+    let realms = (1..).zip(a.realm_shares().into_iter().zip(b.realm_shares()));
+    rows.extend(realms.map(|(level, (a, b))| (format!("停在{}", Realm::from_level(level)), a, b, true)));
+    rows
+}
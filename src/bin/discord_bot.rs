@@ -0,0 +1,344 @@
+//! Discord 机器人版：在频道里一回合一回合地玩，选项做成消息按钮。
+//! 规则全部走 xiuxian-core 的 GameEngine，和图形界面、终端版是同一套引擎，
+//! 这里只负责把对局渲染成消息、把按钮翻译成 `GameCommand`。
+//!
+//! 用法：设置环境变量 `DISCORD_TOKEN` 后运行 `cargo run --bin discord-bot --features discord-bot`，
+//! 机器人需要开启 Message Content 权限。频道里发 `!修仙 [道号]` 开局，`!收工` 结束。
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serenity::all::{
+    ButtonStyle, ChannelId, Client, ComponentInteraction, Context, CreateActionRow, CreateButton,
+    CreateInteractionResponse, CreateInteractionResponseMessage, CreateMessage, EventHandler, GatewayIntents,
+    Interaction, Message, Ready, UserId,
+};
+use serenity::async_trait;
+
+use xiuxian_core::arena::{Action, StateView};
+use xiuxian_core::balance::BalanceConfig;
+use xiuxian_core::director::Director;
+use xiuxian_core::ending;
+use xiuxian_core::engine::{GameCommand, GameEngine, GamePhase};
+use xiuxian_core::name;
+use xiuxian_core::specialty::CareerPath;
+use xiuxian_core::tone::NarrativeTone;
+
+/// 开局命令（后面可以跟道号）
+const START_COMMAND: &str = "!修仙";
+
+/// 结束本频道对局的命令
+const QUIT_COMMAND: &str = "!收工";
+
+/// 账号名当不了道号时用的名字
+const DEFAULT_NAME: &str = "无名道友";
+
+/// Discord 单条消息的字数上限
+const MESSAGE_LIMIT: usize = 2000;
+
+/// 按钮文字的字数上限
+const LABEL_LIMIT: usize = 80;
+
+/// 一个频道里正在进行的对局（只有开局的人能按按钮）
+struct Session {
+    owner: UserId,
+    name: String,
+    engine: GameEngine,
+}
+
+#[derive(Default)]
+struct Handler {
+    sessions: Mutex<HashMap<ChannelId, Session>>,
+}
+
+#[async_trait]
+impl EventHandler for Handler {
+    async fn ready(&self, _: Context, ready: Ready) {
+        println!("🧘 {} 已上线，频道里发送 {} 开局", ready.user.name, START_COMMAND);
+    }
+
+    async fn message(&self, ctx: Context, msg: Message) {
+        if msg.author.bot {
+            return;
+        }
+        let text = msg.content.trim();
+        let reply = if let Some(rest) = text.strip_prefix(START_COMMAND) {
+            let name = match rest.trim() {
+                "" => name::sanitize(&msg.author.name, DEFAULT_NAME),
+                name => name.to_string(),
+            };
+            let mut sessions = self.sessions.lock().expect("对局表锁");
+            match sessions.get(&msg.channel_id) {
+                Some(session) if session.owner != msg.author.id => {
+                    CreateMessage::new().content(format!("这个频道里 {} 正在修炼，等这局收工再来", session.name))
+                }
+                _ => {
+                    let mut engine = GameEngine::new();
+                    match new_run(&mut engine, &name) {
+                        Ok(()) => {
+                            let (content, components) = render(&engine);
+                            let name = engine.player_name.clone();
+                            sessions.insert(msg.channel_id, Session { owner: msg.author.id, name, engine });
+                            CreateMessage::new().content(content).components(components)
+                        }
+                        Err(err) => CreateMessage::new().content(format!("开不了局：{}", err)),
+                    }
+                }
+            }
+        } else if text == QUIT_COMMAND {
+            let mut sessions = self.sessions.lock().expect("对局表锁");
+            match sessions.get(&msg.channel_id) {
+                Some(session) if session.owner == msg.author.id => {
+                    sessions.remove(&msg.channel_id);
+                    CreateMessage::new().content("🍵 收工了，下次再来修炼")
+                }
+                Some(_) => CreateMessage::new().content("只有开局的道友才能收工"),
+                None => return,
+            }
+        } else {
+            return;
+        };
+        if let Err(err) = msg.channel_id.send_message(&ctx.http, reply).await {
+            eprintln!("发送消息失败：{}", err);
+        }
+    }
+
+    async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        let Interaction::Component(component) = interaction else {
+            return;
+        };
+        let response = self.press(&component);
+        if let Err(err) = component.create_response(&ctx.http, response).await {
+            eprintln!("回应按钮失败：{}", err);
+        }
+    }
+}
+
+impl Handler {
+    /// 按下按钮：执行对应命令，原地更新对局消息
+    fn press(&self, component: &ComponentInteraction) -> CreateInteractionResponse {
+        let mut sessions = self.sessions.lock().expect("对局表锁");
+        let Some(session) = sessions.get_mut(&component.channel_id) else {
+            return notice(&format!("这局已经结束了，发送 {} 重新开局", START_COMMAND));
+        };
+        if session.owner != component.user.id {
+            return notice(&format!("这是 {} 的对局，发送 {} 自己开一局吧", session.name, START_COMMAND));
+        }
+        let id = component.data.custom_id.as_str();
+        if !live(&session.engine, id) {
+            return notice("这个按钮已经过时了，看最新的那条消息");
+        }
+        match id {
+            "new" => {
+                let name = session.name.clone();
+                if let Err(err) = new_run(&mut session.engine, &name) {
+                    return notice(&err);
+                }
+            }
+            id => match command(id) {
+                Some(command) => session.engine.execute(command),
+                None => return notice("看不懂这个按钮"),
+            },
+        }
+        // 机器人没有订阅引擎事件的子系统
+        session.engine.bus.drain();
+        let (content, components) = render(&session.engine);
+        CreateInteractionResponse::UpdateMessage(
+            CreateInteractionResponseMessage::new().content(content).components(components),
+        )
+    }
+}
+
+/// 只给按按钮的人看的提示
+fn notice(text: &str) -> CreateInteractionResponse {
+    CreateInteractionResponse::Message(CreateInteractionResponseMessage::new().content(text).ephemeral(true))
+}
+
+/// 按钮 id 对应的引擎命令
+fn command(id: &str) -> Option<GameCommand> {
+    match id {
+        "hint" => Some(GameCommand::Hint),
+        "rematch" => Some(GameCommand::Rematch),
+        id => action(id).map(GameCommand::from),
+    }
+}
+
+/// 推进对局的按钮对应的操作（师傅提示、重开这类不算）
+fn action(id: &str) -> Option<Action> {
+    Some(match id {
+        "choose1" => Action::Choose(0),
+        "choose2" => Action::Choose(1),
+        "choose3" => Action::Choose(2),
+        "weekly" => Action::OpenWeeklyEvent,
+        "next" => Action::NextDay,
+        "promote" => Action::Promote,
+        "decline" => Action::DeclinePromotion,
+        "answer" => Action::AnswerNightCall(true),
+        "hangup" => Action::AnswerNightCall(false),
+        "recap" => Action::CloseRecap,
+        _ => return None,
+    })
+}
+
+/// 按钮在当前阶段还能不能按：频道里的旧消息留着旧按钮，按下去时对局可能早就往前走了
+fn live(engine: &GameEngine, id: &str) -> bool {
+    match id {
+        "new" | "rematch" => engine.phase == GamePhase::GameOver,
+        "hint" => StateView::of(engine).is_some_and(|view| !view.options.is_empty()),
+        id => match (StateView::of(engine), action(id)) {
+            (Some(view), Some(action)) => view.allows(action),
+            _ => false,
+        },
+    }
+}
+
+/// 用同一个道号开新的一局；道号不合规时返回原因
+fn new_run(engine: &mut GameEngine, name: &str) -> Result<(), String> {
+    engine.execute(GameCommand::Restart);
+    engine.player_name = name.to_string();
+    engine.start_game(
+        Vec::new(),
+        BalanceConfig::default(),
+        NarrativeTone::沙雕,
+        Director::default(),
+        CareerPath::default(),
+        Vec::new(),
+        None,
+    )
+}
+
+fn button(id: &str, label: &str, style: ButtonStyle) -> CreateButton {
+    CreateButton::new(id).label(truncate(label, LABEL_LIMIT)).style(style)
+}
+
+fn truncate(text: &str, limit: usize) -> String {
+    if text.chars().count() <= limit {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(limit - 1).collect();
+    cut.push('…');
+    cut
+}
+
+/// 当前事件还能不能选
+fn can_choose(engine: &GameEngine) -> bool {
+    match engine.phase {
+        GamePhase::WeeklyEventDisplay => engine.can_make_weekly_choice(),
+        _ => engine.can_make_daily_choice(),
+    }
+}
+
+fn status(engine: &GameEngine) -> String {
+    let Some(state) = engine.game_state.as_ref() else {
+        return String::new();
+    };
+    let player = &state.player;
+    format!(
+        "📊 第{}天 · {} · 技能点 {} · 压力 {} · 存款 {} 元 · 今晚出局风险 {:.0}%",
+        state.current_day,
+        player.get_realm(),
+        player.skills,
+        player.pressure,
+        player.savings,
+        player.tonight_risk() * 100.0
+    )
+}
+
+/// 当前阶段的消息正文与按钮
+fn render(engine: &GameEngine) -> (String, Vec<CreateActionRow>) {
+    let mut lines = Vec::new();
+    if !engine.result_message.is_empty() {
+        lines.push(engine.result_message.to_string());
+        lines.push(String::new());
+    }
+    let mut buttons = Vec::new();
+    match engine.phase {
+        GamePhase::Start => {}
+        GamePhase::EventDisplay | GamePhase::WeeklyEventDisplay => {
+            lines.push(status(engine));
+            if can_choose(engine) {
+                if let Some((title, desc, options, _)) = engine.current_event_metadata() {
+                    lines.push(String::new());
+                    lines.push(format!("**{}**", title));
+                    lines.push(desc);
+                    for (idx, option) in options.iter().enumerate() {
+                        let gray = if option.gray_risk > 0 { " ⚠灰色操作" } else { "" };
+                        lines.push(format!("**[{}] {}**{}", idx + 1, option.title(), gray));
+                        lines.extend(option.summary().lines().map(|line| format!("> {}", line)));
+                        let hinted = engine.hinted_option == Some(idx);
+                        let label = format!("{}{}. {}", if hinted { "🧙 " } else { "" }, idx + 1, option.title());
+                        let style = if hinted { ButtonStyle::Success } else { ButtonStyle::Primary };
+                        buttons.push(button(&format!("choose{}", idx + 1), &label, style));
+                    }
+                }
+                buttons.push(button("hint", "师傅提示", ButtonStyle::Secondary));
+            } else if engine.weekly_event_pending() {
+                buttons.push(button("weekly", "打开周事件", ButtonStyle::Primary));
+            } else if engine.can_advance() {
+                buttons.push(button("next", "进入下一天", ButtonStyle::Primary));
+            }
+        }
+        GamePhase::PromotionConfirm => {
+            buttons.push(button("promote", "晋升", ButtonStyle::Success));
+            buttons.push(button("decline", "暂缓", ButtonStyle::Secondary));
+        }
+        GamePhase::NightCall => {
+            if let Some(call) = engine.game_state.as_ref().and_then(|g| g.night_call.as_ref()) {
+                lines.push(format!("🌙 夜深了……📞 {} 来电：{}", call.caller, call.plea));
+                lines.push(format!("**[接]** {}", call.accept.summary));
+                lines.push(format!("**[不接]** {}", call.reject.summary));
+            }
+            buttons.push(button("answer", "接", ButtonStyle::Primary));
+            buttons.push(button("hangup", "不接", ButtonStyle::Secondary));
+        }
+        GamePhase::WeeklyRecap => {
+            if let Some(recap) = &engine.weekly_recap {
+                let (grade, comment) = recap.grade();
+                lines.push(format!(
+                    "📅 第{}周周报（第{}~{}天）评级 {}：{}",
+                    recap.week, recap.first_day, recap.last_day, grade, comment
+                ));
+                lines.push(format!(
+                    "技能点 {:+} · 压力值 {:+} · 同事请求同意 {} 次、拒绝 {} 次",
+                    recap.skill_change, recap.pressure_change, recap.npc_accepted, recap.npc_rejected
+                ));
+                if let Some(best) = &recap.best {
+                    lines.push(format!("👍 最佳选择：{}", best));
+                }
+                if let Some(worst) = &recap.worst {
+                    lines.push(format!("👎 最差选择：{}", worst));
+                }
+            }
+            buttons.push(button("recap", "开始新的一周", ButtonStyle::Primary));
+        }
+        GamePhase::GameOver => {
+            if let Some(state) = &engine.game_state {
+                lines.push(format!("```\n{}\n```", ending::card(state)));
+            }
+            buttons.push(button("rematch", "同种子重来", ButtonStyle::Primary));
+            buttons.push(button("new", "新开一局", ButtonStyle::Secondary));
+        }
+    }
+    let rows = if buttons.is_empty() { Vec::new() } else { vec![CreateActionRow::Buttons(buttons)] };
+    (truncate(&lines.join("\n"), MESSAGE_LIMIT), rows)
+}
+
+#[tokio::main]
+async fn main() {
+    let Ok(token) = std::env::var("DISCORD_TOKEN") else {
+        eprintln!("请先设置环境变量 DISCORD_TOKEN");
+        std::process::exit(2);
+    };
+    let intents = GatewayIntents::GUILD_MESSAGES | GatewayIntents::DIRECT_MESSAGES | GatewayIntents::MESSAGE_CONTENT;
+    let mut client = match Client::builder(&token, intents).event_handler(Handler::default()).await {
+        Ok(client) => client,
+        Err(err) => {
+            eprintln!("创建 Discord 客户端失败：{}", err);
+            std::process::exit(1);
+        }
+    };
+    if let Err(err) = client.start().await {
+        eprintln!("Discord 连接中断：{}", err);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,47 @@
+//! 训练接口示例：随机代理。每一步从当前可用的操作里随便挑一个，打若干局后报告平均回报，
+//! 可以当作训练自己代理时的基线，也演示了 `gym::Env` 的 reset/step/action_mask 用法。
+//!
+//! 用法：`cargo run --bin gym-random --features gym -- [局数] [起始种子]`
+
+use rand::rngs::StdRng;
+use rand::seq::IteratorRandom;
+use rand::SeedableRng;
+
+use xiuxian_core::balance::BalanceConfig;
+use xiuxian_core::gym::{Env, OBSERVATION_SIZE};
+
+/// 默认打几局
+const DEFAULT_EPISODES: u32 = 20;
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let episodes = args.next().and_then(|arg| arg.parse().ok()).unwrap_or(DEFAULT_EPISODES);
+    let first_seed: u64 = args.next().and_then(|arg| arg.parse().ok()).unwrap_or(0);
+
+    // 代理自己的随机数也用种子固定，同样的参数每次跑出同样的结果
+    let mut rng = StdRng::seed_from_u64(first_seed);
+    let mut env = Env::new(BalanceConfig::default());
+    let mut total_return = 0.0;
+    println!("观测维度 {}，共 {} 局", OBSERVATION_SIZE, episodes);
+    for episode in 0..u64::from(episodes) {
+        let seed = first_seed.wrapping_add(episode);
+        env.reset(seed);
+        let (mut episode_return, mut steps) = (0.0, 0);
+        loop {
+            let mask = env.action_mask();
+            let Some(action) = (0..mask.len()).filter(|&idx| mask[idx]).choose(&mut rng) else {
+                break;
+            };
+            let step = env.step(action);
+            episode_return += step.reward;
+            steps += 1;
+            if step.done || step.truncated {
+                break;
+            }
+        }
+        let days = env.engine().game_state.as_ref().map_or(0, |state| state.player.days_played);
+        println!("种子 {:>6}  回报 {:>8.1}  步数 {:>5}  天数 {:>4}", seed, episode_return, steps, days);
+        total_return += episode_return;
+    }
+    println!("平均回报 {:.1}", total_return / episodes.max(1) as f32);
+}
@@ -0,0 +1,115 @@
+//! 挑战链接：把一局的开局配置（种子、数值预设、难度、压力模式、飞升等级）和得分编进网页版的地址
+//! （结算页画成二维码），朋友用手机扫码打开网页版，开始界面就按同样的配置填好，还能看到要超过的分数。
+//! 转世加成跟着各人自己的进度走，不写进链接。
+
+use xiuxian_core::balance::{BalancePreset, Difficulty, PressureModel};
+use xiuxian_core::game::GameState;
+
+/// 网页版地址
+pub const WEB_URL: &str = "https://runbinlin.github.io/jbn_daily/";
+
+/// 一条挑战
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Challenge {
+    pub seed: u64,
+    pub preset: BalancePreset,
+    pub difficulty: Difficulty,
+    pub pressure_model: PressureModel,
+    pub ascension: u32,
+    pub score: u32,
+}
+
+/// 枚举值在 `ALL` 里的序号
+fn index_of<T: PartialEq>(all: &[T], value: &T) -> usize {
+    all.iter().position(|v| v == value).unwrap_or_default()
+}
+
+impl Challenge {
+    /// 按一局的开局配置和最终得分出挑战
+    pub fn of(state: &GameState) -> Challenge {
+        let balance = &state.player.balance;
+        Challenge {
+            seed: state.seed,
+            preset: balance.preset,
+            difficulty: balance.difficulty,
+            pressure_model: balance.pressure_model,
+            ascension: state.ascension,
+            score: state.score(),
+        }
+    }
+
+    /// 挑战链接，如 `…/jbn_daily/?seed=42&preset=0&mode=1&pressure=0&asc=2&score=860`
+    /// （preset、mode、pressure 为预设、难度、压力模式在各自 `ALL` 里的序号）
+    pub fn link(&self) -> String {
+        format!(
+            "{}?seed={}&preset={}&mode={}&pressure={}&asc={}&score={}",
+            WEB_URL,
+            self.seed,
+            index_of(&BalancePreset::ALL, &self.preset),
+            index_of(&Difficulty::ALL, &self.difficulty),
+            index_of(&PressureModel::ALL, &self.pressure_model),
+            self.ascension,
+            self.score
+        )
+    }
+
+    /// 从网址的查询串（`?seed=…`，问号可有可无）读出挑战；没有种子时返回 None，其余缺了用默认值
+    #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+    pub fn from_query(query: &str) -> Option<Challenge> {
+        fn pick<T: Copy>(all: &[T], value: &str) -> Option<T> {
+            value.parse().ok().and_then(|i: usize| all.get(i)).copied()
+        }
+        let mut seed = None;
+        let mut challenge = Challenge {
+            seed: 0,
+            preset: BalancePreset::标准,
+            difficulty: Difficulty::default(),
+            pressure_model: PressureModel::default(),
+            ascension: 0,
+            score: 0,
+        };
+        for pair in query.trim_start_matches('?').split('&') {
+            let Some((key, value)) = pair.split_once('=') else {
+                continue;
+            };
+            match key {
+                "seed" => seed = value.parse().ok(),
+                "preset" => challenge.preset = pick(&BalancePreset::ALL, value).unwrap_or(challenge.preset),
+                "mode" => challenge.difficulty = pick(&Difficulty::ALL, value).unwrap_or(challenge.difficulty),
+                "pressure" => {
+                    challenge.pressure_model = pick(&PressureModel::ALL, value).unwrap_or(challenge.pressure_model)
+                }
+                "asc" => challenge.ascension = value.parse().unwrap_or_default(),
+                "score" => challenge.score = value.parse().unwrap_or_default(),
+                _ => {}
+            }
+        }
+        challenge.seed = seed?;
+        Some(challenge)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn link_round_trips() {
+        let challenge = Challenge {
+            seed: 996,
+            preset: BalancePreset::轻量,
+            difficulty: Difficulty::地狱996,
+            pressure_model: PressureModel::惯性,
+            ascension: 3,
+            score: 1234,
+        };
+        let link = challenge.link();
+        let query = link.split_once('?').unwrap().1;
+        assert_eq!(Challenge::from_query(query), Some(challenge));
+        assert_eq!(Challenge::from_query("?mode=2"), None);
+
+        // 旧链接只有种子、难度和得分，其余按默认配置
+        let old = Challenge::from_query("?seed=42&mode=2&score=860").unwrap();
+        assert_eq!((old.preset, old.pressure_model, old.ascension), (BalancePreset::标准, PressureModel::即时, 0));
+    }
+}
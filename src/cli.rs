@@ -0,0 +1,58 @@
+//! 桌面端命令行参数：直接以指定种子、档案、模式启动，方便测试、速通和展台机器
+
+use std::path::PathBuf;
+
+use clap::{Parser, ValueEnum};
+
+use crate::daily;
+
+/// 启动模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LaunchMode {
+    /// 每日挑战：同一天所有人用同一个种子
+    Daily,
+}
+
+#[derive(Debug, Default, Parser)]
+#[command(name = "xiuxian_game", version, about = "修仙编程游戏")]
+pub struct LaunchArgs {
+    /// 本次启动开的每一局都使用这个随机种子
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// 直接进入指定档案，跳过档案选择
+    #[arg(long, value_name = "PROFILE")]
+    pub profile: Option<String>,
+    /// 启动后接着玩档案里第 SLOT 个存档槽位（从 1 数）
+    #[arg(long, value_name = "SLOT")]
+    pub load: Option<usize>,
+    /// 启动模式（daily：每日挑战种子；与 --seed 同时给出时以 --seed 为准）
+    #[arg(long, value_enum)]
+    pub mode: Option<LaunchMode>,
+    /// 全屏启动
+    #[arg(long)]
+    pub fullscreen: bool,
+    /// 额外载入剧本包的目录（可重复）
+    #[arg(long, value_name = "DIR")]
+    pub content_dir: Vec<PathBuf>,
+    /// 在终端里用纯文本游玩，不启动图形界面
+    #[arg(long)]
+    pub cli: bool,
+    /// 以 JSON HTTP 接口对外提供引擎，不启动图形界面（默认监听 127.0.0.1:7878）
+    #[arg(long, value_name = "ADDR", num_args = 0..=1, default_missing_value = crate::server::DEFAULT_ADDR)]
+    pub serve: Option<String>,
+    /// 让内置代理在同一批种子上各打 RUNS 局，打印排行榜后退出（--seed 指定第一个种子）
+    #[arg(long, value_name = "RUNS")]
+    pub arena: Option<u32>,
+    /// 核验赛事成绩码后退出，不启动界面
+    #[arg(long, num_args = 2, value_names = ["KEY", "TOKEN"])]
+    pub verify: Option<Vec<String>>,
+}
+
+impl LaunchArgs {
+    /// 本次启动固定的种子：显式种子优先，其次是当天的每日挑战种子
+    pub fn seed(&self) -> Option<u64> {
+        self.seed.or_else(|| match self.mode? {
+            LaunchMode::Daily => Some(daily::today_seed()),
+        })
+    }
+}
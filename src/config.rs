@@ -0,0 +1,102 @@
+//! 进阶配置（桌面端）：数据目录下可选的 config.toml，放界面里没有的选项
+//! （自定义字体、额外的剧本包目录、更新检查地址、帧率上限）。
+//! 启动时读取并逐项校验；有问题的字段会被忽略并说明原因，其余照常生效。
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+/// 配置文件名（放在数据目录根下）
+pub const CONFIG_FILE: &str = "config.toml";
+
+const FPS_RANGE: std::ops::RangeInclusive<u32> = 10..=480;
+
+/// config.toml 的内容；所有字段都可省略
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AdvancedConfig {
+    /// 优先使用的中文字体文件
+    pub font_path: Option<PathBuf>,
+    /// 除数据目录下 scenarios 外，额外载入剧本包的目录
+    pub content_dirs: Vec<PathBuf>,
+    /// 检查更新用的发布信息接口
+    pub update_endpoint: Option<String>,
+    /// 每秒最多绘制多少帧
+    pub fps_cap: Option<u32>,
+}
+
+impl AdvancedConfig {
+    /// 读取数据目录下的 config.toml；文件不存在时返回默认配置，
+    /// 第二个返回值是给玩家看的错误说明（为空表示一切正常）
+    pub fn load(root: &Path) -> (Self, Vec<String>) {
+        let path = root.join(CONFIG_FILE);
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return (Self::default(), Vec::new()),
+            Err(err) => return (Self::default(), vec![format!("无法读取 {}: {}", path.display(), err)]),
+        };
+        match toml::from_str::<AdvancedConfig>(&text) {
+            Ok(mut config) => {
+                let errors = config.validate();
+                let errors = errors.into_iter().map(|e| format!("{} {}", CONFIG_FILE, e)).collect();
+                (config, errors)
+            }
+            Err(err) => (
+                Self::default(),
+                vec![format!("{} 格式有误，已全部忽略：{}", path.display(), err.message())],
+            ),
+        }
+    }
+
+    /// 追加命令行 `--content-dir` 给出的目录，返回无效目录的说明
+    pub fn add_content_dirs(&mut self, dirs: Vec<PathBuf>) -> Vec<String> {
+        let mut errors = Vec::new();
+        for dir in dirs {
+            if dir.is_dir() {
+                self.content_dirs.push(dir);
+            } else {
+                errors.push(format!("--content-dir：{} 不是目录", dir.display()));
+            }
+        }
+        errors
+    }
+
+    /// 逐项校验，去掉无效的设置并返回原因
+    fn validate(&mut self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if let Some(font) = self.font_path.take() {
+            if font.is_file() {
+                self.font_path = Some(font);
+            } else {
+                errors.push(format!("font_path：找不到字体文件 {}", font.display()));
+            }
+        }
+        self.content_dirs.retain(|dir| {
+            let ok = dir.is_dir();
+            if !ok {
+                errors.push(format!("content_dirs：{} 不是目录", dir.display()));
+            }
+            ok
+        });
+        if let Some(endpoint) = self.update_endpoint.take() {
+            if endpoint.starts_with("https://") || endpoint.starts_with("http://") {
+                self.update_endpoint = Some(endpoint);
+            } else {
+                errors.push(format!("update_endpoint：{} 不是 http(s) 地址", endpoint));
+            }
+        }
+        if let Some(fps) = self.fps_cap.take() {
+            if FPS_RANGE.contains(&fps) {
+                self.fps_cap = Some(fps);
+            } else {
+                errors.push(format!(
+                    "fps_cap：{} 超出范围（{}~{}）",
+                    fps,
+                    FPS_RANGE.start(),
+                    FPS_RANGE.end()
+                ));
+            }
+        }
+        errors
+    }
+}
@@ -0,0 +1,39 @@
+//! “关于”页面的数据：随程序打包的鸣谢名单，以及构建时（build.rs）从 Cargo.lock 整理出的第三方库许可证
+
+/// 鸣谢名单（Markdown）
+pub const CREDITS: &str = include_str!("../CREDITS.md");
+
+/// build.rs 生成的许可证清单，每行“名称\t版本\t许可证”
+const LICENSES: &str = include_str!(concat!(env!("OUT_DIR"), "/licenses.txt"));
+
+/// 一个第三方库
+pub struct Dependency {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub license: &'static str,
+}
+
+/// 参与构建的第三方库
+pub fn dependencies() -> impl Iterator<Item = Dependency> {
+    LICENSES.lines().filter_map(|line| {
+        let mut fields = line.split('\t');
+        Some(Dependency {
+            name: fields.next()?,
+            version: fields.next()?,
+            license: fields.next()?,
+        })
+    })
+}
+
+/// 各许可证下的库数量（多的在前）
+pub fn license_summary() -> Vec<(&'static str, usize)> {
+    let mut summary: Vec<(&'static str, usize)> = Vec::new();
+    for dependency in dependencies() {
+        match summary.iter_mut().find(|(license, _)| *license == dependency.license) {
+            Some((_, count)) => *count += 1,
+            None => summary.push((dependency.license, 1)),
+        }
+    }
+    summary.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    summary
+}
@@ -0,0 +1,16 @@
+//! 每日挑战：同一天（UTC）所有人用同一个种子开局
+
+use chrono::Datelike;
+
+/// 每日挑战种子的扰动值（避免和手动输入的小种子撞车）
+const DAILY_SALT: u64 = 0x5EED_DA11_0C0F_FEE5;
+
+/// 今天的日期（UTC），如 "2024-05-01"，用来记录哪天打过
+pub fn today() -> String {
+    chrono::Utc::now().date_naive().format("%Y-%m-%d").to_string()
+}
+
+/// 今天的每日挑战种子
+pub fn today_seed() -> u64 {
+    chrono::Utc::now().date_naive().num_days_from_ce() as u64 ^ DAILY_SALT
+}
@@ -0,0 +1,210 @@
+//! 数据目录（桌面端）：导出的历史、高光 GIF 等文件统一放在这里，
+//! 不再散落在启动时的工作目录。首次运行会把旧位置的文件迁移过来；目标位置已有同名文件时不覆盖，留在原处报给玩家。
+
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+/// 早期版本直接写在工作目录里的文件名前缀
+const LEGACY_PREFIXES: [&str; 2] = ["xiuxian_highlight_", "xiuxian_history_"];
+
+/// 一次搬移的结果
+#[derive(Debug, Default)]
+pub struct Migration {
+    pub moved: usize,
+    /// 目标位置已有同名文件、留在原处没搬的文件名
+    pub skipped: Vec<String>,
+}
+
+impl Migration {
+    /// 给玩家看的冲突说明（没有冲突时为 None）
+    pub fn conflicts(&self) -> Option<String> {
+        (!self.skipped.is_empty()).then(|| {
+            format!("{} 个文件目标位置已有同名文件，没有覆盖，留在原处：{}", self.skipped.len(), self.skipped.join("、"))
+        })
+    }
+}
+
+/// 玩家数据所在的目录
+#[derive(Debug, Clone)]
+pub struct DataDir {
+    root: PathBuf,
+}
+
+impl DataDir {
+    /// 系统约定的默认位置（如 Linux 下 ~/.local/share/xiuxian_game）
+    pub fn default_root() -> Option<PathBuf> {
+        directories::ProjectDirs::from("io.github", "runbinlin", "xiuxian_game")
+            .map(|dirs| dirs.data_dir().to_path_buf())
+    }
+
+    /// 使用玩家自定义目录；未设置时用默认位置，实在取不到就退回工作目录
+    pub fn resolve(custom: Option<&Path>) -> Self {
+        let root = custom
+            .map(Path::to_path_buf)
+            .or_else(Self::default_root)
+            .unwrap_or_else(|| PathBuf::from("."));
+        DataDir { root }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// 导出文件目录（不存在时创建）
+    pub fn exports(&self) -> Result<PathBuf, String> {
+        let dir = self.root.join("exports");
+        std::fs::create_dir_all(&dir).map_err(|e| format!("无法创建数据目录: {}", e))?;
+        Ok(dir)
+    }
+
+    /// 玩家自制剧本包目录（不存在时创建，方便玩家找到放哪里）
+    pub fn scenarios(&self) -> Result<PathBuf, String> {
+        let dir = self.root.join("scenarios");
+        std::fs::create_dir_all(&dir).map_err(|e| format!("无法创建剧本目录: {}", e))?;
+        Ok(dir)
+    }
+
+    /// 自制事件目录：同格式的 JSON 按 id 覆盖或新增日常/周事件（不存在时创建）
+    pub fn events(&self) -> Result<PathBuf, String> {
+        let dir = self.root.join("events");
+        std::fs::create_dir_all(&dir).map_err(|e| format!("无法创建事件目录: {}", e))?;
+        Ok(dir)
+    }
+
+    /// 自定义图片目录：头像、事件插图（不存在时创建，方便玩家找到放哪里）
+    pub fn assets(&self) -> Result<PathBuf, String> {
+        let dir = self.root.join("assets");
+        std::fs::create_dir_all(&dir).map_err(|e| format!("无法创建图片目录: {}", e))?;
+        Ok(dir)
+    }
+
+    /// 某个档案第 `slot` 个存档槽位的文件（从 0 数，文件名从 1 数；saves/ 目录不存在时创建）
+    pub fn save_slot_file(&self, profile: &str, slot: usize) -> Result<PathBuf, String> {
+        let dir = self.root.join("saves");
+        std::fs::create_dir_all(&dir).map_err(|e| format!("无法创建存档目录: {}", e))?;
+        Ok(dir.join(format!("{}.slot{}.json", save_stem(profile), slot + 1)))
+    }
+
+    /// 旧版本每个档案只有一个续玩存档文件，读到时搬进槽位
+    pub fn legacy_save_file(&self, profile: &str) -> PathBuf {
+        self.root.join("saves").join(format!("{}.json", save_stem(profile)))
+    }
+
+    /// 把工作目录里旧版本留下的导出文件搬进数据目录
+    pub fn migrate_legacy(&self) -> Result<Migration, String> {
+        let cwd = std::env::current_dir().map_err(|e| e.to_string())?;
+        move_files(&cwd, &self.exports()?, |name| {
+            LEGACY_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+        })
+    }
+
+    /// 更换数据目录时，把旧目录里的导出文件和续玩存档一并搬过去
+    pub fn migrate_from(&self, old: &DataDir) -> Result<Migration, String> {
+        let mut total = Migration::default();
+        for sub in ["exports", "saves"] {
+            let from = old.root.join(sub);
+            let to = self.root.join(sub);
+            if !from.is_dir() || from == to {
+                continue;
+            }
+            std::fs::create_dir_all(&to).map_err(|e| e.to_string())?;
+            let migration = move_files(&from, &to, |_| true)?;
+            total.moved += migration.moved;
+            total.skipped.extend(migration.skipped.into_iter().map(|name| format!("{}/{}", sub, name)));
+        }
+        Ok(total)
+    }
+
+    /// 用系统文件管理器打开数据目录
+    pub fn open_in_file_manager(&self) -> Result<(), String> {
+        std::fs::create_dir_all(&self.root).map_err(|e| e.to_string())?;
+        let program = if cfg!(target_os = "windows") {
+            "explorer"
+        } else if cfg!(target_os = "macos") {
+            "open"
+        } else {
+            "xdg-open"
+        };
+        std::process::Command::new(program)
+            .arg(&self.root)
+            .spawn()
+            .map(|_| ())
+            .map_err(|e| format!("无法打开文件管理器: {}", e))
+    }
+}
+
+/// 档案名可能带路径分隔符之类的字符，换成下划线再当文件名；换过字符的再接上档案名的哈希，
+/// 免得“a b”和“a/b”落到同一个文件。没换过的保持原样（旧存档照常读到），其中不会出现“.”，两类不会撞名
+fn save_stem(profile: &str) -> String {
+    let stem: String = profile
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' { c } else { '_' })
+        .collect();
+    if stem == profile {
+        return stem;
+    }
+    let digest = Sha256::digest(profile.as_bytes());
+    let hash: String = digest[..8].iter().map(|b| format!("{:02x}", b)).collect();
+    format!("{}.{}", stem, hash)
+}
+
+/// 移动 `from` 下（不含子目录）名字满足条件的文件到 `to`；`to` 里已有同名文件的不覆盖，记为跳过
+fn move_files(from: &Path, to: &Path, keep: impl Fn(&str) -> bool) -> Result<Migration, String> {
+    let entries = std::fs::read_dir(from).map_err(|e| e.to_string())?;
+    let mut migration = Migration::default();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if !path.is_file() || !keep(name) {
+            continue;
+        }
+        let target = to.join(name);
+        if target.symlink_metadata().is_ok() {
+            migration.skipped.push(name.to_string());
+            continue;
+        }
+        // 跨分区时 rename 会失败，退回复制后删除
+        if std::fs::rename(&path, &target).is_err() {
+            std::fs::copy(&path, &target).map_err(|e| e.to_string())?;
+            std::fs::remove_file(&path).map_err(|e| e.to_string())?;
+        }
+        migration.moved += 1;
+    }
+    Ok(migration)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_stems_do_not_collide() {
+        assert_eq!(save_stem("default"), "default");
+        assert_eq!(save_stem("凌霄_2"), "凌霄_2");
+        let stems = ["a b", "a/b", "a_b", "a.b"].map(save_stem);
+        for (i, stem) in stems.iter().enumerate() {
+            assert!(stems[i + 1..].iter().all(|other| other != stem), "{:?}", stems);
+            assert!(!stem.contains('/'));
+        }
+    }
+
+    #[test]
+    fn moving_files_keeps_existing_targets() {
+        let root = std::env::temp_dir().join(format!("xiuxian_datadir_{}", std::process::id()));
+        let (from, to) = (root.join("from"), root.join("to"));
+        std::fs::create_dir_all(&from).unwrap();
+        std::fs::create_dir_all(&to).unwrap();
+        std::fs::write(from.join("a.json"), "旧").unwrap();
+        std::fs::write(from.join("b.json"), "旧").unwrap();
+        std::fs::write(to.join("a.json"), "新").unwrap();
+
+        let migration = move_files(&from, &to, |_| true).unwrap();
+        assert_eq!((migration.moved, migration.skipped), (1, vec!["a.json".to_string()]));
+        assert_eq!(std::fs::read_to_string(to.join("a.json")).unwrap(), "新");
+        assert!(from.join("a.json").exists() && to.join("b.json").exists());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
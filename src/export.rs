@@ -0,0 +1,418 @@
+//! 历史记录导出：修仙日志（Markdown，适合发给同事看乐子）、CSV（做表格分析）、
+//! JSON（附在 bug 报告里，含属性快照与结局），以及无障碍纯文本（给读屏软件、短信和墨水屏：
+//! 没有表情符号和符号化的排版，数字写成汉字，全部由结构化的历史记录重新生成，不照搬界面上的文字）
+
+use serde::Serialize;
+
+use crate::ending;
+use crate::game::{DailySnapshot, GameState, HistoryEntry, HistoryKind};
+use crate::theme;
+
+/// 导出文件名前缀
+const FILE_PREFIX: &str = "xiuxian_history";
+
+/// 导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Markdown,
+    Csv,
+    Json,
+    PlainText,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 4] =
+        [ExportFormat::Markdown, ExportFormat::Csv, ExportFormat::Json, ExportFormat::PlainText];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "修仙日志",
+            ExportFormat::Csv => "CSV",
+            ExportFormat::Json => "JSON",
+            ExportFormat::PlainText => "无障碍纯文本",
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::PlainText => "txt",
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn mime(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "text/markdown",
+            ExportFormat::Csv => "text/csv",
+            ExportFormat::Json => "application/json",
+            ExportFormat::PlainText => "text/plain",
+        }
+    }
+
+    /// 默认文件名（带时间戳）
+    pub fn file_name(&self) -> String {
+        format!("{}_{}.{}", FILE_PREFIX, chrono::Local::now().format("%Y%m%d_%H%M%S"), self.extension())
+    }
+
+    /// 把对局序列化为文本；`finished` 为真时附上结局
+    pub fn render(&self, state: &GameState, finished: bool) -> Result<String, String> {
+        match self {
+            ExportFormat::Markdown => Ok(journal_markdown(state, finished)),
+            ExportFormat::Csv => Ok(history_csv(&state.player.history)),
+            ExportFormat::Json => serde_json::to_string_pretty(&Journal::new(state, finished))
+                .map_err(|e| format!("JSON 序列化失败: {}", e)),
+            ExportFormat::PlainText => Ok(plain_text(state, finished)),
+        }
+    }
+
+    /// 弹出保存对话框写入玩家选的位置，返回文件路径；玩家取消时返回 None（仅桌面端）
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save_as(
+        &self,
+        state: &GameState,
+        finished: bool,
+        dir: &std::path::Path,
+    ) -> Result<Option<std::path::PathBuf>, String> {
+        let contents = self.render(state, finished)?;
+        let Some(path) = rfd::FileDialog::new()
+            .set_directory(dir)
+            .set_file_name(self.file_name())
+            .add_filter(self.label(), &[self.extension()])
+            .save_file()
+        else {
+            return Ok(None);
+        };
+        std::fs::write(&path, contents).map_err(|e| format!("无法写入文件: {}", e))?;
+        Ok(Some(path))
+    }
+
+    /// 让浏览器把导出内容当作文件下载（仅网页端）
+    #[cfg(target_arch = "wasm32")]
+    pub fn download(&self, state: &GameState, finished: bool) -> Result<String, String> {
+        let contents = self.render(state, finished)?;
+        let file_name = self.file_name();
+        download(&file_name, self.mime(), &contents)?;
+        Ok(file_name)
+    }
+}
+
+/// 把文本写入 `dir/<前缀>_<时间戳>.<扩展名>`，返回文件路径（仅桌面端）
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_text(
+    dir: &std::path::Path,
+    prefix: &str,
+    extension: &str,
+    contents: &str,
+) -> Result<std::path::PathBuf, String> {
+    let path = dir.join(format!(
+        "{}_{}.{}",
+        prefix,
+        chrono::Local::now().format("%Y%m%d_%H%M%S"),
+        extension
+    ));
+    std::fs::write(&path, contents).map_err(|e| format!("无法写入文件: {}", e))?;
+    Ok(path)
+}
+
+/// JSON 导出的完整对局记录
+#[derive(Serialize)]
+struct Journal<'a> {
+    name: &'a str,
+    seed: u64,
+    days: u32,
+    realm: String,
+    score: u32,
+    play_seconds: u64,
+    history: &'a [HistoryEntry],
+    snapshots: &'a [DailySnapshot],
+    ending: Option<String>,
+}
+
+impl<'a> Journal<'a> {
+    fn new(state: &'a GameState, finished: bool) -> Self {
+        let player = &state.player;
+        Journal {
+            name: &player.name,
+            seed: state.seed,
+            days: player.days_played,
+            realm: player.get_realm().to_string(),
+            score: state.score(),
+            play_seconds: state.get_elapsed_seconds(),
+            history: &player.history,
+            snapshots: &player.snapshots,
+            ending: finished.then(|| ending::card(state).to_string()),
+        }
+    }
+}
+
+/// 生成 Markdown 修仙日志：按天列出事件、选择与奖励，每天末尾附属性快照，最后是结局
+fn journal_markdown(state: &GameState, finished: bool) -> String {
+    let player = &state.player;
+    let mut lines = vec![
+        format!("# {} 的修仙日志", player.name),
+        String::new(),
+        format!(
+            "种子 `{}` · 共 {} 天 · {} · 得分 {} · 游玩时间 {}",
+            state.seed,
+            player.days_played,
+            player.get_realm(),
+            state.score(),
+            state.format_time()
+        ),
+    ];
+    let mut day = 0;
+    for entry in &player.history {
+        if entry.day != day {
+            day_footer(&mut lines, &player.snapshots, day);
+            day = entry.day;
+            lines.push(String::new());
+            lines.push(format!("## 第 {} 天", day));
+            lines.push(String::new());
+        }
+        let prefix = match entry.kind {
+            HistoryKind::日常 => "",
+            HistoryKind::周事件 => "【周事件】",
+            HistoryKind::Npc => "【NPC】",
+            HistoryKind::项目 => "【项目】",
+            HistoryKind::晋升 => "【晋升】",
+        };
+        lines.push(format!(
+            "- {}**{}**：{}（技能 {:+}，压力 {:+}）",
+            prefix, entry.event_name, entry.choice, entry.skill_delta, entry.pressure_delta
+        ));
+        lines.extend(entry.story.lines().filter(|line| !line.is_empty()).map(|line| format!("  > {}", line)));
+    }
+    day_footer(&mut lines, &player.snapshots, day);
+    if finished {
+        lines.push(String::new());
+        lines.push("## 结局".to_string());
+        lines.push(String::new());
+        lines.push("```".to_string());
+        lines.push(ending::card(state).to_string());
+        lines.push("```".to_string());
+    }
+    lines.join("\n") + "\n"
+}
+
+/// 当天的属性快照（那天还没结束时没有快照）
+fn day_footer(lines: &mut Vec<String>, snapshots: &[DailySnapshot], day: u32) {
+    if let Some(snapshot) = snapshots.iter().find(|snapshot| snapshot.day == day) {
+        lines.push(format!(
+            "- 📊 当日收工：技能点 {} · 压力 {} · 经验 {}",
+            snapshot.skills, snapshot.pressure, snapshot.experience
+        ));
+    }
+}
+
+/// 生成无障碍纯文本：一行一句，去掉表情符号，数字写成汉字
+fn plain_text(state: &GameState, finished: bool) -> String {
+    let player = &state.player;
+    let plain = |text: &str| theme::strip_emoji(text, false).trim().to_string();
+    let seconds = state.get_elapsed_seconds() as i64;
+    let mut lines = vec![
+        format!("{}的修仙日志，纯文本版。", plain(&player.name)),
+        format!(
+            "随机种子{}。共{}天。境界：{}。得分：{}。游玩时间{}分{}秒。",
+            state.seed,
+            chinese_number(i64::from(player.days_played)),
+            plain(&player.get_realm().to_string()),
+            chinese_number(i64::from(state.score())),
+            chinese_number(seconds / 60),
+            chinese_number(seconds % 60)
+        ),
+    ];
+    let mut day = 0;
+    for entry in &player.history {
+        if entry.day != day {
+            plain_day_footer(&mut lines, &player.snapshots, day);
+            day = entry.day;
+            lines.push(String::new());
+            lines.push(format!("第{}天。", chinese_number(i64::from(day))));
+        }
+        let kind = match entry.kind {
+            HistoryKind::日常 => "日常事件",
+            HistoryKind::周事件 => "周事件",
+            HistoryKind::Npc => "同事请求",
+            HistoryKind::项目 => "项目",
+            HistoryKind::晋升 => "晋升",
+        };
+        lines.push(format!(
+            "{}：{}。选择：{}。技能点{}，压力{}。",
+            kind,
+            plain(&entry.event_name),
+            plain(&entry.choice).replace('\n', "，"),
+            change(entry.skill_delta),
+            change(entry.pressure_delta)
+        ));
+        let story: Vec<String> = entry.story.lines().map(plain).filter(|line| !line.is_empty()).collect();
+        if !story.is_empty() {
+            lines.push(format!("剧情：{}", story.join(" ")));
+        }
+    }
+    plain_day_footer(&mut lines, &player.snapshots, day);
+    if finished {
+        let card = ending::card(state);
+        lines.push(String::new());
+        lines.push(format!("结局：{}。{}", card.ending.title(), plain(&card.description)));
+        lines.push(format!(
+            "第{}天结束，境界{}，技能点{}，压力{}，存款{}元。",
+            chinese_number(i64::from(card.days)),
+            plain(&card.realm.to_string()),
+            chinese_number(i64::from(card.skills)),
+            chinese_number(i64::from(card.pressure)),
+            chinese_number(i64::from(card.savings))
+        ));
+    }
+    lines.join("\n") + "\n"
+}
+
+/// 纯文本版的当天收工快照
+fn plain_day_footer(lines: &mut Vec<String>, snapshots: &[DailySnapshot], day: u32) {
+    if let Some(snapshot) = snapshots.iter().find(|snapshot| snapshot.day == day) {
+        lines.push(format!(
+            "当日收工：技能点{}，压力{}，经验{}。",
+            chinese_number(i64::from(snapshot.skills)),
+            chinese_number(i64::from(snapshot.pressure)),
+            chinese_number(i64::from(snapshot.experience))
+        ));
+    }
+}
+
+/// 数值变化的说法，如“增加五”“减少十二”“不变”
+fn change(delta: i32) -> String {
+    match delta {
+        0 => "不变".to_string(),
+        d if d > 0 => format!("增加{}", chinese_number(i64::from(d))),
+        d => format!("减少{}", chinese_number(-i64::from(d))),
+    }
+}
+
+/// 把整数写成汉字，如 105 → 一百零五，-12 → 负十二，20010 → 二万零一十
+fn chinese_number(n: i64) -> String {
+    const DIGITS: [char; 10] = ['零', '一', '二', '三', '四', '五', '六', '七', '八', '九'];
+    const UNITS: [&str; 4] = ["", "十", "百", "千"];
+    const SECTIONS: [&str; 5] = ["", "万", "亿", "万亿", "亿亿"];
+    if n == 0 {
+        return "零".to_string();
+    }
+    // 四位一节：节内的零只读一次，末尾的零不读
+    let section = |value: u64| {
+        let mut text = String::new();
+        let mut zero = false;
+        for pos in (0..4).rev() {
+            let digit = (value / 10u64.pow(pos as u32) % 10) as usize;
+            if digit == 0 {
+                zero = !text.is_empty();
+            } else {
+                if zero {
+                    text.push('零');
+                    zero = false;
+                }
+                text.push(DIGITS[digit]);
+                text.push_str(UNITS[pos]);
+            }
+        }
+        text
+    };
+    let mut sections = Vec::new();
+    let mut rest = n.unsigned_abs();
+    while rest > 0 {
+        sections.push(rest % 10_000);
+        rest /= 10_000;
+    }
+    let mut text = String::new();
+    let mut gap = false;
+    for (index, &value) in sections.iter().enumerate().rev() {
+        if value == 0 {
+            gap = !text.is_empty();
+            continue;
+        }
+        if !text.is_empty() && (gap || value < 1000) {
+            text.push('零');
+        }
+        text.push_str(&section(value));
+        text.push_str(SECTIONS[index]);
+        gap = false;
+    }
+    // 一十几读作十几
+    if let Some(rest) = text.strip_prefix("一十") {
+        text = format!("十{}", rest);
+    }
+    if n < 0 {
+        format!("负{}", text)
+    } else {
+        text
+    }
+}
+
+const CSV_HEADER: &str =
+    "day,kind,event_id,event_name,choice,option_index,archetype,skill_delta,pressure_delta,story";
+
+/// 生成 CSV（含表头，字段按 RFC 4180 转义）
+fn history_csv(history: &[HistoryEntry]) -> String {
+    let mut lines = vec![CSV_HEADER.to_string()];
+    for entry in history {
+        let fields = [
+            entry.day.to_string(),
+            format!("{:?}", entry.kind),
+            entry.event_id.map(|id| id.to_string()).unwrap_or_default(),
+            entry.event_name.clone(),
+            entry.choice.clone(),
+            entry.option_index.map(|i| i.to_string()).unwrap_or_default(),
+            entry.archetype.map(|a| a.to_string()).unwrap_or_default(),
+            entry.skill_delta.to_string(),
+            entry.pressure_delta.to_string(),
+            entry.story.clone(),
+        ];
+        let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        lines.push(row.join(","));
+    }
+    lines.join("\n") + "\n"
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// 用 Blob 和临时链接触发浏览器下载
+#[cfg(target_arch = "wasm32")]
+fn download(file_name: &str, mime: &str, contents: &str) -> Result<(), String> {
+    use wasm_bindgen::{JsCast, JsValue};
+
+    let describe = |err: JsValue| err.as_string().unwrap_or_else(|| format!("{:?}", err));
+    let document = web_sys::window().and_then(|w| w.document()).ok_or("no document")?;
+    let parts = js_sys::Array::of1(&JsValue::from_str(contents));
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type(&format!("{};charset=utf-8", mime));
+    let blob = web_sys::Blob::new_with_str_sequence_and_options(&parts, &options).map_err(describe)?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob).map_err(describe)?;
+    let link: web_sys::HtmlAnchorElement =
+        document.create_element("a").map_err(describe)?.dyn_into().map_err(|_| "无法创建下载链接")?;
+    link.set_href(&url);
+    link.set_download(file_name);
+    link.click();
+    web_sys::Url::revoke_object_url(&url).map_err(describe)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_are_written_out() {
+        let cases = [(0, "零"), (10, "十"), (12, "十二"), (105, "一百零五"), (1010, "一千零一十"), (-35, "负三十五")];
+        for (n, text) in cases {
+            assert_eq!(chinese_number(n), text);
+        }
+        assert_eq!(chinese_number(20_010), "二万零一十");
+        assert_eq!(chinese_number(100_001_000), "一亿零一千");
+        assert_eq!(change(-3), "减少三");
+    }
+}
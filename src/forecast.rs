@@ -0,0 +1,93 @@
+//! 晋升确认页的推演预览：在后台把“现在晋升”和“缓几天再晋升”各推演若干遍（见 `whatif`），
+//! 边跑边把汇总交给界面。桌面端在单独的线程上跑；网页端没有线程，每帧只跑一小段。
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+#[cfg(target_arch = "wasm32")]
+use std::time::Duration;
+
+#[cfg(target_arch = "wasm32")]
+use instant::Instant;
+
+use crate::game::GameState;
+use crate::whatif::{self, Forecast, Plan};
+
+/// 网页端每帧最多占用的推演时间
+#[cfg(target_arch = "wasm32")]
+const FRAME_SLICE: Duration = Duration::from_millis(6);
+
+/// 一次推演预览（对应某一天的晋升确认）
+pub struct PromotionPreview {
+    pub day: u32,
+    forecasts: [Forecast; 2],  // 按 `Plan::ALL` 的顺序
+    done: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    receiver: Receiver<(usize, whatif::Rollout)>,
+    #[cfg(target_arch = "wasm32")]
+    state: Box<GameState>,
+    #[cfg(target_arch = "wasm32")]
+    next: u32,  // 下一遍推演的序号（两种打算交替进行）
+}
+
+impl PromotionPreview {
+    /// 在后台线程上开始推演，两种打算交替进行，结果陆续从通道送回（预览丢掉后线程随之停下）
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn start(state: &GameState) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let snapshot = state.clone();
+        std::thread::spawn(move || {
+            for index in 0..whatif::ROLLOUTS {
+                for (slot, plan) in Plan::ALL.into_iter().enumerate() {
+                    if sender.send((slot, whatif::rollout(&snapshot, plan, index))).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+        Self { day: state.current_day, forecasts: Default::default(), done: false, receiver }
+    }
+
+    /// 开始推演；每帧调用 `poll` 时跑一小段
+    #[cfg(target_arch = "wasm32")]
+    pub fn start(state: &GameState) -> Self {
+        Self {
+            day: state.current_day,
+            forecasts: Default::default(),
+            done: false,
+            state: Box::new(state.clone()),
+            next: 0,
+        }
+    }
+
+    /// 收下已完成的推演，返回是否还在跑（界面每帧调用）
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn poll(&mut self) -> bool {
+        while !self.done {
+            match self.receiver.try_recv() {
+                Ok((slot, rollout)) => self.forecasts[slot].add(rollout),
+                Err(TryRecvError::Empty) => return true,
+                Err(TryRecvError::Disconnected) => self.done = true,
+            }
+        }
+        false
+    }
+
+    /// 跑这一帧的一小段，返回是否还在跑（界面每帧调用）
+    #[cfg(target_arch = "wasm32")]
+    pub fn poll(&mut self) -> bool {
+        let total = whatif::ROLLOUTS * Plan::ALL.len() as u32;
+        let slice_start = Instant::now();
+        while self.next < total && slice_start.elapsed() < FRAME_SLICE {
+            let slot = (self.next % 2) as usize;
+            self.forecasts[slot].add(whatif::rollout(&self.state, Plan::ALL[slot], self.next / 2));
+            self.next += 1;
+        }
+        self.done = self.next >= total;
+        !self.done
+    }
+
+    /// 两种打算的汇总：(打算, 汇总)
+    pub fn forecasts(&self) -> impl Iterator<Item = (Plan, &Forecast)> {
+        Plan::ALL.into_iter().zip(&self.forecasts)
+    }
+}
@@ -0,0 +1,41 @@
+/// 检测是否连接了手柄（用于自动开启大屏模式）
+///
+/// 桌面端只在 Linux 上通过 /dev/input/js* 检测（Steam Deck 等），
+/// 网页端通过 navigator.getGamepads()。其他平台返回 false，需要手动开启。
+pub fn gamepad_connected() -> bool {
+    platform::gamepad_connected()
+}
+
+#[cfg(all(target_os = "linux", not(target_arch = "wasm32")))]
+mod platform {
+    pub fn gamepad_connected() -> bool {
+        std::fs::read_dir("/dev/input")
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .any(|entry| entry.file_name().to_string_lossy().starts_with("js"))
+            })
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod platform {
+    pub fn gamepad_connected() -> bool {
+        let Some(window) = web_sys::window() else {
+            return false;
+        };
+        window
+            .navigator()
+            .get_gamepads()
+            .map(|pads| pads.iter().any(|pad| !pad.is_null() && !pad.is_undefined()))
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_arch = "wasm32")))]
+mod platform {
+    pub fn gamepad_connected() -> bool {
+        false
+    }
+}
@@ -0,0 +1,77 @@
+//! 幽灵对比：归档每个预设下最好的一局，按天数与当前这局对照
+
+use serde::{Deserialize, Serialize};
+
+use crate::balance::BalancePreset;
+use crate::game::{DailySnapshot, PlayerState, Realm};
+
+/// eframe 存储中保存归档对局的键（按档案加命名空间）
+pub const GHOST_RUNS_KEY: &str = "xiuxian_ghost_runs";
+
+/// 归档的一局：最终成绩 + 每日快照
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedRun {
+    pub preset: BalancePreset,
+    pub realm_level: u32,
+    pub days: u32,
+    pub snapshots: Vec<DailySnapshot>,
+}
+
+impl ArchivedRun {
+    pub fn from_player(player: &PlayerState) -> Self {
+        ArchivedRun {
+            preset: player.balance.preset,
+            realm_level: player.realm_level,
+            days: player.days_played,
+            snapshots: player.snapshots.clone(),
+        }
+    }
+
+    /// 境界更高者更好；同境界时活得更久者更好
+    fn beats(&self, other: &ArchivedRun) -> bool {
+        (self.realm_level, self.days) > (other.realm_level, other.days)
+    }
+
+    /// 某一天结束时的快照
+    pub fn snapshot_on(&self, day: u32) -> Option<&DailySnapshot> {
+        self.snapshots.iter().find(|s| s.day == day)
+    }
+
+    /// 对照提示，如“你上次第20天已是筑基期（技能点 160）”
+    pub fn compare_line(&self, day: u32) -> Option<String> {
+        let snapshot = self.snapshot_on(day)?;
+        let realm = Realm::from_experience(
+            snapshot.experience,
+            &self.preset.config().realm_thresholds,
+        );
+        Some(format!(
+            "👻 你上次第{}天已是{}（技能点 {}，压力 {}）",
+            day, realm, snapshot.skills, snapshot.pressure
+        ))
+    }
+}
+
+/// 各预设下的最佳归档对局
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GhostRuns {
+    runs: Vec<ArchivedRun>,
+}
+
+impl GhostRuns {
+    pub fn best_for(&self, preset: BalancePreset) -> Option<&ArchivedRun> {
+        self.runs.iter().find(|run| run.preset == preset)
+    }
+
+    /// 若这局比已归档的更好就替换（空局不归档）
+    pub fn record(&mut self, run: ArchivedRun) {
+        if run.snapshots.is_empty() {
+            return;
+        }
+        match self.runs.iter_mut().find(|r| r.preset == run.preset) {
+            Some(best) if run.beats(best) => *best = run,
+            Some(_) => {}
+            None => self.runs.push(run),
+        }
+    }
+}
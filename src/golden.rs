@@ -0,0 +1,153 @@
+//! 剧情文本的金样测试：固定种子、按固定脚本做选择，逐字比对产生的结果文案与历史记录。
+//! 文案或格式有意改动时，用 `UPDATE_GOLDEN=1 cargo test golden` 重新生成金样文件并一起提交。
+
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use crate::balance::BalancePreset;
+use crate::director::Director;
+use crate::engine::{GameCommand, GameEngine, GamePhase};
+use crate::export::ExportFormat;
+use crate::game::NpcDecision;
+use crate::specialty::CareerPath;
+use crate::tone::NarrativeTone;
+
+/// 脚本最多推进多少步
+const MAX_STEPS: usize = 120;
+
+fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden").join(name)
+}
+
+/// 比对金样；设置了 UPDATE_GOLDEN 时改为写入
+fn assert_golden(name: &str, actual: &str) {
+    let path = golden_path(name);
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("缺少金样 {}，用 UPDATE_GOLDEN=1 生成", path.display()));
+    if expected != actual {
+        let line = expected
+            .lines()
+            .zip(actual.lines())
+            .position(|(e, a)| e != a)
+            .unwrap_or_else(|| expected.lines().count().min(actual.lines().count()));
+        panic!(
+            "{} 与金样不一致（第 {} 行起）：\n期望：{:?}\n实际：{:?}",
+            name,
+            line + 1,
+            expected.lines().nth(line),
+            actual.lines().nth(line)
+        );
+    }
+}
+
+/// 按固定脚本玩一局：日常选项按天数轮换，周事件选第一项，能晋升就晋升，深夜来电都接；
+/// 返回对局和逐步的文案记录
+fn play_script(seed: u64, tone: NarrativeTone) -> (GameEngine, String) {
+    let mut app = GameEngine::new();
+    app.player_name = "金样道友".to_string();
+    app.start_game(
+        Vec::new(),
+        BalancePreset::标准.config(),
+        tone,
+        Director::default(),
+        CareerPath::default(),
+        Vec::new(),
+        Some(seed),
+    )
+    .expect("名号合规");
+
+    let mut transcript = String::new();
+    for step in 0..MAX_STEPS {
+        let (phase, chosen, day) = {
+            let game = app.game_state.as_ref().expect("已开局");
+            (app.phase, game.event_chosen_today, game.current_day)
+        };
+        let action = match phase {
+            GamePhase::GameOver => break,
+            GamePhase::EventDisplay if !chosen => {
+                let choice = (day % 3) as u8 + 1;
+                app.execute(GameCommand::Choose(choice));
+                format!("选择 {}", choice)
+            }
+            GamePhase::EventDisplay if app.weekly_event_pending() => {
+                app.execute(GameCommand::OpenWeeklyEvent);
+                "打开周事件".to_string()
+            }
+            GamePhase::WeeklyEventDisplay => {
+                app.execute(GameCommand::Choose(1));
+                "周事件选择 1".to_string()
+            }
+            GamePhase::PromotionConfirm if app.game_state.as_ref().is_some_and(|g| g.player.can_promote()) => {
+                app.execute(GameCommand::Promote);
+                "晋升".to_string()
+            }
+            GamePhase::NightCall => {
+                app.execute(GameCommand::AnswerNightCall(NpcDecision::Accept));
+                "接听来电".to_string()
+            }
+            GamePhase::WeeklyRecap => {
+                app.execute(GameCommand::CloseRecap);
+                "看完周报".to_string()
+            }
+            _ => {
+                app.execute(GameCommand::NextDay);
+                "下一天".to_string()
+            }
+        };
+        writeln!(transcript, "## 第 {} 步 · 第 {} 天 · {}", step + 1, day, action).unwrap();
+        // 结束画面带着真实游玩时间，不参与比对
+        let message = app.result_message.to_string();
+        for line in message.lines().filter(|line| !line.contains("游玩时间")) {
+            writeln!(transcript, "{}", line).unwrap();
+        }
+    }
+
+    (app, transcript)
+}
+
+/// 脚本对局的文案记录加上结构化的历史记录
+fn scripted_run(seed: u64, tone: NarrativeTone) -> String {
+    let (app, mut transcript) = play_script(seed, tone);
+    let game = app.game_state.as_ref().expect("已开局");
+    writeln!(transcript, "## 历史").unwrap();
+    for entry in &game.player.history {
+        writeln!(
+            transcript,
+            "第{}天 [{:?}] {} → {}（技能 {:+}，压力 {:+}）",
+            entry.day, entry.kind, entry.event_name, entry.choice, entry.skill_delta, entry.pressure_delta
+        )
+        .unwrap();
+        for line in entry.story.lines() {
+            writeln!(transcript, "    {}", line).unwrap();
+        }
+    }
+    transcript
+}
+
+#[test]
+fn standard_run_matches_golden() {
+    assert_golden("standard_seed_42.txt", &scripted_run(42, NarrativeTone::沙雕));
+}
+
+#[test]
+fn realistic_tone_matches_golden() {
+    assert_golden("realistic_seed_42.txt", &scripted_run(42, NarrativeTone::写实));
+}
+
+#[test]
+fn journal_export_matches_golden() {
+    let (app, _) = play_script(42, NarrativeTone::沙雕);
+    let game = app.game_state.as_ref().expect("已开局");
+    let journal = ExportFormat::Markdown.render(game, app.phase == GamePhase::GameOver).unwrap();
+    assert_golden("journal_seed_42.md", &journal);
+}
+
+#[test]
+fn scripted_run_is_deterministic() {
+    assert_eq!(scripted_run(7, NarrativeTone::沙雕), scripted_run(7, NarrativeTone::沙雕));
+}
@@ -0,0 +1,115 @@
+//! 高光时刻录制（桌面端，需启用 `highlight-capture` 特性）
+//!
+//! 开启后按固定间隔截屏，环形缓冲只保留最近几秒；晋升成功或猝死时冻结缓冲，
+//! 玩家可以在结果画面把这几秒导出为 GIF（保存在数据目录下）。
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use eframe::egui::{self, ColorImage};
+
+/// 截屏间隔（秒）
+const FRAME_INTERVAL: f64 = 0.2;
+/// 最多保留的帧数（约 3 秒）
+const MAX_FRAMES: usize = 15;
+/// 导出 GIF 的最大宽度
+const MAX_WIDTH: usize = 480;
+
+pub struct HighlightRecorder {
+    frames: VecDeque<Arc<ColorImage>>,
+    last_request: f64,
+    captured: Option<Vec<Arc<ColorImage>>>,
+}
+
+impl HighlightRecorder {
+    pub fn new() -> Self {
+        Self {
+            frames: VecDeque::new(),
+            last_request: f64::NEG_INFINITY,
+            captured: None,
+        }
+    }
+
+    /// 每帧调用：收集上一帧的截屏结果，并按间隔请求新的截屏
+    pub fn tick(&mut self, ctx: &egui::Context) {
+        let screenshots: Vec<Arc<ColorImage>> = ctx.input(|i| {
+            i.raw
+                .events
+                .iter()
+                .filter_map(|event| match event {
+                    egui::Event::Screenshot { image, .. } => Some(image.clone()),
+                    _ => None,
+                })
+                .collect()
+        });
+        for image in screenshots {
+            if self.frames.len() == MAX_FRAMES {
+                self.frames.pop_front();
+            }
+            self.frames.push_back(image);
+        }
+
+        let now = ctx.input(|i| i.time);
+        if now - self.last_request >= FRAME_INTERVAL {
+            self.last_request = now;
+            ctx.send_viewport_cmd(egui::ViewportCommand::Screenshot);
+        }
+        ctx.request_repaint_after(std::time::Duration::from_secs_f64(FRAME_INTERVAL));
+    }
+
+    /// 冻结最近几秒的画面，等待玩家保存
+    pub fn freeze(&mut self) {
+        if !self.frames.is_empty() {
+            self.captured = Some(self.frames.iter().cloned().collect());
+        }
+    }
+
+    pub fn has_capture(&self) -> bool {
+        self.captured.is_some()
+    }
+
+    pub fn clear_capture(&mut self) {
+        self.captured = None;
+    }
+
+    /// 把冻结的画面编码为 GIF 写入指定目录
+    pub fn save_gif(&self, dir: &Path) -> Result<PathBuf, String> {
+        let frames = self.captured.as_ref().ok_or("没有可保存的高光时刻")?;
+        let first = frames.first().ok_or("没有可保存的高光时刻")?;
+        let step = first.size[0].div_ceil(MAX_WIDTH).max(1);
+        let width = first.size[0] / step;
+        let height = first.size[1] / step;
+
+        let path = dir.join(format!(
+            "xiuxian_highlight_{}.gif",
+            chrono::Local::now().format("%Y%m%d_%H%M%S")
+        ));
+        let file = std::fs::File::create(&path).map_err(|e| format!("无法创建文件: {}", e))?;
+        let mut encoder = gif::Encoder::new(file, width as u16, height as u16, &[])
+            .map_err(|e| format!("GIF 编码失败: {}", e))?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(|e| format!("GIF 编码失败: {}", e))?;
+
+        for image in frames {
+            // 窗口大小中途变化的帧直接跳过
+            if image.size[0] / step != width || image.size[1] / step != height {
+                continue;
+            }
+            let mut rgba = Vec::with_capacity(width * height * 4);
+            for y in 0..height {
+                for x in 0..width {
+                    let pixel = image.pixels[y * step * image.size[0] + x * step];
+                    rgba.extend_from_slice(&pixel.to_array());
+                }
+            }
+            let mut frame = gif::Frame::from_rgba_speed(width as u16, height as u16, &mut rgba, 10);
+            frame.delay = (FRAME_INTERVAL * 100.0) as u16;
+            encoder
+                .write_frame(&frame)
+                .map_err(|e| format!("GIF 编码失败: {}", e))?;
+        }
+        Ok(path)
+    }
+}
@@ -0,0 +1,37 @@
+//! 开发时热重载调参表（debug 特性，桌面端）：每秒看一眼仓库里 content/weights.json 的修改时间，
+//! 改过就重新读入换上，之后抽到的事件按新权重来，调平衡不用重新编译。
+
+use std::time::SystemTime;
+
+use xiuxian_core::tuning::{self, Tuning};
+
+/// 仓库里的调参表（编译进程序的也是这份）
+const WEIGHTS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/content/weights.json");
+
+/// 检查间隔（秒）
+const POLL_SECS: f64 = 1.0;
+
+#[derive(Default)]
+pub struct TuningWatch {
+    modified: Option<SystemTime>,
+    last_poll: f64,
+}
+
+impl TuningWatch {
+    /// 到点检查一次；文件改过就重新读入，返回读入的结果（第一次检查只记下修改时间）
+    pub fn poll(&mut self, now: f64) -> Option<Result<(), String>> {
+        if now - self.last_poll < POLL_SECS {
+            return None;
+        }
+        self.last_poll = now;
+        let modified = std::fs::metadata(WEIGHTS_PATH).and_then(|meta| meta.modified()).ok()?;
+        if self.modified.replace(modified).is_none_or(|previous| previous == modified) {
+            return None;
+        }
+        let reloaded = std::fs::read_to_string(WEIGHTS_PATH)
+            .map_err(|e| format!("读取调参表失败: {}", e))
+            .and_then(|text| Tuning::parse(&text))
+            .map(tuning::install);
+        Some(reloaded)
+    }
+}
@@ -0,0 +1,112 @@
+//! 状态检查器（debug 特性）：每次引擎推进后把 GameState 序列化成快照，
+//! 对比最近两次快照，列出变化的字段和数值差，用来追查属性漂移之类的问题。
+
+use serde_json::Value;
+
+/// 值太长时截断显示
+const MAX_VALUE_CHARS: usize = 80;
+
+/// 一个发生变化的字段
+#[derive(Debug, Clone)]
+pub struct FieldChange {
+    pub path: String,         // 如 player.pressure、player.history[3]
+    pub before: String,       // 之前的值（新增字段为 “—”）
+    pub after: String,        // 之后的值（被删掉的字段为 “—”）
+    pub delta: Option<f64>,   // 两边都是数字时的差值
+}
+
+/// 最近两次快照及其差异
+#[derive(Debug, Default)]
+pub struct StateInspector {
+    previous: Option<(String, Value)>,
+    latest: Option<(String, Value)>,
+    changes: Vec<FieldChange>,
+}
+
+impl StateInspector {
+    /// 记录一次推进后的快照（`label` 说明是哪些引擎事件），并与上一次比较
+    pub fn record(&mut self, label: String, snapshot: Value) {
+        self.previous = self.latest.replace((label, snapshot));
+        self.changes.clear();
+        if let (Some((_, before)), Some((_, after))) = (&self.previous, &self.latest) {
+            diff("", before, after, &mut self.changes);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// 参与比较的两次推进：(之前, 之后)
+    pub fn labels(&self) -> Option<(&str, &str)> {
+        Some((self.previous.as_ref()?.0.as_str(), self.latest.as_ref()?.0.as_str()))
+    }
+
+    pub fn changes(&self) -> &[FieldChange] {
+        &self.changes
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+fn show(value: Option<&Value>) -> String {
+    let Some(value) = value else {
+        return "—".to_string();
+    };
+    let text = match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    if text.chars().count() > MAX_VALUE_CHARS {
+        format!("{}…", text.chars().take(MAX_VALUE_CHARS).collect::<String>())
+    } else {
+        text
+    }
+}
+
+/// 逐层比较两个快照，把叶子上的差异追加到 `out`
+fn diff(path: &str, before: &Value, after: &Value, out: &mut Vec<FieldChange>) {
+    match (before, after) {
+        (Value::Object(a), Value::Object(b)) => {
+            for (key, old) in a {
+                match b.get(key) {
+                    Some(new) => diff(&join(path, key), old, new, out),
+                    None => out.push(change(join(path, key), Some(old), None)),
+                }
+            }
+            for (key, new) in b.iter().filter(|(key, _)| !a.contains_key(*key)) {
+                out.push(change(join(path, key), None, Some(new)));
+            }
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            for i in 0..a.len().max(b.len()) {
+                let item = format!("{}[{}]", path, i);
+                match (a.get(i), b.get(i)) {
+                    (Some(old), Some(new)) => diff(&item, old, new, out),
+                    (old, new) => out.push(change(item, old, new)),
+                }
+            }
+        }
+        _ if before != after => out.push(change(path.to_string(), Some(before), Some(after))),
+        _ => {}
+    }
+}
+
+fn change(path: String, before: Option<&Value>, after: Option<&Value>) -> FieldChange {
+    let delta = match (before.and_then(Value::as_f64), after.and_then(Value::as_f64)) {
+        (Some(a), Some(b)) => Some(b - a),
+        _ => None,
+    };
+    FieldChange {
+        path,
+        before: show(before),
+        after: show(after),
+        delta,
+    }
+}
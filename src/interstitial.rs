@@ -0,0 +1,99 @@
+//! 过场：几页图文依次翻过，随时可以跳过（Esc 跳过，←/→、回车、空格翻页）。
+//! 序章（修仙编程世界的来历）就是一段过场，首次开局前播放，之后可以从设置里重看。
+
+use eframe::egui::{self, Key, RichText};
+
+/// 过场里的一页
+pub struct Page {
+    pub icon: &'static str,
+    pub title: &'static str,
+    pub body: &'static str,
+}
+
+/// 序章
+pub const INTRO: [Page; 4] = [
+    Page {
+        icon: "🌌",
+        title: "灵气复苏",
+        body: "末法时代的尽头，天地灵气不再藏于名山大川，而是顺着光纤流进了写字楼。\n\
+               从此修仙不必辟谷炼丹，只需一台工位、一块屏幕，和一颗写得动代码的道心。",
+    },
+    Page {
+        icon: "🏯",
+        title: "仙门大厂",
+        body: "昔日的宗门如今挂着公司的牌匾，长老成了领导，功法叫作技术栈，心魔叫作线上事故。\n\
+               每天都有新的需求、新的 bug 与新的同事找上门来，每周还有一场躲不过的大考。",
+    },
+    Page {
+        icon: "⬆",
+        title: "境界之路",
+        body: "从凡人境起步，攒够技能点便可冲击炼气、筑基、结丹、化神、大乘，直至飞升。\n\
+               晋升未必一次成功，失败了就再修一轮——屡败屡战，本就是修仙者的日常。",
+    },
+    Page {
+        icon: "💀",
+        title: "道心与压力",
+        body: "修行最大的敌人是压力：压得太狠会猝死在工位上，一点压力都没有也会在安逸中走火入魔。\n\
+               摸鱼、内卷还是甩锅，每一个选择都在改写你的命数。愿你从 996 一路修到飞升。",
+    },
+];
+
+/// 正在播放的一段过场
+pub struct Interstitial {
+    pages: &'static [Page],
+    index: usize,
+}
+
+impl Interstitial {
+    pub fn new(pages: &'static [Page]) -> Self {
+        Interstitial { pages, index: 0 }
+    }
+
+    /// 画当前页，返回这段过场是否结束（翻过最后一页或跳过）
+    pub fn show(&mut self, ui: &mut egui::Ui) -> bool {
+        let last = self.pages.len().saturating_sub(1);
+        let (skip, next, prev) = ui.input(|i| {
+            (
+                i.key_pressed(Key::Escape),
+                i.key_pressed(Key::ArrowRight) || i.key_pressed(Key::Enter) || i.key_pressed(Key::Space),
+                i.key_pressed(Key::ArrowLeft),
+            )
+        });
+        let Some(page) = self.pages.get(self.index) else {
+            return true;
+        };
+
+        let mut done = skip;
+        let mut forward = next;
+        let mut back = prev;
+        ui.vertical_centered(|ui| {
+            ui.add_space(24.0);
+            ui.label(RichText::new(page.icon).size(48.0));
+            ui.add_space(8.0);
+            ui.heading(page.title);
+            ui.add_space(12.0);
+            ui.set_max_width(480.0);
+            ui.label(page.body);
+            ui.add_space(16.0);
+            let dots: String = (0..self.pages.len()).map(|i| if i == self.index { '●' } else { '○' }).collect();
+            ui.weak(dots);
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                back |= ui.add_enabled(self.index > 0, egui::Button::new("◀ 上一页")).clicked();
+                let label = if self.index == last { "开始修仙 ▶" } else { "下一页 ▶" };
+                forward |= ui.button(label).clicked();
+                done |= ui.button("跳过").clicked();
+            });
+        });
+
+        if back {
+            self.index = self.index.saturating_sub(1);
+        } else if forward {
+            if self.index == last {
+                done = true;
+            }
+            self.index += 1;
+        }
+        done
+    }
+}
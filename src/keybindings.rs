@@ -0,0 +1,94 @@
+use eframe::egui::Key;
+use serde::{Deserialize, Serialize};
+
+/// 可绑定快捷键的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Action {
+    Choose1,
+    Choose2,
+    Choose3,
+    Advance,
+    UseHint,
+    ToggleStatsPanel,
+    ToggleSidePanel,
+}
+
+impl Action {
+    pub const ALL: [Action; 7] = [
+        Action::Choose1,
+        Action::Choose2,
+        Action::Choose3,
+        Action::Advance,
+        Action::UseHint,
+        Action::ToggleStatsPanel,
+        Action::ToggleSidePanel,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::Choose1 => "选择选项 1",
+            Action::Choose2 => "选择选项 2",
+            Action::Choose3 => "选择选项 3",
+            Action::Advance => "进入下一天",
+            Action::UseHint => "师傅提示",
+            Action::ToggleStatsPanel => "显示/隐藏状态栏",
+            Action::ToggleSidePanel => "显示/隐藏历史与NPC",
+        }
+    }
+
+    fn default_key(&self) -> Key {
+        match self {
+            Action::Choose1 => Key::Num1,
+            Action::Choose2 => Key::Num2,
+            Action::Choose3 => Key::Num3,
+            Action::Advance => Key::Space,
+            Action::UseHint => Key::H,
+            Action::ToggleStatsPanel => Key::S,
+            Action::ToggleSidePanel => Key::D,
+        }
+    }
+}
+
+/// 快捷键绑定表（每个操作一个按键，不允许重复）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBindings {
+    bindings: Vec<(Action, Key)>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        KeyBindings {
+            bindings: Action::ALL.iter().map(|a| (*a, a.default_key())).collect(),
+        }
+    }
+}
+
+impl KeyBindings {
+    pub fn key_for(&self, action: Action) -> Option<Key> {
+        self.bindings
+            .iter()
+            .find(|(a, _)| *a == action)
+            .map(|(_, key)| *key)
+    }
+
+    /// 重新绑定按键；若与其他操作冲突则返回冲突的操作，绑定保持不变
+    pub fn rebind(&mut self, action: Action, key: Key) -> Result<(), Action> {
+        if let Some((other, _)) = self
+            .bindings
+            .iter()
+            .find(|(a, k)| *k == key && *a != action)
+        {
+            return Err(*other);
+        }
+        match self.bindings.iter_mut().find(|(a, _)| *a == action) {
+            Some(binding) => binding.1 = key,
+            None => self.bindings.push((action, key)),
+        }
+        Ok(())
+    }
+
+    pub fn reset(&mut self) {
+        *self = KeyBindings::default();
+    }
+}
@@ -0,0 +1,204 @@
+//! AI 台词：NPC 找上门时，按玩家当前属性和最近的抉择，请 OpenAI 兼容接口现编一句台词，
+//! 替换模板里随机挑的那句。桌面端在后台线程里请求，网页端用 fetch，都不阻塞界面；
+//! 没开启、没填 key、断网或接口出错时什么也不做，继续用模板台词。
+
+#[cfg(target_arch = "wasm32")]
+use std::cell::RefCell;
+#[cfg(target_arch = "wasm32")]
+use std::rc::Rc;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::mpsc::{self, Receiver};
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::GameState;
+
+/// 最近几条抉择写进提示词
+const RECENT_CHOICES: usize = 3;
+
+/// 台词最长保留的字数（模型偶尔会写小作文）
+const MAX_LINE_CHARS: usize = 120;
+
+/// AI 台词的接口设置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LlmSettings {
+    pub enabled: bool,
+    pub endpoint: String,  // OpenAI 兼容的 chat/completions 地址
+    pub model: String,
+    pub api_key: String,
+}
+
+impl Default for LlmSettings {
+    fn default() -> Self {
+        LlmSettings {
+            enabled: false,
+            endpoint: "https://api.openai.com/v1/chat/completions".to_string(),
+            model: "gpt-4o-mini".to_string(),
+            api_key: String::new(),
+        }
+    }
+}
+
+impl LlmSettings {
+    /// 开启且填好了地址和 key
+    pub fn is_ready(&self) -> bool {
+        self.enabled && !self.endpoint.trim().is_empty() && !self.api_key.trim().is_empty()
+    }
+}
+
+/// 哪一局、哪一天、哪位 NPC 的请求（同一次请求只生成一次台词）
+pub type VoiceKey = (u64, u32, usize);
+
+/// 当前 NPC 请求对应的键；没有进行中的请求时为 None
+pub fn voice_key(state: &GameState) -> Option<VoiceKey> {
+    let active = state.npc_active_event.as_ref()?;
+    Some((state.seed, state.current_day, active.npc_index))
+}
+
+/// 根据 NPC 设定和玩家近况拼出 chat/completions 请求体
+fn request_body(settings: &LlmSettings, state: &GameState, index: usize) -> Option<String> {
+    let npc = state.today_npcs.get(index)?;
+    let player = &state.player;
+    let system = format!(
+        "你在一款职场修仙文字游戏里扮演同事「{}」：{}。你今天心情{}，对玩家的好感是 {}。\
+         请用一到两句口语化的中文台词，向玩家提出这个请求：{}。只输出台词本身，不要加引号和旁白。",
+        npc.name,
+        npc.description,
+        npc.mood,
+        state.relations.affinity(&npc.name),
+        npc.accept_option.summary
+    );
+    let recent: Vec<String> = player
+        .history
+        .iter()
+        .rev()
+        .filter(|entry| entry.kind.is_choice())
+        .take(RECENT_CHOICES)
+        .map(|entry| format!("第{}天在「{}」中选择了{}", entry.day, entry.event_name, entry.choice))
+        .collect();
+    let user = format!(
+        "玩家道号 {}，第{}天，境界 {}，技能点 {}，压力 {}。最近：{}",
+        player.name,
+        state.current_day,
+        player.get_realm(),
+        player.skills,
+        player.pressure,
+        if recent.is_empty() { "刚入职".to_string() } else { recent.join("；") }
+    );
+    let body = serde_json::json!({
+        "model": settings.model,
+        "messages": [
+            { "role": "system", "content": system },
+            { "role": "user", "content": user },
+        ],
+        "max_tokens": 120,
+        "temperature": 0.9,
+    });
+    Some(body.to_string())
+}
+
+/// 从接口返回里取出台词，去掉引号并截断
+fn parse_line(body: &str) -> Result<String, String> {
+    let json: serde_json::Value = serde_json::from_str(body).map_err(|e| e.to_string())?;
+    let content = json["choices"][0]["message"]["content"]
+        .as_str()
+        .ok_or("返回里没有台词")?;
+    let line: String = content
+        .trim()
+        .trim_matches(['"', '“', '”', '「', '」'])
+        .chars()
+        .take(MAX_LINE_CHARS)
+        .collect();
+    if line.is_empty() {
+        return Err("返回的台词是空的".to_string());
+    }
+    Ok(line)
+}
+
+/// 一次进行中的台词生成，界面每帧轮询
+pub struct NpcVoice {
+    pub key: VoiceKey,
+    #[cfg(not(target_arch = "wasm32"))]
+    receiver: Receiver<Result<String, String>>,
+    #[cfg(target_arch = "wasm32")]
+    result: Rc<RefCell<Option<Result<String, String>>>>,
+}
+
+impl NpcVoice {
+    /// 为当前的 NPC 请求开始生成台词；没有进行中的请求时返回 None
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn spawn(settings: &LlmSettings, state: &GameState) -> Option<Self> {
+        let key = voice_key(state)?;
+        let body = request_body(settings, state, key.2)?;
+        let (sender, receiver) = mpsc::channel();
+        let endpoint = settings.endpoint.trim().to_string();
+        let api_key = settings.api_key.trim().to_string();
+        std::thread::spawn(move || {
+            let _ = sender.send(post(&endpoint, &api_key, &body).and_then(|body| parse_line(&body)));
+        });
+        Some(NpcVoice { key, receiver })
+    }
+
+    /// 为当前的 NPC 请求开始生成台词；没有进行中的请求时返回 None
+    #[cfg(target_arch = "wasm32")]
+    pub fn spawn(settings: &LlmSettings, state: &GameState) -> Option<Self> {
+        let key = voice_key(state)?;
+        let body = request_body(settings, state, key.2)?;
+        let result = Rc::new(RefCell::new(None));
+        let slot = Rc::clone(&result);
+        let endpoint = settings.endpoint.trim().to_string();
+        let api_key = settings.api_key.trim().to_string();
+        wasm_bindgen_futures::spawn_local(async move {
+            let line = post(&endpoint, &api_key, &body).await.and_then(|body| parse_line(&body));
+            *slot.borrow_mut() = Some(line);
+        });
+        Some(NpcVoice { key, result })
+    }
+
+    /// 生成结果到达时返回 `Some(结果)`，尚未完成返回 `None`
+    pub fn poll(&self) -> Option<Result<String, String>> {
+        #[cfg(not(target_arch = "wasm32"))]
+        return self.receiver.try_recv().ok();
+        #[cfg(target_arch = "wasm32")]
+        return self.result.borrow_mut().take();
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn post(endpoint: &str, api_key: &str, body: &str) -> Result<String, String> {
+    ureq::post(endpoint)
+        .set("Authorization", &format!("Bearer {}", api_key))
+        .set("Content-Type", "application/json")
+        .timeout(std::time::Duration::from_secs(20))
+        .send_string(body)
+        .map_err(|e| e.to_string())?
+        .into_string()
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn post(endpoint: &str, api_key: &str, body: &str) -> Result<String, String> {
+    use wasm_bindgen::{JsCast, JsValue};
+    use wasm_bindgen_futures::JsFuture;
+
+    let describe = |err: JsValue| err.as_string().unwrap_or_else(|| format!("{:?}", err));
+    let window = web_sys::window().ok_or("no window")?;
+    let headers = web_sys::Headers::new().map_err(describe)?;
+    headers.set("Authorization", &format!("Bearer {}", api_key)).map_err(describe)?;
+    headers.set("Content-Type", "application/json").map_err(describe)?;
+    let init = web_sys::RequestInit::new();
+    init.set_method("POST");
+    init.set_headers(&headers);
+    init.set_body(&JsValue::from_str(body));
+    let response: web_sys::Response = JsFuture::from(window.fetch_with_str_and_init(endpoint, &init))
+        .await
+        .map_err(describe)?
+        .dyn_into()
+        .map_err(describe)?;
+    if !response.ok() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    let text = JsFuture::from(response.text().map_err(describe)?).await.map_err(describe)?;
+    text.as_string().ok_or_else(|| "返回不是文本".to_string())
+}
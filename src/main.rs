@@ -1,29 +1,152 @@
-mod game;
+mod challenge;
+#[cfg(not(target_arch = "wasm32"))]
+mod cli;
+#[cfg(not(target_arch = "wasm32"))]
+mod config;
+mod credits;
+#[cfg(not(target_arch = "wasm32"))]
+mod datadir;
+mod export;
+mod forecast;
+mod assets;
+mod audio;
+#[cfg(all(feature = "debug", not(target_arch = "wasm32")))]
+mod balance_ab;
+mod daily;
+mod gamepad;
+mod ghost;
+#[cfg(test)]
+mod golden;
+#[cfg(feature = "highlight-capture")]
+mod highlight;
+#[cfg(all(feature = "debug", not(target_arch = "wasm32")))]
+mod hotreload;
+#[cfg(feature = "debug")]
+mod inspector;
+mod interstitial;
+mod keybindings;
+mod llm;
+mod news;
+#[cfg(not(target_arch = "wasm32"))]
+mod notify;
+#[cfg(not(target_arch = "wasm32"))]
+mod overlay;
+mod profile;
+#[cfg(feature = "debug")]
+mod profiler;
+mod settings;
+mod saveslots;
+mod scenery;
+mod sharecode;
+#[cfg(not(target_arch = "wasm32"))]
+mod server;
+mod simulation;
+#[cfg(not(target_arch = "wasm32"))]
+mod terminal;
+mod updates;
+mod theme;
+mod tournament;
+mod widgets;
+#[cfg(target_arch = "wasm32")]
+mod webassets;
 
-use eframe::egui::{self, Color32, FontData, FontDefinitions, FontFamily, Key, Visuals};
+// 游戏逻辑在 xiuxian-core 库里（不依赖 egui），按原模块名引入，界面代码照旧用 `crate::game::…`
+use xiuxian_core::{
+    achievements, ascension, balance, bond, bus, commute, course, director, ending, engine, favor,
+    finance, game, gift, idle, items, message, name, office, onboarding, opensource, pickrate, policy, portfolio, rebirth, scenario, season, shuffle, specialty, speedrun, tag, tone, warning, week, whatif,
+};
+
+use eframe::egui::{self, FontData, FontDefinitions, FontFamily, Key};
+use assets::{AssetCache, AssetKey};
+use achievements::{Achievement, Achievements};
+use balance::{BalancePreset, Difficulty, PressureModel};
+use challenge::Challenge;
+use office::Location;
+use bus::{GameEvent, SessionStats};
 use eframe::{App, CreationContext, Frame};
+use export::ExportFormat;
+use interstitial::Interstitial;
 
-use game::{GameState, NpcDecision, OptionInfo};
+use course::Course;
+use opensource::EveningPlan;
+use portfolio::Instrument;
+use director::Director;
+use engine::{GameCommand, GameEngine, GamePhase};
+use game::{
+    ArchetypeTally, ChoiceArchetype, DailySnapshot, GameState, HistoryEntry, HistoryKind, NpcDecision,
+    NpcOption, OptionInfo, Realm,
+};
+use ghost::{ArchivedRun, GhostRuns};
+use pickrate::PickStats;
+use rebirth::{Boon, MetaProgress};
+use saveslots::{SaveManager, SLOT_COUNT};
+use scenario::{ScenarioPack, ScenarioScores};
+use tone::NarrativeTone;
+use warning::DarkContent;
+use tournament::TournamentResult;
+use items::Item;
+use keybindings::Action;
+use message::Style;
+use policy::AutoPolicy;
+use profile::Profiles;
+use season::{ContentPack, PackOverride};
+use shuffle::ShuffleMode;
+use speedrun::{BestSplits, SpeedrunRecord};
+use settings::{AppearancePrefs, LayoutMode, Settings};
+use specialty::{CareerPath, SkillTrack};
+use tag::EventTag;
+use week::Weekday;
+use theme::{Palette, PalettePreset};
 
-#[cfg(target_arch = "wasm32")]
-const EMBEDDED_FONT: &[u8] = include_bytes!("../web/fonts/NotoSansSC-Regular.ttf");
+/// 游戏名（窗口标题里对局信息后面跟着它）
+const APP_TITLE: &str = "修仙编程游戏";
+
+/// 窗口（或手机浏览器）宽度低于这个值时切换成窄屏布局
+const NARROW_WIDTH: f32 = 600.0;
+/// 窄屏或触屏时按钮的最小高度，手指好点中
+const TOUCH_TARGET: f32 = 44.0;
 
 // 桌面端入口
 #[cfg(not(target_arch = "wasm32"))]
 fn main() -> eframe::Result<()> {
+    use clap::Parser;
     use eframe::{NativeOptions, egui::ViewportBuilder};
+
+    let mut args = cli::LaunchArgs::parse();
+    // 命令行核验赛事成绩码，不启动界面
+    if let Some(verify) = args.verify.take() {
+        std::process::exit(tournament::run_verify_cli(&verify));
+    }
+    // 终端版：纯文本游玩，不启动界面
+    if args.cli {
+        std::process::exit(terminal::run(&args));
+    }
+    // 代打擂台：内置代理比一比，打印排行榜
+    if let Some(runs) = args.arena {
+        use xiuxian_core::arena::{self, Arena};
+        let arena = Arena::new(balance::BalanceConfig::default(), runs, args.seed().unwrap_or(1));
+        let standings = arena.run(&mut arena::builtin_agents());
+        println!("⚔ 代打擂台：{} 个代理 × {} 局\n", standings.len(), runs);
+        print!("{}", arena::Leaderboard(&standings));
+        std::process::exit(0);
+    }
+    // 服务器模式：用 JSON HTTP 接口对外提供引擎，不启动界面
+    if let Some(addr) = args.serve.take() {
+        std::process::exit(server::run(&args, &addr));
+    }
     
     let options = NativeOptions {
         viewport: ViewportBuilder::default()
-            .with_title("修仙编程游戏")
+            .with_title(APP_TITLE)
             .with_inner_size([800.0, 600.0])
-            .with_min_inner_size([400.0, 300.0]),
+            .with_min_inner_size([400.0, 300.0])
+            .with_fullscreen(args.fullscreen),
         ..Default::default()
     };
     eframe::run_native(
-        "修仙编程游戏",
+        APP_TITLE,
         options,
-        Box::new(|cc| Box::new(XiuxianApp::new(cc))),
+        Box::new(|cc| Box::new(XiuxianApp::new(cc, args))),
     )
 }
 
@@ -46,232 +169,3836 @@ fn main() {
     });
 }
 
-/// 配置中文字体
-#[allow(unused_mut)]
-fn setup_chinese_fonts(ctx: &egui::Context) {
+/// 把中文字体排到各字体族最前面
+fn install_chinese_font(ctx: &egui::Context, font_data: Vec<u8>) {
     let mut fonts = FontDefinitions::default();
+    fonts
+        .font_data
+        .insert("chinese_font".to_owned(), FontData::from_owned(font_data));
 
-    // Web 端：使用内嵌字体，确保中文正常显示
-    #[cfg(target_arch = "wasm32")]
-    {
-        fonts
-            .font_data
-            .insert("embedded_chinese".to_owned(), FontData::from_static(EMBEDDED_FONT));
+    fonts
+        .families
+        .entry(FontFamily::Proportional)
+        .or_default()
+        .insert(0, "chinese_font".to_owned());
 
-        fonts
-            .families
-            .entry(FontFamily::Proportional)
-            .or_default()
-            .insert(0, "embedded_chinese".to_owned());
+    fonts
+        .families
+        .entry(FontFamily::Monospace)
+        .or_default()
+        .insert(0, "chinese_font".to_owned());
 
-        fonts
-            .families
-            .entry(FontFamily::Monospace)
-            .or_default()
-            .insert(0, "embedded_chinese".to_owned());
-    }
+    ctx.set_fonts(fonts);
+}
 
-    // 桌面端：尝试加载系统中文字体
-    #[cfg(not(target_arch = "wasm32"))]
-    {
-        let font_paths = [
-            "/System/Library/Fonts/PingFang.ttc",
-            "/System/Library/Fonts/STHeiti Light.ttc",
-            "/System/Library/Fonts/Hiragino Sans GB.ttc",
-            "/Library/Fonts/Arial Unicode.ttf",
-            // Windows
-            "C:\\Windows\\Fonts\\msyh.ttc",
-            "C:\\Windows\\Fonts\\simhei.ttf",
-            // Linux
-            "/usr/share/fonts/truetype/noto/NotoSansCJK-Regular.ttc",
-        ];
-
-        for path in &font_paths {
-            if let Ok(font_data) = std::fs::read(path) {
-                fonts.font_data.insert(
-                    "chinese_font".to_owned(),
-                    FontData::from_owned(font_data),
-                );
-                
-                fonts
-                    .families
-                    .entry(FontFamily::Proportional)
-                    .or_default()
-                    .insert(0, "chinese_font".to_owned());
-                
-                fonts
-                    .families
-                    .entry(FontFamily::Monospace)
-                    .or_default()
-                    .insert(0, "chinese_font".to_owned());
-                
-                break;
+/// 配置中文字体：依次尝试玩家指定的字体（设置里的优先，其次 config.toml）和系统中文字体，
+/// 返回是否装上了中文字体（网页端的字体单独下载，见 webassets）
+#[cfg(not(target_arch = "wasm32"))]
+fn setup_chinese_fonts(ctx: &egui::Context, custom_fonts: &[Option<&std::path::Path>]) -> bool {
+    let font_paths = [
+        "/System/Library/Fonts/PingFang.ttc",
+        "/System/Library/Fonts/STHeiti Light.ttc",
+        "/System/Library/Fonts/Hiragino Sans GB.ttc",
+        "/Library/Fonts/Arial Unicode.ttf",
+        // Windows
+        "C:\\Windows\\Fonts\\msyh.ttc",
+        "C:\\Windows\\Fonts\\simhei.ttf",
+        // Linux
+        "/usr/share/fonts/truetype/noto/NotoSansCJK-Regular.ttc",
+    ];
+
+    let custom = custom_fonts.iter().flatten().map(|path| path.to_path_buf());
+    let system = font_paths.iter().map(std::path::PathBuf::from);
+    for path in custom.chain(system) {
+        match std::fs::read(&path) {
+            Ok(font_data) if is_font_file(&font_data) => {
+                install_chinese_font(ctx, font_data);
+                return true;
             }
+            _ => {}
         }
     }
+    false
+}
 
-    ctx.set_fonts(fonts);
+/// 文件头是否为 TrueType/OpenType 字体或字体集
+#[cfg(not(target_arch = "wasm32"))]
+fn is_font_file(data: &[u8]) -> bool {
+    matches!(data.get(..4), Some([0, 1, 0, 0] | b"OTTO" | b"ttcf" | b"true"))
+}
+
+/// 每个档案独立保存的数据
+struct ProfileData {
+    settings: Settings,
+    lifetime_tally: ArchetypeTally,
+    best_splits: BestSplits,
+    ghost_runs: GhostRuns,
+    scenario_scores: ScenarioScores,
+    pick_stats: PickStats,
+    achievements: Achievements,
+    meta_progress: MetaProgress,
+}
+
+/// 把文字编成二维码贴图（黑白，四周留 4 格白边，放大时不做平滑）
+fn qr_texture(ctx: &egui::Context, text: &str) -> Option<egui::TextureHandle> {
+    let code = qrcode::QrCode::with_error_correction_level(text, qrcode::EcLevel::M).ok()?;
+    let colors = code.to_colors();
+    let width = code.width();
+    const QUIET: usize = 4;
+    let side = width + QUIET * 2;
+    let pixels = (0..side * side)
+        .map(|i| {
+            let (x, y) = (i % side, i / side);
+            let inside = (QUIET..QUIET + width).contains(&x) && (QUIET..QUIET + width).contains(&y);
+            if inside && colors[(y - QUIET) * width + (x - QUIET)] == qrcode::Color::Dark {
+                egui::Color32::BLACK
+            } else {
+                egui::Color32::WHITE
+            }
+        })
+        .collect();
+    let image = egui::ColorImage { size: [side, side], pixels };
+    Some(ctx.load_texture("challenge_qr", image, egui::TextureOptions::NEAREST))
+}
+
+/// 读取某个档案的设置、生涯统计、速通最佳成绩、归档对局、挑战成绩与成就
+fn load_profile(storage: Option<&dyn eframe::Storage>, profile: &str) -> ProfileData {
+    fn load<T: serde::de::DeserializeOwned + Default>(
+        storage: Option<&dyn eframe::Storage>,
+        key: &str,
+    ) -> T {
+        storage
+            .and_then(|s| eframe::get_value(s, key))
+            .unwrap_or_default()
+    }
+    let key = |base: &str| Profiles::key_for(profile, base);
+    ProfileData {
+        settings: load(storage, &key(settings::STORAGE_KEY)),
+        lifetime_tally: load(storage, &key(settings::LIFETIME_STATS_KEY)),
+        best_splits: load(storage, &key(speedrun::BEST_SPLITS_KEY)),
+        ghost_runs: load(storage, &key(ghost::GHOST_RUNS_KEY)),
+        scenario_scores: load(storage, &key(scenario::SCENARIO_SCORES_KEY)),
+        pick_stats: load(storage, &key(pickrate::PICK_STATS_KEY)),
+        achievements: load(storage, &key(achievements::ACHIEVEMENTS_KEY)),
+        meta_progress: load(storage, &key(rebirth::META_PROGRESS_KEY)),
+    }
+}
+
+/// 数据目录下 assets/ 里的图片资源
+#[cfg(not(target_arch = "wasm32"))]
+fn asset_cache(data_dir: &datadir::DataDir) -> AssetCache {
+    AssetCache::new(data_dir.assets().unwrap_or_else(|_| data_dir.root().join("assets")))
+}
+
+/// 定位数据目录；首次运行时把旧版本写在工作目录里的导出文件搬进来
+#[cfg(not(target_arch = "wasm32"))]
+fn init_data_dir(settings: &mut Settings) -> (datadir::DataDir, String) {
+    let data_dir = datadir::DataDir::resolve(settings.data_dir.as_deref());
+    let mut notice = String::new();
+    if !settings.legacy_migrated {
+        match data_dir.migrate_legacy() {
+            Ok(migration) => {
+                let moved = (migration.moved > 0).then(|| format!("已把 {} 个旧文件迁移到数据目录", migration.moved));
+                notice = moved.into_iter().chain(migration.conflicts()).collect::<Vec<_>>().join("；");
+            }
+            Err(err) => notice = format!("迁移旧文件失败: {}", err),
+        }
+        settings.legacy_migrated = true;
+    }
+    (data_dir, notice)
 }
 
 struct XiuxianApp {
-    game: GameApp,
+    game: GameEngine,
+    settings: Settings,
+    profiles: Profiles,
+    profile_chosen: bool,             // 本次启动是否已在档案界面确认
+    new_profile_name: String,
+    profile_error: Option<String>,
+    show_settings: bool,
+    show_risk_calculator: bool,
+    auto_play: AutoPlay,
+    rebinding: Option<Action>,        // 正在等待新按键的操作
+    keybind_error: Option<String>,
+    gamepad_detected: bool,
+    last_gamepad_poll: f64,
+    applied_big_screen: bool,
+    applied_weekend: bool,            // 当前界面是否用的周末配色
+    window_title: String,             // 当前的窗口/页面标题
+    touch_detected: bool,             // 本次运行收到过触摸输入
+    ime_composing: bool,              // 输入法正在组词（这时的回车是给输入法的）
+    narrow: bool,                     // 本帧可用宽度不足，用窄屏布局
+    gameplay_tab: GameplayTab,        // 窄屏布局下当前查看的页签
+    pending_choice: Option<(u32, bool, usize)>,  // 两段确认中已选中的选项（天数、是否周事件、第几个）
+    last_idle_tick: f64,              // 挂机修炼上次记账的界面时间
+    lifetime_tally: ArchetypeTally,   // 之前各局累计的选择倾向（不含本局）
+    session_stats: SessionStats,      // 本次运行的统计（订阅引擎事件）
+    best_splits: BestSplits,          // 之前各局的速通最佳成绩（不含本局）
+    ghost_runs: GhostRuns,            // 之前各局中最好的一局（用于幽灵对比）
+    scenario_scores: ScenarioScores,  // 挑战剧本的最高分（单独记录）
+    pick_stats: PickStats,            // 跨局累计的选项选择率（代打和不打乱选项时的选择不计）
+    achievements: Achievements,       // 跨局解锁的成就
+    show_achievements: bool,
+    seed_input: String,               // 开始界面手填的种子（空为随机）
+    incoming_challenge: Option<Challenge>,  // 扫码打开网页版时带来的挑战（开始界面提示要超过的分数）
+    challenge_qr: Option<(String, egui::TextureHandle)>,  // 结算页挑战二维码（按链接缓存）
+    saves: SaveManager,               // 当前档案的存档槽位
+    active_slot: Option<usize>,       // 这一局存在哪个槽位
+    selected_slot: usize,             // 开始界面选中的槽位（新开的局存进这里）
+    save_notice: String,              // 读写存档失败的原因
+    meta_progress: MetaProgress,      // 转世攒下的仙缘点与兑换的永久加成
+    rebirth_notice: String,
+    scenario_packs: Vec<ScenarioPack>,
+    bannable_events: Vec<(usize, String)>,  // 开始界面可选禁用的日常事件
+    scenario_errors: Vec<String>,     // 载入自制剧本包时的错误
+    browsing_scenarios: bool,         // 开始界面切到挑战剧本列表
+    tournament_token: String,         // 本局生成的赛事成绩码
+    observer_tab: ObserverTab,        // 结束画面当前查看的页签
+    #[cfg(feature = "highlight-capture")]
+    highlights: highlight::HighlightRecorder,
+    #[cfg(feature = "debug")]
+    inspector: inspector::StateInspector,
+    #[cfg(feature = "debug")]
+    show_inspector: bool,
+    #[cfg(feature = "debug")]
+    profiler: profiler::FrameProfiler,
+    #[cfg(feature = "debug")]
+    show_profiler: bool,
+    #[cfg(all(feature = "debug", not(target_arch = "wasm32")))]
+    tuning_watch: hotreload::TuningWatch,
+    #[cfg(all(feature = "debug", not(target_arch = "wasm32")))]
+    balance_ab: balance_ab::BalanceAb,
+    #[cfg(all(feature = "debug", not(target_arch = "wasm32")))]
+    show_balance_ab: bool,
+    highlight_notice: String,
+    export_notice: String,
+    history_kind: Option<HistoryKind>,  // 历史面板只看这一类（None 为全部）
+    history_query: String,             // 历史面板的搜索关键字
+    history_page: usize,               // 历史面板的页码（从最新一页算起）
+    show_changelog: bool,
+    show_about: bool,
+    interstitial: Option<Interstitial>,  // 正在播放的过场（序章等）
+    show_simulation: bool,
+    sim_policy: AutoPolicy,           // 平衡模拟用的代打策略
+    sim_runs: u32,
+    promotion_preview: Option<forecast::PromotionPreview>,  // 晋升确认页的推演预览
+    simulation: Option<simulation::Simulation>,
+    npc_voice: Option<llm::NpcVoice>,   // 正在生成的 AI 台词
+    voiced_npc: Option<llm::VoiceKey>,  // 最近一次请求过 AI 台词的 NPC 请求
+    llm_notice: String,               // 最近一次 AI 台词失败的原因
+    play_clock: Option<instant::Instant>,  // 上一帧计入游玩时间的时刻（暂停时为 None）
+    assets: AssetCache,               // 头像与事件插图
+    audio: audio::Audio,              // 音效与背景音乐
+    #[cfg(not(target_arch = "wasm32"))]
+    update_checker: Option<updates::UpdateChecker>,
+    #[cfg(not(target_arch = "wasm32"))]
+    available_update: Option<updates::Release>,
+    #[cfg(not(target_arch = "wasm32"))]
+    data_dir: datadir::DataDir,
+    #[cfg(not(target_arch = "wasm32"))]
+    data_dir_input: String,
+    #[cfg(not(target_arch = "wasm32"))]
+    data_notice: String,
+    #[cfg(not(target_arch = "wasm32"))]
+    font_loaded: bool,                // 是否装上了中文字体（装不上时设置里提示手动指定）
+    #[cfg(not(target_arch = "wasm32"))]
+    font_input: String,
+    #[cfg(not(target_arch = "wasm32"))]
+    font_notice: String,
+    #[cfg(not(target_arch = "wasm32"))]
+    config: config::AdvancedConfig,
+    #[cfg(not(target_arch = "wasm32"))]
+    launch_errors: Vec<String>,       // config.toml 与命令行参数的问题，在顶部提示
+    #[cfg(not(target_arch = "wasm32"))]
+    last_frame: std::time::Instant,   // 帧率上限用
+    #[cfg(not(target_arch = "wasm32"))]
+    stream_overlay: overlay::StreamOverlay,
+    #[cfg(not(target_arch = "wasm32"))]
+    daily_reminded: Option<String>,   // 本次运行里已经提醒过的日期
+    #[cfg(target_arch = "wasm32")]
+    web_assets: Option<webassets::AssetLoader>,  // 字体与内容还在下载时为 Some
+}
+
+/// 自动修仙控制：按固定节奏让策略代打
+struct AutoPlay {
+    enabled: bool,
+    policy: AutoPolicy,
+    seconds_per_step: f32,
+    last_step: f64,
+}
+
+impl AutoPlay {
+    fn new() -> Self {
+        Self {
+            enabled: false,
+            policy: AutoPolicy::GreedySafe,
+            seconds_per_step: 0.8,
+            last_step: 0.0,
+        }
+    }
 }
 
 impl XiuxianApp {
-    fn new(cc: &CreationContext<'_>) -> Self {
+    fn new(cc: &CreationContext<'_>, #[cfg(not(target_arch = "wasm32"))] launch: cli::LaunchArgs) -> Self {
+        #[cfg_attr(target_arch = "wasm32", allow(unused_mut))]
+        let mut profiles: Profiles = cc
+            .storage
+            .and_then(|storage| eframe::get_value(storage, profile::PROFILES_KEY))
+            .unwrap_or_default();
+        // 命令行指定了档案时直接进入，跳过档案选择
+        #[cfg_attr(target_arch = "wasm32", allow(unused_mut))]
+        let mut profile_chosen = false;
+        #[cfg_attr(target_arch = "wasm32", allow(unused_mut))]
+        let mut profile_error = None;
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(profile) = &launch.profile {
+            if profiles.names.contains(profile) {
+                profiles.current = profile.clone();
+                profile_chosen = true;
+            } else {
+                profile_error = Some(format!("--profile：找不到档案“{}”", profile));
+            }
+        }
+        let ProfileData {
+            #[cfg_attr(target_arch = "wasm32", allow(unused_mut))]
+            mut settings,
+            lifetime_tally,
+            best_splits,
+            ghost_runs,
+            scenario_scores,
+            pick_stats,
+            achievements,
+            meta_progress,
+        } = load_profile(cc.storage, &profiles.current);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let (data_dir, data_notice) = init_data_dir(&mut settings);
+        #[cfg(not(target_arch = "wasm32"))]
+        let assets = asset_cache(&data_dir);
+        #[cfg(target_arch = "wasm32")]
+        let assets = AssetCache::new("./assets".to_string());
+        #[cfg(not(target_arch = "wasm32"))]
+        let (saves, save_errors) = SaveManager::load(&data_dir, &profiles.current);
+        #[cfg(target_arch = "wasm32")]
+        let (saves, save_errors) = SaveManager::load(cc.storage, &profiles.current);
+        let selected_slot = saves.first_empty().unwrap_or(0);
+        #[cfg(not(target_arch = "wasm32"))]
+        let (config, launch_errors) = {
+            let (mut config, mut errors) = config::AdvancedConfig::load(data_dir.root());
+            errors.extend(config.add_content_dirs(launch.content_dir.clone()));
+            // 自制事件要在列出可禁用事件、校验剧本和开局之前装好
+            match data_dir.events() {
+                Ok(dir) => {
+                    use xiuxian_core::events::{self, EventLibrary};
+                    let (library, event_errors) = EventLibrary::with_overrides(&dir);
+                    errors.extend(event_errors.into_iter().map(|err| format!("自制事件 {}", err)));
+                    events::install(library);
+                }
+                Err(err) => errors.push(err),
+            }
+            (config, errors)
+        };
+
         // 设置中文字体
-        setup_chinese_fonts(&cc.egui_ctx);
-        
-        // 设置更大的默认字体大小
-        let mut style = (*cc.egui_ctx.style()).clone();
-        style.text_styles.iter_mut().for_each(|(_, font_id)| {
-            font_id.size *= 1.2; // 放大 20%
-        });
-        cc.egui_ctx.set_style(style);
-
-        let mut visuals = Visuals::dark();
-        visuals.override_text_color = Some(Color32::WHITE);
-        visuals.panel_fill = Color32::BLACK;
-        visuals.window_fill = Color32::BLACK;
-        visuals.extreme_bg_color = Color32::BLACK;
-        visuals.hyperlink_color = Color32::WHITE;
-        cc.egui_ctx.set_visuals(visuals);
-        
-        Self { game: GameApp::new() }
+        #[cfg(not(target_arch = "wasm32"))]
+        let font_loaded = setup_chinese_fonts(
+            &cc.egui_ctx,
+            &[settings.appearance.font_path.as_deref(), config.font_path.as_deref()],
+        );
+        theme::apply(&cc.egui_ctx, &settings.accessibility, &settings.appearance, false);
+        // 设置默认字体大小（大屏模式在 update 中按需切换）
+        theme::apply_scale(&cc.egui_ctx, settings.appearance.ui_scale, settings.big_screen);
+
+        let applied_big_screen = settings.big_screen;
+        settings.idle.resume(chrono::Utc::now().timestamp());
+        #[cfg(not(target_arch = "wasm32"))]
+        let settings_check_updates = settings.check_updates;
+        // 扫挑战二维码打开网页版时，地址里带着种子和开局配置
+        #[cfg(target_arch = "wasm32")]
+        let incoming_challenge = web_sys::window()
+            .and_then(|window| window.location().search().ok())
+            .and_then(|query| Challenge::from_query(&query));
+        #[cfg(not(target_arch = "wasm32"))]
+        let incoming_challenge: Option<Challenge> = None;
+        if let Some(challenge) = incoming_challenge {
+            settings.balance = challenge.preset;
+            settings.difficulty = challenge.difficulty;
+            settings.pressure_model = challenge.pressure_model;
+            settings.ascension = challenge.ascension;
+        }
+        let mut game = GameEngine::new();
+        game.dark_content = settings.dark_content;
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            game.launch_seed = launch.seed();
+        }
+        #[cfg_attr(target_arch = "wasm32", allow(unused_mut))]
+        let mut app = Self {
+            game,
+            settings,
+            profiles,
+            profile_chosen,
+            new_profile_name: String::new(),
+            profile_error,
+            show_settings: false,
+            show_risk_calculator: false,
+            auto_play: AutoPlay::new(),
+            rebinding: None,
+            keybind_error: None,
+            gamepad_detected: false,
+            last_gamepad_poll: f64::NEG_INFINITY,
+            applied_big_screen,
+            applied_weekend: false,
+            window_title: APP_TITLE.to_string(),
+            touch_detected: false,
+            ime_composing: false,
+            narrow: false,
+            gameplay_tab: GameplayTab::事件,
+            pending_choice: None,
+            last_idle_tick: 0.0,
+            lifetime_tally,
+            session_stats: SessionStats::default(),
+            best_splits,
+            ghost_runs,
+            scenario_scores,
+            pick_stats,
+            achievements,
+            show_achievements: false,
+            seed_input: incoming_challenge.map(|c| c.seed.to_string()).unwrap_or_default(),
+            incoming_challenge,
+            challenge_qr: None,
+            saves,
+            active_slot: None,
+            selected_slot,
+            save_notice: save_errors.join("\n"),
+            meta_progress,
+            rebirth_notice: String::new(),
+            scenario_packs: Vec::new(),
+            // 网页端的事件库要等内容下载完才有（见 load_web_assets）
+            bannable_events: if cfg!(target_arch = "wasm32") {
+                Vec::new()
+            } else {
+                GameState::bannable_events()
+            },
+            scenario_errors: Vec::new(),
+            browsing_scenarios: false,
+            tournament_token: String::new(),
+            observer_tab: ObserverTab::结果,
+            #[cfg(feature = "highlight-capture")]
+            highlights: highlight::HighlightRecorder::new(),
+            #[cfg(feature = "debug")]
+            inspector: inspector::StateInspector::default(),
+            #[cfg(feature = "debug")]
+            show_inspector: false,
+            #[cfg(feature = "debug")]
+            profiler: profiler::FrameProfiler::default(),
+            #[cfg(feature = "debug")]
+            show_profiler: false,
+            #[cfg(all(feature = "debug", not(target_arch = "wasm32")))]
+            tuning_watch: hotreload::TuningWatch::default(),
+            #[cfg(all(feature = "debug", not(target_arch = "wasm32")))]
+            balance_ab: balance_ab::BalanceAb::default(),
+            #[cfg(all(feature = "debug", not(target_arch = "wasm32")))]
+            show_balance_ab: false,
+            highlight_notice: String::new(),
+            export_notice: String::new(),
+            history_kind: None,
+            history_query: String::new(),
+            history_page: 0,
+            show_changelog: false,
+            show_about: false,
+            interstitial: None,
+            show_simulation: false,
+            sim_policy: AutoPolicy::GreedySafe,
+            sim_runs: 200,
+            promotion_preview: None,
+            simulation: None,
+            npc_voice: None,
+            voiced_npc: None,
+            llm_notice: String::new(),
+            play_clock: None,
+            audio: audio::Audio::new(assets.root().clone()),
+            assets,
+            #[cfg(not(target_arch = "wasm32"))]
+            update_checker: settings_check_updates
+                .then(|| updates::UpdateChecker::spawn(config.update_endpoint.clone())),
+            #[cfg(not(target_arch = "wasm32"))]
+            available_update: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            data_dir_input: data_dir.root().display().to_string(),
+            #[cfg(not(target_arch = "wasm32"))]
+            data_dir,
+            #[cfg(not(target_arch = "wasm32"))]
+            data_notice,
+            #[cfg(not(target_arch = "wasm32"))]
+            font_loaded,
+            #[cfg(not(target_arch = "wasm32"))]
+            font_input: String::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            font_notice: String::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            config,
+            #[cfg(not(target_arch = "wasm32"))]
+            launch_errors,
+            #[cfg(not(target_arch = "wasm32"))]
+            last_frame: std::time::Instant::now(),
+            #[cfg(not(target_arch = "wasm32"))]
+            stream_overlay: overlay::StreamOverlay::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            daily_reminded: None,
+            #[cfg(target_arch = "wasm32")]
+            web_assets: Some(webassets::AssetLoader::start(&cc.egui_ctx)),
+        };
+        #[cfg(not(target_arch = "wasm32"))]
+        app.load_scenarios();
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(slot) = launch.load {
+            app.resume_launch_slot(slot);
+        }
+        app
     }
 
-    fn draw_start(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
-        ui.heading("📖 欢迎来到修仙编程世界");
-        ui.label("请输入你的修仙名号，然后回车开始：");
-        ui.add_space(8.0);
+    /// 命令行 `--load`：接着玩当前档案的第 `slot` 个槽位（从 1 数），读不了时在开始界面说明原因
+    #[cfg(not(target_arch = "wasm32"))]
+    fn resume_launch_slot(&mut self, slot: usize) {
+        match slot.checked_sub(1).filter(|index| *index < SLOT_COUNT) {
+            Some(index) if !self.saves.is_empty(index) => {
+                self.resume_slot(index);
+                self.profile_chosen |= self.game.in_run();
+            }
+            Some(_) => self.save_notice = format!("--load：槽位 {} 是空的", slot),
+            None => self.save_notice = format!("--load：槽位要在 1~{} 之间", SLOT_COUNT),
+        }
+    }
 
-        let response = ui.add(
-            egui::TextEdit::singleline(&mut self.game.player_name)
-                .hint_text("如：凌霄程序侠")
-                .desired_width(240.0),
+    /// 载入内置剧本包、数据目录下与 config.toml 指定目录里的自制剧本包（重复 id 的剧本跳过）
+    fn load_scenarios(&mut self) {
+        let (daily_ids, weekly_ids) = GameState::content_ids();
+        #[cfg_attr(target_arch = "wasm32", allow(unused_mut))]
+        let mut loaded = vec![Ok(ScenarioPack::builtin(&daily_ids, &weekly_ids))];
+        #[cfg(not(target_arch = "wasm32"))]
+        match self.data_dir.scenarios() {
+            Ok(dir) => loaded.extend(ScenarioPack::load_dir(&dir, &daily_ids, &weekly_ids)),
+            Err(err) => loaded.push(Err(err)),
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        for dir in &self.config.content_dirs {
+            loaded.extend(ScenarioPack::load_dir(dir, &daily_ids, &weekly_ids));
+        }
+
+        self.scenario_packs.clear();
+        self.scenario_errors.clear();
+        let mut seen: Vec<String> = Vec::new();
+        for result in loaded {
+            match result {
+                Ok(mut pack) => {
+                    pack.scenarios.retain(|s| {
+                        let fresh = !seen.contains(&s.id);
+                        if !fresh {
+                            self.scenario_errors
+                                .push(format!("剧本 id “{}” 重复，已跳过", s.id));
+                        }
+                        seen.push(s.id.clone());
+                        fresh
+                    });
+                    self.scenario_packs.push(pack);
+                }
+                Err(err) => self.scenario_errors.push(err),
+            }
+        }
+    }
+
+    /// 本局不是挑战剧本时才计入生涯统计、最佳成绩与归档
+    fn regular_run(&self) -> Option<&GameState> {
+        self.game
+            .game_state
+            .as_ref()
+            .filter(|state| state.scenario.is_none())
+    }
+
+    /// 把当前档案的设置与生涯统计写入存储
+    fn store_profile(&self, storage: &mut dyn eframe::Storage) {
+        let key = |base: &str| self.profiles.key(base);
+        eframe::set_value(storage, &key(settings::STORAGE_KEY), &self.settings);
+        // 进行中的这局存进了续玩存档，打完再计入；已经结束还没归档的这局在这里一并计入
+        let finished = self.regular_run().filter(|_| !self.game.in_run());
+        let run = finished
+            .map(|state| state.player.archetype_tally())
+            .unwrap_or_default();
+        eframe::set_value(
+            storage,
+            &key(settings::LIFETIME_STATS_KEY),
+            &self.lifetime_tally.merged(&run),
+        );
+        let mut best = self.best_splits.clone();
+        if let Some(state) = finished {
+            best.record(state.player.balance.preset, &state.splits);
+        }
+        eframe::set_value(storage, &key(speedrun::BEST_SPLITS_KEY), &best);
+        let mut ghosts = self.ghost_runs.clone();
+        if let Some(state) = finished {
+            ghosts.record(ArchivedRun::from_player(&state.player));
+        }
+        eframe::set_value(storage, &key(ghost::GHOST_RUNS_KEY), &ghosts);
+        eframe::set_value(
+            storage,
+            &key(scenario::SCENARIO_SCORES_KEY),
+            &self.scenario_scores,
         );
+        eframe::set_value(storage, &key(pickrate::PICK_STATS_KEY), &self.pick_stats);
+        eframe::set_value(storage, &key(achievements::ACHIEVEMENTS_KEY), &self.achievements);
+        eframe::set_value(storage, &key(rebirth::META_PROGRESS_KEY), &self.meta_progress);
+    }
+
+    /// 把进行中的这局写进它的槽位，再把改过的槽位落地
+    #[cfg_attr(not(target_arch = "wasm32"), allow(unused_variables))]
+    fn store_run(&mut self, storage: &mut dyn eframe::Storage) {
+        if let (Some(slot), Some(save)) = (self.active_slot, self.game.save()) {
+            if let Err(err) = self.saves.store(slot, &save) {
+                self.save_notice = err;
+            }
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Err(err) = self.saves.persist(&self.data_dir) {
+            self.save_notice = err;
+        }
+        #[cfg(target_arch = "wasm32")]
+        self.saves.persist(storage);
+    }
 
-        if response.lost_focus() && ctx.input(|i| i.key_pressed(Key::Enter)) {
-            self.game.start_game();
+    /// 接着玩某个槽位里的存档
+    fn resume_slot(&mut self, slot: usize) {
+        match self.saves.load_slot(slot) {
+            Ok(Some(save)) => {
+                self.game.resume(save);
+                self.active_slot = Some(slot);
+                self.selected_slot = slot;
+                self.save_notice.clear();
+            }
+            Ok(None) => {}
+            Err(err) => self.save_notice = err,
         }
+    }
 
-        ui.add_space(12.0);
-        let start_enabled = !self.game.player_name.trim().is_empty();
-        if ui
-            .add_enabled(start_enabled, egui::Button::new("开始修仙"))
-            .clicked()
+    /// 放弃槽位里的存档（覆盖或删除），那一局照常计入生涯统计与归档
+    fn abandon_slot(&mut self, slot: usize) {
+        if let Ok(Some(save)) = self.saves.load_slot(slot) {
+            if save.state.scenario.is_none() {
+                let state = &save.state;
+                self.lifetime_tally = self.lifetime_tally.merged(&state.player.archetype_tally());
+                self.best_splits.record(state.player.balance.preset, &state.splits);
+                self.ghost_runs.record(ArchivedRun::from_player(&state.player));
+            }
+        }
+        self.saves.delete(slot);
+    }
+
+    /// 刚开的新局占用开始界面选中的槽位（原来的存档作废）
+    fn claim_selected_slot(&mut self) {
+        if self.game.in_run() {
+            self.abandon_slot(self.selected_slot);
+            self.active_slot = Some(self.selected_slot);
+        }
+    }
+
+    /// 切换到另一个档案：先保存当前档案与进行中的这局，再载入新档案（有存档可以继续）
+    fn switch_profile(&mut self, ctx: &egui::Context, frame: &mut Frame, name: String) {
+        if let Some(storage) = frame.storage_mut() {
+            self.store_profile(storage);
+            self.store_run(storage);
+        }
+        self.profiles.current = name;
+        let ProfileData {
+            #[cfg_attr(target_arch = "wasm32", allow(unused_mut))]
+            mut settings,
+            lifetime_tally,
+            best_splits,
+            ghost_runs,
+            scenario_scores,
+            pick_stats,
+            achievements,
+            meta_progress,
+        } = load_profile(frame.storage(), &self.profiles.current);
+        #[cfg(not(target_arch = "wasm32"))]
         {
-            self.game.start_game();
+            let (data_dir, data_notice) = init_data_dir(&mut settings);
+            self.data_dir_input = data_dir.root().display().to_string();
+            self.assets = asset_cache(&data_dir);
+            self.audio = audio::Audio::new(self.assets.root().clone());
+            self.data_dir = data_dir;
+            self.data_notice = data_notice;
+            if settings.appearance.font_path != self.settings.appearance.font_path {
+                self.font_loaded = setup_chinese_fonts(
+                    ctx,
+                    &[settings.appearance.font_path.as_deref(), self.config.font_path.as_deref()],
+                );
+            }
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        let (saves, save_errors) = SaveManager::load(&self.data_dir, &self.profiles.current);
+        #[cfg(target_arch = "wasm32")]
+        let (saves, save_errors) = SaveManager::load(frame.storage(), &self.profiles.current);
+        self.selected_slot = saves.first_empty().unwrap_or(0);
+        self.saves = saves;
+        self.active_slot = None;
+        self.save_notice = save_errors.join("\n");
+        theme::apply(ctx, &settings.accessibility, &settings.appearance, self.applied_weekend);
+        theme::apply_scale(ctx, settings.appearance.ui_scale, settings.big_screen);
+        settings.idle.resume(chrono::Utc::now().timestamp());
+        self.applied_big_screen = settings.big_screen;
+        self.game.dark_content = settings.dark_content;
+        self.settings = settings;
+        self.lifetime_tally = lifetime_tally;
+        self.best_splits = best_splits;
+        self.ghost_runs = ghost_runs;
+        self.scenario_scores = scenario_scores;
+        self.pick_stats = pick_stats;
+        self.achievements = achievements;
+        self.meta_progress = meta_progress;
+        self.rebirth_notice.clear();
+        self.game.execute(GameCommand::Restart);
+        self.tournament_token.clear();
+        self.auto_play.enabled = false;
+        self.browsing_scenarios = false;
+        self.load_scenarios();
+    }
+
+    /// 档案选择界面（每次启动先在这里确认是谁在玩）
+    fn draw_profile_select(&mut self, ui: &mut egui::Ui, frame: &mut Frame) {
+        ui.heading("👤 选择档案");
+        ui.label("每个档案有独立的设置与统计，适合一台电脑多人轮流修仙");
+        ui.add_space(8.0);
+
+        let mut chosen = None;
+        for name in &self.profiles.names {
+            let label = if *name == self.profiles.current {
+                format!("▶ {}（上次使用）", name)
+            } else {
+                name.clone()
+            };
+            if ui.button(label).clicked() {
+                chosen = Some(name.clone());
+            }
         }
 
         ui.add_space(12.0);
-        ui.label("提示: 输入字符，Enter 开始");
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.new_profile_name)
+                    .hint_text("新档案名")
+                    .desired_width(160.0),
+            );
+            if ui.button("➕ 新建档案").clicked() {
+                match self.profiles.add(&self.new_profile_name) {
+                    Ok(name) => {
+                        self.new_profile_name.clear();
+                        self.profile_error = None;
+                        chosen = Some(name);
+                    }
+                    Err(err) => self.profile_error = Some(err),
+                }
+            }
+        });
+        if let Some(error) = &self.profile_error {
+            ui.colored_label(self.palette().negative, error);
+        }
+
+        if let Some(name) = chosen {
+            if name != self.profiles.current {
+                self.switch_profile(ui.ctx(), frame, name);
+            }
+            self.profile_chosen = true;
+        }
     }
 
-    fn draw_gameplay(&mut self, ui: &mut egui::Ui) {
-        if self.game.game_state.is_none() {
-            ui.label("请先输入名号开始游戏。");
-            return;
+    /// 按当前设置（季节内容包、数值预设、叙事风格）开局
+    fn start_game(&mut self) {
+        if let Ok(seed) = self.typed_seed() {
+            self.start_game_with_seed(seed);
         }
+    }
 
-        if let Some(state) = self.game.game_state.as_ref() {
-            self.draw_stats(ui, state);
+    /// 开始界面填的种子：空为不指定，填的不是数字为错误
+    fn typed_seed(&self) -> Result<Option<u64>, String> {
+        let text = self.seed_input.trim();
+        if text.is_empty() {
+            return Ok(None);
         }
+        text.parse().map(Some).map_err(|_| "种子要填非负整数".to_string())
+    }
 
-        ui.add_space(12.0);
-        self.draw_event_panel(ui);
-        ui.add_space(16.0);
+    /// 用今天的每日挑战种子开局
+    fn start_daily(&mut self) {
+        self.start_game_with_seed(Some(daily::today_seed()));
+    }
 
-        if let Some(state) = self.game.game_state.as_ref() {
-            self.draw_history(ui, state);
+    /// 这个档案还没打过任何一局，也没走过新手引导周
+    fn first_run(&self) -> bool {
+        !self.settings.onboarded && self.lifetime_tally.total() == 0
+    }
+
+    /// `seed` 为空时用命令行固定的种子或随机种子；用到每日挑战种子就记下今天打过了。
+    /// 档案的第一局（没指定种子时）改走新手引导周
+    fn start_game_with_seed(&mut self, seed: Option<u64>) {
+        let guided = seed.is_none() && self.game.launch_seed.is_none() && self.first_run();
+        let seed = if guided { Some(onboarding::demo_week().seed) } else { seed };
+        let packs = self.settings.seasons.active_packs();
+        self.game.shuffle = self.settings.shuffle;
+        // 名号不合规时不开局，输入框下面已经显示了原因
+        let started = self.game.start_game(
+            packs,
+            self.settings
+                .balance
+                .config()
+                .with_difficulty(self.settings.difficulty)
+                .with_pressure_model(self.settings.pressure_model)
+                .with_boons(&self.meta_progress),
+            self.settings.tone,
+            self.settings.director,
+            self.settings.career,
+            self.settings.muted_tags.clone(),
+            seed,
+        );
+        if started.is_err() {
+            return;
+        }
+        self.claim_selected_slot();
+        let level = self.settings.ascension.min(self.settings.ascension_unlocked);
+        if let Some(state) = self.game.game_state.as_mut() {
+            if guided {
+                state.follow_guide();
+                self.settings.onboarded = true;
+            }
+            state.ban_events(self.settings.banned_events.clone());
+            state.ascend(level);
+        }
+        if self.game.game_state.as_ref().is_some_and(|s| s.seed == daily::today_seed()) {
+            self.settings.daily_played = Some(daily::today());
         }
     }
 
-    fn draw_stats(&self, ui: &mut egui::Ui, state: &GameState) {
-        ui.group(|ui| {
-            ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 4.0);
-            ui.label(format!(
-                "修仙者: {} | 境界: {} | 技能点: {} | 压力值: {}",
-                state.player.name,
-                state.player.get_realm(),
-                state.player.skills,
-                state.player.pressure
-            ));
-            ui.label(format!(
-                "第{}天 | 第{}周 | ⏱️ 游玩时间: {}",
-                state.current_day,
-                state.current_week,
-                state.format_time()
-            ));
-        });
+    /// 今天的每日挑战是否已经打过
+    fn daily_played_today(&self) -> bool {
+        self.settings.daily_played.as_deref() == Some(daily::today().as_str())
     }
 
-    fn draw_event_panel(&mut self, ui: &mut egui::Ui) {
-        if let Some((title, desc, options, is_weekly)) = self.game.current_event_metadata() {
-            ui.heading(title);
-            ui.label(desc);
-            ui.add_space(10.0);
+    fn draw_start(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        // 第一次来到开始界面先放序章
+        if !self.settings.intro_seen {
+            self.settings.intro_seen = true;
+            self.interstitial = Some(Interstitial::new(&interstitial::INTRO));
+        }
+        ui.heading("📖 欢迎来到修仙编程世界");
+        self.draw_save_slots(ui);
+        if !self.save_notice.is_empty() {
+            ui.colored_label(self.palette().negative, &self.save_notice);
+        }
+        ui.add_space(8.0);
+        ui.label("请输入你的修仙名号，然后回车开始：");
+        if let Some(seed) = self.game.launch_seed {
+            ui.small(format!("🎲 本次启动固定种子 {}", seed));
+        }
+        if let Some(challenge) = self.incoming_challenge {
+            ui.colored_label(
+                self.palette().highlight,
+                format!(
+                    "📱 好友发来挑战：种子 {} · {} · {} · 压力{} · 飞升 {}，对方得了 {} 分，超过它！",
+                    challenge.seed,
+                    challenge.preset.label(),
+                    challenge.difficulty.label(),
+                    challenge.pressure_model.label(),
+                    challenge.ascension,
+                    challenge.score
+                ),
+            );
+            if challenge.ascension > self.settings.ascension_unlocked {
+                ui.small(format!(
+                    "你还没解锁飞升 {}，这局按飞升 {} 开，分数不好直接比",
+                    challenge.ascension, self.settings.ascension_unlocked
+                ));
+            }
+        }
+        ui.add_space(8.0);
+        ui.horizontal(|ui| {
+            ui.label("🎲 种子：");
+            ui.add(
+                egui::TextEdit::singleline(&mut self.seed_input)
+                    .hint_text("留空为随机")
+                    .desired_width(160.0),
+            )
+            .on_hover_text("填朋友分享的种子，就能挑战同一局（每日挑战不受影响）");
+            if let Err(err) = self.typed_seed() {
+                ui.colored_label(self.palette().negative, err);
+            }
+        });
 
-            let can_choose = if is_weekly {
-                self.game.can_make_weekly_choice()
-            } else {
-                self.game.can_make_daily_choice()
-            };
+        let enter = self.name_enter_pressed(ctx);
+        let named = name::validate(&self.game.player_name).is_ok();
+        let confirmed = ui
+            .horizontal(|ui| {
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut self.game.player_name)
+                        .hint_text("如：凌霄程序侠")
+                        .desired_width(240.0),
+                );
+                // 手机软键盘常常没有回车，给一个明确的确认按钮（点了顺便收起键盘）
+                let tapped = self.touch_detected
+                    && ui.add_enabled(named, egui::Button::new("确认").min_size(egui::vec2(64.0, 32.0))).clicked();
+                if tapped {
+                    response.surrender_focus();
+                }
+                (response.lost_focus() && enter) || tapped
+            })
+            .inner;
+        self.draw_name_error(ui);
+        if confirmed {
+            self.start_game();
+        }
 
-            for (idx, option) in options.iter().enumerate() {
-                let label = format!("选项 {}: {}", idx + 1, option.desc.replace('\n', " "));
-                if ui
-                    .add_enabled(can_choose, egui::Button::new(label))
-                    .clicked()
-                {
-                    self.game.apply_choice((idx + 1) as u8);
+        ui.add_space(8.0);
+        for preset in BalancePreset::ALL {
+            ui.radio_value(&mut self.settings.balance, preset, preset.label())
+                .on_hover_text(preset.description());
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("难度：");
+            for difficulty in Difficulty::ALL {
+                ui.radio_value(&mut self.settings.difficulty, difficulty, difficulty.label())
+                    .on_hover_text(difficulty.description());
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("压力模型：");
+            for model in PressureModel::ALL {
+                ui.radio_value(&mut self.settings.pressure_model, model, model.label())
+                    .on_hover_text(model.description());
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("叙事风格：");
+            for tone in NarrativeTone::ALL {
+                ui.radio_value(&mut self.settings.tone, tone, tone.to_string())
+                    .on_hover_text(tone.description());
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("选项顺序：");
+            for mode in ShuffleMode::ALL {
+                ui.radio_value(&mut self.settings.shuffle, mode, mode.to_string())
+                    .on_hover_text(mode.description());
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("职业路线：");
+            for career in CareerPath::ALL {
+                ui.radio_value(&mut self.settings.career, career, career.label())
+                    .on_hover_text(career.description());
+            }
+        });
+
+        ui.horizontal_wrapped(|ui| {
+            ui.label("屏蔽题材：");
+            for tag in EventTag::ALL {
+                let mut muted = self.settings.muted_tags.contains(&tag);
+                if ui.checkbox(&mut muted, format!("{} {}", tag.icon(), tag)).changed() {
+                    if muted {
+                        self.settings.muted_tags.push(tag);
+                    } else {
+                        self.settings.muted_tags.retain(|t| *t != tag);
+                    }
                 }
             }
+        });
 
-            if !self.game.result_message.is_empty() {
-                ui.add_space(10.0);
-                ui.label(&self.game.result_message);
+        self.draw_event_bans(ui);
+        if self.settings.ascension_unlocked > 0 {
+            self.draw_ascension_picker(ui);
+        }
+        if self.meta_progress.rebirths > 0 {
+            self.draw_rebirth_shop(ui);
+        }
+
+        ui.checkbox(&mut self.settings.speedrun, "⏱ 速通模式（显示计时与境界分段）");
+        ui.checkbox(&mut self.settings.show_ghost, "👻 对照最佳一局（同一天的成绩）");
+        ui.horizontal(|ui| {
+            if ui.button("🎯 挑战模式…").clicked() {
+                self.browsing_scenarios = true;
+            }
+            let label = format!("🏅 成就（{}/{}）", self.achievements.count(), Achievement::ALL.len());
+            if ui.button(label).clicked() {
+                self.show_achievements = true;
+            }
+        });
+
+        ui.add_space(12.0);
+        let start_enabled = named;
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(start_enabled && self.typed_seed().is_ok(), egui::Button::new("开始修仙"))
+                .clicked()
+            {
+                self.start_game();
+            }
+            let daily_label = if self.daily_played_today() {
+                "📅 每日挑战（今天已打）"
+            } else {
+                "📅 每日挑战"
+            };
+            if ui
+                .add_enabled(start_enabled, egui::Button::new(daily_label))
+                .on_hover_text("同一天所有人用同一个种子开局")
+                .clicked()
+            {
+                self.start_daily();
+            }
+        });
+
+        ui.add_space(12.0);
+        ui.label("提示: 输入字符，Enter 开始");
+    }
+
+    /// 存档槽位：选中的槽位用来放新开的局；有存档的可以继续、复制到别的槽位或删除
+    fn draw_save_slots(&mut self, ui: &mut egui::Ui) {
+        let palette = self.palette();
+        let mut resume = None;
+        let mut copy = None;
+        let mut delete = None;
+        ui.strong("存档槽位");
+        egui::Grid::new("save_slots").num_columns(3).striped(true).show(ui, |ui| {
+            for slot in 0..SLOT_COUNT {
+                ui.radio_value(&mut self.selected_slot, slot, format!("槽位 {}", slot + 1));
+                match self.saves.info(slot) {
+                    None => {
+                        ui.weak("空");
+                    }
+                    Some(Ok(info)) => {
+                        ui.label(format!("{} · {} · 第{}天", info.player_name, info.realm, info.day))
+                            .on_hover_text(format!("最后游玩：{}", info.last_played()));
+                    }
+                    Some(Err(err)) => {
+                        ui.colored_label(palette.negative, format!("存档读不出来：{}", err));
+                    }
+                }
+                ui.horizontal(|ui| {
+                    if self.saves.is_empty(slot) {
+                        return;
+                    }
+                    if let Some(Ok(info)) = self.saves.info(slot) {
+                        ui.weak(info.last_played());
+                        if ui.small_button("▶ 继续").clicked() {
+                            resume = Some(slot);
+                        }
+                    }
+                    ui.menu_button("📋 复制到…", |ui| {
+                        for target in (0..SLOT_COUNT).filter(|target| *target != slot) {
+                            let label = if self.saves.is_empty(target) {
+                                format!("槽位 {}（空）", target + 1)
+                            } else {
+                                format!("槽位 {}（覆盖）", target + 1)
+                            };
+                            if ui.button(label).clicked() {
+                                copy = Some((slot, target));
+                                ui.close_menu();
+                            }
+                        }
+                    });
+                    if ui.small_button("🗑 删除").clicked() {
+                        delete = Some(slot);
+                    }
+                });
+                ui.end_row();
+            }
+        });
+        if !self.saves.is_empty(self.selected_slot) {
+            ui.small(format!("在槽位 {} 开始新的一局会覆盖这个存档", self.selected_slot + 1));
+        }
+        if let Some(slot) = resume {
+            self.resume_slot(slot);
+        }
+        if let Some((from, to)) = copy {
+            self.saves.copy(from, to);
+        }
+        if let Some(slot) = delete {
+            self.abandon_slot(slot);
+        }
+    }
+
+    /// 名号输入框里的回车：输入法正在组词、或者这一帧刚把词上屏时，回车是给输入法的，不算开局
+    fn name_enter_pressed(&mut self, ctx: &egui::Context) -> bool {
+        let was_composing = self.ime_composing;
+        let (enter, composing, committed) = ctx.input(|i| {
+            let mut composing = was_composing;
+            let mut committed = false;
+            for event in &i.events {
+                match event {
+                    egui::Event::CompositionStart => composing = true,
+                    egui::Event::CompositionUpdate(text) => composing = !text.is_empty(),
+                    egui::Event::CompositionEnd(_) => {
+                        composing = false;
+                        committed = true;
+                    }
+                    _ => {}
+                }
+            }
+            (i.key_pressed(Key::Enter), composing, committed)
+        });
+        self.ime_composing = composing;
+        enter && !was_composing && !composing && !committed
+    }
+
+    /// 新开局禁用的日常事件（点名字取消禁用）
+    fn draw_event_bans(&mut self, ui: &mut egui::Ui) {
+        let banned = &mut self.settings.banned_events;
+        ui.horizontal_wrapped(|ui| {
+            ui.label(format!("禁用事件（最多 {} 个）：", game::MAX_BANNED_EVENTS));
+            let mut removed = None;
+            for id in banned.iter() {
+                let name = self
+                    .bannable_events
+                    .iter()
+                    .find(|(event_id, _)| event_id == id)
+                    .map_or("未知事件", |(_, name)| name.as_str());
+                if ui.small_button(format!("🚫 {} ✖", name)).clicked() {
+                    removed = Some(*id);
+                }
+            }
+            if let Some(id) = removed {
+                banned.retain(|b| *b != id);
+            }
+            if banned.len() < game::MAX_BANNED_EVENTS {
+                egui::ComboBox::from_id_source("ban_event")
+                    .selected_text("添加…")
+                    .show_ui(ui, |ui| {
+                        for (id, name) in &self.bannable_events {
+                            if !banned.contains(id) && ui.selectable_label(false, name).clicked() {
+                                banned.push(*id);
+                            }
+                        }
+                    });
+            }
+        });
+    }
+
+    /// 选择新开局的飞升等级，并列出叠加的不利条件
+    fn draw_ascension_picker(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("🌟 飞升等级：");
+            for level in 0..=self.settings.ascension_unlocked {
+                ui.radio_value(&mut self.settings.ascension, level, level.to_string());
+            }
+        });
+        let level = self.settings.ascension;
+        if level > 0 {
+            ui.weak(format!("得分 ×{:.2}", ascension::score_multiplier(level)));
+            for handicap in ascension::handicaps(level) {
+                ui.weak(format!("· {}", handicap.description()));
+            }
+        }
+    }
+
+    /// 转世兑换处：用仙缘点升级永久加成，下一局开局生效
+    fn draw_rebirth_shop(&mut self, ui: &mut egui::Ui) {
+        let progress = &mut self.meta_progress;
+        ui.collapsing(format!("☯ 转世兑换处（仙缘点 {}）", progress.karma), |ui| {
+            ui.weak(format!(
+                "已转世 {} 次，历代累计获得仙缘点 {}",
+                progress.rebirths, progress.lifetime_karma
+            ));
+            egui::Grid::new("rebirth_shop_grid").num_columns(3).show(ui, |ui| {
+                for boon in Boon::ALL {
+                    let level = progress.level(boon);
+                    ui.strong(format!("{} {}/{}", boon, level, boon.max_level()));
+                    ui.label(if level > 0 { boon.description(level) } else { boon.description(1) });
+                    if level < boon.max_level() {
+                        let cost = boon.cost(level);
+                        let button = egui::Button::new(format!("升级（{} 点）", cost));
+                        let response = ui
+                            .add_enabled(progress.karma >= cost, button)
+                            .on_hover_text(format!("升级后：{}", boon.description(level + 1)));
+                        if response.clicked() {
+                            self.rebirth_notice = match progress.buy(boon) {
+                                Ok(()) => format!("{}升到 {} 级，下一局开局生效", boon, level + 1),
+                                Err(err) => err,
+                            };
+                        }
+                    } else {
+                        ui.weak("已满级");
+                    }
+                    ui.end_row();
+                }
+            });
+            if !self.rebirth_notice.is_empty() {
+                ui.small(&self.rebirth_notice);
+            }
+        });
+    }
+
+    /// 转世：把这一局折成仙缘点收下，回到开局界面
+    fn reincarnate(&mut self) {
+        let Some(karma) = self.regular_run().map(MetaProgress::karma_for) else {
+            return;
+        };
+        self.meta_progress.reincarnate(karma);
+        self.rebirth_notice = format!("转世成功，获得仙缘点 {}", karma);
+        self.restart();
+    }
+
+    /// 名号输入框下的错误提示（还没填时不提示）
+    fn draw_name_error(&self, ui: &mut egui::Ui) {
+        if self.game.player_name.is_empty() {
+            return;
+        }
+        if let Err(err) = name::validate(&self.game.player_name) {
+            ui.colored_label(self.palette().negative, err);
+        }
+    }
+
+    /// 挑战剧本列表：固定种子与事件顺序，成绩单独记录
+    fn draw_scenarios(&mut self, ui: &mut egui::Ui) {
+        if ui.button("⬅ 返回").clicked() {
+            self.browsing_scenarios = false;
+        }
+        ui.heading("🎯 挑战模式");
+        ui.label("每个剧本的事件顺序和随机种子都是固定的，大家面对的是同一局。成绩单独记录，不计入生涯统计。");
+        ui.add_space(8.0);
+        ui.add(
+            egui::TextEdit::singleline(&mut self.game.player_name)
+                .hint_text("修仙名号")
+                .desired_width(240.0),
+        );
+        self.draw_name_error(ui);
+        ui.add_space(8.0);
+
+        let can_start = name::validate(&self.game.player_name).is_ok();
+        let mut chosen = None;
+        for pack in &self.scenario_packs {
+            ui.strong(&pack.name);
+            for scenario in &pack.scenarios {
+                ui.group(|ui| {
+                    ui.horizontal(|ui| {
+                        ui.strong(&scenario.name);
+                        if let Some(best) = self.scenario_scores.best_for(&scenario.id) {
+                            ui.label(format!("🏆 最高分 {}", best));
+                        }
+                    });
+                    ui.label(&scenario.description);
+                    ui.small(format!(
+                        "{}天 · {} · 种子 {}",
+                        scenario.daily.len(),
+                        scenario.preset.label(),
+                        scenario.seed
+                    ));
+                    if ui
+                        .add_enabled(can_start, egui::Button::new("开始挑战"))
+                        .clicked()
+                    {
+                        chosen = Some(scenario.clone());
+                    }
+                });
+            }
+            ui.add_space(8.0);
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        ui.small(format!(
+            "自制剧本包放在 {} 下（JSON 格式，参考内置剧本）",
+            self.data_dir.root().join("scenarios").display()
+        ));
+        for error in &self.scenario_errors {
+            ui.colored_label(self.palette().negative, error);
+        }
+
+        if let Some(scenario) = chosen {
+            self.browsing_scenarios = false;
+            self.game.shuffle = self.settings.shuffle;
+            if self
+                .game
+                .start_scenario(scenario, self.settings.tone, self.settings.career)
+                .is_ok()
+            {
+                self.claim_selected_slot();
+            }
+        }
+    }
+
+    fn draw_gameplay(&mut self, ui: &mut egui::Ui) {
+        if self.game.game_state.is_none() {
+            ui.label("请先输入名号开始游戏。");
+            return;
+        }
+
+        if self.narrow || self.touch_detected {
+            let spacing = ui.spacing_mut();
+            spacing.interact_size.y = spacing.interact_size.y.max(TOUCH_TARGET);
+            spacing.button_padding.y = spacing.button_padding.y.max(8.0);
+        }
+        if self.narrow {
+            self.draw_narrow_gameplay(ui);
+            return;
+        }
+
+        // 三栏布局下状态与历史由侧边栏绘制，中间只放事件
+        if self.detailed_layout() {
+            self.draw_event_panel(ui);
+            return;
+        }
+
+        self.draw_status_column(ui, true);
+        ui.add_space(12.0);
+        self.draw_event_panel(ui);
+        ui.add_space(16.0);
+
+        self.draw_history_section(ui, 200.0);
+        ui.add_space(16.0);
+        ui.group(|ui| self.draw_npc_section(ui));
+    }
+
+    /// 窄屏：状态栏常驻顶部，下面事件、状态详情、历史、同事分页切换
+    fn draw_narrow_gameplay(&mut self, ui: &mut egui::Ui) {
+        if let Some(state) = self.game.game_state.as_ref() {
+            self.draw_stats(ui, state);
+        }
+        ui.add_space(6.0);
+        ui.columns(GameplayTab::ALL.len(), |columns| {
+            for (column, tab) in columns.iter_mut().zip(GameplayTab::ALL) {
+                column.with_layout(egui::Layout::top_down_justified(egui::Align::Center), |ui| {
+                    ui.selectable_value(&mut self.gameplay_tab, tab, tab.label());
+                });
+            }
+        });
+        ui.separator();
+        match self.gameplay_tab {
+            GameplayTab::事件 => self.draw_event_panel(ui),
+            GameplayTab::状态 => self.draw_status_column(ui, false),
+            // 历史不再套一层限高的滚动区，直接跟着整页滑动
+            GameplayTab::历史 => self.draw_history_section(ui, f32::INFINITY),
+            GameplayTab::同事 => self.draw_npc_section(ui),
+        }
+    }
+
+    /// 三栏布局（窄屏时退回单栏）
+    fn detailed_layout(&self) -> bool {
+        self.settings.layout == LayoutMode::Detailed && !self.narrow
+    }
+
+    /// 事件面板里成排的按钮：窄屏时改成一行一个、占满宽度
+    fn button_row(&self) -> egui::Layout {
+        if self.narrow {
+            egui::Layout::top_down_justified(egui::Align::Center)
+        } else {
+            egui::Layout::left_to_right(egui::Align::Center)
+        }
+    }
+
+    /// `with_stats` 为 false 时不画顶部状态栏（窄屏布局已经常驻显示）
+    fn draw_status_column(&mut self, ui: &mut egui::Ui, with_stats: bool) {
+        let mut upgrade_housing = false;
+        let mut course_click = None;
+        let mut portfolio_click = None;
+        let mut evening = None;
+        let mut companion_used = None;
+        let mut create_alt = false;
+        let mut used_item = None;
+        let mut deliver = false;
+        if let Some(state) = self.game.game_state.as_ref() {
+            if with_stats {
+                self.draw_stats(ui, state);
+            }
+            companion_used = self.draw_companion_actions(ui, state);
+            create_alt = self.draw_persona(ui, state);
+            deliver = Self::draw_favor(ui, state);
+            used_item = Self::draw_inventory(ui, state);
+            self.draw_biography(ui, state);
+            egui::CollapsingHeader::new("📊 修炼统计")
+                .default_open(false)
+                .show(ui, |ui| self.draw_training_stats(ui, state));
+            self.draw_tendencies(ui, state);
+            self.draw_specialty(ui, state);
+            upgrade_housing = self.draw_finance(ui, state, true);
+            course_click = self.draw_courses(ui, state);
+            portfolio_click = self.draw_portfolio(ui, state);
+            evening = self.draw_open_source(ui, state);
+        }
+        if let (Some(plan), Some(game)) = (evening, self.game.game_state.as_mut()) {
+            game.set_evening(plan);
+        }
+        if let Some(index) = companion_used {
+            self.game.execute(GameCommand::UseCompanion(index));
+        }
+        if create_alt {
+            self.game.execute(GameCommand::CreateAlt);
+        }
+        if let Some(item) = used_item {
+            self.game.execute(GameCommand::UseItem(item));
+        }
+        if deliver {
+            self.game.execute(GameCommand::DeliverFavor);
+        }
+        if upgrade_housing {
+            self.game.execute(GameCommand::UpgradeHousing);
+        }
+        match course_click {
+            Some(CourseClick::Enroll(course)) => self.game.execute(GameCommand::Enroll(course)),
+            Some(CourseClick::Drop) => self.game.execute(GameCommand::DropCourse),
+            None => {}
+        }
+        match portfolio_click {
+            Some(PortfolioClick::Buy(instrument)) => self.game.execute(GameCommand::Invest(instrument)),
+            Some(PortfolioClick::Sell(instrument)) => self.game.execute(GameCommand::SellInvestment(instrument)),
+            None => {}
+        }
+        if self.settings.speedrun {
+            self.draw_splits(ui);
+        }
+    }
+
+    fn draw_auto_play_controls(&mut self, ui: &mut egui::Ui) {
+        let auto = &mut self.auto_play;
+        ui.toggle_value(&mut auto.enabled, "🤖 自动修仙");
+        egui::ComboBox::from_id_source("auto_policy")
+            .selected_text(auto.policy.label())
+            .show_ui(ui, |ui| {
+                for policy in AutoPolicy::ALL {
+                    ui.selectable_value(&mut auto.policy, policy, policy.label());
+                }
+            });
+        ui.add(
+            egui::Slider::new(&mut auto.seconds_per_step, 0.1..=3.0)
+                .suffix(" 秒/步")
+                .max_decimals(1),
+        );
+    }
+
+    /// 自动修仙：到点后让策略走一步
+    fn tick_auto_play(&mut self, ctx: &egui::Context) {
+        if !self.auto_play.enabled {
+            return;
+        }
+        let now = ctx.input(|i| i.time);
+        let interval = self.auto_play.seconds_per_step as f64;
+        if now - self.auto_play.last_step >= interval {
+            self.auto_play.last_step = now;
+            if !self.game.auto_step(self.auto_play.policy) {
+                self.auto_play.enabled = false;
+            }
+        }
+        ctx.request_repaint_after(std::time::Duration::from_secs_f64(interval));
+    }
+
+    /// 挂机修炼：按现实时间记账，攒够一轮就结算到进行中的这局
+    fn tick_idle(&mut self, ctx: &egui::Context) {
+        let now = ctx.input(|i| i.time);
+        let elapsed = now - std::mem::replace(&mut self.last_idle_tick, now);
+        let idle = &mut self.settings.idle;
+        idle.accrue(chrono::Utc::now().timestamp(), elapsed);
+        // 没有进行中的对局时只攒时间，等开局后再结算
+        if !idle.enabled || !self.game.in_run() {
+            return;
+        }
+        let rounds = idle.take_rounds();
+        if rounds > 0 {
+            self.game.execute(GameCommand::IdleTrain(rounds));
+        }
+        ctx.request_repaint_after(std::time::Duration::from_secs_f64(idle.seconds_to_next_round()));
+    }
+
+    /// 处理游戏内快捷键（输入框获得焦点或正在改键时不响应）
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        // 过场自己处理翻页和跳过的按键
+        if self.rebinding.is_some() || self.interstitial.is_some() || ctx.memory(|m| m.focused().is_some()) {
+            return;
+        }
+        let pressed: Vec<Action> = Action::ALL
+            .iter()
+            .copied()
+            .filter(|action| {
+                self.settings
+                    .keys
+                    .key_for(*action)
+                    .is_some_and(|key| ctx.input(|i| i.key_pressed(key)))
+            })
+            .collect();
+
+        for action in pressed {
+            match action {
+                Action::Choose1 | Action::Choose2 | Action::Choose3 => {
+                    let in_event = matches!(
+                        self.game.phase,
+                        GamePhase::EventDisplay | GamePhase::WeeklyEventDisplay
+                    );
+                    if in_event {
+                        let choice = match action {
+                            Action::Choose1 => 1,
+                            Action::Choose2 => 2,
+                            _ => 3,
+                        };
+                        self.game.execute(GameCommand::Choose(choice));
+                    } else if self.game.phase == GamePhase::NightCall {
+                        match action {
+                            Action::Choose1 => self.game.execute(GameCommand::AnswerNightCall(NpcDecision::Accept)),
+                            Action::Choose2 => self.game.execute(GameCommand::AnswerNightCall(NpcDecision::Reject)),
+                            _ => {}
+                        }
+                    }
+                }
+                Action::Advance => {
+                    if self.game.phase == GamePhase::WeeklyRecap {
+                        self.game.execute(GameCommand::CloseRecap);
+                    } else if self.game.can_advance() {
+                        self.game.execute(GameCommand::NextDay);
+                    }
+                }
+                Action::UseHint => self.game.execute(GameCommand::Hint),
+                Action::ToggleStatsPanel => {
+                    let panels = &mut self.settings.panels;
+                    panels.stats_visible = !panels.stats_visible;
+                }
+                Action::ToggleSidePanel => {
+                    let panels = &mut self.settings.panels;
+                    panels.side_visible = !panels.side_visible;
+                }
+            }
+        }
+    }
+
+    fn draw_key_bindings(&mut self, ui: &mut egui::Ui) {
+        // 等待玩家按下新按键，Esc 取消
+        if let Some(action) = self.rebinding {
+            let pressed = ui.input(|i| {
+                i.events.iter().find_map(|event| match event {
+                    egui::Event::Key {
+                        key, pressed: true, ..
+                    } => Some(*key),
+                    _ => None,
+                })
+            });
+            if let Some(key) = pressed {
+                self.rebinding = None;
+                if key != Key::Escape {
+                    self.keybind_error = self
+                        .settings
+                        .keys
+                        .rebind(action, key)
+                        .err()
+                        .map(|other| format!("按键 {} 已被「{}」占用", key.name(), other.label()));
+                }
+            }
+        }
+
+        egui::Grid::new("key_bindings_grid").show(ui, |ui| {
+            for action in Action::ALL {
+                ui.label(action.label());
+                let text = if self.rebinding == Some(action) {
+                    "请按下新按键…".to_string()
+                } else {
+                    self.settings
+                        .keys
+                        .key_for(action)
+                        .map(|key| key.name().to_string())
+                        .unwrap_or_else(|| "未绑定".to_string())
+                };
+                let response = ui.button(text);
+                if response.clicked() {
+                    // 交出焦点，避免新按键（如空格）再次触发这个按钮
+                    response.surrender_focus();
+                    self.rebinding = Some(action);
+                    self.keybind_error = None;
+                }
+                ui.end_row();
+            }
+        });
+        if let Some(error) = &self.keybind_error {
+            ui.colored_label(self.palette().negative, error);
+        }
+        if ui.button("恢复默认按键").clicked() {
+            self.settings.keys.reset();
+            self.rebinding = None;
+            self.keybind_error = None;
+        }
+    }
+
+    fn draw_settings_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_settings;
+        egui::Window::new("⚙ 设置")
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label("界面布局：");
+                for mode in [LayoutMode::Compact, LayoutMode::Detailed] {
+                    ui.radio_value(&mut self.settings.layout, mode, mode.label());
+                }
+
+                ui.separator();
+                ui.heading("无障碍");
+                let prefs = &mut self.settings.accessibility;
+                let mut changed = false;
+                egui::ComboBox::from_label("配色方案")
+                    .selected_text(prefs.palette.label())
+                    .show_ui(ui, |ui| {
+                        for preset in PalettePreset::ALL {
+                            changed |= ui
+                                .selectable_value(&mut prefs.palette, preset, preset.label())
+                                .changed();
+                        }
+                    });
+                changed |= ui.checkbox(&mut prefs.high_contrast, "高对比度").changed();
+                changed |= ui
+                    .checkbox(&mut prefs.reduced_motion, "减少动效（关闭面板/折叠动画）")
+                    .changed();
+                if changed {
+                    theme::apply(ctx, prefs, &self.settings.appearance, self.applied_weekend);
+                }
+
+                self.draw_appearance_settings(ui, ctx);
+
+                ui.separator();
+                ui.heading("内容提示");
+                ui.horizontal(|ui| {
+                    ui.label("黑色段子与死亡描写：");
+                    for mode in DarkContent::ALL {
+                        ui.radio_value(&mut self.settings.dark_content, mode, mode.to_string())
+                            .on_hover_text(mode.description());
+                    }
+                });
+                self.game.dark_content = self.settings.dark_content;
+
+                ui.separator();
+                ui.heading("声音");
+                let sound = &mut self.settings.audio;
+                ui.checkbox(&mut sound.muted, "🔇 静音");
+                ui.add_enabled_ui(!sound.muted, |ui| {
+                    ui.add(egui::Slider::new(&mut sound.sfx_volume, 0.0..=1.0).text("音效音量"));
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut sound.music, "背景音乐");
+                        ui.add_enabled(sound.music, egui::Slider::new(&mut sound.music_volume, 0.0..=1.0).text("音量"));
+                    });
+                });
+                #[cfg(all(not(feature = "audio"), not(target_arch = "wasm32")))]
+                ui.weak("这个版本编译时没有开启 audio 特性，不会出声");
+
+                ui.separator();
+                ui.heading("大屏模式");
+                ui.checkbox(&mut self.settings.big_screen, "开启大屏模式（大字号、按键提示、长按显示说明）");
+                ui.checkbox(&mut self.settings.big_screen_auto, "检测到手柄时自动开启");
+                if self.gamepad_detected {
+                    ui.label("🎮 已检测到手柄");
+                }
+
+                ui.separator();
+                ui.heading("触屏");
+                ui.checkbox(&mut self.settings.touch_confirm, "选项两段确认（先点选中，再点一次或按确认才提交）");
+                ui.checkbox(&mut self.settings.touch_confirm_auto, "检测到触屏时自动开启");
+                if self.touch_detected {
+                    ui.label("👆 已检测到触屏");
+                }
+
+                #[cfg(feature = "highlight-capture")]
+                {
+                    ui.separator();
+                    ui.checkbox(
+                        &mut self.settings.record_highlights,
+                        "🎞 录制高光时刻（晋升成功或猝死时可导出 GIF）",
+                    );
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    #[cfg(not(target_arch = "wasm32"))]
+                    ui.checkbox(&mut self.settings.check_updates, "启动时检查新版本");
+                    #[cfg(not(target_arch = "wasm32"))]
+                    ui.checkbox(&mut self.settings.daily_reminder, "🔔 每日挑战提醒")
+                        .on_hover_text("窗口最小化时，如果今天的每日挑战还没打，发一条桌面通知");
+                    #[cfg(not(target_arch = "wasm32"))]
+                    ui.checkbox(&mut self.settings.stream_overlay, "📺 直播小窗")
+                        .on_hover_text("置顶的迷你窗口，只显示天数、境界、压力（速通模式下附带计时）");
+                    if ui.button("📜 更新日志").clicked() {
+                        self.show_changelog = true;
+                    }
+                    if ui.button("ℹ 关于").clicked() {
+                        self.show_about = true;
+                    }
+                    if ui.button("🌌 重看序章").clicked() {
+                        self.interstitial = Some(Interstitial::new(&interstitial::INTRO));
+                        self.show_settings = false;
+                    }
+                    ui.weak(format!("当前版本 v{}", updates::CURRENT_VERSION));
+                });
+
+                #[cfg(not(target_arch = "wasm32"))]
+                self.draw_data_dir_settings(ui);
+
+                ui.separator();
+                ui.heading("挂机修炼");
+                let idle = &mut self.settings.idle;
+                let (skill, pressure) = idle::rewards(1);
+                ui.checkbox(&mut idle.enabled, "🧘 挂机修炼")
+                    .on_hover_text(format!(
+                        "开着游戏或离线时（最多攒 8 小时），每 {} 分钟折算技能 {:+}、压力 {:+}，结算到进行中的这局",
+                        idle::SECONDS_PER_ROUND / 60.0,
+                        skill,
+                        pressure
+                    ));
+                if idle.enabled {
+                    ui.weak(format!("已攒 {:.0} 分钟", idle.banked_seconds() / 60.0));
+                }
+
+                ui.separator();
+                ui.heading("事件导演");
+                ui.add(
+                    egui::Slider::new(&mut self.settings.director.aggressiveness, 0.0..=1.0)
+                        .text("干预力度"),
+                )
+                .on_hover_text("0 为纯随机；越高越会在你快撑不住时安排轻松事件、太安逸时加点戏（下一局生效）");
+                if ui.button("⚖ 平衡模拟").on_hover_text("用代打策略按当前数值、难度和导演连打很多局，看看存活率").clicked() {
+                    self.show_simulation = true;
+                }
+
+                ui.separator();
+                ui.heading("AI 台词");
+                let llm = &mut self.settings.llm;
+                ui.checkbox(&mut llm.enabled, "🤖 用 AI 现编 NPC 台词")
+                    .on_hover_text("按你的属性和最近的抉择生成台词；没填 key 或请求失败时照旧用模板台词");
+                if llm.enabled {
+                    egui::Grid::new("llm_grid").num_columns(2).show(ui, |ui| {
+                        ui.label("接口地址：");
+                        ui.add(egui::TextEdit::singleline(&mut llm.endpoint).desired_width(260.0));
+                        ui.end_row();
+                        ui.label("模型：");
+                        ui.add(egui::TextEdit::singleline(&mut llm.model).desired_width(180.0));
+                        ui.end_row();
+                        ui.label("API key：");
+                        ui.add(egui::TextEdit::singleline(&mut llm.api_key).password(true).desired_width(260.0));
+                        ui.end_row();
+                    });
+                    if !self.llm_notice.is_empty() {
+                        ui.weak(format!("上次生成失败：{}", self.llm_notice));
+                    }
+                }
+
+                ui.separator();
+                ui.heading("赛事");
+                ui.horizontal(|ui| {
+                    ui.label("赛事密钥：");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.settings.tournament_key)
+                            .password(true)
+                            .hint_text("由主办方提供")
+                            .desired_width(180.0),
+                    );
+                });
+
+                ui.separator();
+                ui.heading("季节活动");
+                ui.label("限时内容包按现实日期自动开放，也可以手动强制开关（下一局生效）");
+                egui::Grid::new("season_grid").num_columns(3).show(ui, |ui| {
+                    for pack in ContentPack::ALL {
+                        ui.label(pack.to_string());
+                        ui.label(pack.window_label());
+                        let mut current = self.settings.seasons.override_for(pack);
+                        egui::ComboBox::from_id_source(("season_override", pack as u8))
+                            .selected_text(current.label())
+                            .show_ui(ui, |ui| {
+                                for option in PackOverride::ALL {
+                                    ui.selectable_value(&mut current, option, option.label());
+                                }
+                            });
+                        self.settings.seasons.set_override(pack, current);
+                        ui.end_row();
+                    }
+                });
+
+                ui.separator();
+                ui.heading("快捷键");
+                self.draw_key_bindings(ui);
+
+                ui.separator();
+                ui.weak(self.session_stats.summary());
+            });
+        self.show_settings = open;
+    }
+
+    /// 选项是否要两段确认：手动开启，或检测到触屏时自动开启
+    fn two_stage_confirm(&self) -> bool {
+        self.settings.touch_confirm || (self.settings.touch_confirm_auto && self.touch_detected)
+    }
+
+    /// 一旦收到触摸输入就认定是触屏设备（本次运行内不再改回）
+    fn update_touch(&mut self, ctx: &egui::Context) {
+        if !self.touch_detected {
+            self.touch_detected = ctx.input(|i| {
+                i.any_touches() || i.events.iter().any(|event| matches!(event, egui::Event::Touch { .. }))
+            });
+        }
+    }
+
+    /// 大屏模式：手动开启，或检测到手柄时自动开启
+    fn big_screen(&self) -> bool {
+        self.settings.big_screen || (self.settings.big_screen_auto && self.gamepad_detected)
+    }
+
+    /// 大屏模式下在按钮上标出对应的快捷键
+    fn key_hint(&self, action: Action) -> String {
+        match self.settings.keys.key_for(action) {
+            Some(key) if self.big_screen() => format!("〔{}〕", key.name()),
+            _ => String::new(),
+        }
+    }
+
+    fn update_big_screen(&mut self, ctx: &egui::Context) {
+        let now = ctx.input(|i| i.time);
+        if self.settings.big_screen_auto && now - self.last_gamepad_poll > 2.0 {
+            self.last_gamepad_poll = now;
+            self.gamepad_detected = gamepad::gamepad_connected();
+        }
+        let big_screen = self.big_screen();
+        if big_screen != self.applied_big_screen {
+            self.applied_big_screen = big_screen;
+            theme::apply_scale(ctx, self.settings.appearance.ui_scale, big_screen);
+        }
+    }
+
+    /// 外观：界面缩放、深浅色、强调色、表情符号、历史条数与字体，改动立即生效
+    fn draw_appearance_settings(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        ui.separator();
+        ui.heading("外观");
+        let [r, g, b, _] = self.palette_without_accent().highlight.to_array();
+        let appearance = &mut self.settings.appearance;
+        let scale = egui::Slider::new(&mut appearance.ui_scale, AppearancePrefs::SCALE_RANGE)
+            .step_by(0.1)
+            .text("界面缩放");
+        if ui.add(scale).changed() {
+            theme::apply_scale(ctx, appearance.ui_scale, self.applied_big_screen);
+        }
+        let mut changed = ui.checkbox(&mut appearance.light, "浅色主题").changed();
+        ui.horizontal(|ui| {
+            let mut custom = appearance.accent.is_some();
+            if ui.checkbox(&mut custom, "自定义强调色").changed() {
+                appearance.accent = custom.then_some([r, g, b]);
+                changed = true;
+            }
+            if let Some(accent) = appearance.accent.as_mut() {
+                changed |= ui.color_edit_button_srgb(accent).changed();
+            }
+        });
+        if changed {
+            theme::apply(ctx, &self.settings.accessibility, appearance, self.applied_weekend);
+        }
+        ui.checkbox(&mut appearance.show_emoji, "在剧情与历史里显示表情符号");
+        ui.checkbox(&mut appearance.scenery, "对局中显示办公室背景（窗外天色随时段和天气变化）");
+        ui.horizontal(|ui| {
+            ui.label("历史面板最多显示最近");
+            ui.add(egui::DragValue::new(&mut appearance.history_limit).clamp_range(0..=10_000).speed(10));
+            ui.label("条（0 为不限）");
+        });
+        #[cfg(not(target_arch = "wasm32"))]
+        self.draw_font_settings(ui, ctx);
+    }
+
+    /// 不套用自定义强调色时的配色（打开自定义时以它为起点）
+    fn palette_without_accent(&self) -> Palette {
+        let appearance = AppearancePrefs { accent: None, ..self.settings.appearance.clone() };
+        theme::palette(&self.settings.accessibility, &appearance, self.applied_weekend)
+    }
+
+    /// 中文字体：系统字体找不到时，可以手动指定一个字体文件（优先于 config.toml）
+    #[cfg(not(target_arch = "wasm32"))]
+    fn draw_font_settings(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        if !self.font_loaded {
+            ui.colored_label(self.palette().negative, "没找到中文字体，文字可能显示为方块，请手动指定字体文件");
+        }
+        if let Some(path) = &self.settings.appearance.font_path {
+            ui.small(format!("当前字体：{}", path.display()));
+        }
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.font_input)
+                    .hint_text("字体文件路径（.ttf/.ttc/.otf）")
+                    .desired_width(220.0),
+            );
+            if ui.button("📂 选择…").clicked() {
+                if let Some(path) = rfd::FileDialog::new()
+                    .set_title("选择中文字体")
+                    .add_filter("字体", &["ttf", "ttc", "otf"])
+                    .pick_file()
+                {
+                    self.font_input = path.display().to_string();
+                }
+            }
+        });
+        ui.horizontal(|ui| {
+            let input = self.font_input.trim();
+            if ui.add_enabled(!input.is_empty(), egui::Button::new("载入字体")).clicked() {
+                let path = std::path::PathBuf::from(input);
+                match std::fs::read(&path) {
+                    // 不是字体文件时 egui 会直接崩溃，先看一眼文件头
+                    Ok(font_data) if !is_font_file(&font_data) => {
+                        self.font_notice = format!("{} 不是 TrueType/OpenType 字体文件", path.display());
+                    }
+                    Ok(font_data) => {
+                        install_chinese_font(ctx, font_data);
+                        self.font_loaded = true;
+                        self.font_notice = format!("已载入字体 {}", path.display());
+                        self.settings.appearance.font_path = Some(path);
+                    }
+                    Err(err) => self.font_notice = format!("读取字体失败：{}", err),
+                }
+            }
+            if self.settings.appearance.font_path.is_some() && ui.button("恢复默认").clicked() {
+                self.settings.appearance.font_path = None;
+                self.font_loaded = setup_chinese_fonts(ctx, &[self.config.font_path.as_deref()]);
+                self.font_notice.clear();
+            }
+        });
+        if !self.font_notice.is_empty() {
+            ui.small(&self.font_notice);
+        }
+    }
+
+    /// 数据目录：查看、打开、更换（更换时搬走已有的导出文件）
+    #[cfg(not(target_arch = "wasm32"))]
+    fn draw_data_dir_settings(&mut self, ui: &mut egui::Ui) {
+        ui.separator();
+        ui.heading("数据目录");
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.data_dir_input).desired_width(260.0));
+            if ui.button("📂 打开").clicked() {
+                if let Err(err) = self.data_dir.open_in_file_manager() {
+                    self.data_notice = err;
+                }
+            }
+        });
+        ui.horizontal(|ui| {
+            let input = self.data_dir_input.trim();
+            let changed = !input.is_empty() && std::path::Path::new(input) != self.data_dir.root();
+            if ui.add_enabled(changed, egui::Button::new("更改")).clicked() {
+                self.change_data_dir(Some(std::path::PathBuf::from(input)));
+            }
+            if ui.button("恢复默认").clicked() {
+                self.change_data_dir(None);
+            }
+        });
+        if !self.data_notice.is_empty() {
+            ui.small(&self.data_notice);
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn change_data_dir(&mut self, custom: Option<std::path::PathBuf>) {
+        let new_dir = datadir::DataDir::resolve(custom.as_deref());
+        self.data_notice = match new_dir.migrate_from(&self.data_dir) {
+            Ok(migration) => {
+                let switched = match migration.moved {
+                    0 => format!("数据目录已切换到 {}", new_dir.root().display()),
+                    moved => format!("数据目录已切换，搬移了 {} 个文件", moved),
+                };
+                match migration.conflicts() {
+                    Some(conflicts) => format!("{}；{}", switched, conflicts),
+                    None => switched,
+                }
+            }
+            Err(err) => format!("搬移文件失败: {}", err),
+        };
+        self.data_dir_input = new_dir.root().display().to_string();
+        self.assets = asset_cache(&new_dir);
+        self.audio = audio::Audio::new(self.assets.root().clone());
+        self.data_dir = new_dir;
+        self.settings.data_dir = custom;
+        self.load_scenarios();
+    }
+
+    /// 累计游玩时间：打开设置、窗口失焦或最小化时暂停
+    fn tick_play_time(&mut self, ctx: &egui::Context) {
+        let paused = self.show_settings
+            || !self.game.in_run()
+            || !ctx.input(|i| i.focused)
+            || ctx.input(|i| i.viewport().minimized) == Some(true);
+        let Some(state) = self.game.game_state.as_mut().filter(|_| !paused) else {
+            self.play_clock = None;
+            return;
+        };
+        let now = instant::Instant::now();
+        if let Some(last) = self.play_clock {
+            state.tick(now - last);
+        }
+        self.play_clock = Some(now);
+    }
+
+    /// AI 台词：NPC 找上门时请求一次，台词到了就换掉模板台词（请求已处理完则丢弃）
+    fn tick_npc_voice(&mut self, ctx: &egui::Context) {
+        let finished = self.npc_voice.as_ref().and_then(|voice| Some((voice.key, voice.poll()?)));
+        if let Some((key, result)) = finished {
+            self.npc_voice = None;
+            match result {
+                Ok(line) => {
+                    self.llm_notice.clear();
+                    let state = self.game.game_state.as_mut().filter(|state| llm::voice_key(state) == Some(key));
+                    if let Some(state) = state {
+                        state.voice_npc(key.2, &line);
+                    }
+                }
+                Err(err) => self.llm_notice = err,
+            }
+        }
+        if self.npc_voice.is_some() {
+            ctx.request_repaint_after(std::time::Duration::from_millis(200));
+            return;
+        }
+        if !self.settings.llm.is_ready() {
+            return;
+        }
+        let Some(state) = self.game.game_state.as_ref() else {
+            return;
+        };
+        let key = llm::voice_key(state);
+        if key.is_none() || key == self.voiced_npc {
+            return;
+        }
+        self.voiced_npc = key;
+        self.npc_voice = llm::NpcVoice::spawn(&self.settings.llm, state);
+    }
+
+    /// 轮询后台的更新检查，有新版本时在顶部显示一条可关闭的提示
+    #[cfg(not(target_arch = "wasm32"))]
+    fn draw_update_banner(&mut self, ctx: &egui::Context) {
+        if let Some(result) = self.update_checker.as_ref().and_then(|c| c.poll()) {
+            self.available_update = result;
+            self.update_checker = None;
+        }
+        if self.update_checker.is_some() {
+            ctx.request_repaint_after(std::time::Duration::from_millis(500));
+        }
+        let Some(release) = self.available_update.clone() else {
+            return;
+        };
+        egui::TopBottomPanel::top("update_banner").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(format!("🆕 新版本可用：{}", release.version));
+                if !release.url.is_empty() {
+                    ui.hyperlink_to("前往下载", &release.url);
+                }
+                if ui.button("更新日志").clicked() {
+                    self.show_changelog = true;
+                }
+                if ui.small_button("✖").clicked() {
+                    self.available_update = None;
+                }
+            });
+        });
+    }
+
+    /// config.toml 或命令行参数有问题时在顶部列出，可关闭
+    #[cfg(not(target_arch = "wasm32"))]
+    fn draw_launch_banner(&mut self, ctx: &egui::Context) {
+        if self.launch_errors.is_empty() {
+            return;
+        }
+        let palette = self.palette();
+        egui::TopBottomPanel::top("launch_banner").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.vertical(|ui| {
+                    for err in &self.launch_errors {
+                        ui.colored_label(palette.negative, format!("⚠ {}", err));
+                    }
+                });
+                if ui.small_button("✖").clicked() {
+                    self.launch_errors.clear();
+                }
+            });
+        });
+    }
+
+    /// 网页端：字体与内容下载完之前只显示进度（此时还没有中文字体，只用 ASCII），
+    /// 到齐后装上字体、装入内容；返回是否已可以进入游戏
+    #[cfg(target_arch = "wasm32")]
+    fn load_web_assets(&mut self, ctx: &egui::Context) -> bool {
+        let Some(loader) = &self.web_assets else {
+            return true;
+        };
+        if loader.finished() {
+            for (kind, bytes) in loader.take() {
+                match kind {
+                    webassets::AssetKind::Font => install_chinese_font(ctx, bytes),
+                    webassets::AssetKind::Content(file) => file.install(String::from_utf8_lossy(&bytes).into_owned()),
+                }
+            }
+            self.web_assets = None;
+            // 剧本校验和可禁用事件都要用到事件库
+            self.bannable_events = GameState::bannable_events();
+            self.load_scenarios();
+            return true;
+        }
+        const MB: f32 = 1024.0 * 1024.0;
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.add_space(ui.available_height() / 3.0);
+                ui.heading("Loading...");
+                ui.add_space(8.0);
+                for (url, loaded, total) in loader.progress() {
+                    let text = match total {
+                        Some(total) => format!("{}  {:.1} / {:.1} MB", url, loaded as f32 / MB, total as f32 / MB),
+                        None => format!("{}  {:.1} MB", url, loaded as f32 / MB),
+                    };
+                    let fraction = total.map_or(0.0, |total| loaded as f32 / total.max(1) as f32);
+                    ui.add(egui::ProgressBar::new(fraction).desired_width(360.0).text(text));
+                }
+                if let Some(err) = loader.error() {
+                    ui.add_space(8.0);
+                    ui.colored_label(egui::Color32::LIGHT_RED, format!("Failed to load {}", err));
+                    ui.label("Refresh the page to retry.");
+                }
+            });
+        });
+        false
+    }
+
+    /// 按 config.toml 的帧率上限，把这一帧剩余的时间睡掉
+    #[cfg(not(target_arch = "wasm32"))]
+    fn limit_frame_rate(&mut self) {
+        if let Some(fps) = self.config.fps_cap {
+            let budget = std::time::Duration::from_secs_f64(1.0 / fps as f64);
+            let elapsed = self.last_frame.elapsed();
+            if elapsed < budget {
+                std::thread::sleep(budget - elapsed);
+            }
+        }
+        self.last_frame = std::time::Instant::now();
+    }
+
+    /// 最小化时若今天的每日挑战还没打，发一条桌面通知（每天一次）
+    #[cfg(not(target_arch = "wasm32"))]
+    fn remind_daily(&mut self, ctx: &egui::Context) {
+        if !self.settings.daily_reminder || self.daily_played_today() {
+            return;
+        }
+        let today = daily::today();
+        if self.daily_reminded.as_ref() == Some(&today) {
+            return;
+        }
+        // 最小化后界面不再因输入刷新，定时醒来看一眼
+        ctx.request_repaint_after(std::time::Duration::from_secs(60));
+        if ctx.input(|i| i.viewport().minimized) == Some(true) {
+            notify::remind_daily_challenge();
+            self.daily_reminded = Some(today);
+        }
+    }
+
+    /// 开启直播小窗时把最新的天数、境界、压力同步过去
+    #[cfg(not(target_arch = "wasm32"))]
+    fn update_stream_overlay(&mut self, ctx: &egui::Context) {
+        if !self.settings.stream_overlay {
+            return;
+        }
+        let palette = self.palette();
+        let speedrun = self.settings.speedrun;
+        self.stream_overlay.set(self.game.game_state.as_ref().map(|state| {
+            let pressure = state.player.pressure;
+            overlay::OverlaySnapshot {
+                day: state.current_day,
+                realm: state.player.get_realm(),
+                pressure,
+                pressure_band: game::PlayerState::pressure_band(pressure),
+                pressure_color: if pressure >= 50 { palette.negative } else { palette.pressure },
+                timer_ms: speedrun.then(|| state.elapsed_millis()),
+            }
+        }));
+        if !self.stream_overlay.show(ctx) {
+            self.settings.stream_overlay = false;
+        }
+    }
+
+    /// 状态检查面板：最近两次推进之间 GameState 的变化
+    #[cfg(feature = "debug")]
+    fn draw_inspector(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_inspector;
+        egui::Window::new("🔍 状态检查")
+            .open(&mut open)
+            .default_width(520.0)
+            .show(ctx, |ui| {
+                let Some((before, after)) = self.inspector.labels() else {
+                    ui.weak("推进两次之后才有可比较的快照");
+                    return;
+                };
+                ui.label(format!("之前：{}", before));
+                ui.label(format!("之后：{}", after));
+                ui.horizontal(|ui| {
+                    ui.label(format!("共 {} 处变化", self.inspector.changes().len()));
+                    if ui.small_button("清空").clicked() {
+                        self.inspector.clear();
+                    }
+                });
+                ui.separator();
+                egui::ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                    egui::Grid::new("inspector_grid").striped(true).num_columns(4).show(ui, |ui| {
+                        ui.strong("字段");
+                        ui.strong("之前");
+                        ui.strong("之后");
+                        ui.strong("变化");
+                        ui.end_row();
+                        for change in self.inspector.changes() {
+                            ui.monospace(&change.path);
+                            ui.label(&change.before);
+                            ui.label(&change.after);
+                            ui.label(change.delta.map(|d| format!("{:+}", d)).unwrap_or_default());
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+        self.show_inspector = open;
+    }
+
+    /// 帧耗时面板：每帧耗时、分配次数，以及事件元数据路径的开销
+    #[cfg(feature = "debug")]
+    fn draw_profiler(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_profiler;
+        egui::Window::new("⏱ 帧耗时")
+            .open(&mut open)
+            .default_width(360.0)
+            .show(ctx, |ui| {
+                let frames = self.profiler.frames();
+                let metadata = self.profiler.metadata();
+                ui.label(format!(
+                    "最近 {} 帧 · 平均帧间隔 {:.1} ms · 超出 {:.1} ms 预算 {} 帧",
+                    frames.samples,
+                    self.profiler.avg_interval_ms(),
+                    profiler::FRAME_BUDGET_MS,
+                    self.profiler.over_budget()
+                ));
+                egui::Grid::new("profiler_grid").striped(true).num_columns(6).show(ui, |ui| {
+                    for title in ["", "平均耗时", "最长耗时", "平均分配", "最多分配", "平均分配量"] {
+                        ui.strong(title);
+                    }
+                    ui.end_row();
+                    for (name, summary) in [("整帧", frames), ("事件元数据", metadata)] {
+                        ui.label(name);
+                        ui.monospace(format!("{:.3} ms", summary.avg_ms));
+                        ui.monospace(format!("{:.3} ms", summary.max_ms));
+                        ui.monospace(format!("{:.0} 次", summary.avg_allocs));
+                        ui.monospace(format!("{} 次", summary.max_allocs));
+                        ui.monospace(format!("{:.1} KB", summary.avg_kb));
+                        ui.end_row();
+                    }
+                });
+                if frames.avg_allocs > 0.0 {
+                    ui.weak(format!(
+                        "事件元数据占每帧分配的 {:.0}%",
+                        metadata.avg_allocs / frames.avg_allocs * 100.0
+                    ));
+                }
+
+                // 最近各帧耗时的柱状图，红线为帧预算
+                let (rect, _) = ui.allocate_exact_size(egui::vec2(ui.available_width(), 60.0), egui::Sense::hover());
+                let painter = ui.painter_at(rect);
+                let scale = rect.height() / (profiler::FRAME_BUDGET_MS * 2.0) as f32;
+                let budget_y = rect.bottom() - profiler::FRAME_BUDGET_MS as f32 * scale;
+                let bar = rect.width() / profiler::WINDOW as f32;
+                for (i, ms) in self.profiler.frame_times().enumerate() {
+                    let x = rect.left() + i as f32 * bar;
+                    let top = (rect.bottom() - ms as f32 * scale).max(rect.top());
+                    let color = if ms > profiler::FRAME_BUDGET_MS {
+                        egui::Color32::LIGHT_RED
+                    } else {
+                        egui::Color32::LIGHT_GREEN
+                    };
+                    painter.rect_filled(
+                        egui::Rect::from_min_max(egui::pos2(x, top), egui::pos2(x + bar * 0.8, rect.bottom())),
+                        0.0,
+                        color,
+                    );
+                }
+                painter.hline(rect.x_range(), budget_y, egui::Stroke::new(1.0, egui::Color32::RED));
+                if ui.small_button("清空").clicked() {
+                    self.profiler.clear();
+                }
+            });
+        self.show_profiler = open;
+    }
+
+    /// 平衡模拟窗口：后台连打很多局，边跑边显示进度和汇总
+    fn draw_simulation(&mut self, ctx: &egui::Context) {
+        let running = self.simulation.as_mut().is_some_and(|sim| sim.poll());
+        if running {
+            ctx.request_repaint_after(std::time::Duration::from_millis(100));
+        }
+        if !self.show_simulation {
+            return;
+        }
+        let mut open = self.show_simulation;
+        egui::Window::new("⚖ 平衡模拟")
+            .open(&mut open)
+            .default_width(360.0)
+            .show(ctx, |ui| {
+                ui.weak("按当前的数值预设、难度和事件导演设置，用代打策略连打很多局");
+                ui.add_enabled_ui(!running, |ui| {
+                    egui::ComboBox::from_label("代打策略")
+                        .selected_text(self.sim_policy.label())
+                        .show_ui(ui, |ui| {
+                            for policy in AutoPolicy::ALL {
+                                ui.selectable_value(&mut self.sim_policy, policy, policy.label());
+                            }
+                        });
+                    ui.add(egui::Slider::new(&mut self.sim_runs, 10..=5000).logarithmic(true).text("局数"));
+                });
+                ui.horizontal(|ui| {
+                    if running {
+                        if ui.button("⏹ 停止").clicked() {
+                            if let Some(sim) = &mut self.simulation {
+                                sim.cancel();
+                            }
+                        }
+                    } else if ui.button("▶ 开始").clicked() {
+                        self.simulation = Some(simulation::Simulation::start(simulation::SimConfig {
+                            balance: self
+                                .settings
+                                .balance
+                                .config()
+                                .with_difficulty(self.settings.difficulty)
+                                .with_pressure_model(self.settings.pressure_model),
+                            director: self.settings.director,
+                            policy: self.sim_policy,
+                            runs: self.sim_runs,
+                            seed: rand::random(),
+                        }));
+                    }
+                });
+                let Some(sim) = &self.simulation else {
+                    return;
+                };
+                let (done, total) = sim.progress();
+                ui.add(
+                    egui::ProgressBar::new(done as f32 / total.max(1) as f32)
+                        .text(format!("{} / {} 局", done, total)),
+                );
+                if let Some(secs) = sim.elapsed_secs() {
+                    ui.weak(format!("用时 {:.1} 秒", secs));
+                }
+                let report = sim.report();
+                if report.runs == 0 {
+                    return;
+                }
+                egui::Grid::new("simulation_grid").num_columns(2).show(ui, |ui| {
+                    ui.label("存活率");
+                    ui.monospace(format!("{:.1}%", report.survival_rate() * 100.0));
+                    ui.end_row();
+                    ui.label("平均天数");
+                    ui.monospace(format!("{:.1} 天", report.avg_days()));
+                    ui.end_row();
+                    ui.label("平均技能点");
+                    ui.monospace(format!("{:.0}", report.avg_skills()));
+                    ui.end_row();
+                });
+                ui.label("结束时的境界：");
+                for (level, share) in (1..).zip(report.realm_shares()) {
+                    ui.add(egui::ProgressBar::new(share).text(format!("{} {:.1}%", Realm::from_level(level), share * 100.0)));
+                }
+            });
+        self.show_simulation = open;
+    }
+
+    /// 平衡 A/B 对比窗口：两份配置文件改动后自动重新读入、重跑模拟，并排显示结果
+    #[cfg(all(feature = "debug", not(target_arch = "wasm32")))]
+    fn draw_balance_ab(&mut self, ctx: &egui::Context) {
+        let (running, reloaded) = self.balance_ab.tick(ctx.input(|i| i.time), self.settings.director);
+        if running {
+            ctx.request_repaint_after(std::time::Duration::from_millis(100));
+        }
+        if !reloaded.is_empty() {
+            self.game.push_notice(format!("🆎 平衡配置 {} 已重新载入", reloaded.join("、")));
+        }
+        if !self.show_balance_ab {
+            return;
+        }
+        let mut open = self.show_balance_ab;
+        let current = self
+            .settings
+            .balance
+            .config()
+            .with_difficulty(self.settings.difficulty)
+            .with_pressure_model(self.settings.pressure_model);
+        let ab = &mut self.balance_ab;
+        egui::Window::new("🆎 平衡对比")
+            .open(&mut open)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                ui.weak("两份配置用同一批种子各模拟一遍；改了文件保存后自动重新读入");
+                for (index, (name, path)) in balance_ab::SLOTS.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.strong(*name);
+                        ui.monospace(*path).on_hover_text("BalanceConfig 的 JSON，缺的字段按标准预设补齐");
+                        if ui.small_button("导出当前预设").clicked() {
+                            if let Err(err) = ab.export(index, &current) {
+                                ab.slots[index].error = Some(err);
+                            }
+                        }
+                    });
+                    match (&ab.slots[index].error, &ab.slots[index].config) {
+                        (Some(err), _) => ui.colored_label(egui::Color32::LIGHT_RED, err),
+                        (None, Some(config)) => ui.weak(format!("{} · {}", config.preset.label(), config.difficulty.label())),
+                        (None, None) => ui.weak("文件还不存在"),
+                    };
+                }
+                ui.add_enabled_ui(!running, |ui| {
+                    egui::ComboBox::from_label("代打策略")
+                        .selected_text(ab.policy.label())
+                        .show_ui(ui, |ui| {
+                            for policy in AutoPolicy::ALL {
+                                ui.selectable_value(&mut ab.policy, policy, policy.label());
+                            }
+                        });
+                    ui.add(egui::Slider::new(&mut ab.runs, 10..=5000).logarithmic(true).text("每份局数"));
+                });
+                ui.horizontal(|ui| {
+                    if running {
+                        if ui.button("⏹ 停止").clicked() {
+                            ab.stop();
+                        }
+                    } else if ui.add_enabled(ab.ready(), egui::Button::new("▶ 对比")).clicked() {
+                        ab.start(self.settings.director);
+                    }
+                    ui.checkbox(&mut ab.auto_rerun, "配置改动后自动重跑");
+                });
+                let Some(progress) = ab.progress() else {
+                    return;
+                };
+                for ((name, _), (done, total)) in balance_ab::SLOTS.iter().zip(progress) {
+                    ui.add(
+                        egui::ProgressBar::new(done as f32 / total.max(1) as f32)
+                            .text(format!("{} {} / {} 局", name, done, total)),
+                    );
+                }
+                egui::Grid::new("balance_ab_grid").striped(true).num_columns(4).show(ui, |ui| {
+                    for title in ["", "A", "B", "B − A"] {
+                        ui.strong(title);
+                    }
+                    ui.end_row();
+                    for (label, a, b, percent) in ab.rows() {
+                        ui.label(label);
+                        if percent {
+                            ui.monospace(format!("{:.1}%", a * 100.0));
+                            ui.monospace(format!("{:.1}%", b * 100.0));
+                            ui.monospace(format!("{:+.1}%", (b - a) * 100.0));
+                        } else {
+                            ui.monospace(format!("{:.1}", a));
+                            ui.monospace(format!("{:.1}", b));
+                            ui.monospace(format!("{:+.1}", b - a));
+                        }
+                        ui.end_row();
+                    }
+                });
+            });
+        self.show_balance_ab = open;
+    }
+
+    fn draw_changelog(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_changelog;
+        egui::Window::new("📜 更新日志")
+            .open(&mut open)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                    widgets::markdown(ui, updates::CHANGELOG);
+                });
+            });
+        self.show_changelog = open;
+    }
+
+    /// 关于：鸣谢名单、剧本包作者、本次载入的内容数量与第三方库许可证
+    fn draw_about(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_about;
+        egui::Window::new("ℹ 关于")
+            .open(&mut open)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical().max_height(480.0).show(ui, |ui| {
+                    ui.strong(format!("{} v{}", APP_TITLE, updates::CURRENT_VERSION));
+                    widgets::markdown(ui, credits::CREDITS);
+
+                    ui.separator();
+                    ui.strong("剧本包作者");
+                    for pack in &self.scenario_packs {
+                        let author = if pack.author.is_empty() { "佚名" } else { pack.author.as_str() };
+                        ui.label(format!("{} —— {}（{} 个剧本）", pack.name, author, pack.scenarios.len()));
+                    }
+
+                    // 桌面端的事件库带着玩家的覆盖文件，这里数的是实际载入的
+                    ui.separator();
+                    ui.strong("已载入内容");
+                    let (daily, weekly) = xiuxian_core::events::library().counts();
+                    let scenarios: usize = self.scenario_packs.iter().map(|pack| pack.scenarios.len()).sum();
+                    egui::Grid::new("content_counts").num_columns(2).show(ui, |ui| {
+                        for (label, count) in [
+                            ("日常事件", daily),
+                            ("周事件", weekly),
+                            ("同事", xiuxian_core::roster::roster_size()),
+                            ("结局", ending::Ending::ALL.len()),
+                            ("成就", Achievement::ALL.len()),
+                            ("道具", Item::ALL.len()),
+                            ("挑战剧本", scenarios),
+                        ] {
+                            ui.label(label);
+                            ui.monospace(count.to_string());
+                            ui.end_row();
+                        }
+                    });
+
+                    ui.separator();
+                    egui::CollapsingHeader::new("📦 第三方库许可证").show(ui, |ui| {
+                        ui.small("构建时从 Cargo.lock 整理，只列出本机构建时下载过源码的库");
+                        for (license, count) in credits::license_summary() {
+                            ui.label(format!("{}：{} 个", license, count));
+                        }
+                        ui.add_space(6.0);
+                        egui::Grid::new("dependency_licenses").num_columns(3).striped(true).show(ui, |ui| {
+                            for dependency in credits::dependencies() {
+                                ui.label(dependency.name);
+                                ui.weak(dependency.version);
+                                ui.label(dependency.license);
+                                ui.end_row();
+                            }
+                        });
+                    });
+                });
+            });
+        self.show_about = open;
+    }
+
+    /// 晋升成功或猝死时冻结最近几秒画面
+    fn on_highlight_moment(&mut self) {
+        #[cfg(feature = "highlight-capture")]
+        if self.settings.record_highlights {
+            self.highlights.freeze();
+            self.highlight_notice.clear();
+        }
+    }
+
+    fn update_highlights(&mut self, ctx: &egui::Context) {
+        #[cfg(feature = "highlight-capture")]
+        if self.settings.record_highlights {
+            self.highlights.tick(ctx);
+        }
+        #[cfg(not(feature = "highlight-capture"))]
+        let _ = ctx;
+    }
+
+    /// 把引擎本帧发布的事件分发给各个订阅的子系统
+    fn dispatch_engine_events(&mut self) {
+        let events = self.game.bus.drain();
+        #[cfg(feature = "debug")]
+        if let (false, Some(state)) = (events.is_empty(), &self.game.game_state) {
+            let label: Vec<String> = events.iter().map(|e| format!("{:?}", e)).collect();
+            self.inspector.record(label.join(" + "), state.inspect());
+        }
+        self.audio.on_events(&events, &self.settings.audio);
+        if !events.is_empty() {
+            self.collect_achievements();
+        }
+        for event in events {
+            self.session_stats.on_event(&event);
+            // 选项顺序不打乱时谈不上位置偏好，不计入
+            if !self.auto_play.enabled && self.game.shuffle == ShuffleMode::每次打乱 {
+                self.pick_stats.on_event(&event);
+            }
+            match event {
+                GameEvent::DeathRolled { survived: false }
+                | GameEvent::PromotionResolved { success: true, .. } => self.on_highlight_moment(),
+                _ => {}
+            }
+            match event {
+                GameEvent::PromotionResolved { realm: Realm::化神期, .. } => self.collect_victory(),
+                GameEvent::RunEnded => {
+                    // 打完的局不再留存档（照常由 store_profile 计入生涯统计）
+                    if let Some(slot) = self.active_slot {
+                        self.saves.delete(slot);
+                    }
+                    self.collect_scenario_result();
+                    self.collect_victory();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// 挑战结束时记录成绩，刷新最高分时在结果里提示
+    fn collect_scenario_result(&mut self) {
+        let Some((id, score)) = self
+            .game
+            .game_state
+            .as_ref()
+            .and_then(|state| Some((state.scenario.as_ref()?.id.clone(), state.score())))
+        else {
+            return;
+        };
+        if self.scenario_scores.record(&id, score) {
+            self.game.result_message.push(Style::喜报, "\n🏆 新的最高分！");
+        }
+    }
+
+    /// 常规对局通关时解锁下一个飞升等级
+    fn collect_victory(&mut self) {
+        let run_over = self.game.phase == GamePhase::GameOver;
+        let Some(level) = self
+            .regular_run()
+            .filter(|state| !self.game.victory_recorded && state.is_victory(run_over))
+            .map(|state| state.ascension)
+        else {
+            return;
+        };
+        self.game.victory_recorded = true;
+        if level >= self.settings.ascension_unlocked && level < ascension::MAX_LEVEL {
+            self.settings.ascension_unlocked = level + 1;
+            self.game.push_notice(format!(
+                "🌟 通关！解锁飞升等级 {}：{}",
+                level + 1,
+                ascension::handicaps(level + 1)[level as usize].description()
+            ));
+        }
+    }
+
+    /// 按当前对局检查还没解锁的成就，新解锁的弹一条提示（代打时不检查）
+    fn collect_achievements(&mut self) {
+        if self.auto_play.enabled {
+            return;
+        }
+        let run_over = self.game.phase == GamePhase::GameOver;
+        let Some(state) = &self.game.game_state else {
+            return;
+        };
+        for achievement in self.achievements.check(state, run_over, &daily::today()) {
+            self.game.push_notice(format!("🏅 成就解锁：{} {}", achievement.icon(), achievement));
+        }
+    }
+
+    /// 结果画面上的“保存高光时刻”按钮
+    fn draw_highlight_button(&mut self, ui: &mut egui::Ui) {
+        #[cfg(feature = "highlight-capture")]
+        if self.highlights.has_capture() && ui.button("🎞 保存高光时刻").clicked() {
+            let saved = self.data_dir.exports().and_then(|dir| self.highlights.save_gif(&dir));
+            self.highlight_notice = match saved {
+                Ok(path) => format!("已保存到 {}", path.display()),
+                Err(err) => err,
+            };
+            self.highlights.clear_capture();
+        }
+        if !self.highlight_notice.is_empty() {
+            ui.label(&self.highlight_notice);
+        }
+    }
+
+    /// 对局中的办公室背景（高对比度下不画，免得影响可读性）
+    fn draw_scenery(&self, ui: &egui::Ui) {
+        if !self.settings.appearance.scenery || self.settings.accessibility.high_contrast || self.interstitial.is_some() {
+            return;
+        }
+        let Some(state) = self.game.game_state.as_ref().filter(|_| self.game.in_run()) else {
+            return;
+        };
+        // 一天的进度：还没做日常是上午，做完是下午，晋升、深夜来电和周报都在夜里
+        let progress = match self.game.phase {
+            GamePhase::PromotionConfirm | GamePhase::NightCall | GamePhase::WeeklyRecap => 0.95,
+            _ if state.event_chosen_today => 0.65,
+            _ => 0.3,
+        };
+        let palette = self.palette();
+        scenery::paint(ui.painter(), ui.clip_rect(), progress, state.weather(), palette.background, palette.text);
+    }
+
+    fn palette(&self) -> Palette {
+        theme::palette(&self.settings.accessibility, &self.settings.appearance, self.applied_weekend)
+    }
+
+    /// 把天数、境界、压力写进窗口标题（网页端是页面标题），切到后台也能看到；只在变化时更新
+    #[cfg_attr(target_arch = "wasm32", allow(unused_variables))]
+    fn update_window_title(&mut self, ctx: &egui::Context) {
+        let title = match self.game.game_state.as_ref().filter(|_| self.game.in_run()) {
+            Some(state) => format!(
+                "第{}天 · {} · 压力{} — {}",
+                state.current_day,
+                Realm::from_level(state.player.realm_level),
+                state.player.pressure,
+                APP_TITLE
+            ),
+            None => APP_TITLE.to_string(),
+        };
+        if title == self.window_title {
+            return;
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        ctx.send_viewport_cmd(egui::ViewportCommand::Title(title.clone()));
+        #[cfg(target_arch = "wasm32")]
+        if let Some(document) = web_sys::window().and_then(|window| window.document()) {
+            document.set_title(&title);
+        }
+        self.window_title = title;
+    }
+
+    /// 对局中的周六周日换一套轻松配色
+    fn update_weekend_theme(&mut self, ctx: &egui::Context) {
+        let weekend = self.game.in_run()
+            && self
+                .game
+                .game_state
+                .as_ref()
+                .is_some_and(|state| Weekday::of(state.current_day).is_weekend());
+        if weekend != self.applied_weekend {
+            self.applied_weekend = weekend;
+            theme::apply(ctx, &self.settings.accessibility, &self.settings.appearance, weekend);
+        }
+    }
+
+    fn draw_stats(&self, ui: &mut egui::Ui, state: &GameState) {
+        let palette = self.palette();
+        ui.group(|ui| {
+            ui.style_mut().spacing.item_spacing = egui::vec2(8.0, 4.0);
+            // 最近14天快照 + 当前值
+            let recent = state.player.recent_snapshots(14);
+            let skill_trend: Vec<i32> = recent
+                .iter()
+                .map(|s| s.skills)
+                .chain(std::iter::once(state.player.skills))
+                .collect();
+            let pressure_trend: Vec<i32> = recent
+                .iter()
+                .map(|s| s.pressure)
+                .chain(std::iter::once(state.player.pressure))
+                .collect();
+            // 最佳一局同期的快照；有任何一天缺失就不画，避免错位
+            let ghost = (self.settings.show_ghost && state.scenario.is_none())
+                .then(|| self.ghost_runs.best_for(state.player.balance.preset))
+                .flatten();
+            let ghost_days: Vec<&DailySnapshot> = ghost
+                .and_then(|run| {
+                    recent
+                        .iter()
+                        .map(|s| s.day)
+                        .chain(std::iter::once(state.current_day))
+                        .map(|day| run.snapshot_on(day))
+                        .collect()
+                })
+                .unwrap_or_default();
+            let ghost_skills: Vec<i32> = ghost_days.iter().map(|s| s.skills).collect();
+            let ghost_pressure: Vec<i32> = ghost_days.iter().map(|s| s.pressure).collect();
+            let name = match state.player.title() {
+                Some(title) => format!("{}「{}」", state.player.name, title),
+                None => state.player.name.clone(),
+            };
+            let hold = self.big_screen();
+            let trend_text = |values: &[i32]| {
+                format!("趋势: {} → {}", values[0], values[values.len() - 1])
+            };
+            let draw_trends = |ui: &mut egui::Ui| {
+                let response = widgets::sparkline(ui, &skill_trend, &ghost_skills, palette.skill);
+                widgets::tooltip(response, &trend_text(&skill_trend), hold);
+                ui.label(format!("| 压力值: {}", state.player.pressure));
+                let response = widgets::sparkline(ui, &pressure_trend, &ghost_pressure, palette.pressure);
+                widgets::tooltip(response, &trend_text(&pressure_trend), hold);
+            };
+            ui.horizontal_wrapped(|ui| {
+                let avatar = self.assets.texture(ui.ctx(), &AssetKey::Avatar);
+                widgets::portrait(ui, avatar, &state.player.name, 24.0);
+                if self.narrow {
+                    // 窄屏分两行：第一行名号境界，第二行技能与压力
+                    ui.label(format!("修仙者: {} | 境界: {}", name, state.player.get_realm()));
+                } else {
+                    ui.label(format!(
+                        "修仙者: {} | 境界: {} | 技能点: {}",
+                        name,
+                        state.player.get_realm(),
+                        state.player.skills
+                    ));
+                    draw_trends(ui);
+                }
+            });
+            if self.narrow {
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(format!("技能点: {}", state.player.skills));
+                    draw_trends(ui);
+                });
+            }
+            let weekday = Weekday::of(state.current_day);
+            let day = match state.player.balance.max_days {
+                Some(max) => format!("第{}/{}天 {}", state.current_day, max, weekday),
+                None => format!("第{}天 {}", state.current_day, weekday),
+            };
+            let day = if weekday.is_weekend() { format!("{} 🏖 休息日", day) } else { day };
+            ui.label(format!(
+                "{} | 第{}周 | {} | ⏱️ 游玩时间: {}",
+                day,
+                state.current_week,
+                state.weather().label(),
+                state.format_time()
+            ));
+            ui.weak(state.almanac().label());
+            if state.player.pressure_momentum != 0 {
+                ui.weak(format!("🌊 压力惯性 {:+}（之后几晚陆续落地）", state.player.pressure_momentum));
+            }
+            if let Some(scenario) = &state.scenario {
+                ui.label(format!("🎯 挑战：{} · 种子 {}", scenario.name, state.seed));
+            }
+            if state.ascension > 0 {
+                ui.label(format!(
+                    "🌟 飞升等级 {}（得分 ×{:.2}）",
+                    state.ascension,
+                    ascension::score_multiplier(state.ascension)
+                ));
+            } else if state.director.aggressiveness > 0.0 {
+                ui.weak(Director::describe(state.director.mood(&state.world())));
+            }
+            if let Some(warning) = state.player.slacking_warning() {
+                ui.colored_label(palette.negative, warning);
+            }
+            if let Some(warning) = state.player.grind.warning() {
+                ui.colored_label(palette.negative, warning);
+            }
+            if let Some(days_left) = state.player.probation {
+                let deadline = match days_left {
+                    0 => "今天是最后期限".to_string(),
+                    days => format!("还剩 {} 天", days),
+                };
+                ui.colored_label(
+                    palette.negative,
+                    format!("📋 绩效改进计划：{}，技能补回正数才能过关", deadline),
+                );
+            }
+            if state.audit.gray_ops > 0 {
+                ui.colored_label(
+                    palette.negative,
+                    format!("🕶 灰色操作 {} 次 · {}", state.audit.gray_ops, state.audit.unease()),
+                );
+            }
+            for companion in &state.companions {
+                ui.label(format!(
+                    "🤝 同伴: {}（{}）",
+                    companion.name,
+                    companion.ability.passive_label()
+                ));
+            }
+            if state.rollback_armed() {
+                ui.colored_label(palette.positive, "🛡 免死回滚已就绪");
+            }
+            if let Some(arc) = &state.arc {
+                ui.label(format!(
+                    "🎤 {}准备中（第{}/{}步，准备度 {}）",
+                    arc.arc.label(),
+                    arc.step + 1,
+                    arc.arc.steps().len(),
+                    arc.preparation
+                ));
+            }
+            if let Some(quest) = &state.quest {
+                let stages = quest.quest.stages();
+                let current = quest.current_stage().map_or("收尾", |stage| stage.name);
+                ui.label(format!(
+                    "📋 {}：{}阶段（质量 {} · 技术债 {}）",
+                    quest.quest.label(),
+                    current,
+                    quest.quality,
+                    quest.debt
+                ));
+                let route: Vec<&str> = stages.iter().map(|stage| stage.name).collect();
+                ui.add(
+                    egui::ProgressBar::new(quest.stage as f32 / stages.len() as f32)
+                        .desired_width(220.0)
+                        .text(route.join(" → ")),
+                );
+            }
+            if state.player.reputation > 0 {
+                ui.label(format!("🌟 声望: {}", state.player.reputation));
+            }
+            if let Some(line) = ghost.and_then(|run| run.compare_line(state.current_day)) {
+                ui.label(egui::RichText::new(line).weak());
+            }
+        });
+    }
+
+    fn draw_biography(&self, ui: &mut egui::Ui, state: &GameState) {
+        egui::CollapsingHeader::new("📜 人物志")
+            .default_open(false)
+            .show(ui, |ui| {
+                for line in state.player.biography().lines() {
+                    ui.label(line);
+                }
+            });
+    }
+
+    /// 修炼统计：技能、经验、压力随天数变化的曲线，以及各类选择的次数与摸鱼/内卷率
+    fn draw_training_stats(&self, ui: &mut egui::Ui, state: &GameState) {
+        let palette = self.palette();
+        let player = &state.player;
+        // 每天结束时的快照 + 当前值
+        let current = DailySnapshot {
+            day: state.current_day,
+            skills: player.skills,
+            pressure: player.pressure,
+            experience: player.experience,
+            pressure_momentum: player.pressure_momentum,
+        };
+        let points: Vec<&DailySnapshot> = player
+            .snapshots
+            .iter()
+            .filter(|s| s.day < state.current_day)
+            .chain(std::iter::once(&current))
+            .collect();
+        let days: Vec<u32> = points.iter().map(|s| s.day).collect();
+        if days.len() < 2 {
+            ui.weak("多修炼几天才画得出曲线");
+        } else {
+            let growth = [
+                ("技能", points.iter().map(|s| s.skills).collect(), palette.skill),
+                ("经验", points.iter().map(|s| s.experience as i32).collect(), palette.highlight),
+            ];
+            widgets::line_chart(ui, &days, &growth, 120.0);
+            let pressure = [("压力", points.iter().map(|s| s.pressure).collect(), palette.pressure)];
+            widgets::line_chart(ui, &days, &pressure, 80.0);
+        }
+
+        let count = |kind: HistoryKind| player.history.iter().filter(|e| e.kind == kind).count();
+        ui.label(format!(
+            "日常选择 {} 次 · 周事件 {} 次 · 同事请求 {} 次 · 晋升 {} 次",
+            count(HistoryKind::日常),
+            count(HistoryKind::周事件),
+            count(HistoryKind::Npc),
+            count(HistoryKind::晋升)
+        ));
+        let tally = player.archetype_tally();
+        if tally.total() > 0 {
+            ui.label(format!(
+                "摸鱼率 {:.0}% · 内卷率 {:.0}% · 甩锅率 {:.0}%",
+                tally.share(ChoiceArchetype::摸鱼) * 100.0,
+                tally.share(ChoiceArchetype::内卷) * 100.0,
+                tally.share(ChoiceArchetype::甩锅) * 100.0
+            ));
+        }
+        if let Some(peak) = points.iter().max_by_key(|s| s.pressure) {
+            ui.weak(format!("压力峰值 {}（第{}天）", peak.pressure, peak.day));
+        }
+    }
+
+    /// 选择倾向分析：本局与生涯累计的内卷/甩锅/摸鱼占比
+    fn draw_tendencies(&self, ui: &mut egui::Ui, state: &GameState) {
+        let palette = self.palette();
+        let color = |archetype: ChoiceArchetype| match archetype {
+            ChoiceArchetype::内卷 => palette.pressure,
+            ChoiceArchetype::甩锅 => palette.negative,
+            ChoiceArchetype::摸鱼 => palette.positive,
+        };
+        let run = state.player.archetype_tally();
+        let lifetime = self.lifetime_tally.merged(&run);
+        egui::CollapsingHeader::new("📊 选择倾向")
+            .default_open(false)
+            .show(ui, |ui| {
+                for (title, tally) in [("本局", run), ("生涯累计", lifetime)] {
+                    ui.strong(format!("{}（共{}次选择）", title, tally.total()));
+                    let rows: Vec<(String, f32, egui::Color32)> = ChoiceArchetype::ALL
+                        .iter()
+                        .map(|a| (a.to_string(), tally.share(*a), color(*a)))
+                        .collect();
+                    widgets::share_bars(ui, &rows);
+                    ui.add_space(4.0);
+                }
+            });
+        self.draw_pick_stats(ui);
+        let tag_stats = state.tag_stats();
+        if tag_stats.is_empty() {
+            return;
+        }
+        egui::CollapsingHeader::new("🏷 各类事件表现")
+            .default_open(false)
+            .show(ui, |ui| {
+                for stat in &tag_stats {
+                    let color = if stat.skill < 0 { palette.negative } else { palette.text };
+                    ui.colored_label(color, format!("{} {}", stat.tag.icon(), stat.summary()));
+                }
+            });
+    }
+
+    /// 选项选择率：各位置实际被选的比例与随机期望的对比，以及最常遇到的事件里各原始选项的占比
+    fn draw_pick_stats(&self, ui: &mut egui::Ui) {
+        let stats = &self.pick_stats;
+        if stats.total() == 0 {
+            return;
+        }
+        let palette = self.palette();
+        egui::CollapsingHeader::new("🎯 选项选择率")
+            .default_open(false)
+            .show(ui, |ui| {
+                ui.strong(format!("按位置（共{}次选择，选项顺序是打乱的）", stats.total()));
+                for (idx, slot) in stats.positions.iter().enumerate() {
+                    let bias = slot.rate() - slot.expected_rate();
+                    // 偏离随机期望超过 10 个百分点就标出来
+                    let color = if bias.abs() > 0.10 { palette.negative } else { palette.text };
+                    ui.colored_label(
+                        color,
+                        format!(
+                            "第{}个：{:.0}%（随机期望 {:.0}%，{}/{} 次）",
+                            idx + 1,
+                            slot.rate() * 100.0,
+                            slot.expected_rate() * 100.0,
+                            slot.picked,
+                            slot.offered
+                        ),
+                    );
+                }
+                ui.add_space(4.0);
+                ui.strong("常见事件的原始选项");
+                for event in stats.most_played(5) {
+                    let total = event.total().max(1) as f32;
+                    let shares: Vec<String> = event
+                        .picks
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, picks)| format!("{} {:.0}%", (b'A' + idx as u8) as char, *picks as f32 / total * 100.0))
+                        .collect();
+                    ui.label(format!("{}：{}", event.name, shares.join(" · ")));
+                }
+            });
+    }
+
+    /// 同伴的专属技能按钮（每周一次）；返回点了哪位同伴
+    fn draw_companion_actions(&self, ui: &mut egui::Ui, state: &GameState) -> Option<usize> {
+        let mut used = None;
+        ui.horizontal_wrapped(|ui| {
+            for (index, companion) in state.companions.iter().enumerate() {
+                let ready = companion.ready(state.current_week) && state.player.is_alive;
+                if ui
+                    .add_enabled(ready, egui::Button::new(companion.ability.label()))
+                    .on_hover_text(format!("{}：{}", companion.name, companion.ability.description()))
+                    .on_disabled_hover_text("本周已用过")
+                    .clicked()
+                {
+                    used = Some(index);
+                }
+            }
+        });
+        used
+    }
+
+    /// 分身：显示休息中那位的状态，或修出分身的按钮；返回是否点了修出分身
+    fn draw_persona(&self, ui: &mut egui::Ui, state: &GameState) -> bool {
+        if let Some(resting) = &state.alt {
+            ui.label(format!(
+                "🪞 {} 休息中（明天上班）| 境界: {} | 技能点: {} | 压力: {}",
+                resting.name,
+                resting.get_realm(),
+                resting.skills,
+                resting.pressure
+            ));
+            false
+        } else if state.can_create_alt() {
+            ui.button("🪞 修出分身")
+                .on_hover_text("分身从凡人境起步，和本体隔天轮流上班，各自的技能与压力分开算，存款和投资共用；任何一个倒下都算出局")
+                .clicked()
+        } else {
+            false
+        }
+    }
+
+    /// 技能专精面板：三条技能线与职业路线的晋升要求
+    fn draw_specialty(&self, ui: &mut egui::Ui, state: &GameState) {
+        let palette = self.palette();
+        let player = &state.player;
+        let requirement = player.skill_requirement();
+        egui::CollapsingHeader::new(format!("🧭 专精（{}）", player.career.label()))
+            .default_open(false)
+            .show(ui, |ui| {
+                let total: i32 = SkillTrack::ALL.iter().map(|t| player.tracks.get(*t)).sum();
+                let scale = total.max(1) as f32;
+                let rows: Vec<(String, f32, egui::Color32)> = SkillTrack::ALL
+                    .iter()
+                    .map(|track| {
+                        let points = player.tracks.get(*track);
+                        (
+                            format!("{} {}", track.label(), points),
+                            points as f32 / scale,
+                            palette.skill,
+                        )
+                    })
+                    .collect();
+                widgets::share_bars(ui, &rows);
+                let met = player.career.meets(&player.tracks, requirement);
+                ui.colored_label(
+                    if met { palette.positive } else { palette.negative },
+                    format!("晋升要求：{}", player.career.progress_line(&player.tracks, requirement)),
+                )
+                .on_hover_text(player.career.description());
+                ui.small("带标签的事件全额计入对应技能线；其余成长平摊到三条线，境界越高摊得越少");
+            });
+    }
+
+    /// 投资面板：总市值与盈亏走势、各项理财的持仓，买入或清仓
+    fn draw_portfolio(&self, ui: &mut egui::Ui, state: &GameState) -> Option<PortfolioClick> {
+        let palette = self.palette();
+        let player = &state.player;
+        let portfolio = &player.portfolio;
+        let mut click = None;
+        egui::CollapsingHeader::new("📊 投资")
+            .default_open(false)
+            .show(ui, |ui| {
+                let profit = portfolio.total_value() - portfolio.total_cost();
+                ui.horizontal(|ui| {
+                    ui.label(format!("总市值 {} 元（本金 {} 元）", portfolio.total_value(), portfolio.total_cost()));
+                    let color = if profit < 0 { palette.negative } else { palette.positive };
+                    ui.colored_label(color, format!("{:+}", profit));
+                });
+                if portfolio.history.len() >= 2 {
+                    let response = widgets::sparkline(ui, &portfolio.history, &[], palette.skill);
+                    widgets::tooltip(response, &format!("最近 {} 天的总市值", portfolio.history.len()), self.big_screen());
+                }
+                egui::Grid::new("portfolio_grid").num_columns(3).show(ui, |ui| {
+                    for instrument in Instrument::ALL {
+                        let value = portfolio.value(instrument);
+                        ui.label(instrument.label()).on_hover_text(instrument.description());
+                        ui.label(format!("{} 元（{:+}）", value, value - portfolio.cost(instrument)));
+                        ui.horizontal(|ui| {
+                            if ui
+                                .add_enabled(
+                                    player.is_alive && player.savings >= portfolio::LOT,
+                                    egui::Button::new(format!("买入 {}", portfolio::LOT)),
+                                )
+                                .clicked()
+                            {
+                                click = Some(PortfolioClick::Buy(instrument));
+                            }
+                            if ui
+                                .add_enabled(player.is_alive && value > 0, egui::Button::new("清仓"))
+                                .clicked()
+                            {
+                                click = Some(PortfolioClick::Sell(instrument));
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+            });
+        click
+    }
+
+    /// 进修面板：在上的课程进度、可报名的课程、已获得的加成
+    fn draw_courses(&self, ui: &mut egui::Ui, state: &GameState) -> Option<CourseClick> {
+        let palette = self.palette();
+        let player = &state.player;
+        let mut click = None;
+        egui::CollapsingHeader::new("📚 进修")
+            .default_open(false)
+            .show(ui, |ui| {
+                if let Some(enrollment) = &player.enrollment {
+                    let course = enrollment.course;
+                    ui.strong(course.label());
+                    ui.add(
+                        egui::ProgressBar::new(
+                            enrollment.nights_attended as f32 / course.days() as f32,
+                        )
+                        .text(format!(
+                            "已上 {}/{} 晚，还剩 {} 晚",
+                            enrollment.nights_attended,
+                            course.days(),
+                            enrollment.nights_left()
+                        )),
+                    );
+                    if ui
+                        .add_enabled(player.is_alive, egui::Button::new("退课"))
+                        .on_hover_text(format!("退还 {} 元（没上的课时退一半）", enrollment.refund()))
+                        .clicked()
+                    {
+                        click = Some(CourseClick::Drop);
+                    }
+                } else {
+                    ui.label("报名后每天晚上都要去上课，结业获得永久加成：");
+                    for course in Course::ALL {
+                        let affordable = player.savings >= course.fee();
+                        if ui
+                            .add_enabled(
+                                affordable && player.is_alive,
+                                egui::Button::new(format!("{}（{} 元）", course.label(), course.fee())),
+                            )
+                            .on_hover_text(course.description())
+                            .clicked()
+                        {
+                            click = Some(CourseClick::Enroll(course));
+                        }
+                    }
+                }
+                for perk in &player.perks {
+                    ui.colored_label(palette.positive, format!("✨ {}：{}", perk, perk.description()));
+                }
+            });
+        click
+    }
+
+    /// 同事委托：内容与进度，要交道具的带“交付”按钮；返回是否点了交付
+    fn draw_favor(ui: &mut egui::Ui, state: &GameState) -> bool {
+        let Some(favor) = &state.favor else {
+            return false;
+        };
+        let mut deliver = false;
+        ui.horizontal_wrapped(|ui| {
+            ui.label(format!("📨 委托：{}（{}）", favor.describe(), favor.progress(state.current_day)))
+                .on_hover_text(format!(
+                    "办成好感 +{}，还能拿到锦囊；过期好感 -{}",
+                    favor::FAVOR_AFFINITY,
+                    favor::FAVOR_LETDOWN
+                ));
+            if let Some(item) = favor.wanted_item() {
+                let ready = state.player.is_alive && state.player.inventory.has(item);
+                deliver = ui
+                    .add_enabled(ready, egui::Button::new("交付"))
+                    .on_disabled_hover_text(format!("背包里还没有{}", item))
+                    .clicked();
+            }
+        });
+        deliver
+    }
+
+    /// 背包：持有的道具，能手动用的带“使用”按钮；返回点了哪件
+    fn draw_inventory(ui: &mut egui::Ui, state: &GameState) -> Option<Item> {
+        let inventory = &state.player.inventory;
+        let total: u32 = inventory.items().iter().map(|(_, count)| count).sum();
+        let mut used = None;
+        egui::CollapsingHeader::new(format!("🎒 背包 · {}", total))
+            .default_open(false)
+            .show(ui, |ui| {
+                if inventory.is_empty() {
+                    ui.weak("空空如也，做事件、帮同事时偶尔会捡到道具");
+                    return;
+                }
+                for (item, count) in inventory.items() {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{} {} ×{}", item.icon(), item, count))
+                            .on_hover_text(item.description());
+                        if item.is_usable() {
+                            if ui
+                                .add_enabled(state.player.is_alive, egui::Button::new("使用"))
+                                .on_hover_text(item.description())
+                                .clicked()
+                            {
+                                used = Some(*item);
+                            }
+                        } else {
+                            ui.weak("自动生效");
+                        }
+                    });
+                }
+            });
+        used
+    }
+
+    /// 晚间安排与开源项目面板；返回新选的晚间安排
+    fn draw_open_source(&self, ui: &mut egui::Ui, state: &GameState) -> Option<EveningPlan> {
+        let palette = self.palette();
+        let player = &state.player;
+        let project = &player.open_source;
+        let mut chosen = None;
+        egui::CollapsingHeader::new(format!("🌙 晚上 · ⭐ {}", project.stars))
+            .default_open(false)
+            .show(ui, |ui| {
+                if let Some(enrollment) = &player.enrollment {
+                    ui.weak(format!("晚上要去上{}，没空干别的", enrollment.course.label()));
+                }
+                ui.horizontal(|ui| {
+                    for plan in EveningPlan::ALL {
+                        if ui
+                            .add_enabled(
+                                player.is_alive,
+                                egui::SelectableLabel::new(player.evening == plan, plan.label()),
+                            )
+                            .on_hover_text(plan.description())
+                            .clicked()
+                        {
+                            chosen = Some(plan);
+                        }
+                    }
+                });
+                if project.commits == 0 {
+                    ui.weak("还没有开源项目，晚上写第一行代码就有了");
+                    return;
+                }
+                ui.label(format!(
+                    "⭐ {} star · {} 次提交 · 积压 {} 个 issue",
+                    project.stars, project.commits, project.backlog
+                ));
+                match project.next_milestone() {
+                    Some(milestone) => {
+                        ui.add(
+                            egui::ProgressBar::new(project.stars as f32 / milestone as f32)
+                                .text(format!("下一个里程碑 {} star", milestone)),
+                        );
+                    }
+                    None => {
+                        ui.colored_label(palette.highlight, "🏷 已是技术网红");
+                    }
+                }
+                if project.idle_nights > 0 {
+                    ui.colored_label(
+                        palette.negative,
+                        format!("已经 {} 个晚上没管项目了", project.idle_nights),
+                    );
+                }
+            });
+        chosen
+    }
+
+    /// 财务面板：存款、居住条件、下次发薪、最近的薪资单；返回是否点了升级居住条件
+    fn draw_finance(&self, ui: &mut egui::Ui, state: &GameState, interactive: bool) -> bool {
+        let palette = self.palette();
+        let mut upgrade = false;
+        egui::CollapsingHeader::new("💰 财务")
+            .default_open(false)
+            .show(ui, |ui| {
+                let savings = state.player.savings;
+                let color = if savings < 0 { palette.negative } else { palette.positive };
+                ui.horizontal(|ui| {
+                    ui.label("存款：");
+                    ui.colored_label(color, format!("{} 元", savings));
+                });
+                if state.desperation.is_some() && savings < 0 {
+                    ui.colored_label(palette.negative, "🆘 已破产：想办法把存款补回来");
+                }
+                ui.label(format!(
+                    "距离发工资还有 {} 天（月薪 {} 元）",
+                    finance::days_until_payday(state.player.days_played),
+                    finance::Payslip::salary_for(state.player.realm_level)
+                ));
+
+                let housing = state.player.housing;
+                ui.label(format!(
+                    "居住：{}（每月{} {} 元，每天减压 {}）",
+                    housing.label(),
+                    housing.cost_label(),
+                    housing.monthly_cost(),
+                    housing.daily_relief()
+                ));
+                ui.label(format!(
+                    "通勤：{}（{}，周报时可更换）",
+                    state.player.commute.label(),
+                    state.player.commute.description()
+                ));
+                let market = &state.player.market;
+                ui.label(format!("🧋 奶茶：{} 元一杯 | 📈 大盘：{} 点", market.milk_tea_price, market.index));
+                if let (true, Some((next, cost))) = (interactive, housing.upgrade()) {
+                    upgrade = ui
+                        .add_enabled(
+                            savings >= cost && state.player.is_alive,
+                            egui::Button::new(format!("升级到{}（{} 元）", next.label(), cost)),
+                        )
+                        .on_hover_text(format!(
+                            "每月{} {} 元，每天减压 {}，并解锁居家事件",
+                            next.cost_label(),
+                            next.monthly_cost(),
+                            next.daily_relief()
+                        ))
+                        .clicked();
+                }
+
+                let Some(payslip) = state.payslips.last() else {
+                    return;
+                };
+                ui.add_space(4.0);
+                ui.strong(format!("第{}月薪资单", payslip.month));
+                egui::Grid::new("payslip_grid").num_columns(2).show(ui, |ui| {
+                    ui.label("工资");
+                    ui.colored_label(palette.positive, format!("+{}", payslip.salary));
+                    ui.end_row();
+                    ui.label(payslip.housing.cost_label());
+                    ui.colored_label(palette.negative, format!("-{}", payslip.rent));
+                    ui.end_row();
+                    ui.label(format!("奶茶（{}杯）", payslip.milk_tea_cups));
+                    ui.colored_label(palette.negative, format!("-{}", payslip.milk_tea));
+                    ui.end_row();
+                    ui.label("结余");
+                    ui.label(format!("{:+}", payslip.net()));
+                    ui.end_row();
+                });
+            });
+        upgrade
+    }
+
+    /// 结束本局前把选择倾向并入生涯累计，分段成绩并入最佳成绩，并归档这一局
+    fn archive_run(&mut self) {
+        if let Some(state) = self.game.game_state.as_ref().filter(|s| s.scenario.is_none()) {
+            self.lifetime_tally = self.lifetime_tally.merged(&state.player.archetype_tally());
+            self.best_splits.record(state.player.balance.preset, &state.splits);
+            self.ghost_runs.record(ArchivedRun::from_player(&state.player));
+        }
+        self.tournament_token.clear();
+        self.highlight_notice.clear();
+        self.pending_choice = None;
+        self.observer_tab = ObserverTab::结果;
+    }
+
+    fn restart(&mut self) {
+        self.archive_run();
+        self.game.execute(GameCommand::Restart);
+    }
+
+    /// 用同一种子重开，复盘哪里做错了
+    fn rematch(&mut self) {
+        self.archive_run();
+        self.game.execute(GameCommand::Rematch);
+    }
+
+    /// 速通面板：实时计时、各境界分段（与最佳成绩对比）、最佳成绩表与导出
+    fn draw_splits(&mut self, ui: &mut egui::Ui) {
+        let Some(state) = self.game.game_state.as_ref() else {
+            return;
+        };
+        let palette = self.palette();
+        let preset = state.player.balance.preset;
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.strong("⏱ 速通");
+                ui.label(
+                    egui::RichText::new(speedrun::format_millis(state.elapsed_millis()))
+                        .monospace()
+                        .size(20.0),
+                );
+            });
+            egui::Grid::new("splits_grid").num_columns(4).striped(true).show(ui, |ui| {
+                ui.strong("境界");
+                ui.strong("天数");
+                ui.strong("用时");
+                ui.strong("对比最佳");
+                ui.end_row();
+                for level in 2..=7 {
+                    let realm = game::Realm::from_level(level);
+                    let best = self.best_splits.best_for(preset, realm);
+                    ui.label(realm.to_string());
+                    match state.splits.iter().find(|s| s.realm == realm) {
+                        Some(split) => {
+                            ui.label(format!("第{}天", split.day));
+                            ui.monospace(speedrun::format_millis(split.elapsed_ms));
+                            match best {
+                                Some(best) => {
+                                    let color = if split.elapsed_ms <= best.elapsed_ms {
+                                        palette.positive
+                                    } else {
+                                        palette.negative
+                                    };
+                                    ui.colored_label(
+                                        color,
+                                        speedrun::format_delta(split.elapsed_ms, best.elapsed_ms),
+                                    );
+                                }
+                                None => {
+                                    ui.colored_label(palette.highlight, "首次");
+                                }
+                            }
+                        }
+                        None => {
+                            ui.weak("-");
+                            ui.weak(
+                                best.map(|b| speedrun::format_millis(b.elapsed_ms))
+                                    .unwrap_or_else(|| "-".to_string()),
+                            );
+                            ui.label("");
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+
+            egui::CollapsingHeader::new(format!("🏆 最佳成绩（{}）", preset.label()))
+                .default_open(false)
+                .show(ui, |ui| {
+                    let table = self.best_splits.table(preset);
+                    if table.is_empty() {
+                        ui.label("还没有完成过境界突破");
+                    }
+                    for split in table {
+                        ui.label(format!(
+                            "{}：第{}天，{}",
+                            split.realm,
+                            split.day,
+                            speedrun::format_millis(split.elapsed_ms)
+                        ));
+                    }
+                });
+
+            let record = SpeedrunRecord {
+                player: &state.player.name,
+                preset,
+                exported_at: chrono::Local::now().to_rfc3339(),
+                splits: &state.splits,
+                history: &state.player.history,
+            };
+            if ui
+                .add_enabled(!state.splits.is_empty(), egui::Button::new("📤 导出分段"))
+                .on_hover_text("导出分段用时与全部选择记录，便于他人核验")
+                .clicked()
+            {
+                let json = record.to_json();
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    let saved = json.and_then(|text| {
+                        let dir = self.data_dir.exports()?;
+                        export::save_text(&dir, "xiuxian_splits", "json", &text)
+                    });
+                    self.export_notice = match saved {
+                        Ok(path) => format!("已导出到 {}", path.display()),
+                        Err(err) => err,
+                    };
+                }
+                #[cfg(target_arch = "wasm32")]
+                {
+                    self.export_notice = match json {
+                        Ok(text) => {
+                            ui.output_mut(|o| o.copied_text = text);
+                            "分段记录已复制到剪贴板".to_string()
+                        }
+                        Err(err) => err,
+                    };
+                }
+            }
+        });
+        ui.ctx().request_repaint_after(std::time::Duration::from_millis(50));
+    }
+
+    fn draw_risk_calculator(&mut self, ctx: &egui::Context, options: &[OptionInfo]) {
+        let Some(state) = self.game.game_state.as_ref() else {
+            return;
+        };
+        let mut open = self.show_risk_calculator;
+        egui::Window::new("🧮 风险计算器")
+            .open(&mut open)
+            .resizable(false)
+            .default_pos(ctx.screen_rect().right_top() + egui::vec2(-360.0, 60.0))
+            .show(ctx, |ui| {
+                ui.label("假设选择各选项后的推演（不会真的执行）：");
+                egui::Grid::new("risk_calculator_grid")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong("选项");
+                        ui.strong("技能");
+                        ui.strong("压力");
+                        ui.strong("今晚出局率");
+                        ui.strong("距晋升");
+                        ui.end_row();
+                        for (idx, option) in options.iter().enumerate() {
+                            let projection = state.player.simulate_option(state.player.scale_outcome(option.value));
+                            ui.label(format!("选项 {}", idx + 1));
+                            ui.label(format!("{} → {}", state.player.skills, projection.skills));
+                            ui.label(format!(
+                                "{} → {}（{}）",
+                                state.player.pressure, projection.pressure, projection.pressure_band
+                            ));
+                            ui.label(format!("{:.0}%", projection.risk_tonight * 100.0));
+                            ui.label(match projection.days_to_promotion {
+                                Some(0) => "可晋升".to_string(),
+                                Some(days) => format!("约{}天", days),
+                                None => "遥遥无期".to_string(),
+                            });
+                            ui.end_row();
+                        }
+                    });
+            });
+        self.show_risk_calculator = open;
+    }
+
+    fn draw_weekly_banner(&mut self, ui: &mut egui::Ui) {
+        if !self.game.weekly_event_pending() {
+            return;
+        }
+        egui::Frame::none()
+            .fill(self.palette().alert_fill)
+            .inner_margin(8.0)
+            .rounding(4.0)
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.strong("⚠️ 本周大考未处理！");
+                    if ui.button("立即处理").clicked() {
+                        self.game.execute(GameCommand::OpenWeeklyEvent);
+                    }
+                });
+            });
+        ui.add_space(8.0);
+    }
+
+    fn draw_event_panel(&mut self, ui: &mut egui::Ui) {
+        self.draw_weekly_banner(ui);
+        #[cfg(feature = "debug")]
+        let metadata = self.profiler.measure_metadata(|| self.game.current_event_metadata());
+        #[cfg(not(feature = "debug"))]
+        let metadata = self.game.current_event_metadata();
+        if let Some((title, desc, options, is_weekly)) = metadata {
+            let show_emoji = self.settings.appearance.show_emoji;
+            ui.heading(theme::strip_emoji(&title, show_emoji));
+            self.draw_event_art(ui, is_weekly);
+            ui.label(theme::strip_emoji(&desc, show_emoji));
+            if let Some(note) = self.game.game_state.as_ref().and_then(GameState::script_note) {
+                ui.add_space(6.0);
+                ui.colored_label(self.palette().highlight, format!("🧭 {}", note));
+            }
+            ui.add_space(10.0);
+
+            let can_choose = if is_weekly {
+                self.game.can_make_weekly_choice()
+            } else {
+                self.game.can_make_daily_choice()
+            };
+
+            let hinted = self.game.hinted_option.filter(|_| can_choose);
+            let highlight_color = self.palette().highlight;
+            let selected_color = self.palette().positive;
+            // 两段确认：第一下只选中，再点一次或按确认才提交（换了事件选中作废）
+            let two_stage = self.two_stage_confirm();
+            let day = self.game.game_state.as_ref().map_or(0, |state| state.current_day);
+            let pending = self
+                .pending_choice
+                .filter(|&(pending_day, weekly, _)| can_choose && two_stage && (pending_day, weekly) == (day, is_weekly))
+                .map(|(_, _, idx)| idx);
+            let choose_actions = [Action::Choose1, Action::Choose2, Action::Choose3];
+            for (idx, option) in options.iter().enumerate() {
+                let hint = option.outcome_hint();
+                let key_hint = self.key_hint(choose_actions[idx.min(2)]);
+                let gray = if option.gray_risk > 0 { " ⚠灰色操作" } else { "" };
+                let title = format!(
+                    "{}选项 {}: {}{}",
+                    key_hint,
+                    idx + 1,
+                    theme::strip_emoji(option.title(), show_emoji),
+                    gray
+                );
+                let highlighted = hinted == Some(idx);
+                let selected = pending == Some(idx);
+                let response = widgets::option_card(
+                    ui,
+                    can_choose,
+                    if selected { Some(selected_color) } else { highlighted.then_some(highlight_color) },
+                    hint.icon(),
+                    &title,
+                    &theme::strip_emoji(option.summary(), show_emoji),
+                );
+                let tip = if selected {
+                    "再点一次或按“确认选择”提交"
+                } else if highlighted {
+                    "🧙 师傅：选这个最稳"
+                } else {
+                    hint.tooltip()
+                };
+                let response = widgets::tooltip(response, tip, self.big_screen());
+                if response.clicked() {
+                    if two_stage && !selected {
+                        self.pending_choice = Some((day, is_weekly, idx));
+                    } else {
+                        self.pending_choice = None;
+                        self.game.execute(GameCommand::Choose((idx + 1) as u8));
+                    }
+                }
+                ui.add_space(4.0);
+            }
+
+            if let Some(idx) = pending {
+                ui.with_layout(self.button_row(), |ui| {
+                    let confirm = egui::Button::new(format!("✅ 确认选择选项 {}", idx + 1)).fill(selected_color);
+                    if ui.add(confirm).clicked() {
+                        self.pending_choice = None;
+                        self.game.execute(GameCommand::Choose((idx + 1) as u8));
+                    }
+                    if ui.button("取消").clicked() {
+                        self.pending_choice = None;
+                    }
+                });
+            }
+
+            if can_choose {
+                let uses_left = self
+                    .game
+                    .game_state
+                    .as_ref()
+                    .map(|g| g.hint_uses_left)
+                    .unwrap_or(0);
+                let button = egui::Button::new(format!(
+                    "{}🧙 师傅提示（剩余{}次）",
+                    self.key_hint(Action::UseHint),
+                    uses_left
+                ));
+                ui.with_layout(self.button_row(), |ui| {
+                    let response = ui.add_enabled(uses_left > 0 && hinted.is_none(), button);
+                    let tip = "师傅会根据当前压力和技能点，指出今晚最不容易出局的选项";
+                    if widgets::tooltip(response, tip, self.big_screen()).clicked() {
+                        self.game.execute(GameCommand::Hint);
+                    }
+                    ui.toggle_value(&mut self.show_risk_calculator, "🧮 风险计算器");
+                });
+                if self.show_risk_calculator {
+                    self.draw_risk_calculator(ui.ctx(), &options);
+                }
+            }
+
+            if !self.game.result_message.is_empty() {
+                ui.add_space(10.0);
+                widgets::result_message(ui, &self.game.result_message, &self.palette(), self.settings.appearance.show_emoji);
+            }
+            self.draw_highlight_button(ui);
+
+            ui.add_space(14.0);
+            let advance = egui::Button::new(format!("{}进入下一天", self.key_hint(Action::Advance)));
+            let clicked = ui
+                .with_layout(self.button_row(), |ui| ui.add_enabled(self.game.can_advance(), advance).clicked())
+                .inner;
+            if clicked {
+                self.game.execute(GameCommand::NextDay);
+            }
+        } else {
+            ui.label("今日暂无事件");
+        }
+    }
+
+    fn draw_history_section(&mut self, ui: &mut egui::Ui, max_height: f32) {
+        let mut collapsed = self.settings.panels.history_collapsed;
+        widgets::collapsible_section(ui, "🧾 历史记录", &mut collapsed, |ui| {
+            self.draw_history_export(ui);
+            self.draw_history_filter(ui);
+            if let Some(state) = self.game.game_state.as_ref() {
+                self.history_page = self.draw_history(ui, state, max_height);
+            }
+        });
+        self.settings.panels.history_collapsed = collapsed;
+    }
+
+    /// 导出按钮：桌面端弹保存对话框，网页端让浏览器下载
+    fn draw_history_export(&mut self, ui: &mut egui::Ui) {
+        let Some(state) = self.game.game_state.as_ref() else {
+            return;
+        };
+        let finished = self.game.phase == GamePhase::GameOver;
+        ui.horizontal(|ui| {
+            for format in ExportFormat::ALL {
+                let button = egui::Button::new(format!("📤 导出 {}", format.label()));
+                if !ui.add_enabled(!state.player.history.is_empty(), button).clicked() {
+                    continue;
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    let saved = self.data_dir.exports().and_then(|dir| format.save_as(state, finished, &dir));
+                    match saved {
+                        Ok(Some(path)) => self.export_notice = format!("已导出到 {}", path.display()),
+                        Ok(None) => {}
+                        Err(err) => self.export_notice = err,
+                    }
+                }
+                #[cfg(target_arch = "wasm32")]
+                {
+                    self.export_notice = match format.download(state, finished) {
+                        Ok(file_name) => format!("已下载 {}", file_name),
+                        Err(err) => err,
+                    };
+                }
             }
+        });
+        if !self.export_notice.is_empty() {
+            ui.small(&self.export_notice);
+        }
+    }
 
-            ui.add_space(14.0);
-            let can_advance = if let Some(state) = self.game.game_state.as_ref() {
-                matches!(self.game.phase, GamePhase::EventDisplay)
-                    && state.event_chosen_today
-                    && (state.today_weekly_event.is_none() || state.weekly_event_chosen_today)
-            } else {
-                false
-            };
-            if ui
-                .add_enabled(can_advance, egui::Button::new("进入下一天"))
-                .clicked()
-            {
-                self.game.next_day();
+    /// 历史面板的筛选：按类型、按关键字（条件变了就回到第一页）
+    fn draw_history_filter(&mut self, ui: &mut egui::Ui) {
+        if self.game.game_state.is_none() {
+            return;
+        }
+        let (kind, query) = (self.history_kind, self.history_query.clone());
+        ui.horizontal_wrapped(|ui| {
+            ui.selectable_value(&mut self.history_kind, None, "全部");
+            for kind in HistoryKind::ALL {
+                ui.selectable_value(&mut self.history_kind, Some(kind), kind.label());
             }
-        } else {
-            ui.label("今日暂无事件");
+        });
+        ui.add(egui::TextEdit::singleline(&mut self.history_query).hint_text("🔍 搜索事件、选择或剧情"));
+        if kind != self.history_kind || query != self.history_query {
+            self.history_page = 0;
         }
     }
 
-    fn draw_history(&self, ui: &mut egui::Ui, state: &GameState) {
-        ui.group(|ui| {
-            ui.heading("🧾 历史记录");
-            egui::ScrollArea::vertical()
-                .max_height(200.0)
-                .show(ui, |ui| {
-                    if state.player.history.is_empty() {
-                        ui.label("暂无记录");
-                    } else {
-                        for record in state.player.history.iter().rev() {
-                            ui.label(record);
+    /// 按筛选条件分页显示历史记录，返回翻页后的页码
+    fn draw_history(&self, ui: &mut egui::Ui, state: &GameState, max_height: f32) -> usize {
+        // 每页显示多少条，长局的历史分页渲染
+        const PAGE_SIZE: usize = 50;
+        let palette = self.palette();
+        let appearance = &self.settings.appearance;
+        let limit = if appearance.history_limit == 0 { usize::MAX } else { appearance.history_limit };
+        let records: Vec<&HistoryEntry> = state
+            .player
+            .history
+            .iter()
+            .rev()
+            .take(limit)
+            .filter(|entry| self.history_kind.is_none_or(|kind| entry.kind == kind))
+            .filter(|entry| entry.matches(&self.history_query))
+            .collect();
+        let pages = records.len().div_ceil(PAGE_SIZE).max(1);
+        let mut page = self.history_page.min(pages - 1);
+        ui.weak(format!("本局难度：{}", state.player.balance.difficulty.label()));
+        if pages > 1 {
+            ui.horizontal(|ui| {
+                if ui.add_enabled(page > 0, egui::Button::new("◀ 较新")).clicked() {
+                    page -= 1;
+                }
+                ui.label(format!("第 {}/{} 页（共 {} 条）", page + 1, pages, records.len()));
+                if ui.add_enabled(page + 1 < pages, egui::Button::new("较早 ▶")).clicked() {
+                    page += 1;
+                }
+            });
+        }
+        // 在可调高度的面板里填满剩余空间，其他场合使用固定上限
+        egui::ScrollArea::vertical()
+            .max_height(max_height.min(ui.available_height()))
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                if state.player.history.is_empty() {
+                    ui.label("暂无记录");
+                } else if records.is_empty() {
+                    ui.label("没有符合条件的记录");
+                }
+                for record in records.iter().skip(page * PAGE_SIZE).take(PAGE_SIZE) {
+                    let color = match record.merit() {
+                        merit if merit > 0 => palette.positive,
+                        merit if merit < 0 => palette.negative,
+                        _ => palette.text,
+                    };
+                    ui.horizontal(|ui| {
+                        if record.kind == HistoryKind::Npc {
+                            let portrait =
+                                self.assets.texture(ui.ctx(), &AssetKey::Portrait(record.event_name.clone()));
+                            widgets::portrait(ui, portrait, &record.event_name, 16.0);
                         }
-                    }
-                });
+                        ui.colored_label(color, theme::strip_emoji(&record.to_string(), appearance.show_emoji));
+                        if !record.elapsed.is_zero() {
+                            ui.weak(format!("⏱ 当天用时 {}", speedrun::format_millis(record.elapsed.as_millis() as u64)));
+                        }
+                    });
+                }
+            });
+        page
+    }
+
+    /// 当前事件的插图（没有插图的事件不占位置）
+    fn draw_event_art(&self, ui: &mut egui::Ui, is_weekly: bool) {
+        let Some(state) = self.game.game_state.as_ref() else {
+            return;
+        };
+        let id = match state.get_weekly_event().filter(|_| is_weekly) {
+            Some(weekly) => weekly.id,
+            None => state.get_today_event().id,
+        };
+        let key = AssetKey::EventArt { weekly: is_weekly, id };
+        if let Some(texture) = self.assets.texture(ui.ctx(), &key) {
+            let width = ui.available_width().min(texture.size_vec2().x);
+            ui.add(egui::Image::new(&texture).max_width(width).rounding(6.0));
+        }
+    }
+
+    fn draw_npc_section(&mut self, ui: &mut egui::Ui) {
+        let mut collapsed = self.settings.panels.npc_collapsed;
+        widgets::collapsible_section(ui, "🤝 每日 NPC", &mut collapsed, |ui| {
+            self.draw_npc_panel(ui);
         });
+        self.settings.panels.npc_collapsed = collapsed;
     }
 
     fn draw_npc_panel(&mut self, ui: &mut egui::Ui) {
-        ui.heading("🤝 每日 NPC");
-        ui.add_space(6.0);
 
         let Some(_) = self.game.game_state.as_ref() else {
             ui.label("开始游戏后可遇到 NPC");
@@ -291,6 +4018,7 @@ impl XiuxianApp {
                             npc.ai_model.clone(),
                             npc.accept_option.summary.clone(),
                             npc.reject_option.summary.clone(),
+                            npc.mood,
                             npc.interacted,
                         )
                     })
@@ -311,6 +4039,7 @@ impl XiuxianApp {
                                     npc.ai_model.clone(),
                                     npc.accept_option.clone(),
                                     npc.reject_option.clone(),
+                                    npc.haggle.clone(),
                                     npc.interacted,
                                 )
                             })
@@ -321,30 +4050,109 @@ impl XiuxianApp {
 
         ui.label("每位 NPC 都连接了自定义 AI 模型，随时抛出特殊事件。");
 
+        let palette = self.palette();
         let phase_allows = matches!(
             self.game.phase,
             GamePhase::EventDisplay | GamePhase::WeeklyEventDisplay
         );
         let can_interact = player_alive && phase_allows;
         if !player_alive {
-            ui.colored_label(Color32::RED, "你已离开公司，无法与 NPC 互动。");
+            ui.colored_label(palette.negative, "你已离开公司，无法与 NPC 互动。");
         } else if !phase_allows {
             ui.label("当前阶段暂不支持 NPC 互动。");
         }
 
+        let (relations, savings, keepsakes) = self
+            .game
+            .game_state
+            .as_ref()
+            .map(|g| (g.relations.clone(), g.player.savings, g.player.keepsakes.clone()))
+            .unwrap_or_default();
+        for keepsake in &keepsakes {
+            ui.colored_label(palette.positive, format!("🔗 信物「{}」：{}", keepsake, keepsake.description()));
+        }
+        self.draw_office_map(ui, can_interact);
+
         if npc_snapshot.is_empty() {
             ui.label("今天没有遇到 NPC");
         } else {
-            for (idx, (name, desc, ai_model, accept_tip, reject_tip, interacted)) in
+            for (idx, (name, desc, ai_model, accept_tip, reject_tip, mood, interacted)) in
                 npc_snapshot.into_iter().enumerate()
             {
                 ui.separator();
-                ui.label(format!("{} · {}", name, ai_model));
+                let portrait = self.assets.texture(ui.ctx(), &AssetKey::Portrait(name.clone()));
+                ui.horizontal(|ui| {
+                    widgets::portrait(ui, portrait, &name, 28.0);
+                    ui.label(format!("{} · {}", name, ai_model));
+                    ui.label(mood.emoji())
+                        .on_hover_text(format!("今天{}", mood));
+                });
                 ui.label(desc);
                 ui.label(format!("同意：{}", accept_tip));
                 ui.label(format!("拒绝：{}", reject_tip));
+                ui.horizontal(|ui| {
+                    match relations.affinity(&name) {
+                        affinity if affinity < 0 => {
+                            ui.colored_label(palette.negative, format!("💢 厌恶 {}", -affinity))
+                                .on_hover_text("厌恶太深的同事会在周事件里给你穿小鞋");
+                        }
+                        affinity => {
+                            ui.label(format!("💗 好感 {}/{}", affinity, gift::MAX_AFFINITY))
+                                .on_hover_text(format!("好感到 {} 有专属剧情", bond::BOND_AFFINITY));
+                        }
+                    }
+                    if relations.has_bonded(&name) {
+                        ui.label("🔗").on_hover_text("已结下羁绊");
+                    }
+                    let gifted = relations.gifted_today(&name);
+                    let mut chosen = None;
+                    ui.add_enabled_ui(can_interact && !gifted, |ui| {
+                        ui.menu_button("🎁 送礼", |ui| {
+                            for item in gift::catalog() {
+                                if ui
+                                    .add_enabled(
+                                        savings >= item.price,
+                                        egui::Button::new(format!("{}（{} 元）", item.label, item.price)),
+                                    )
+                                    .clicked()
+                                {
+                                    chosen = Some(item.id.clone());
+                                    ui.close_menu();
+                                }
+                            }
+                        });
+                    });
+                    if gifted {
+                        ui.weak("今天已送过");
+                    }
+                    if let (Some(id), Some(game_state)) = (chosen, self.game.game_state.as_mut()) {
+                        if let Err(err) = game_state.give_gift(idx, &id) {
+                            game_state.npc_interaction_message = err;
+                        }
+                    }
+                });
+                match gift::taste_of(&name) {
+                    Some(taste) if relations.knows_taste(&name) => {
+                        ui.small(format!("💝 {}", taste.summary()));
+                    }
+                    Some(_) => {
+                        ui.weak("💝 喜好：多聊聊就知道了");
+                    }
+                    None => {}
+                }
+                let can_recruit = self
+                    .game
+                    .game_state
+                    .as_ref()
+                    .is_some_and(|g| g.can_recruit(&name));
+                if can_recruit && ui.button(format!("🤝 邀请{}加入团队", name)).clicked() {
+                    if let Some(game_state) = self.game.game_state.as_mut() {
+                        game_state.npc_interaction_message =
+                            game_state.recruit(&name).unwrap_or_else(|err| err);
+                    }
+                }
                 if interacted {
-                    ui.colored_label(Color32::LIGHT_GREEN, "状态：已处理");
+                    ui.colored_label(palette.positive, "状态：已处理");
                 } else if ui
                     .add_enabled(
                         can_interact,
@@ -359,14 +4167,25 @@ impl XiuxianApp {
             }
         }
 
-        if let Some((_, prompt, name, ai_model, accept_option, reject_option, interacted)) =
+        if let Some((_, prompt, name, ai_model, accept_option, reject_option, haggle, interacted)) =
             active_event
         {
             ui.separator();
-            ui.label(format!("🎯 {} · {}", name, ai_model));
+            let portrait = self.assets.texture(ui.ctx(), &AssetKey::Portrait(name.clone()));
+            ui.horizontal(|ui| {
+                widgets::portrait(ui, portrait, &name, 20.0);
+                ui.label(format!("🎯 {} · {}", name, ai_model));
+            });
             ui.label(prompt);
-            ui.label(format!("同意：{}", accept_option.summary));
-            ui.label(format!("拒绝：{}", reject_option.summary));
+            if self.npc_voice.is_some() {
+                ui.weak("🤖 正在现编台词…");
+            }
+            let gray = |option: &NpcOption| if option.gray_risk > 0 { " ⚠灰色操作" } else { "" };
+            ui.label(format!("同意：{}{}", accept_option.summary, gray(&accept_option)));
+            ui.label(format!("拒绝：{}{}", reject_option.summary, gray(&reject_option)));
+            if let Some(haggle) = &haggle {
+                ui.label(format!("讨价还价：{}（靠沟通，谈崩了更糟）{}", haggle.summary, gray(&haggle.win)));
+            }
 
             if !interacted {
                 ui.horizontal(|ui| {
@@ -374,17 +4193,20 @@ impl XiuxianApp {
                         .add_enabled(can_interact, egui::Button::new("同意"))
                         .clicked()
                     {
-                        if let Some(game_state) = self.game.game_state.as_mut() {
-                            game_state.resolve_active_npc_event(NpcDecision::Accept);
-                        }
+                        self.game.execute(GameCommand::ResolveNpc(NpcDecision::Accept));
                     }
                     if ui
                         .add_enabled(can_interact, egui::Button::new("拒绝"))
                         .clicked()
                     {
-                        if let Some(game_state) = self.game.game_state.as_mut() {
-                            game_state.resolve_active_npc_event(NpcDecision::Reject);
-                        }
+                        self.game.execute(GameCommand::ResolveNpc(NpcDecision::Reject));
+                    }
+                    if haggle.is_some()
+                        && ui
+                            .add_enabled(can_interact, egui::Button::new("讨价还价"))
+                            .clicked()
+                    {
+                        self.game.execute(GameCommand::ResolveNpc(NpcDecision::Negotiate));
                     }
                 });
             }
@@ -396,260 +4218,626 @@ impl XiuxianApp {
         }
     }
 
+    /// 办公室地图：每天挑一处待着，点房间即前往（今天挑过后只显示所在）
+    fn draw_office_map(&mut self, ui: &mut egui::Ui, can_interact: bool) {
+        let Some(state) = self.game.game_state.as_ref() else {
+            return;
+        };
+        let visit = state.visit;
+        let moved_today = visit.is_some_and(|visit| visit.day == state.current_day);
+        let zones: Vec<(String, &str)> = Location::ALL
+            .iter()
+            .map(|place| (format!("{} {}", place.icon(), place), place.description()))
+            .collect();
+        let selected = visit.and_then(|visit| Location::ALL.iter().position(|place| *place == visit.place));
+        ui.label(match visit {
+            Some(visit) if moved_today => format!("🗺 今天待在{}（明天可以换地方）", visit.place),
+            Some(visit) => format!("🗺 上次待在{}，今天去哪儿？", visit.place),
+            None => "🗺 今天去哪儿待着？常在那里的同事更容易碰上".to_string(),
+        });
+        let palette = self.palette();
+        if let Some(index) = widgets::zone_map(ui, &zones, selected, can_interact && !moved_today, &palette) {
+            self.game.execute(GameCommand::MoveTo(Location::ALL[index]));
+        }
+    }
+
     fn draw_promotion(&mut self, ui: &mut egui::Ui) {
         ui.heading("修仙晋升确认");
         ui.add_space(10.0);
-        for line in self.game.result_message.lines() {
-            ui.label(line);
-        }
+        widgets::result_message(ui, &self.game.result_message, &self.palette(), self.settings.appearance.show_emoji);
+        ui.add_space(10.0);
+        self.draw_promotion_preview(ui);
         ui.add_space(16.0);
 
         if ui.button("晋升").clicked() {
-            self.game.promote_yes();
+            self.promotion_preview = None;
+            self.game.execute(GameCommand::Promote);
         }
         if ui.button("暂缓晋升").clicked() {
-            self.game.promote_no();
+            self.promotion_preview = None;
+            self.game.execute(GameCommand::DeclinePromotion);
         }
     }
 
-    fn draw_game_over(&mut self, ui: &mut egui::Ui) {
-        ui.heading("游戏结束");
-        ui.add_space(10.0);
-        for line in self.game.result_message.lines() {
-            ui.label(line);
+    /// 晋升推演：后台把“现在晋升”和“缓几天”各推演若干遍，边跑边更新存活率和技能点
+    fn draw_promotion_preview(&mut self, ui: &mut egui::Ui) {
+        let Some(state) = &self.game.game_state else {
+            return;
+        };
+        if self.promotion_preview.as_ref().is_none_or(|preview| preview.day != state.current_day) {
+            self.promotion_preview = Some(forecast::PromotionPreview::start(state));
         }
-        ui.add_space(16.0);
+        let Some(preview) = self.promotion_preview.as_mut() else {
+            return;
+        };
+        if preview.poll() {
+            ui.ctx().request_repaint_after(std::time::Duration::from_millis(100));
+        }
+        ui.label(format!(
+            "🔮 推演接下来 {} 天（各 {} 遍，按稳健策略代打）：",
+            whatif::HORIZON_DAYS,
+            whatif::ROLLOUTS
+        ));
+        egui::Grid::new("promotion_preview_grid").striped(true).num_columns(4).show(ui, |ui| {
+            for title in ["", "存活率", "晋升成功", "平均技能点"] {
+                ui.strong(title);
+            }
+            ui.end_row();
+            for (plan, forecast) in preview.forecasts() {
+                ui.label(format!("{}（{} 遍）", plan, forecast.runs));
+                if forecast.runs == 0 {
+                    ui.weak("推演中…");
+                } else {
+                    ui.monospace(format!("{:.0}%", forecast.survival_rate() * 100.0));
+                    ui.monospace(format!("{:.0}%", forecast.promotion_rate() * 100.0));
+                    ui.monospace(format!("{:.0}", forecast.avg_skills()));
+                }
+                ui.end_row();
+            }
+        });
+    }
 
-        if ui.button("重新开始").clicked() {
-            self.game.restart();
+    /// 周报：新的一周开始前，回顾上一周
+    fn draw_weekly_recap(&mut self, ui: &mut egui::Ui) {
+        let Some(recap) = self.game.weekly_recap.as_ref() else {
+            return;
+        };
+        let palette = self.palette();
+        let (grade, comment) = recap.grade();
+        ui.heading(format!(
+            "📅 第{}周周报（第{}~{}天）",
+            recap.week, recap.first_day, recap.last_day
+        ));
+        ui.add_space(8.0);
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(grade).size(32.0).strong().color(palette.highlight));
+            ui.label(comment);
+        });
+        ui.add_space(8.0);
+        egui::Grid::new("weekly_recap_grid").num_columns(2).show(ui, |ui| {
+            let signed = |v: i32| if v >= 0 { format!("+{}", v) } else { v.to_string() };
+            ui.label("技能点变化");
+            ui.colored_label(palette.skill, signed(recap.skill_change));
+            ui.end_row();
+            ui.label("压力值变化");
+            ui.colored_label(palette.pressure, signed(recap.pressure_change));
+            ui.end_row();
+            ui.label("同事往来");
+            ui.label(format!("同意 {} 次，拒绝 {} 次", recap.npc_accepted, recap.npc_rejected));
+            ui.end_row();
+        });
+        ui.add_space(8.0);
+        if let Some(best) = &recap.best {
+            ui.colored_label(palette.positive, format!("👍 最佳选择：{}", best));
+        }
+        if let Some(worst) = &recap.worst {
+            ui.colored_label(palette.negative, format!("👎 最差选择：{}", worst));
+        }
+        ui.add_space(8.0);
+        if let Some(state) = &mut self.game.game_state {
+            ui.label("下周怎么通勤？");
+            ui.horizontal_wrapped(|ui| {
+                for commute in commute::Commute::ALL {
+                    let selected = state.player.commute == commute;
+                    if ui
+                        .selectable_label(selected, commute.label())
+                        .on_hover_text(commute.description())
+                        .clicked()
+                    {
+                        state.set_commute(commute);
+                    }
+                }
+            });
+        }
+        ui.add_space(16.0);
+        let label = format!("开始新的一周{}", self.key_hint(Action::Advance));
+        if ui.button(label).clicked() {
+            self.game.execute(GameCommand::CloseRecap);
         }
     }
-}
 
-impl App for XiuxianApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut Frame) {
-        egui::SidePanel::right("npc_side_panel")
-            .resizable(true)
-            .default_width(320.0)
-            .min_width(240.0)
+    /// 深夜来电：夜色背景上弹出必须当场答复的电话
+    fn draw_night_call(&mut self, ctx: &egui::Context, ui: &mut egui::Ui) {
+        let Some(call) = self.game.game_state.as_ref().and_then(|g| g.night_call.clone()) else {
+            return;
+        };
+        let palette = self.palette();
+        ui.heading("🌙 夜深了……");
+        ui.weak("手机突然响了起来。");
+        let mut answer = None;
+        egui::Window::new(format!("📞 {} 来电", call.caller))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
             .show(ctx, |ui| {
-                ui.add_space(10.0);
-                self.draw_npc_panel(ui);
+                let portrait = self.assets.texture(ctx, &AssetKey::Portrait(call.caller.clone()));
+                ui.horizontal(|ui| {
+                    widgets::portrait(ui, portrait, &call.caller, 40.0);
+                    ui.label(egui::RichText::new(&call.plea).strong());
+                });
+                ui.add_space(8.0);
+                ui.colored_label(palette.negative, "⚠️ 这通电话的后果远比白天的事大");
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    let accept = format!("{}{}", call.accept.summary, self.key_hint(Action::Choose1));
+                    if ui.button(accept).clicked() {
+                        answer = Some(NpcDecision::Accept);
+                    }
+                    let reject = format!("{}{}", call.reject.summary, self.key_hint(Action::Choose2));
+                    if ui.button(reject).clicked() {
+                        answer = Some(NpcDecision::Reject);
+                    }
+                });
             });
+        if let Some(decision) = answer {
+            self.game.execute(GameCommand::AnswerNightCall(decision));
+        }
+    }
 
-        egui::CentralPanel::default().show(ctx, |ui| {
-            ui.add_space(10.0);
-            ui.heading("================ 修仙编程游戏 ================");
-            ui.label("从 996 到飞升的征途");
-            ui.add_space(16.0);
-
-            match self.game.phase {
-                GamePhase::Start => self.draw_start(ui, ctx),
-                GamePhase::EventDisplay | GamePhase::WeeklyEventDisplay => self.draw_gameplay(ui),
-                GamePhase::PromotionConfirm => self.draw_promotion(ui),
-                GamePhase::GameOver => self.draw_game_over(ui),
+    /// 结果画面上的赛事成绩码：用赛事密钥签名，生成后复制到剪贴板
+    fn draw_tournament_token(&mut self, ui: &mut egui::Ui) {
+        let has_key = !self.settings.tournament_key.is_empty();
+        let button = ui
+            .add_enabled(has_key, egui::Button::new("🏆 生成赛事成绩码"))
+            .on_disabled_hover_text("先在设置里填写主办方提供的赛事密钥");
+        if button.clicked() {
+            if let Some(state) = &self.game.game_state {
+                let signed = TournamentResult::from_state(state).sign(&self.settings.tournament_key);
+                self.tournament_token = signed.unwrap_or_else(|err| err);
+                let token = self.tournament_token.clone();
+                ui.output_mut(|o| o.copied_text = token);
             }
-        });
+        }
+        if !self.tournament_token.is_empty() {
+            ui.small("已复制到剪贴板，提交给主办方即可：");
+            ui.add(
+                egui::TextEdit::multiline(&mut self.tournament_token.as_str())
+                    .desired_rows(3)
+                    .desired_width(f32::INFINITY),
+            );
+        }
+        ui.add_space(8.0);
     }
-}
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum GamePhase {
-    Start,
-    EventDisplay,
-    WeeklyEventDisplay,
-    PromotionConfirm,
-    GameOver,
-}
+    /// 结算页的挑战二维码：编码开局配置和得分，朋友扫码打开网页版挑战同一局（挑战模式的局不出码）
+    fn draw_challenge_qr(&mut self, ui: &mut egui::Ui) {
+        let Some(state) = self.game.game_state.as_ref().filter(|state| state.scenario.is_none()) else {
+            return;
+        };
+        let link = Challenge::of(state).link();
+        egui::CollapsingHeader::new("📱 扫码挑战同一局")
+            .default_open(false)
+            .show(ui, |ui| {
+                if self.challenge_qr.as_ref().is_none_or(|(cached, _)| *cached != link) {
+                    self.challenge_qr = qr_texture(ui.ctx(), &link).map(|texture| (link.clone(), texture));
+                }
+                match &self.challenge_qr {
+                    Some((_, texture)) => {
+                        ui.add(egui::Image::new(texture).fit_to_exact_size(egui::vec2(180.0, 180.0)));
+                    }
+                    None => {
+                        ui.weak("链接太长，生成不了二维码");
+                    }
+                }
+                ui.horizontal(|ui| {
+                    ui.weak(&link);
+                    if ui.small_button("📋 复制链接").clicked() {
+                        ui.output_mut(|o| o.copied_text = link.clone());
+                    }
+                });
+            });
+    }
 
-struct GameApp {
-    phase: GamePhase,
-    game_state: Option<GameState>,
-    player_name: String,
-    result_message: String,
-}
+    /// 结束画面：结果之外还能只读查看最终状态、完整历史与同事往来，看完再重开
+    fn draw_game_over(&mut self, ui: &mut egui::Ui) {
+        ui.heading("游戏结束");
+        ui.horizontal(|ui| {
+            for tab in ObserverTab::ALL {
+                ui.selectable_value(&mut self.observer_tab, tab, tab.label());
+            }
+        });
+        ui.separator();
 
-impl GameApp {
-    fn new() -> Self {
-        Self {
-            phase: GamePhase::Start,
-            game_state: None,
-            player_name: String::new(),
-            result_message: String::new(),
+        match (self.observer_tab, self.game.game_state.as_ref()) {
+            (ObserverTab::状态, Some(state)) => {
+                self.draw_stats(ui, state);
+                self.draw_biography(ui, state);
+                self.draw_tendencies(ui, state);
+                self.draw_finance(ui, state, false);
+            }
+            (ObserverTab::统计, Some(state)) => self.draw_training_stats(ui, state),
+            (ObserverTab::历史, Some(state)) => self.history_page = self.draw_history(ui, state, 360.0),
+            (ObserverTab::同事, Some(state)) => Self::draw_npc_dealings(ui, state),
+            (ObserverTab::成就, _) => self.draw_achievements(ui),
+            _ => {
+                if let Some(state) = &self.game.game_state {
+                    let mut card = ending::card(state);
+                    card.description = self.game.dark_content.ending_description(card.ending, card.description);
+                    widgets::ending_card(ui, &card, &self.palette());
+                    if ui.small_button("📋 复制结局").clicked() {
+                        ui.output_mut(|o| o.copied_text = card.to_string());
+                    }
+                    ui.add_space(8.0);
+                }
+                widgets::result_message(ui, &self.game.result_message, &self.palette(), self.settings.appearance.show_emoji);
+                if let Some(state) = &self.game.game_state {
+                    ui.horizontal(|ui| {
+                        ui.weak(format!(
+                            "本局种子: {} · 难度: {}",
+                            state.seed,
+                            state.player.balance.difficulty.label()
+                        ));
+                        if ui
+                            .small_button("📋 复制种子")
+                            .on_hover_text("朋友在开始界面填这个种子，就能挑战同一局")
+                            .clicked()
+                        {
+                            ui.output_mut(|o| o.copied_text = state.seed.to_string());
+                        }
+                    });
+                    if state.ascension > 0 {
+                        ui.label(format!("🌟 飞升等级 {} 得分: {}", state.ascension, state.score()));
+                    }
+                    let banned = state.banned_event_names();
+                    if !banned.is_empty() {
+                        ui.weak(format!("🚫 本局禁用：{}", banned.join("、")));
+                    }
+                }
+                self.draw_challenge_qr(ui);
+                ui.add_space(16.0);
+                self.draw_highlight_button(ui);
+                self.draw_tournament_token(ui);
+            }
         }
+
+        ui.add_space(12.0);
+        ui.horizontal(|ui| {
+            if ui.button("重新开始").clicked() {
+                self.restart();
+            }
+            if ui
+                .button("🔁 重开这个种子")
+                .on_hover_text("同样的种子、同样的事件顺序，换个选法再来一次")
+                .clicked()
+            {
+                self.rematch();
+            }
+            if let Some(karma) = self.regular_run().map(MetaProgress::karma_for) {
+                if ui
+                    .button(format!("☯ 转世（仙缘点 +{}）", karma))
+                    .on_hover_text("把这一世的修为折成仙缘点，在开局界面兑换永久加成")
+                    .clicked()
+                {
+                    self.reincarnate();
+                }
+            }
+        });
     }
 
-    fn start_game(&mut self) {
-        if !self.player_name.trim().is_empty() {
-            self.game_state = Some(GameState::new(self.player_name.clone()));
-            self.phase = GamePhase::EventDisplay;
-            self.result_message.clear();
+    /// 成就列表：已解锁的带解锁日期，未解锁的灰显并写明条件
+    fn draw_achievements(&self, ui: &mut egui::Ui) {
+        let palette = self.palette();
+        ui.strong(format!("已解锁 {}/{}", self.achievements.count(), Achievement::ALL.len()));
+        egui::ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+            egui::Grid::new("achievements_grid")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    for achievement in Achievement::ALL {
+                        match self.achievements.unlocked_on(achievement) {
+                            Some(date) => {
+                                ui.colored_label(palette.positive, format!("{} {}", achievement.icon(), achievement));
+                                ui.label(achievement.description());
+                                ui.weak(date);
+                            }
+                            None => {
+                                ui.weak(format!("🔒 {}", achievement));
+                                ui.weak(achievement.description());
+                                ui.weak("未解锁");
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+
+    fn draw_achievements_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_achievements;
+        egui::Window::new("🏅 成就")
+            .open(&mut open)
+            .default_width(420.0)
+            .show(ctx, |ui| self.draw_achievements(ui));
+        self.show_achievements = open;
+    }
+
+    /// 本局与各位同事的往来（同意/拒绝次数）
+    fn draw_npc_dealings(ui: &mut egui::Ui, state: &GameState) {
+        let dealings = state.player.npc_dealings();
+        if dealings.is_empty() {
+            ui.label("这局没和同事打过交道");
+            return;
         }
+        egui::Grid::new("npc_dealings_grid")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.strong("同事");
+                ui.strong("同意");
+                ui.strong("拒绝");
+                ui.end_row();
+                for (name, accepted, rejected) in dealings {
+                    ui.label(name);
+                    ui.label(accepted.to_string());
+                    ui.label(rejected.to_string());
+                    ui.end_row();
+                }
+            });
     }
+}
 
-    fn apply_choice(&mut self, choice: u8) {
-        use GamePhase::*;
+/// 进修面板上的点击
+#[derive(Debug, Clone, Copy)]
+enum CourseClick {
+    Enroll(Course),
+    Drop,
+}
 
-        if let Some(game) = &mut self.game_state {
-            match self.phase {
-                EventDisplay => {
-                    if game.event_chosen_today {
-                        self.result_message = "今天已经选择过了！\n按 \"进入下一天\" 继续".to_string();
-                        return;
-                    }
+/// 投资面板上的点击
+#[derive(Debug, Clone, Copy)]
+enum PortfolioClick {
+    Buy(Instrument),
+    Sell(Instrument),
+}
 
-                    let daily_event = game.get_today_event().clone();
-                    let idx = choice.saturating_sub(1) as usize;
-                    let option = match daily_event.shuffled_options.get(idx) {
-                        Some(opt) => opt,
-                        None => return,
-                    };
+/// 窄屏布局下事件、历史、同事分页显示
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GameplayTab {
+    事件,
+    状态,
+    历史,
+    同事,
+}
 
-                    let (skill_reward, pressure_change) = option.value;
-                    let choice_desc = option.desc.clone();
-                    let story = option.story.clone();
+impl GameplayTab {
+    const ALL: [GameplayTab; 4] = [GameplayTab::事件, GameplayTab::状态, GameplayTab::历史, GameplayTab::同事];
 
-                    game.player.gain_reward(skill_reward, pressure_change);
-                    let choice_text = choice_desc.split('\n').next().unwrap_or("").to_string();
-                    game.player.add_history(
-                        format!("{} - {}\n💬 {}", daily_event.name, choice_text, story),
-                        skill_reward,
-                        pressure_change,
-                    );
+    fn label(&self) -> &'static str {
+        match self {
+            GameplayTab::事件 => "📜 事件",
+            GameplayTab::状态 => "📊 状态",
+            GameplayTab::历史 => "🧾 历史",
+            GameplayTab::同事 => "👥 同事",
+        }
+    }
+}
 
-                    game.event_chosen_today = true;
+/// 结束画面的只读页签
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ObserverTab {
+    结果,
+    状态,
+    统计,
+    历史,
+    同事,
+    成就,
+}
 
-                    if let Some(weekly) = game.get_weekly_event() {
-                        self.phase = WeeklyEventDisplay;
-                        self.result_message = format!("📖 {}\n\n⚠️ 周事件触发：{}", story, weekly.name);
-                    } else {
-                        self.result_message = format!("📖 {}\n\n点击 \"进入下一天\" 继续", story);
-                    }
-                }
-                WeeklyEventDisplay => {
-                    if game.weekly_event_chosen_today {
-                        self.result_message = "本周事件已完成！\n点击 \"进入下一天\" 继续".to_string();
-                        return;
-                    }
+impl ObserverTab {
+    const ALL: [ObserverTab; 6] = [
+        ObserverTab::结果,
+        ObserverTab::状态,
+        ObserverTab::统计,
+        ObserverTab::历史,
+        ObserverTab::同事,
+        ObserverTab::成就,
+    ];
 
-                    if let Some(weekly) = game.get_weekly_event().cloned() {
-                        let idx = choice.saturating_sub(1) as usize;
-                        let option = match weekly.shuffled_options.get(idx) {
-                            Some(opt) => opt,
-                            None => return,
-                        };
+    fn label(&self) -> &'static str {
+        match self {
+            ObserverTab::结果 => "🏁 结果",
+            ObserverTab::状态 => "📊 最终状态",
+            ObserverTab::统计 => "📈 修炼统计",
+            ObserverTab::历史 => "🧾 完整历史",
+            ObserverTab::同事 => "👥 同事往来",
+            ObserverTab::成就 => "🏅 成就",
+        }
+    }
+}
 
-                        let (skill_reward, pressure_change) = option.value;
-                        let choice_desc = option.desc.clone();
-                        let story = option.story.clone();
+impl App for XiuxianApp {
+    fn update(&mut self, ctx: &egui::Context, frame: &mut Frame) {
+        #[cfg(target_arch = "wasm32")]
+        if !self.load_web_assets(ctx) {
+            return;
+        }
+        #[cfg(feature = "debug")]
+        self.profiler.begin_frame();
+        self.update_big_screen(ctx);
+        self.update_touch(ctx);
+        // 还没摆面板，这时的可用宽度就是整个窗口（手机上是浏览器视口）
+        self.narrow = ctx.available_rect().width() < NARROW_WIDTH;
+        self.audio.update_music(&self.settings.audio, self.game.in_run());
+        self.update_weekend_theme(ctx);
+        self.update_window_title(ctx);
+        self.update_highlights(ctx);
+        self.dispatch_engine_events();
+        self.tick_npc_voice(ctx);
+        self.tick_play_time(ctx);
+        self.tick_auto_play(ctx);
+        self.tick_idle(ctx);
+        self.handle_shortcuts(ctx);
 
-                        game.player.gain_reward(skill_reward, pressure_change);
-                        let choice_text = choice_desc.split('\n').next().unwrap_or("").to_string();
-                        game.player.add_history(
-                            format!("【周事件】{} - {}\n💬 {}", weekly.name, choice_text, story),
-                            skill_reward,
-                            pressure_change,
-                        );
+        #[cfg(all(feature = "debug", not(target_arch = "wasm32")))]
+        match self.tuning_watch.poll(ctx.input(|i| i.time)) {
+            Some(Ok(())) => self.game.push_notice("🔧 调参表已重新载入"),
+            Some(Err(err)) => self.game.push_notice(format!("🔧 调参表没换：{}", err)),
+            None => {}
+        }
+        #[cfg(all(feature = "debug", not(target_arch = "wasm32")))]
+        self.draw_balance_ab(ctx);
 
-                        game.weekly_event_chosen_today = true;
-                        game.today_weekly_event = None;
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.limit_frame_rate();
+            self.draw_update_banner(ctx);
+            self.draw_launch_banner(ctx);
+            self.update_stream_overlay(ctx);
+            self.remind_daily(ctx);
+        }
 
-                        self.phase = EventDisplay;
-                        self.result_message = format!("📖 {}\n\n周事件完成！点击 \"进入下一天\" 继续", story);
-                    }
+        egui::TopBottomPanel::top("menu_bar").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                if ui.selectable_label(self.show_settings, "⚙ 设置").clicked() {
+                    self.show_settings = !self.show_settings;
                 }
-                _ => {}
-            }
+                if ui
+                    .selectable_label(!self.profile_chosen, format!("👤 {}", self.profiles.current))
+                    .on_hover_text("切换档案（会结束当前这局）")
+                    .clicked()
+                {
+                    self.profile_chosen = false;
+                }
+                if self.game.game_state.is_some() {
+                    ui.separator();
+                    self.draw_auto_play_controls(ui);
+                }
+                if self.detailed_layout() {
+                    ui.separator();
+                    let panels = &mut self.settings.panels;
+                    ui.toggle_value(&mut panels.stats_visible, "📊 状态栏");
+                    ui.toggle_value(&mut panels.side_visible, "🧾 历史/NPC");
+                }
+                #[cfg(feature = "debug")]
+                {
+                    ui.separator();
+                    ui.toggle_value(&mut self.show_inspector, "🔍 状态检查");
+                    ui.toggle_value(&mut self.show_profiler, "⏱ 帧耗时");
+                    #[cfg(not(target_arch = "wasm32"))]
+                    ui.toggle_value(&mut self.show_balance_ab, "🆎 平衡对比");
+                }
+            });
+        });
+        self.draw_settings_window(ctx);
+        self.draw_changelog(ctx);
+        self.draw_about(ctx);
+        self.draw_simulation(ctx);
+        self.draw_achievements_window(ctx);
+        #[cfg(feature = "debug")]
+        {
+            self.draw_inspector(ctx);
+            self.draw_profiler(ctx);
         }
-    }
 
-    fn next_day(&mut self) {
-        if let Some(game) = &mut self.game_state {
-            game.player.check_death();
+        let in_gameplay = matches!(
+            self.game.phase,
+            GamePhase::EventDisplay | GamePhase::WeeklyEventDisplay
+        );
 
-            if !game.player.is_alive {
-                self.phase = GamePhase::GameOver;
-                self.result_message = format!(
-                    "【{}】\n\n游玩时间: {}\n天数: {}\n技能点: {}\n压力值: {}\n修仙境界: {}",
-                    game.player.get_death_message(),
-                    game.format_time(),
-                    game.player.days_played,
-                    game.player.skills,
-                    game.player.pressure,
-                    game.player.get_realm()
-                );
-            } else if game.player.can_promote() {
-                self.phase = GamePhase::PromotionConfirm;
-                let failure_percent = (5.0 * (game.player.promotion_attempts as f32 + 1.0)).min(95.0) as i32;
-                self.result_message = format!(
-                    "你已积累足够经验！\n是否选择晋升？\n(失败率: {}%)\n点击下方按钮进行选择",
-                    failure_percent
-                );
-            } else {
-                game.next_day();
-                self.phase = GamePhase::EventDisplay;
-                self.result_message.clear();
+        if in_gameplay {
+            if let Some(state) = &self.game.game_state {
+                let headlines = news::headlines(state);
+                let color = self.palette().highlight;
+                let animate = !self.settings.accessibility.reduced_motion;
+                egui::TopBottomPanel::top("news_ticker").show(ctx, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.strong("📰 公司新闻");
+                        widgets::ticker(ui, &headlines, color, animate);
+                    });
+                });
             }
         }
-    }
 
-    fn promote_yes(&mut self) {
-        if let Some(game) = &mut self.game_state {
-            let (success, msg) = game.player.attempt_promotion();
-            self.result_message = msg;
-            if success {
-                game.next_day();
-                self.phase = GamePhase::EventDisplay;
-            } else {
-                self.result_message.push_str("\n\n点击 \"进入下一天\" 继续努力");
-            }
-        }
-    }
+        if self.detailed_layout() {
+            let panels = self.settings.panels.clone();
+            egui::SidePanel::left("stats_side_panel")
+                .resizable(true)
+                .default_width(260.0)
+                .min_width(200.0)
+                .show_animated(ctx, in_gameplay && panels.stats_visible, |ui| {
+                    ui.add_space(10.0);
+                    egui::ScrollArea::vertical().show(ui, |ui| self.draw_status_column(ui, true));
+                });
 
-    fn promote_no(&mut self) {
-        if let Some(game) = &mut self.game_state {
-            game.next_day();
-            self.phase = GamePhase::EventDisplay;
-            self.result_message.clear();
+            egui::SidePanel::right("npc_side_panel")
+                .resizable(true)
+                .default_width(320.0)
+                .min_width(240.0)
+                .show_animated(ctx, panels.side_visible, |ui| {
+                    ui.add_space(10.0);
+                    // 历史记录放在可拖拽高度的子面板里，折叠时不占空间
+                    if in_gameplay && !panels.history_collapsed {
+                        egui::TopBottomPanel::top("history_panel")
+                            .resizable(true)
+                            .default_height(240.0)
+                            .height_range(80.0..=ui.available_height() * 0.8)
+                            .show_inside(ui, |ui| {
+                                self.draw_history_section(ui, f32::INFINITY);
+                            });
+                    } else if in_gameplay {
+                        self.draw_history_section(ui, 0.0);
+                        ui.separator();
+                    }
+                    egui::ScrollArea::vertical().show(ui, |ui| self.draw_npc_section(ui));
+                });
         }
-    }
 
-    fn restart(&mut self) {
-        *self = GameApp::new();
-    }
-
-    fn current_event_metadata(&self) -> Option<(String, String, Vec<OptionInfo>, bool)> {
-        let game_state = self.game_state.as_ref()?;
-        if matches!(self.phase, GamePhase::WeeklyEventDisplay) {
-            let weekly = game_state.get_weekly_event()?;
-            Some((
-                format!("【周事件】{}", weekly.name),
-                weekly.description.clone(),
-                weekly.shuffled_options.clone(),
-                true,
-            ))
-        } else {
-            let daily = game_state.get_today_event();
-            Some((
-                format!("【日常事件】{}", daily.name),
-                daily.description.clone(),
-                daily.shuffled_options.clone(),
-                false,
-            ))
-        }
-    }
+        egui::CentralPanel::default().show(ctx, |ui| {
+            self.draw_scenery(ui);
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                ui.add_space(10.0);
+                ui.heading("================ 修仙编程游戏 ================");
+                ui.label("从 996 到飞升的征途");
+                ui.add_space(16.0);
 
-    fn can_make_daily_choice(&self) -> bool {
-        self.game_state
-            .as_ref()
-            .map(|g| !g.event_chosen_today)
-            .unwrap_or(false)
+                if let Some(interstitial) = self.interstitial.as_mut() {
+                    if interstitial.show(ui) {
+                        self.interstitial = None;
+                    }
+                    return;
+                }
+                if !self.profile_chosen {
+                    self.draw_profile_select(ui, frame);
+                    return;
+                }
+                match self.game.phase {
+                    GamePhase::Start if self.browsing_scenarios => self.draw_scenarios(ui),
+                    GamePhase::Start => self.draw_start(ui, ctx),
+                    GamePhase::EventDisplay | GamePhase::WeeklyEventDisplay => self.draw_gameplay(ui),
+                    GamePhase::PromotionConfirm => self.draw_promotion(ui),
+                    GamePhase::WeeklyRecap => self.draw_weekly_recap(ui),
+                    GamePhase::NightCall => self.draw_night_call(ctx, ui),
+                    GamePhase::GameOver => self.draw_game_over(ui),
+                }
+            });
+        });
+        #[cfg(feature = "debug")]
+        self.profiler.end_frame();
     }
 
-    fn can_make_weekly_choice(&self) -> bool {
-        self.game_state
-            .as_ref()
-            .map(|g| g.today_weekly_event.is_some() && !g.weekly_event_chosen_today)
-            .unwrap_or(false)
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, profile::PROFILES_KEY, &self.profiles);
+        self.store_profile(storage);
+        self.store_run(storage);
     }
 }
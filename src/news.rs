@@ -0,0 +1,77 @@
+//! 公司新闻：根据当前世界状态生成滚动播报的新闻标题
+
+use crate::game::{GameState, PlayerState};
+use crate::week;
+
+/// 同门卷王每天稳定积累的技能点（用于“对手进度”播报）
+const RIVAL_DAILY_SKILLS: i32 = 5;
+
+/// 生成当前的新闻标题列表（顺序即播报顺序）
+pub fn headlines(state: &GameState) -> Vec<String> {
+    let player = &state.player;
+    let mut news = vec![project_health(player), festival_countdown(state)];
+    if let Some(saga) = &state.saga {
+        news.push(format!("【连载】「{}」余波未平，周五大考将迎来续集", saga.previous_event));
+    }
+    if let Some(rival) = rival_progress(state) {
+        news.push(rival);
+    }
+    if let Some(gossip) = latest_gossip(player) {
+        news.push(gossip);
+    }
+    news
+}
+
+/// 项目健康度：由玩家压力折射整个项目组的状态
+fn project_health(player: &PlayerState) -> String {
+    let band = PlayerState::pressure_band(player.pressure);
+    let status = match player.pressure {
+        0..=19 => "需求排期宽松，项目组集体摸鱼",
+        20..=49 => "项目按计划推进，偶有加班",
+        50..=69 => "线上告警频发，周报已改为日报",
+        _ => "项目全面亮红灯，工位灯火通明",
+    };
+    format!("【项目】{}（压力{}）", status, band)
+}
+
+/// 距离周五大考的倒计时
+fn festival_countdown(state: &GameState) -> String {
+    if state.weekly_event_pending() {
+        return "【公告】本周大考进行中，请各位道友严阵以待".to_string();
+    }
+    let interval = state.player.balance.weekly_event_interval.max(1);
+    match week::days_until_weekly_event(state.current_day, interval) {
+        0 => format!("【公告】本场大考已结束，下一场{}天后开考", interval),
+        days_left => format!("【公告】距离周五大考还有 {} 天", days_left),
+    }
+}
+
+/// 同门卷王的进度（以今日出没的第一位 NPC 为对手）
+fn rival_progress(state: &GameState) -> Option<String> {
+    let rival = state
+        .today_npcs
+        .first()
+        .or_else(|| state.npc_master.first())?;
+    let rival_skills = state.current_day as i32 * RIVAL_DAILY_SKILLS;
+    let gap = rival_skills - state.player.skills;
+    let comparison = if gap > 0 {
+        format!("领先{} {} 点", state.player.name, gap)
+    } else if gap < 0 {
+        format!("落后{} {} 点", state.player.name, -gap)
+    } else {
+        format!("与{}并驾齐驱", state.player.name)
+    };
+    Some(format!(
+        "【同门】{} 已累计 {} 技能点，{}",
+        rival.name, rival_skills, comparison
+    ))
+}
+
+/// 最近一次抉择的八卦
+fn latest_gossip(player: &PlayerState) -> Option<String> {
+    let entry = player.history.iter().rev().find(|entry| entry.kind.is_choice())?;
+    Some(format!(
+        "【八卦】听说第{}天有人在「{}」中选择了{}",
+        entry.day, entry.event_name, entry.choice
+    ))
+}
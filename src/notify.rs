@@ -0,0 +1,12 @@
+//! 桌面通知（桌面端）：窗口最小化时提醒今天的每日挑战还没打
+
+/// 在后台线程里发一条提醒（系统不支持通知时静默忽略）
+pub fn remind_daily_challenge() {
+    std::thread::spawn(|| {
+        let _ = notify_rust::Notification::new()
+            .appname("修仙编程游戏")
+            .summary("📅 每日挑战")
+            .body("今天的每日挑战还没打，道友速来渡劫！")
+            .show();
+    });
+}
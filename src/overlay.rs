@@ -0,0 +1,77 @@
+//! 直播小窗（桌面端）：单独的置顶迷你窗口，只显示天数、境界、压力与速通计时。
+//! 小窗有自己的刷新回调；计时跟着主窗口的游玩时间走，主窗口最小化去休息时计时暂停。
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use eframe::egui::{self, Color32};
+
+use crate::game::Realm;
+use crate::speedrun;
+
+/// 小窗展示的数据，由主窗口每帧更新
+#[derive(Debug, Clone)]
+pub struct OverlaySnapshot {
+    pub day: u32,
+    pub realm: Realm,
+    pub pressure: i32,
+    pub pressure_band: &'static str,
+    pub pressure_color: Color32,
+    pub timer_ms: Option<u64>,  // 速通模式下的累计游玩时间（毫秒）
+}
+
+/// 直播小窗：主窗口与小窗之间共享最新数据
+#[derive(Default)]
+pub struct StreamOverlay {
+    snapshot: Arc<Mutex<Option<OverlaySnapshot>>>,
+    closed: Arc<AtomicBool>,  // 玩家点了小窗的关闭按钮
+}
+
+impl StreamOverlay {
+    /// 更新小窗数据（没有进行中的对局时传 None）
+    pub fn set(&self, snapshot: Option<OverlaySnapshot>) {
+        if let Ok(mut current) = self.snapshot.lock() {
+            *current = snapshot;
+        }
+    }
+
+    /// 每帧在主窗口里调用以保持小窗开启；玩家关掉小窗时返回 false
+    pub fn show(&self, ctx: &egui::Context) -> bool {
+        if self.closed.swap(false, Ordering::Relaxed) {
+            return false;
+        }
+        let snapshot = Arc::clone(&self.snapshot);
+        let closed = Arc::clone(&self.closed);
+        ctx.show_viewport_deferred(
+            egui::ViewportId::from_hash_of("stream_overlay"),
+            egui::ViewportBuilder::default()
+                .with_title("修仙小窗")
+                .with_inner_size([200.0, 90.0])
+                .with_always_on_top()
+                .with_resizable(false),
+            move |ctx, _class| {
+                if ctx.input(|i| i.viewport().close_requested()) {
+                    closed.store(true, Ordering::Relaxed);
+                    ctx.request_repaint_of(egui::ViewportId::ROOT);
+                }
+                let current = snapshot.lock().ok().and_then(|s| s.clone());
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    let Some(s) = current else {
+                        ui.weak("还没开局");
+                        return;
+                    };
+                    ui.strong(format!("第 {} 天 · {}", s.day, s.realm));
+                    ui.colored_label(s.pressure_color, format!("压力 {}（{}）", s.pressure, s.pressure_band));
+                    if let Some(ms) = s.timer_ms {
+                        ui.monospace(speedrun::format_millis(ms));
+                        ctx.request_repaint_after(Duration::from_millis(100));
+                    }
+                });
+                // 没有计时时也定期刷新，跟上主窗口里的变化
+                ctx.request_repaint_after(Duration::from_millis(500));
+            },
+        );
+        true
+    }
+}
@@ -0,0 +1,59 @@
+//! 本机多档案：每个档案有独立的设置与统计，存储键按档案名加命名空间
+//! （桌面端文件存储与网页端 localStorage 通用）
+
+use serde::{Deserialize, Serialize};
+
+/// eframe 存储中保存档案列表的键
+pub const PROFILES_KEY: &str = "xiuxian_profiles";
+/// 默认档案沿用不带命名空间的旧键，老玩家的数据自动归入该档案
+pub const DEFAULT_PROFILE: &str = "默认";
+/// 档案名最大长度（字符）
+const MAX_NAME_CHARS: usize = 16;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profiles {
+    pub names: Vec<String>,
+    pub current: String,
+}
+
+impl Default for Profiles {
+    fn default() -> Self {
+        Profiles {
+            names: vec![DEFAULT_PROFILE.to_string()],
+            current: DEFAULT_PROFILE.to_string(),
+        }
+    }
+}
+
+impl Profiles {
+    /// 某个档案下的存储键
+    pub fn key_for(profile: &str, base: &str) -> String {
+        if profile == DEFAULT_PROFILE {
+            base.to_string()
+        } else {
+            format!("{}::{}", base, profile)
+        }
+    }
+
+    /// 当前档案下的存储键
+    pub fn key(&self, base: &str) -> String {
+        Self::key_for(&self.current, base)
+    }
+
+    /// 新建档案（名字去掉首尾空白后不能为空、不能重名）
+    pub fn add(&mut self, name: &str) -> Result<String, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("档案名不能为空".to_string());
+        }
+        if name.chars().count() > MAX_NAME_CHARS {
+            return Err(format!("档案名最多{}个字", MAX_NAME_CHARS));
+        }
+        if self.names.iter().any(|n| n == name) {
+            return Err(format!("档案“{}”已存在", name));
+        }
+        self.names.push(name.to_string());
+        Ok(name.to_string())
+    }
+}
@@ -0,0 +1,213 @@
+//! 帧耗时检查（debug 特性）：统计每帧界面逻辑耗时、每帧堆分配次数与字节数，
+//! 以及事件元数据（current_event_metadata，每帧都把标题、描述和选项克隆一遍）这条路径单独的开销，
+//! 用来量化渲染时大量克隆的代价、对比优化前后的数字。
+//! 分配次数来自下面的计数分配器，只在开了 debug 特性时替换全局分配器；统计的是整个进程（含后台线程）的分配。
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use instant::Instant;
+
+/// 统计最近多少帧
+pub const WINDOW: usize = 120;
+
+/// 每帧的时间预算（毫秒，按 60 帧算）
+pub const FRAME_BUDGET_MS: f64 = 1000.0 / 60.0;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// 计数分配器：转交给系统分配器，顺便记下分配次数与字节数
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+fn count(bytes: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    ALLOCATED_BYTES.fetch_add(bytes as u64, Ordering::Relaxed);
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// 一段时间内的分配次数与字节数
+#[derive(Debug, Clone, Copy, Default)]
+struct Allocs {
+    count: u64,
+    bytes: u64,
+}
+
+impl Allocs {
+    fn now() -> Self {
+        Self {
+            count: ALLOCATIONS.load(Ordering::Relaxed),
+            bytes: ALLOCATED_BYTES.load(Ordering::Relaxed),
+        }
+    }
+
+    /// 从 `self` 这个时刻到现在新增的分配
+    fn elapsed(self) -> Self {
+        let now = Self::now();
+        Self {
+            count: now.count - self.count,
+            bytes: now.bytes - self.bytes,
+        }
+    }
+}
+
+/// 一次测量：耗时与期间的分配
+#[derive(Debug, Clone, Copy, Default)]
+struct Sample {
+    micros: f64,
+    allocs: Allocs,
+}
+
+impl Sample {
+    fn add(&mut self, other: Sample) {
+        self.micros += other.micros;
+        self.allocs.count += other.allocs.count;
+        self.allocs.bytes += other.allocs.bytes;
+    }
+}
+
+/// 最近若干次测量的汇总
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Summary {
+    pub samples: usize,
+    pub avg_ms: f64,
+    pub max_ms: f64,
+    pub avg_allocs: f64,
+    pub max_allocs: u64,
+    pub avg_kb: f64,
+}
+
+fn summarize(samples: &VecDeque<Sample>) -> Summary {
+    if samples.is_empty() {
+        return Summary::default();
+    }
+    let n = samples.len() as f64;
+    Summary {
+        samples: samples.len(),
+        avg_ms: samples.iter().map(|s| s.micros).sum::<f64>() / n / 1000.0,
+        max_ms: samples.iter().map(|s| s.micros).fold(0.0, f64::max) / 1000.0,
+        avg_allocs: samples.iter().map(|s| s.allocs.count).sum::<u64>() as f64 / n,
+        max_allocs: samples.iter().map(|s| s.allocs.count).max().unwrap_or(0),
+        avg_kb: samples.iter().map(|s| s.allocs.bytes).sum::<u64>() as f64 / n / 1024.0,
+    }
+}
+
+fn push(window: &mut VecDeque<Sample>, sample: Sample) {
+    if window.len() == WINDOW {
+        window.pop_front();
+    }
+    window.push_back(sample);
+}
+
+/// 帧耗时统计：每帧开头 `begin_frame`、结尾 `end_frame`，要单独统计的路径包在 `measure_metadata` 里
+#[derive(Debug, Default)]
+pub struct FrameProfiler {
+    frame_start: Option<(Instant, Allocs)>,
+    last_frame_start: Option<Instant>,
+    metadata_this_frame: Option<Sample>,
+    frames: VecDeque<Sample>,
+    intervals: VecDeque<f64>,   // 相邻两帧开头的间隔（毫秒）
+    metadata: VecDeque<Sample>, // 每帧事件元数据路径的合计
+}
+
+impl FrameProfiler {
+    pub fn begin_frame(&mut self) {
+        let now = Instant::now();
+        if let Some(last) = self.last_frame_start.replace(now) {
+            if self.intervals.len() == WINDOW {
+                self.intervals.pop_front();
+            }
+            self.intervals.push_back(now.duration_since(last).as_secs_f64() * 1000.0);
+        }
+        self.frame_start = Some((now, Allocs::now()));
+        self.metadata_this_frame = None;
+    }
+
+    pub fn end_frame(&mut self) {
+        let Some((start, allocs)) = self.frame_start.take() else {
+            return;
+        };
+        push(
+            &mut self.frames,
+            Sample {
+                micros: start.elapsed().as_secs_f64() * 1_000_000.0,
+                allocs: allocs.elapsed(),
+            },
+        );
+        if let Some(sample) = self.metadata_this_frame.take() {
+            push(&mut self.metadata, sample);
+        }
+    }
+
+    /// 统计一次事件元数据路径的耗时与分配（同一帧里多次调用会累加）
+    pub fn measure_metadata<T>(&mut self, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let allocs = Allocs::now();
+        let result = f();
+        let sample = Sample {
+            micros: start.elapsed().as_secs_f64() * 1_000_000.0,
+            allocs: allocs.elapsed(),
+        };
+        self.metadata_this_frame.get_or_insert_with(Sample::default).add(sample);
+        result
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// 每帧界面逻辑（update 内）的汇总
+    pub fn frames(&self) -> Summary {
+        summarize(&self.frames)
+    }
+
+    /// 事件元数据路径的汇总（只算调用过的帧）
+    pub fn metadata(&self) -> Summary {
+        summarize(&self.metadata)
+    }
+
+    /// 平均帧间隔（毫秒）
+    pub fn avg_interval_ms(&self) -> f64 {
+        if self.intervals.is_empty() {
+            return 0.0;
+        }
+        self.intervals.iter().sum::<f64>() / self.intervals.len() as f64
+    }
+
+    /// 界面逻辑超出帧预算的帧数
+    pub fn over_budget(&self) -> usize {
+        self.frames
+            .iter()
+            .filter(|s| s.micros / 1000.0 > FRAME_BUDGET_MS)
+            .count()
+    }
+
+    /// 最近各帧的界面逻辑耗时（毫秒），从旧到新
+    pub fn frame_times(&self) -> impl Iterator<Item = f64> + '_ {
+        self.frames.iter().map(|s| s.micros / 1000.0)
+    }
+}
@@ -0,0 +1,231 @@
+//! 存档槽位：每个档案有 `SLOT_COUNT` 个互不影响的续玩存档。
+//! 桌面端存成数据目录下的 saves/<档案>.slot<N>.json，网页端存在 eframe 存储（localStorage）里，
+//! 键是档案命名空间下的 `xiuxian_save#<N>`。改动先记在内存里，`persist` 时统一落地；
+//! 旧版本每个档案只有一个存档，读到时搬进第一个空槽位，旧格式的存档也顺手按新格式重写。
+
+use xiuxian_core::save::SaveGame;
+
+#[cfg(target_arch = "wasm32")]
+use crate::profile::Profiles;
+#[cfg(target_arch = "wasm32")]
+use xiuxian_core::save;
+
+/// 每个档案的存档槽位数
+pub const SLOT_COUNT: usize = 3;
+
+/// 槽位列表上显示的概要
+#[derive(Debug, Clone)]
+pub struct SlotInfo {
+    pub player_name: String,
+    pub realm: String,
+    pub day: u32,
+    pub saved_at: i64,  // 最后游玩（存档）时间，Unix 秒
+}
+
+impl SlotInfo {
+    fn of(save: &SaveGame) -> Self {
+        SlotInfo {
+            player_name: save.state.player.name.clone(),
+            realm: save.state.player.get_realm().to_string(),
+            day: save.state.current_day,
+            saved_at: save.saved_at,
+        }
+    }
+
+    /// 最后游玩时间（本地时间，如“2024-05-01 21:30”）
+    pub fn last_played(&self) -> String {
+        chrono::DateTime::from_timestamp(self.saved_at, 0)
+            .map(|time| time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default()
+    }
+}
+
+/// 一个有存档的槽位：原文加上解析出的概要（读不出来时是原因）
+#[derive(Debug, Clone)]
+struct Slot {
+    json: String,
+    info: Result<SlotInfo, String>,
+}
+
+impl Slot {
+    /// 读入存档原文；旧格式能升级的换成新格式的原文，返回是否需要写回
+    fn from_text(text: String) -> (Self, bool) {
+        match SaveGame::from_json(&text) {
+            Ok(save) => {
+                let outdated = SaveGame::is_outdated(&text);
+                let json = if outdated { save.to_json().unwrap_or(text) } else { text };
+                (Slot { json, info: Ok(SlotInfo::of(&save)) }, outdated)
+            }
+            Err(err) => (Slot { json: text, info: Err(err) }, false),
+        }
+    }
+}
+
+/// 一个档案的全部存档槽位
+pub struct SaveManager {
+    profile: String,
+    slots: Vec<Option<Slot>>,
+    dirty: Vec<bool>,      // 改过还没落地的槽位
+    legacy_adopted: bool,  // 搬进了旧版本的单存档，落地后要清掉旧位置
+}
+
+impl SaveManager {
+    fn empty(profile: &str) -> Self {
+        SaveManager {
+            profile: profile.to_string(),
+            slots: vec![None; SLOT_COUNT],
+            dirty: vec![false; SLOT_COUNT],
+            legacy_adopted: false,
+        }
+    }
+
+    /// 读取某个档案的全部槽位（桌面端），返回读不了的原因
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load(data_dir: &crate::datadir::DataDir, profile: &str) -> (Self, Vec<String>) {
+        let mut manager = Self::empty(profile);
+        let mut errors = Vec::new();
+        for slot in 0..SLOT_COUNT {
+            let read = data_dir.save_slot_file(profile, slot).and_then(|path| {
+                match std::fs::read_to_string(path) {
+                    Ok(text) => Ok(Some(text)),
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                    Err(err) => Err(format!("读取存档失败: {}", err)),
+                }
+            });
+            match read {
+                Ok(text) => manager.fill(slot, text),
+                Err(err) => errors.push(format!("槽位 {}：{}", slot + 1, err)),
+            }
+        }
+        if let Ok(text) = std::fs::read_to_string(data_dir.legacy_save_file(profile)) {
+            if let Err(err) = manager.adopt_legacy(text) {
+                errors.push(err);
+            }
+        }
+        if let Err(err) = manager.persist(data_dir) {
+            errors.push(err);
+        }
+        (manager, errors)
+    }
+
+    /// 读取某个档案的全部槽位（网页端，空字符串表示没有存档）
+    #[cfg(target_arch = "wasm32")]
+    pub fn load(storage: Option<&dyn eframe::Storage>, profile: &str) -> (Self, Vec<String>) {
+        let mut manager = Self::empty(profile);
+        let mut errors = Vec::new();
+        let read = |key: &str| storage.and_then(|s| s.get_string(key)).filter(|text| !text.is_empty());
+        for slot in 0..SLOT_COUNT {
+            manager.fill(slot, read(&slot_key(profile, slot)));
+        }
+        if let Some(text) = read(&Profiles::key_for(profile, save::SAVE_KEY)) {
+            if let Err(err) = manager.adopt_legacy(text) {
+                errors.push(err);
+            }
+        }
+        (manager, errors)
+    }
+
+    fn fill(&mut self, slot: usize, text: Option<String>) {
+        let Some(text) = text else {
+            return;
+        };
+        let (loaded, outdated) = Slot::from_text(text);
+        self.slots[slot] = Some(loaded);
+        self.dirty[slot] |= outdated;
+    }
+
+    /// 旧版本的单存档搬进第一个空槽位
+    fn adopt_legacy(&mut self, text: String) -> Result<(), String> {
+        let slot = self.first_empty().ok_or("存档槽位已满，旧版本的存档没有搬进来")?;
+        self.fill(slot, Some(text));
+        self.dirty[slot] = true;
+        self.legacy_adopted = true;
+        Ok(())
+    }
+
+    /// 把改过的槽位写进文件（桌面端），删掉已经搬进槽位的旧存档
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn persist(&mut self, data_dir: &crate::datadir::DataDir) -> Result<(), String> {
+        for slot in 0..SLOT_COUNT {
+            if !self.dirty[slot] {
+                continue;
+            }
+            let path = data_dir.save_slot_file(&self.profile, slot)?;
+            match &self.slots[slot] {
+                Some(saved) => std::fs::write(path, &saved.json).map_err(|e| format!("写入存档失败: {}", e))?,
+                None if path.exists() => std::fs::remove_file(path).map_err(|e| format!("删除存档失败: {}", e))?,
+                None => {}
+            }
+            self.dirty[slot] = false;
+        }
+        if std::mem::take(&mut self.legacy_adopted) {
+            std::fs::remove_file(data_dir.legacy_save_file(&self.profile))
+                .map_err(|e| format!("删除旧存档失败: {}", e))?;
+        }
+        Ok(())
+    }
+
+    /// 把改过的槽位写进 eframe 存储（网页端），清掉已经搬进槽位的旧存档
+    #[cfg(target_arch = "wasm32")]
+    pub fn persist(&mut self, storage: &mut dyn eframe::Storage) {
+        for slot in 0..SLOT_COUNT {
+            if std::mem::take(&mut self.dirty[slot]) {
+                let json = self.slots[slot].as_ref().map(|saved| saved.json.clone()).unwrap_or_default();
+                storage.set_string(&slot_key(&self.profile, slot), json);
+            }
+        }
+        if std::mem::take(&mut self.legacy_adopted) {
+            storage.set_string(&Profiles::key_for(&self.profile, save::SAVE_KEY), String::new());
+        }
+    }
+
+    /// 槽位概要（空槽位为 None，存档坏了为 Err）
+    pub fn info(&self, slot: usize) -> Option<&Result<SlotInfo, String>> {
+        self.slots.get(slot)?.as_ref().map(|saved| &saved.info)
+    }
+
+    pub fn is_empty(&self, slot: usize) -> bool {
+        self.info(slot).is_none()
+    }
+
+    pub fn first_empty(&self) -> Option<usize> {
+        (0..SLOT_COUNT).find(|slot| self.is_empty(*slot))
+    }
+
+    /// 读出槽位里的存档（槽位不动）
+    pub fn load_slot(&self, slot: usize) -> Result<Option<SaveGame>, String> {
+        match self.slots.get(slot).and_then(Option::as_ref) {
+            Some(saved) => SaveGame::from_json(&saved.json).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// 把存档写进槽位（覆盖原有的）
+    pub fn store(&mut self, slot: usize, save: &SaveGame) -> Result<(), String> {
+        let json = save.to_json()?;
+        self.slots[slot] = Some(Slot { json, info: Ok(SlotInfo::of(save)) });
+        self.dirty[slot] = true;
+        Ok(())
+    }
+
+    pub fn delete(&mut self, slot: usize) {
+        if self.slots[slot].take().is_some() {
+            self.dirty[slot] = true;
+        }
+    }
+
+    /// 把一个槽位复制到另一个（覆盖目标槽位）
+    pub fn copy(&mut self, from: usize, to: usize) {
+        if from == to {
+            return;
+        }
+        self.slots[to] = self.slots[from].clone();
+        self.dirty[to] = true;
+    }
+}
+
+/// 网页端某个槽位的存储键
+#[cfg(target_arch = "wasm32")]
+fn slot_key(profile: &str, slot: usize) -> String {
+    Profiles::key_for(profile, &format!("{}#{}", save::SAVE_KEY, slot + 1))
+}
@@ -0,0 +1,104 @@
+//! 办公室背景：对局中在中央面板后面画一张简单的场景——窗外的天色随一天的进度和当天天气变化，
+//! 窗下是工位的剪影。颜色都往底色里压得很淡，不和前面的文字抢眼。
+
+use eframe::egui::{self, Color32, Pos2, Rect, Stroke, Vec2};
+
+use xiuxian_core::mood::Weather;
+
+/// 窗外天色的关键帧：(一天的进度, 颜色)，进度 0 为清晨、1 为深夜
+const SKY: [(f32, Color32); 4] = [
+    (0.0, Color32::from_rgb(250, 196, 150)),
+    (0.45, Color32::from_rgb(128, 186, 236)),
+    (0.75, Color32::from_rgb(240, 128, 84)),
+    (1.0, Color32::from_rgb(22, 26, 64)),
+];
+
+/// 两种颜色按比例混合
+fn mix(a: Color32, b: Color32, t: f32) -> Color32 {
+    let t = t.clamp(0.0, 1.0);
+    let channel = |x: u8, y: u8| (x as f32 + (y as f32 - x as f32) * t).round() as u8;
+    Color32::from_rgb(channel(a.r(), b.r()), channel(a.g(), b.g()), channel(a.b(), b.b()))
+}
+
+/// 某个时刻窗外的颜色（阴雨天发灰，雾霾天发黄）
+fn sky(progress: f32, weather: Weather) -> Color32 {
+    let progress = progress.clamp(0.0, 1.0);
+    let color = SKY
+        .windows(2)
+        .find(|pair| progress <= pair[1].0)
+        .map(|pair| mix(pair[0].1, pair[1].1, (progress - pair[0].0) / (pair[1].0 - pair[0].0)))
+        .unwrap_or(SKY[SKY.len() - 1].1);
+    match weather {
+        Weather::晴 => color,
+        Weather::雨 => mix(color, Color32::from_rgb(96, 104, 116), 0.55),
+        Weather::雾霾 => mix(color, Color32::from_rgb(150, 136, 104), 0.6),
+    }
+}
+
+/// 在 `rect` 里画背景；`base` 是面板底色，`ink` 是文字色（剪影往它靠一点）
+pub fn paint(painter: &egui::Painter, rect: Rect, progress: f32, weather: Weather, base: Color32, ink: Color32) {
+    let outside = sky(progress, weather);
+    let night = progress > 0.85;
+    // 整个房间染一点天色，再把窗户开在右上角
+    painter.rect_filled(rect, 0.0, mix(base, outside, 0.06));
+    let window = Rect::from_min_size(
+        rect.min + Vec2::new(rect.width() * 0.58, rect.height() * 0.08),
+        Vec2::new(rect.width() * 0.34, rect.height() * 0.38),
+    );
+    painter.rect_filled(window, 4.0, mix(base, outside, 0.3));
+
+    // 太阳或月亮：从左往右划过窗口
+    let orb = Pos2::new(
+        window.left() + window.width() * (0.15 + 0.7 * progress.clamp(0.0, 1.0)),
+        window.top() + window.height() * if night { 0.3 } else { 0.25 + 0.3 * (progress - 0.45).abs() },
+    );
+    let orb_color = if night { Color32::from_rgb(230, 230, 210) } else { Color32::from_rgb(255, 236, 170) };
+    if weather == Weather::晴 {
+        painter.circle_filled(orb, window.height() * 0.08, mix(base, orb_color, 0.35));
+    }
+    if night && weather == Weather::晴 {
+        for i in 0..7 {
+            let x = window.left() + window.width() * ((i * 37 % 100) as f32 / 100.0);
+            let y = window.top() + window.height() * ((i * 53 % 60) as f32 / 100.0);
+            painter.circle_filled(Pos2::new(x, y), 1.0, mix(base, Color32::WHITE, 0.3));
+        }
+    }
+    if weather == Weather::雨 {
+        let stroke = Stroke::new(1.0, mix(base, Color32::from_rgb(170, 190, 210), 0.25));
+        for i in 0..12 {
+            let x = window.left() + window.width() * (i as f32 + 0.5) / 12.0;
+            let y = window.top() + window.height() * ((i * 29 % 70) as f32 / 100.0);
+            painter.line_segment([Pos2::new(x, y), Pos2::new(x - 4.0, y + 12.0)], stroke);
+        }
+    }
+
+    // 窗框
+    let frame = Stroke::new(3.0, mix(base, ink, 0.12));
+    painter.rect_stroke(window, 4.0, frame);
+    painter.line_segment([Pos2::new(window.center().x, window.top()), Pos2::new(window.center().x, window.bottom())], frame);
+    painter.line_segment([Pos2::new(window.left(), window.center().y), Pos2::new(window.right(), window.center().y)], frame);
+
+    // 工位剪影：桌面、显示器、椅背
+    let silhouette = mix(base, ink, 0.07);
+    let desk_top = rect.top() + rect.height() * 0.82;
+    painter.rect_filled(
+        Rect::from_min_max(Pos2::new(rect.left(), desk_top), Pos2::new(rect.right(), desk_top + 6.0)),
+        0.0,
+        silhouette,
+    );
+    for (x, width) in [(0.12, 0.16), (0.62, 0.2)] {
+        let left = rect.left() + rect.width() * x;
+        let monitor = Rect::from_min_size(
+            Pos2::new(left, desk_top - rect.height() * 0.13),
+            Vec2::new(rect.width() * width, rect.height() * 0.11),
+        );
+        painter.rect_filled(monitor, 3.0, silhouette);
+        let stand = Rect::from_center_size(Pos2::new(monitor.center().x, desk_top - rect.height() * 0.01), Vec2::new(8.0, rect.height() * 0.02));
+        painter.rect_filled(stand, 0.0, silhouette);
+    }
+    let chair = Rect::from_min_size(
+        Pos2::new(rect.left() + rect.width() * 0.36, desk_top - rect.height() * 0.08),
+        Vec2::new(rect.width() * 0.1, rect.height() * 0.18),
+    );
+    painter.rect_filled(chair, 8.0, silhouette);
+}
@@ -0,0 +1,424 @@
+//! 服务器模式（桌面端 `--serve`）：不开窗口，用一个小小的 JSON HTTP 接口对外提供引擎，
+//! 网页看板、聊天机器人、自动代打的程序都可以通过它开局、看状态、做选择。
+//! 规则全部走 GameEngine，这里只负责路由、把请求翻译成命令、把对局渲染成 JSON。
+//!
+//! 接口：
+//!   POST   /games                 开局，请求体可选 `{"name": "道号", "seed": 42}`
+//!   GET    /games/{id}            查看对局
+//!   POST   /games/{id}/commands   执行命令，如 `{"command": "choose", "option": 1}`
+//!   DELETE /games/{id}            结束对局
+//!
+//! 命令先按当前阶段检查，做不了的返回 409；闲置太久的对局会被清掉，同时进行的对局也有上限。
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::balance::BalanceConfig;
+use crate::cli::LaunchArgs;
+use crate::director::Director;
+use crate::ending;
+use crate::engine::{GameCommand, GameEngine, GamePhase};
+use crate::game::{NpcDecision, PlayerState};
+use xiuxian_core::arena::{Action, StateView};
+use crate::specialty::CareerPath;
+use crate::tone::NarrativeTone;
+
+/// `--serve` 不带地址时监听的地址
+pub const DEFAULT_ADDR: &str = "127.0.0.1:7878";
+
+/// 没给道号时用的名字
+const DEFAULT_NAME: &str = "无名道友";
+
+/// 请求体最多读这么多字节
+const MAX_BODY: u64 = 64 * 1024;
+
+/// 最多同时保留这么多局
+const MAX_GAMES: usize = 256;
+
+/// 对局闲置超过这么久就清掉
+const IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// 开局请求
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct NewGame {
+    name: Option<String>,
+    seed: Option<u64>,
+}
+
+/// 对局命令（对应 GameEngine 的命令，只开放推进对局需要的那些）
+#[derive(Debug, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+enum ApiCommand {
+    /// 选当前事件的第几个选项，从 1 开始
+    Choose { option: u8 },
+    OpenWeeklyEvent,
+    Hint,
+    NextDay,
+    Promote,
+    DeclinePromotion,
+    /// 深夜来电：true 接，false 不接
+    AnswerNightCall { accept: bool },
+    CloseRecap,
+    Rematch,
+}
+
+impl ApiCommand {
+    /// 当前阶段能不能执行这个命令（和代打一样按 `StateView::allows` 判断），不能时给出原因
+    fn check(&self, engine: &GameEngine) -> Result<(), &'static str> {
+        if let ApiCommand::Rematch = self {
+            return if engine.phase == GamePhase::GameOver { Ok(()) } else { Err("对局还没结束，不能重开") };
+        }
+        let Some(view) = StateView::of(engine) else {
+            return Err("这局已经结束了");
+        };
+        let allowed = match *self {
+            ApiCommand::Choose { option } => option >= 1 && view.allows(Action::Choose(option as usize - 1)),
+            ApiCommand::Hint => !view.options.is_empty(),
+            ApiCommand::OpenWeeklyEvent => view.allows(Action::OpenWeeklyEvent),
+            ApiCommand::NextDay => view.allows(Action::NextDay),
+            ApiCommand::Promote => view.allows(Action::Promote),
+            ApiCommand::DeclinePromotion => view.allows(Action::DeclinePromotion),
+            ApiCommand::AnswerNightCall { accept } => view.allows(Action::AnswerNightCall(accept)),
+            ApiCommand::CloseRecap => view.allows(Action::CloseRecap),
+            ApiCommand::Rematch => false,
+        };
+        if allowed { Ok(()) } else { Err("现在不能这么做") }
+    }
+
+    fn to_command(&self) -> GameCommand {
+        match *self {
+            ApiCommand::Choose { option } => GameCommand::Choose(option),
+            ApiCommand::OpenWeeklyEvent => GameCommand::OpenWeeklyEvent,
+            ApiCommand::Hint => GameCommand::Hint,
+            ApiCommand::NextDay => GameCommand::NextDay,
+            ApiCommand::Promote => GameCommand::Promote,
+            ApiCommand::DeclinePromotion => GameCommand::DeclinePromotion,
+            ApiCommand::AnswerNightCall { accept } => GameCommand::AnswerNightCall(if accept {
+                NpcDecision::Accept
+            } else {
+                NpcDecision::Reject
+            }),
+            ApiCommand::CloseRecap => GameCommand::CloseRecap,
+            ApiCommand::Rematch => GameCommand::Rematch,
+        }
+    }
+}
+
+/// 对局的 JSON 视图
+#[derive(Serialize)]
+struct GameView {
+    id: u32,
+    phase: String,
+    day: u32,
+    message: String,
+    player: Option<PlayerView>,
+    event: Option<EventView>,
+    can_advance: bool,
+    weekly_event_pending: bool,
+    hinted_option: Option<usize>,  // 师傅提示的选项，从 1 开始
+    night_call: Option<NightCallView>,
+    recap: Option<RecapView>,
+    ending: Option<String>,
+}
+
+/// 玩家的公开属性（存档里的内部字段不往外给）
+#[derive(Serialize)]
+struct PlayerView {
+    name: String,
+    realm: String,
+    experience: u32,
+    skills: i32,
+    pressure: i32,
+    days_played: u32,
+    is_alive: bool,
+    savings: i32,
+    reputation: u32,
+    can_promote: bool,
+}
+
+impl From<&PlayerState> for PlayerView {
+    fn from(player: &PlayerState) -> Self {
+        PlayerView {
+            name: player.name.clone(),
+            realm: player.get_realm().to_string(),
+            experience: player.experience,
+            skills: player.skills,
+            pressure: player.pressure,
+            days_played: player.days_played,
+            is_alive: player.is_alive,
+            savings: player.savings,
+            reputation: player.reputation,
+            can_promote: player.can_promote(),
+        }
+    }
+}
+
+/// 当前可选的事件（不公开选项的具体数值，和界面上看到的一样）
+#[derive(Serialize)]
+struct EventView {
+    title: String,
+    description: String,
+    weekly: bool,
+    options: Vec<OptionView>,
+}
+
+#[derive(Serialize)]
+struct OptionView {
+    title: String,
+    summary: String,
+    gray: bool,  // 灰色操作
+}
+
+#[derive(Serialize)]
+struct NightCallView {
+    caller: String,
+    plea: String,
+    accept: String,
+    reject: String,
+}
+
+#[derive(Serialize)]
+struct RecapView {
+    week: u32,
+    grade: &'static str,
+    comment: &'static str,
+    skill_change: i32,
+    pressure_change: i32,
+}
+
+/// 启动服务器，返回进程退出码
+pub fn run(args: &LaunchArgs, addr: &str) -> i32 {
+    let server = match Server::http(addr) {
+        Ok(server) => server,
+        Err(err) => {
+            eprintln!("无法监听 {}：{}", addr, err);
+            return 1;
+        }
+    };
+    println!("🧘 修仙编程游戏 · 服务器模式，监听 http://{}", addr);
+    let mut games = Games { games: HashMap::new(), next_id: 1, launch_seed: args.seed() };
+    for mut request in server.incoming_requests() {
+        let (status, body) = games.handle(&mut request);
+        let header = Header::from_bytes("Content-Type", "application/json; charset=utf-8").expect("合法的响应头");
+        let response = Response::from_string(body.to_string()).with_status_code(status).with_header(header);
+        if let Err(err) = request.respond(response) {
+            eprintln!("回应请求失败：{}", err);
+        }
+    }
+    0
+}
+
+/// 一局对局和它最近一次被访问的时间
+struct Game {
+    engine: GameEngine,
+    last_seen: Instant,
+}
+
+/// 进行中的对局
+struct Games {
+    games: HashMap<u32, Game>,
+    next_id: u32,
+    launch_seed: Option<u64>,  // 命令行固定的种子
+}
+
+impl Games {
+    /// 处理一个请求，返回 (状态码, 响应体)
+    fn handle(&mut self, request: &mut Request) -> (u16, serde_json::Value) {
+        let method = request.method().clone();
+        let path = request.url().split('?').next().unwrap_or("").trim_end_matches('/').to_string();
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let body = match read_body(request) {
+            Ok(body) => body,
+            Err(err) => return error(400, &err),
+        };
+        self.expire();
+        match (&method, segments.as_slice()) {
+            (Method::Post, ["games"]) => {
+                let new_game: NewGame = if body.trim().is_empty() {
+                    NewGame::default()
+                } else {
+                    match serde_json::from_str(&body) {
+                        Ok(new_game) => new_game,
+                        Err(err) => return error(400, &format!("开局参数有误：{}", err)),
+                    }
+                };
+                if self.games.len() >= MAX_GAMES {
+                    return error(503, "同时进行的对局太多了，稍后再开");
+                }
+                let mut engine = GameEngine::new();
+                engine.launch_seed = self.launch_seed;
+                engine.player_name = new_game
+                    .name
+                    .filter(|name| !name.trim().is_empty())
+                    .unwrap_or_else(|| DEFAULT_NAME.to_string());
+                if let Err(err) = engine.start_game(
+                    Vec::new(),
+                    BalanceConfig::default(),
+                    NarrativeTone::沙雕,
+                    Director::default(),
+                    CareerPath::default(),
+                    Vec::new(),
+                    new_game.seed,
+                ) {
+                    return error(400, &err);
+                }
+                let id = self.next_id;
+                self.next_id += 1;
+                self.games.insert(id, Game { engine, last_seen: Instant::now() });
+                (201, self.view(id))
+            }
+            (Method::Get, ["games", id]) => match self.find(id) {
+                Some(id) => (200, self.view(id)),
+                None => error(404, "没有这局"),
+            },
+            (Method::Post, ["games", id, "commands"]) => {
+                let Some(id) = self.find(id) else {
+                    return error(404, "没有这局");
+                };
+                let command: ApiCommand = match serde_json::from_str(&body) {
+                    Ok(command) => command,
+                    Err(err) => return error(400, &format!("看不懂这个命令：{}", err)),
+                };
+                let engine = &mut self.games.get_mut(&id).expect("已确认存在").engine;
+                if let Err(reason) = command.check(engine) {
+                    return error(409, reason);
+                }
+                engine.execute(command.to_command());
+                // 服务器没有订阅引擎事件的子系统
+                engine.bus.drain();
+                (200, self.view(id))
+            }
+            (Method::Delete, ["games", id]) => match self.find(id) {
+                Some(id) => {
+                    self.games.remove(&id);
+                    (200, json!({ "id": id, "deleted": true }))
+                }
+                None => error(404, "没有这局"),
+            },
+            _ => error(404, "没有这个接口"),
+        }
+    }
+
+    /// 清掉闲置太久的对局
+    fn expire(&mut self) {
+        self.games.retain(|_, game| game.last_seen.elapsed() < IDLE_TIMEOUT);
+    }
+
+    /// 找到对局并记一次访问
+    fn find(&mut self, id: &str) -> Option<u32> {
+        let id = id.parse().ok()?;
+        let game = self.games.get_mut(&id)?;
+        game.last_seen = Instant::now();
+        Some(id)
+    }
+
+    fn view(&self, id: u32) -> serde_json::Value {
+        let engine = &self.games[&id].engine;
+        let state = engine.game_state.as_ref();
+        let choosing = match engine.phase {
+            GamePhase::EventDisplay => engine.can_make_daily_choice(),
+            GamePhase::WeeklyEventDisplay => engine.can_make_weekly_choice(),
+            _ => false,
+        };
+        let event = engine.current_event_metadata().filter(|_| choosing).map(|(title, description, options, weekly)| {
+            EventView {
+                title,
+                description,
+                weekly,
+                options: options
+                    .iter()
+                    .map(|option| OptionView {
+                        title: option.title().to_string(),
+                        summary: option.summary().to_string(),
+                        gray: option.gray_risk > 0,
+                    })
+                    .collect(),
+            }
+        });
+        let night_call = state
+            .and_then(|state| state.night_call.as_ref())
+            .filter(|_| engine.phase == GamePhase::NightCall)
+            .map(|call| NightCallView {
+                caller: call.caller.clone(),
+                plea: call.plea.clone(),
+                accept: call.accept.summary.clone(),
+                reject: call.reject.summary.clone(),
+            });
+        let recap = engine.weekly_recap.as_ref().filter(|_| engine.phase == GamePhase::WeeklyRecap).map(|recap| {
+            let (grade, comment) = recap.grade();
+            RecapView {
+                week: recap.week,
+                grade,
+                comment,
+                skill_change: recap.skill_change,
+                pressure_change: recap.pressure_change,
+            }
+        });
+        let view = GameView {
+            id,
+            phase: format!("{:?}", engine.phase),
+            day: state.map_or(0, |state| state.current_day),
+            message: engine.result_message.to_string(),
+            player: state.map(|state| PlayerView::from(&state.player)),
+            event,
+            can_advance: engine.can_advance(),
+            weekly_event_pending: engine.weekly_event_pending(),
+            hinted_option: engine.hinted_option.map(|idx| idx + 1),
+            night_call,
+            recap,
+            ending: state
+                .filter(|_| engine.phase == GamePhase::GameOver)
+                .map(|state| ending::card(state).to_string()),
+        };
+        serde_json::to_value(view).unwrap_or_else(|err| json!({ "error": err.to_string() }))
+    }
+}
+
+fn read_body(request: &mut Request) -> Result<String, String> {
+    let mut body = String::new();
+    request
+        .as_reader()
+        .take(MAX_BODY)
+        .read_to_string(&mut body)
+        .map_err(|err| format!("读取请求体失败：{}", err))?;
+    Ok(body)
+}
+
+fn error(status: u16, message: &str) -> (u16, serde_json::Value) {
+    (status, json!({ "error": message }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commands_are_checked_against_the_phase() {
+        let mut engine = GameEngine::new();
+        engine.player_name = DEFAULT_NAME.to_string();
+        engine
+            .start_game(
+                Vec::new(),
+                BalanceConfig::default(),
+                NarrativeTone::沙雕,
+                Director::default(),
+                CareerPath::default(),
+                Vec::new(),
+                Some(42),
+            )
+            .expect("名号合规");
+        assert!(ApiCommand::NextDay.check(&engine).is_err(), "日常没做不能过夜");
+        assert!(ApiCommand::Rematch.check(&engine).is_err(), "对局没结束不能重开");
+        assert!(ApiCommand::CloseRecap.check(&engine).is_err());
+        assert!(ApiCommand::Choose { option: 0 }.check(&engine).is_err(), "选项从 1 开始");
+        assert!(ApiCommand::Choose { option: 1 }.check(&engine).is_ok());
+        engine.execute(ApiCommand::Choose { option: 1 }.to_command());
+        assert!(ApiCommand::Choose { option: 1 }.check(&engine).is_err(), "今天已经选过了");
+    }
+}
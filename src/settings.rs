@@ -0,0 +1,209 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::audio::AudioSettings;
+use crate::balance::{BalancePreset, Difficulty, PressureModel};
+use crate::director::Director;
+use crate::idle::IdleClock;
+use crate::keybindings::KeyBindings;
+use crate::llm::LlmSettings;
+use crate::season::SeasonSettings;
+use crate::shuffle::ShuffleMode;
+use crate::specialty::CareerPath;
+use crate::tag::EventTag;
+use crate::theme::PalettePreset;
+use crate::tone::NarrativeTone;
+use crate::warning::DarkContent;
+
+/// eframe 存储中保存设置的键
+pub const STORAGE_KEY: &str = "xiuxian_settings";
+/// eframe 存储中保存跨局统计的键
+pub const LIFETIME_STATS_KEY: &str = "xiuxian_lifetime_stats";
+
+/// 界面布局模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LayoutMode {
+    /// 单列紧凑布局：所有内容纵向排列
+    Compact,
+    /// 三栏详细布局：状态 | 事件 | 历史+NPC
+    Detailed,
+}
+
+impl LayoutMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LayoutMode::Compact => "紧凑单列",
+            LayoutMode::Detailed => "三栏详细",
+        }
+    }
+}
+
+/// 各面板的显示/折叠状态（面板宽高由 egui 内存自动持久化）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PanelPrefs {
+    pub stats_visible: bool,
+    pub side_visible: bool,
+    pub history_collapsed: bool,
+    pub npc_collapsed: bool,
+}
+
+impl Default for PanelPrefs {
+    fn default() -> Self {
+        PanelPrefs {
+            stats_visible: true,
+            side_visible: true,
+            history_collapsed: false,
+            npc_collapsed: false,
+        }
+    }
+}
+
+/// 无障碍偏好：配色、高对比度、减少动效，由所有绘制/动画相关代码共同读取
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccessibilityPrefs {
+    pub palette: PalettePreset,
+    pub high_contrast: bool,
+    pub reduced_motion: bool,
+}
+
+impl Default for AccessibilityPrefs {
+    fn default() -> Self {
+        AccessibilityPrefs {
+            palette: PalettePreset::Standard,
+            high_contrast: false,
+            reduced_motion: false,
+        }
+    }
+}
+
+impl AccessibilityPrefs {
+    /// egui 动画时长（减少动效时关闭折叠/面板滑入等过渡动画）
+    pub fn animation_time(&self) -> f32 {
+        if self.reduced_motion {
+            0.0
+        } else {
+            1.0 / 12.0
+        }
+    }
+}
+
+/// 外观偏好：界面缩放、深浅色、强调色等，设置窗口里随时可调
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppearancePrefs {
+    pub ui_scale: f32,              // 界面缩放（1.0 为 egui 原始大小）
+    pub light: bool,                // 浅色主题
+    pub accent: Option<[u8; 3]>,    // 自定义强调色（None 用配色方案自带的）
+    pub show_emoji: bool,           // 剧情与历史文字里显示表情符号
+    pub history_limit: usize,       // 历史面板最多列出最近多少条（0 为不限）
+    pub font_path: Option<PathBuf>, // 手动指定的中文字体，优先于 config.toml（仅桌面端）
+    pub scenery: bool,              // 对局中在中央面板后面画办公室背景
+}
+
+impl Default for AppearancePrefs {
+    fn default() -> Self {
+        AppearancePrefs {
+            ui_scale: 1.2,
+            light: false,
+            accent: None,
+            show_emoji: true,
+            history_limit: 0,
+            font_path: None,
+            scenery: true,
+        }
+    }
+}
+
+impl AppearancePrefs {
+    /// 界面缩放的可调范围
+    pub const SCALE_RANGE: std::ops::RangeInclusive<f32> = 0.8..=2.0;
+}
+
+/// 玩家可调整的界面设置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub layout: LayoutMode,
+    pub panels: PanelPrefs,
+    pub accessibility: AccessibilityPrefs,
+    pub appearance: AppearancePrefs,
+    pub keys: KeyBindings,
+    pub big_screen: bool,        // 手动开启大屏模式
+    pub big_screen_auto: bool,   // 检测到手柄时自动开启
+    pub touch_confirm: bool,     // 选项两段确认（防止误触）
+    pub touch_confirm_auto: bool, // 检测到触屏时自动开启两段确认
+    pub record_highlights: bool, // 录制高光时刻（需 highlight-capture 特性）
+    pub seasons: SeasonSettings,
+    pub check_updates: bool,     // 启动时检查新版本（仅桌面端）
+    pub data_dir: Option<PathBuf>, // 自定义数据目录（None 为系统默认位置）
+    pub stream_overlay: bool,    // 打开置顶的直播小窗（仅桌面端）
+    pub legacy_migrated: bool,   // 是否已迁移旧版本散落在工作目录的文件
+    pub intro_seen: bool,        // 序章是否已经播放过（首次开局前自动播放一次）
+    pub onboarded: bool,         // 是否已经走过新手引导周（档案的第一局自动走）
+    pub balance: BalancePreset,  // 新开局使用的数值预设
+    pub difficulty: Difficulty,  // 新开局使用的难度
+    pub pressure_model: PressureModel, // 新开局的压力结算方式
+    pub speedrun: bool,          // 速通模式：显示计时与分段
+    pub show_ghost: bool,        // 叠加显示最佳一局同期的成绩
+    pub tournament_key: String,  // 赛事密钥（由主办方提供，用于签名成绩码）
+    pub tone: NarrativeTone,     // 新开局使用的叙事风格
+    pub shuffle: ShuffleMode,    // 新开局的选项排序方式
+    pub dark_content: DarkContent, // 黑色段子与死亡描写的处理方式（独立于叙事风格）
+    pub director: Director,      // 事件导演的干预力度
+    pub career: CareerPath,      // 新开局选择的职业路线
+    pub muted_tags: Vec<EventTag>, // 新开局屏蔽的事件题材
+    pub banned_events: Vec<usize>, // 新开局禁用的日常事件 id
+    pub idle: IdleClock,         // 挂机修炼的开关与时间账本
+    pub daily_played: Option<String>, // 最近一次打每日挑战的日期（UTC）
+    pub daily_reminder: bool,    // 最小化时用桌面通知提醒每日挑战（仅桌面端）
+    pub ascension: u32,          // 新开局使用的飞升等级
+    pub ascension_unlocked: u32, // 已解锁的最高飞升等级
+    pub llm: LlmSettings,        // AI 生成 NPC 台词
+    pub audio: AudioSettings,    // 音效与背景音乐
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            layout: LayoutMode::Detailed,
+            panels: PanelPrefs::default(),
+            accessibility: AccessibilityPrefs::default(),
+            appearance: AppearancePrefs::default(),
+            keys: KeyBindings::default(),
+            big_screen: false,
+            big_screen_auto: true,
+            touch_confirm: false,
+            touch_confirm_auto: true,
+            record_highlights: false,
+            seasons: SeasonSettings::default(),
+            check_updates: true,
+            data_dir: None,
+            stream_overlay: false,
+            legacy_migrated: false,
+            intro_seen: false,
+            onboarded: false,
+            balance: BalancePreset::标准,
+            difficulty: Difficulty::正常,
+            pressure_model: PressureModel::default(),
+            speedrun: false,
+            show_ghost: true,
+            tournament_key: String::new(),
+            tone: NarrativeTone::沙雕,
+            shuffle: ShuffleMode::default(),
+            dark_content: DarkContent::default(),
+            director: Director::default(),
+            career: CareerPath::default(),
+            muted_tags: Vec::new(),
+            banned_events: Vec::new(),
+            idle: IdleClock::default(),
+            daily_played: None,
+            daily_reminder: false,
+            ascension: 0,
+            ascension_unlocked: 0,
+            llm: LlmSettings::default(),
+            audio: AudioSettings::default(),
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! 带签名的分享码：`前缀.十六进制内容.十六进制签名`（目前用于赛事成绩码）。
+//! 分享码来自玩家粘贴或文件，可能是任意字节：解析时只返回给人看的错误说明，绝不 panic。
+//! 不依赖游戏逻辑，fuzz/ 下的模糊测试直接引用本文件。
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 分享码最大长度（字节），超过的直接拒绝，免得超大输入拖慢解析
+pub const MAX_CODE_LEN: usize = 256 * 1024;
+
+/// 用密钥签名内容，生成分享码
+pub fn encode(prefix: &str, payload: &[u8], key: &str) -> String {
+    format!("{}.{}.{}", prefix, to_hex(payload), to_hex(&mac_for(key, payload)))
+}
+
+/// 解析并核验分享码，返回签名覆盖的原始内容
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+pub fn decode(code: &str, prefix: &str, key: &str) -> Result<Vec<u8>, String> {
+    let code = code.trim();
+    if code.len() > MAX_CODE_LEN {
+        return Err(format!("内容过长（超过 {} KB），不像是有效的分享码", MAX_CODE_LEN / 1024));
+    }
+    let mut parts = code.split('.');
+    let (Some(head), Some(payload), Some(mac), None) = (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err("格式不对，应为 前缀.内容.签名".to_string());
+    };
+    if head != prefix {
+        return Err(format!("前缀“{}”不对（应为 {}），可能来自别的游戏版本", truncate(head), prefix));
+    }
+    let payload = from_hex(payload).ok_or("内容损坏（不是有效的十六进制）")?;
+    let mac = from_hex(mac).ok_or("签名损坏（不是有效的十六进制）")?;
+    let mut verifier = HmacSha256::new_from_slice(key.as_bytes()).expect("HMAC 接受任意长度密钥");
+    verifier.update(&payload);
+    verifier
+        .verify_slice(&mac)
+        .map_err(|_| "签名不符：内容被改动过，或密钥不对".to_string())?;
+    Ok(payload)
+}
+
+fn mac_for(key: &str, payload: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key.as_bytes()).expect("HMAC 接受任意长度密钥");
+    mac.update(payload);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+fn from_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// 错误信息里回显玩家输入时只取开头一小段
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+fn truncate(text: &str) -> String {
+    const SHOWN: usize = 16;
+    if text.chars().count() > SHOWN {
+        format!("{}…", text.chars().take(SHOWN).collect::<String>())
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "测试密钥";
+
+    #[test]
+    fn round_trip() {
+        let code = encode("demo1", "你好".as_bytes(), KEY);
+        assert_eq!(decode(&code, "demo1", KEY).unwrap(), "你好".as_bytes());
+        assert!(decode(&code, "demo1", "别的密钥").is_err());
+    }
+
+    #[test]
+    fn malformed_input_is_an_error_not_a_panic() {
+        let valid = encode("demo1", b"{\"a\":1}", KEY);
+        let cases = [
+            String::new(),
+            ".".repeat(5),
+            "demo1".to_string(),
+            "demo1.zz.zz".to_string(),
+            "demo1.abc.def".to_string(),
+            "demo1.修仙.修仙".to_string(),
+            "其他.00.00".repeat(3),
+            "x".repeat(MAX_CODE_LEN + 1),
+        ];
+        for case in cases {
+            assert!(decode(&case, "demo1", KEY).is_err(), "{:?}", case);
+        }
+        // 逐个字节截断、篡改合法分享码
+        for i in 0..valid.len() {
+            let _ = decode(&valid[..i], "demo1", KEY);
+            let mut bytes = valid.clone().into_bytes();
+            bytes[i] ^= 0x01;
+            let tampered = String::from_utf8_lossy(&bytes);
+            assert!(decode(&tampered, "demo1", KEY).is_err());
+        }
+    }
+}
@@ -0,0 +1,236 @@
+//! 平衡模拟：用代打策略按当前的数值预设、难度和事件导演连打很多局，统计存活率、天数和境界分布，
+//! 用来检查改了平衡参数或导演力度之后手感有没有跑偏。
+//! 桌面端在 rayon 线程池上并行跑，每打完一局通过通道把结果送回界面；
+//! 网页端没有线程，每帧只跑一小段时间（分片协作调度），界面不会卡住。
+
+use instant::Instant;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Arc;
+#[cfg(target_arch = "wasm32")]
+use std::time::Duration;
+
+use crate::balance::BalanceConfig;
+use crate::director::Director;
+use crate::engine::{GameEngine, GamePhase};
+use crate::policy::AutoPolicy;
+use crate::specialty::CareerPath;
+use crate::tone::NarrativeTone;
+
+/// 不限天数的预设也最多模拟这么多天，免得稳健策略一局打不完
+pub const MAX_SIMULATED_DAYS: u32 = 365;
+
+/// 单局最多推进多少步（防止策略卡住时死循环）
+const MAX_STEPS: u32 = 20_000;
+
+/// 网页端每帧最多占用的模拟时间
+#[cfg(target_arch = "wasm32")]
+const FRAME_SLICE: Duration = Duration::from_millis(8);
+
+/// 一次模拟的参数
+#[derive(Debug, Clone)]
+pub struct SimConfig {
+    pub balance: BalanceConfig,
+    pub director: Director,
+    pub policy: AutoPolicy,
+    pub runs: u32,
+    pub seed: u64,  // 第 i 局用 seed + i
+}
+
+/// 一局的结果
+#[derive(Debug, Clone, Copy)]
+pub struct RunOutcome {
+    pub days: u32,
+    pub survived: bool,
+    pub realm_level: u32,
+    pub skills: i32,
+}
+
+/// 已完成各局的汇总
+#[derive(Debug, Clone, Default)]
+pub struct SimReport {
+    pub runs: u32,
+    pub survived: u32,
+    pub total_days: u64,
+    pub total_skills: i64,
+    pub realm_counts: [u32; 7],  // 结束时的晋升等级：凡人境 … 飞升
+}
+
+impl SimReport {
+    pub fn add(&mut self, outcome: RunOutcome) {
+        self.runs += 1;
+        self.survived += u32::from(outcome.survived);
+        self.total_days += u64::from(outcome.days);
+        self.total_skills += i64::from(outcome.skills);
+        let realm = (outcome.realm_level.max(1) as usize - 1).min(self.realm_counts.len() - 1);
+        self.realm_counts[realm] += 1;
+    }
+
+    pub fn survival_rate(&self) -> f32 {
+        self.survived as f32 / self.runs.max(1) as f32
+    }
+
+    pub fn avg_days(&self) -> f32 {
+        self.total_days as f32 / self.runs.max(1) as f32
+    }
+
+    pub fn avg_skills(&self) -> f32 {
+        self.total_skills as f32 / self.runs.max(1) as f32
+    }
+
+    /// 结束时停在各境界的比例
+    pub fn realm_shares(&self) -> [f32; 7] {
+        self.realm_counts.map(|count| count as f32 / self.runs.max(1) as f32)
+    }
+}
+
+/// 用代打策略打完一局
+pub fn run_once(config: &SimConfig, index: u32) -> RunOutcome {
+    let mut balance = config.balance.clone();
+    balance.max_days = Some(balance.max_days.map_or(MAX_SIMULATED_DAYS, |days| days.min(MAX_SIMULATED_DAYS)));
+    let mut engine = GameEngine::new();
+    engine.player_name = "模拟道友".to_string();
+    engine.start_game(
+        Vec::new(),
+        balance,
+        NarrativeTone::沙雕,
+        config.director,
+        CareerPath::default(),
+        Vec::new(),
+        Some(config.seed.wrapping_add(u64::from(index))),
+    )
+    .expect("模拟用的名号合规");
+    let mut steps = 0;
+    while steps < MAX_STEPS && engine.auto_step(config.policy) {
+        // 模拟不需要订阅引擎事件，及时清掉免得越攒越多
+        engine.bus.drain();
+        steps += 1;
+    }
+    let state = engine.game_state.as_ref().expect("已开局");
+    RunOutcome {
+        days: state.player.days_played,
+        survived: state.player.is_alive && engine.phase == GamePhase::GameOver,
+        realm_level: state.player.realm_level,
+        skills: state.player.skills,
+    }
+}
+
+/// 一次进行中（或已结束）的模拟
+pub struct Simulation {
+    total: u32,
+    report: SimReport,
+    started: Instant,
+    elapsed_secs: Option<f32>,  // 结束时记下用时
+    #[cfg(not(target_arch = "wasm32"))]
+    receiver: Receiver<RunOutcome>,
+    #[cfg(not(target_arch = "wasm32"))]
+    cancel: Arc<AtomicBool>,
+    #[cfg(target_arch = "wasm32")]
+    config: SimConfig,
+    #[cfg(target_arch = "wasm32")]
+    cancelled: bool,
+}
+
+impl Simulation {
+    /// 在线程池上开始模拟，结果陆续从通道送回
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn start(config: SimConfig) -> Self {
+        use rayon::prelude::*;
+
+        let (sender, receiver) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let total = config.runs;
+        let stop = Arc::clone(&cancel);
+        std::thread::spawn(move || {
+            (0..config.runs).into_par_iter().for_each_with(sender, |sender, index| {
+                if !stop.load(Ordering::Relaxed) {
+                    let _ = sender.send(run_once(&config, index));
+                }
+            });
+        });
+        Self {
+            total,
+            report: SimReport::default(),
+            started: Instant::now(),
+            elapsed_secs: None,
+            receiver,
+            cancel,
+        }
+    }
+
+    /// 开始模拟；每帧调用 `poll` 时跑一小段
+    #[cfg(target_arch = "wasm32")]
+    pub fn start(config: SimConfig) -> Self {
+        Self {
+            total: config.runs,
+            report: SimReport::default(),
+            started: Instant::now(),
+            elapsed_secs: None,
+            config,
+            cancelled: false,
+        }
+    }
+
+    /// 收下已完成的局，返回是否还在跑（界面每帧调用）
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn poll(&mut self) -> bool {
+        if self.elapsed_secs.is_some() {
+            return false;
+        }
+        loop {
+            match self.receiver.try_recv() {
+                Ok(outcome) => self.report.add(outcome),
+                Err(TryRecvError::Empty) => return true,
+                Err(TryRecvError::Disconnected) => {
+                    self.elapsed_secs = Some(self.started.elapsed().as_secs_f32());
+                    return false;
+                }
+            }
+        }
+    }
+
+    /// 跑这一帧的一小段，返回是否还在跑（界面每帧调用）
+    #[cfg(target_arch = "wasm32")]
+    pub fn poll(&mut self) -> bool {
+        if self.elapsed_secs.is_some() {
+            return false;
+        }
+        let slice_start = Instant::now();
+        while !self.cancelled && self.report.runs < self.total && slice_start.elapsed() < FRAME_SLICE {
+            let outcome = run_once(&self.config, self.report.runs);
+            self.report.add(outcome);
+        }
+        if self.cancelled || self.report.runs >= self.total {
+            self.elapsed_secs = Some(self.started.elapsed().as_secs_f32());
+            return false;
+        }
+        true
+    }
+
+    /// 停止模拟（已完成的局保留在汇总里）
+    pub fn cancel(&mut self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        self.cancel.store(true, Ordering::Relaxed);
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.cancelled = true;
+        }
+    }
+
+    /// (已完成局数, 总局数)
+    pub fn progress(&self) -> (u32, u32) {
+        (self.report.runs, self.total)
+    }
+
+    pub fn report(&self) -> &SimReport {
+        &self.report
+    }
+
+    /// 模拟结束时的总用时（秒）；还在跑时为 None
+    pub fn elapsed_secs(&self) -> Option<f32> {
+        self.elapsed_secs
+    }
+}
@@ -0,0 +1,236 @@
+//! 终端版（桌面端 `--cli`）：不开窗口，在终端里用纯文本游玩——数字选选项，回车进入下一天。
+//! 规则全部走 GameEngine，和图形界面是同一套引擎，这里只负责打印和把输入翻译成命令。
+
+use std::io::{self, BufRead, Write};
+
+use crate::balance::BalanceConfig;
+use crate::cli::LaunchArgs;
+use crate::director::Director;
+use crate::ending;
+use crate::engine::{GameCommand, GameEngine, GamePhase};
+use crate::game::NpcDecision;
+use crate::name;
+use crate::specialty::CareerPath;
+use crate::tone::NarrativeTone;
+
+/// 没输入道号时用的名字
+const DEFAULT_NAME: &str = "无名道友";
+
+const HELP: &str = "\
+操作说明：
+  1/2/3  选择对应选项（深夜来电：1 接，2 不接）
+  回车    进入下一天 / 看完周报
+  y/n    晋升 / 暂缓晋升
+  w      打开今天的周事件
+  h      师傅提示（消耗一次次数）
+  s      查看属性
+  ?      显示本说明
+  q      退出";
+
+/// 运行终端版，返回进程退出码
+pub fn run(args: &LaunchArgs) -> i32 {
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+
+    println!("🧘 修仙编程游戏 · 终端版");
+    println!("{}\n", HELP);
+    let name = loop {
+        let Some(name) = prompt(&mut input, &format!("请输入你的道号（直接回车用“{}”）：", DEFAULT_NAME)) else {
+            return 0;
+        };
+        if name.trim().is_empty() {
+            break DEFAULT_NAME.to_string();
+        }
+        match name::validate(&name) {
+            Ok(name) => break name,
+            Err(err) => println!("{}", err),
+        }
+    };
+
+    let mut engine = GameEngine::new();
+    engine.launch_seed = args.seed();
+    new_run(&mut engine, &name);
+
+    // 只有执行了命令才重新打印整屏，查看说明、输错时只重复输入提示
+    let mut redraw = true;
+    loop {
+        // 终端版没有订阅引擎事件的子系统
+        engine.bus.drain();
+        let (body, hint) = screen(&engine);
+        if redraw {
+            println!("{}", body);
+        }
+        let asked = std::time::Instant::now();
+        let Some(line) = prompt(&mut input, hint) else {
+            return 0;
+        };
+        // 终端版没有窗口焦点可言，等玩家输入的时间都算游玩时间
+        let in_run = engine.in_run();
+        if let Some(state) = engine.game_state.as_mut().filter(|_| in_run) {
+            state.tick(asked.elapsed());
+        }
+        redraw = true;
+        match (engine.phase, line.trim()) {
+            (_, "q") => return 0,
+            (_, "?") => {
+                println!("{}", HELP);
+                redraw = false;
+            }
+            (_, "s") => {
+                println!("{}", status(&engine));
+                redraw = false;
+            }
+            (GamePhase::GameOver, "r") => engine.execute(GameCommand::Rematch),
+            (GamePhase::GameOver, "n") => new_run(&mut engine, &name),
+            (GamePhase::PromotionConfirm, "y") => engine.execute(GameCommand::Promote),
+            (GamePhase::PromotionConfirm, "n") => engine.execute(GameCommand::DeclinePromotion),
+            (GamePhase::NightCall, "1") => engine.execute(GameCommand::AnswerNightCall(NpcDecision::Accept)),
+            (GamePhase::NightCall, "2") => engine.execute(GameCommand::AnswerNightCall(NpcDecision::Reject)),
+            (GamePhase::WeeklyRecap, "") => engine.execute(GameCommand::CloseRecap),
+            (GamePhase::EventDisplay, "") if engine.can_advance() => engine.execute(GameCommand::NextDay),
+            (GamePhase::EventDisplay, "w") if engine.weekly_event_pending() => {
+                engine.execute(GameCommand::OpenWeeklyEvent)
+            }
+            (GamePhase::EventDisplay | GamePhase::WeeklyEventDisplay, "h") => {
+                engine.execute(GameCommand::Hint);
+                match engine.hinted_option {
+                    Some(idx) => println!("🧙 师傅：选 {} 最稳", idx + 1),
+                    None => println!("🧙 师傅提示已经用完了"),
+                }
+                redraw = false;
+            }
+            (GamePhase::EventDisplay | GamePhase::WeeklyEventDisplay, choice) if can_choose(&engine) => {
+                match choice.parse::<u8>() {
+                    Ok(choice @ 1..=3) => engine.execute(GameCommand::Choose(choice)),
+                    _ => {
+                        println!("请输入 1-3 选择选项（? 查看操作说明）");
+                        redraw = false;
+                    }
+                }
+            }
+            _ => {
+                println!("看不懂这个操作（? 查看操作说明）");
+                redraw = false;
+            }
+        }
+    }
+}
+
+/// 用同一个道号开新的一局（命令行固定的种子仍然生效）
+fn new_run(engine: &mut GameEngine, name: &str) {
+    engine.execute(GameCommand::Restart);
+    engine.player_name = name.to_string();
+    engine.start_game(
+        Vec::new(),
+        BalanceConfig::default(),
+        NarrativeTone::沙雕,
+        Director::default(),
+        CareerPath::default(),
+        Vec::new(),
+        None,
+    )
+    .expect("道号开局前已经校验过");
+}
+
+/// 打印提示并读一行；输入结束（Ctrl-D）时返回 None
+fn prompt(input: &mut impl BufRead, text: &str) -> Option<String> {
+    print!("{}", text);
+    let _ = io::stdout().flush();
+    let mut line = String::new();
+    match input.read_line(&mut line) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(line),
+    }
+}
+
+/// 当前事件还能不能选
+fn can_choose(engine: &GameEngine) -> bool {
+    match engine.phase {
+        GamePhase::WeeklyEventDisplay => engine.can_make_weekly_choice(),
+        _ => engine.can_make_daily_choice(),
+    }
+}
+
+fn status(engine: &GameEngine) -> String {
+    let Some(state) = engine.game_state.as_ref() else {
+        return String::new();
+    };
+    let player = &state.player;
+    format!(
+        "📊 第{}天 · {} · 技能点 {} · 压力 {} · 存款 {} 元 · 今晚出局风险 {:.0}%",
+        state.current_day,
+        player.get_realm(),
+        player.skills,
+        player.pressure,
+        player.savings,
+        player.tonight_risk() * 100.0
+    )
+}
+
+/// 当前阶段要显示的文字与输入提示
+fn screen(engine: &GameEngine) -> (String, &'static str) {
+    let mut lines = vec![String::new(), "─".repeat(40)];
+    if !engine.result_message.is_empty() {
+        lines.push(engine.result_message.to_string());
+        lines.push(String::new());
+    }
+    let hint = match engine.phase {
+        GamePhase::Start => "> ",
+        GamePhase::EventDisplay | GamePhase::WeeklyEventDisplay => {
+            lines.push(status(engine));
+            if can_choose(engine) {
+                if let Some((title, desc, options, _)) = engine.current_event_metadata() {
+                    lines.push(String::new());
+                    lines.push(title);
+                    lines.push(desc);
+                    for (idx, option) in options.iter().enumerate() {
+                        let gray = if option.gray_risk > 0 { " ⚠灰色操作" } else { "" };
+                        lines.push(format!("  [{}] {}{}", idx + 1, option.title(), gray));
+                        lines.extend(option.summary().lines().map(|line| format!("      {}", line)));
+                    }
+                }
+                "选择 1-3（h 提示）> "
+            } else if engine.weekly_event_pending() {
+                "w 打开今天的周事件 > "
+            } else {
+                "回车进入下一天 > "
+            }
+        }
+        GamePhase::PromotionConfirm => "y 晋升 / n 暂缓 > ",
+        GamePhase::NightCall => {
+            if let Some(call) = engine.game_state.as_ref().and_then(|g| g.night_call.as_ref()) {
+                lines.push(format!("🌙 夜深了……📞 {} 来电：{}", call.caller, call.plea));
+                lines.push(format!("  [1] {}", call.accept.summary));
+                lines.push(format!("  [2] {}", call.reject.summary));
+            }
+            "1 接 / 2 不接 > "
+        }
+        GamePhase::WeeklyRecap => {
+            if let Some(recap) = &engine.weekly_recap {
+                let (grade, comment) = recap.grade();
+                lines.push(format!(
+                    "📅 第{}周周报（第{}~{}天）评级 {}：{}",
+                    recap.week, recap.first_day, recap.last_day, grade, comment
+                ));
+                lines.push(format!(
+                    "技能点 {:+} · 压力值 {:+} · 同事请求同意 {} 次、拒绝 {} 次",
+                    recap.skill_change, recap.pressure_change, recap.npc_accepted, recap.npc_rejected
+                ));
+                if let Some(best) = &recap.best {
+                    lines.push(format!("👍 最佳选择：{}", best));
+                }
+                if let Some(worst) = &recap.worst {
+                    lines.push(format!("👎 最差选择：{}", worst));
+                }
+            }
+            "回车开始新的一周 > "
+        }
+        GamePhase::GameOver => {
+            if let Some(state) = &engine.game_state {
+                lines.push(ending::card(state).to_string());
+            }
+            "r 同种子重来 / n 新开一局 / q 退出 > "
+        }
+    };
+    (lines.join("\n"), hint)
+}
@@ -0,0 +1,218 @@
+use eframe::egui::{self, Color32, Stroke, Visuals};
+use serde::{Deserialize, Serialize};
+
+use std::borrow::Cow;
+
+use crate::settings::{AccessibilityPrefs, AppearancePrefs};
+
+/// 设置字号与控件尺寸：按玩家选的缩放（默认放大 20%），大屏模式再放大并加大点击区域
+pub fn apply_scale(ctx: &egui::Context, ui_scale: f32, big_screen: bool) {
+    let base = egui::Style::default();
+    let scale = if big_screen { ui_scale * 1.4 } else { ui_scale };
+    let spacing_scale = if big_screen { 1.6 } else { 1.0 };
+    ctx.style_mut(|style| {
+        for (text_style, font_id) in style.text_styles.iter_mut() {
+            if let Some(base_font) = base.text_styles.get(text_style) {
+                font_id.size = base_font.size * scale;
+            }
+        }
+        style.spacing.button_padding = base.spacing.button_padding * spacing_scale;
+        style.spacing.interact_size = base.spacing.interact_size * spacing_scale;
+        style.spacing.item_spacing = base.spacing.item_spacing * spacing_scale;
+    });
+}
+
+/// 根据无障碍与外观偏好取得当前配色；周末换成轻松的配色。
+/// 高对比度优先：不换周末配色，也不用浅色主题和自定义强调色
+pub fn palette(prefs: &AccessibilityPrefs, appearance: &AppearancePrefs, weekend: bool) -> Palette {
+    let mut palette = prefs.palette.palette();
+    if prefs.high_contrast {
+        return palette.high_contrast();
+    }
+    if weekend {
+        palette = palette.weekend();
+    }
+    if appearance.light {
+        palette = palette.light();
+    }
+    if let Some([r, g, b]) = appearance.accent {
+        palette.highlight = Color32::from_rgb(r, g, b);
+    }
+    palette
+}
+
+/// 把无障碍与外观偏好应用到界面（配色与动画时长）
+pub fn apply(ctx: &egui::Context, prefs: &AccessibilityPrefs, appearance: &AppearancePrefs, weekend: bool) {
+    let palette = palette(prefs, appearance, weekend);
+    let mut visuals = palette.visuals();
+    if prefs.high_contrast {
+        let strong = Stroke::new(2.0, palette.text);
+        visuals.widgets.noninteractive.bg_stroke = Stroke::new(1.5, palette.text);
+        visuals.widgets.inactive.bg_stroke = strong;
+        visuals.widgets.inactive.fg_stroke = strong;
+        visuals.widgets.hovered.bg_stroke = Stroke::new(3.0, palette.highlight);
+        visuals.widgets.active.bg_stroke = Stroke::new(3.0, palette.highlight);
+        visuals.selection.stroke = Stroke::new(2.0, palette.highlight);
+    }
+    ctx.set_visuals(visuals);
+    let animation_time = prefs.animation_time();
+    ctx.style_mut(|style| style.animation_time = animation_time);
+}
+
+/// 配色预设（色弱友好方案基于 Okabe-Ito 调色板）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PalettePreset {
+    Standard,
+    /// 绿色弱：避免红/绿对比，改用蓝/橙
+    Deuteranopia,
+    /// 红色弱：红色会显得很暗，改用蓝/黄
+    Protanopia,
+}
+
+impl PalettePreset {
+    pub const ALL: [PalettePreset; 3] = [
+        PalettePreset::Standard,
+        PalettePreset::Deuteranopia,
+        PalettePreset::Protanopia,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PalettePreset::Standard => "标准配色",
+            PalettePreset::Deuteranopia => "绿色弱友好",
+            PalettePreset::Protanopia => "红色弱友好",
+        }
+    }
+
+    pub fn palette(&self) -> Palette {
+        match self {
+            PalettePreset::Standard => Palette {
+                text: Color32::WHITE,
+                background: Color32::BLACK,
+                positive: Color32::LIGHT_GREEN,
+                negative: Color32::RED,
+                skill: Color32::LIGHT_GREEN,
+                pressure: Color32::from_rgb(255, 140, 0),
+                highlight: Color32::GOLD,
+                alert_fill: Color32::from_rgb(120, 20, 20),
+            },
+            PalettePreset::Deuteranopia => Palette {
+                text: Color32::WHITE,
+                background: Color32::BLACK,
+                positive: Color32::from_rgb(86, 180, 233),  // 天蓝
+                negative: Color32::from_rgb(213, 94, 0),    // 朱红
+                skill: Color32::from_rgb(86, 180, 233),
+                pressure: Color32::from_rgb(230, 159, 0),   // 橙
+                highlight: Color32::from_rgb(240, 228, 66), // 黄
+                alert_fill: Color32::from_rgb(110, 60, 0),
+            },
+            PalettePreset::Protanopia => Palette {
+                text: Color32::WHITE,
+                background: Color32::BLACK,
+                positive: Color32::from_rgb(0, 114, 178),   // 蓝
+                negative: Color32::from_rgb(230, 159, 0),   // 橙
+                skill: Color32::from_rgb(86, 180, 233),
+                pressure: Color32::from_rgb(240, 228, 66),
+                highlight: Color32::from_rgb(204, 121, 167), // 紫红
+                alert_fill: Color32::from_rgb(0, 60, 100),
+            },
+        }
+    }
+}
+
+/// 界面中所有语义颜色的集中定义，界面代码不直接写死 Color32
+#[derive(Debug, Clone, Copy)]
+pub struct Palette {
+    pub text: Color32,
+    pub background: Color32,
+    pub positive: Color32,
+    pub negative: Color32,
+    pub skill: Color32,
+    pub pressure: Color32,
+    pub highlight: Color32,
+    pub alert_fill: Color32,
+}
+
+impl Palette {
+    /// 高对比度：纯黑白底色，强调色统一为亮黄
+    pub fn high_contrast(self) -> Palette {
+        Palette {
+            text: Color32::WHITE,
+            background: Color32::BLACK,
+            highlight: Color32::YELLOW,
+            alert_fill: Color32::from_rgb(90, 0, 90),
+            ..self
+        }
+    }
+
+    /// 周末配色：底色换成暖一点的深青，强调色换成天蓝；表示好坏的颜色保持不变（照顾色弱配色）
+    pub fn weekend(self) -> Palette {
+        Palette {
+            background: Color32::from_rgb(18, 32, 36),
+            highlight: Color32::from_rgb(135, 206, 235),
+            alert_fill: Color32::from_rgb(40, 70, 80),
+            ..self
+        }
+    }
+
+    /// 浅色主题：米白底色深色字，各语义色压暗一些免得在浅底上看不清
+    pub fn light(self) -> Palette {
+        let darken = |color: Color32| {
+            let [r, g, b, _] = color.to_array();
+            let dim = |c: u8| (f32::from(c) * 0.6) as u8;
+            Color32::from_rgb(dim(r), dim(g), dim(b))
+        };
+        Palette {
+            text: Color32::from_rgb(30, 30, 30),
+            background: Color32::from_rgb(246, 242, 232),
+            positive: darken(self.positive),
+            negative: darken(self.negative),
+            skill: darken(self.skill),
+            pressure: darken(self.pressure),
+            highlight: darken(self.highlight),
+            alert_fill: Color32::from_rgb(250, 220, 205),
+        }
+    }
+
+    /// 底色是否为浅色（决定整体界面用深色还是浅色控件）
+    pub fn is_light(&self) -> bool {
+        let [r, g, b, _] = self.background.to_array();
+        u32::from(r) + u32::from(g) + u32::from(b) > 3 * 128
+    }
+
+    /// 生成整体界面配色
+    pub fn visuals(&self) -> Visuals {
+        let mut visuals = if self.is_light() { Visuals::light() } else { Visuals::dark() };
+        visuals.override_text_color = Some(self.text);
+        visuals.panel_fill = self.background;
+        visuals.window_fill = self.background;
+        visuals.extreme_bg_color = self.background;
+        visuals.hyperlink_color = self.text;
+        visuals
+    }
+}
+
+/// 关掉表情符号时去掉文字里的 emoji（连带后面的空格），其余文字原样保留
+pub fn strip_emoji(text: &str, show_emoji: bool) -> Cow<'_, str> {
+    fn is_emoji(c: char) -> bool {
+        matches!(
+            u32::from(c),
+            0x1F000..=0x1FAFF | 0x2300..=0x23FF | 0x2600..=0x27BF | 0x2B00..=0x2BFF | 0xFE0F | 0x200D
+        )
+    }
+    if show_emoji || !text.chars().any(is_emoji) {
+        return Cow::Borrowed(text);
+    }
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if is_emoji(c) {
+            if chars.peek() == Some(&' ') {
+                chars.next();
+            }
+        } else {
+            plain.push(c);
+        }
+    }
+    Cow::Owned(plain)
+}
@@ -0,0 +1,118 @@
+//! 赛事成绩码：把种子、全部选择与得分打包，用赛事密钥做 HMAC 签名，
+//! 主办方用桌面端的 `--verify` 命令核验，防止手改分数。
+
+use serde::{Deserialize, Serialize};
+
+use crate::balance::{BalancePreset, Difficulty};
+use crate::game::{GameState, HistoryEntry, HistoryKind};
+use crate::sharecode;
+
+/// 成绩码前缀（带格式版本号）
+const TOKEN_PREFIX: &str = "xiuxian1";
+
+/// 成绩码里的对局结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TournamentResult {
+    pub player: String,
+    pub seed: u64,
+    pub preset: BalancePreset,
+    #[serde(default)]
+    pub difficulty: Difficulty,
+    pub scenario: Option<String>,
+    pub score: u32,
+    pub days: u32,
+    /// 本局禁用的日常事件 id
+    #[serde(default)]
+    pub banned: Vec<usize>,
+    /// 每次选择的简码，如 "3D31B"（第3天日常事件31选B）、"5N张三+"（第5天同意张三）、"9Q智眼项目上线"（第9天项目结算）
+    pub choices: Vec<String>,
+}
+
+impl TournamentResult {
+    pub fn from_state(state: &GameState) -> Self {
+        TournamentResult {
+            player: state.player.name.clone(),
+            seed: state.seed,
+            preset: state.player.balance.preset,
+            difficulty: state.player.balance.difficulty,
+            scenario: state.scenario.as_ref().map(|s| s.id.clone()),
+            score: state.score(),
+            days: state.player.days_played,
+            banned: state.banned_events.clone(),
+            choices: state.player.history.iter().map(choice_code).collect(),
+        }
+    }
+
+    /// 生成签名后的成绩码
+    pub fn sign(&self, key: &str) -> Result<String, String> {
+        let payload = serde_json::to_string(self).map_err(|e| format!("序列化失败: {}", e))?;
+        Ok(sharecode::encode(TOKEN_PREFIX, payload.as_bytes(), key))
+    }
+
+    /// 核验成绩码：签名不符或格式错误时返回原因
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn verify(token: &str, key: &str) -> Result<Self, String> {
+        let payload = sharecode::decode(token, TOKEN_PREFIX, key).map_err(|e| format!("成绩码无效：{}", e))?;
+        serde_json::from_slice(&payload).map_err(|e| format!("成绩码内容无法解析: {}", e))
+    }
+}
+
+/// 一条历史记录的选择简码
+fn choice_code(entry: &HistoryEntry) -> String {
+    let option = |index: Option<u32>| {
+        index
+            .and_then(|i| char::from_u32('A' as u32 + i))
+            .unwrap_or('?')
+    };
+    match entry.kind {
+        HistoryKind::日常 | HistoryKind::周事件 => format!(
+            "{}{}{}{}",
+            entry.day,
+            if entry.kind == HistoryKind::日常 { 'D' } else { 'W' },
+            entry.event_id.unwrap_or_default(),
+            option(entry.option_index)
+        ),
+        HistoryKind::Npc => format!(
+            "{}N{}{}",
+            entry.day,
+            entry.event_name,
+            if entry.choice.ends_with("(同意)") { '+' } else { '-' }
+        ),
+        HistoryKind::项目 => format!("{}Q{}", entry.day, entry.event_name),
+        HistoryKind::晋升 => format!("{}P{}", entry.day, if entry.choice.starts_with("晋升成功") { '+' } else { '-' }),
+    }
+}
+
+/// 命令行核验：`xiuxian_game --verify <赛事密钥> <成绩码或成绩码文件>`，返回进程退出码
+#[cfg(not(target_arch = "wasm32"))]
+pub fn run_verify_cli(args: &[String]) -> i32 {
+    let [key, token] = args else {
+        eprintln!("用法: xiuxian_game --verify <赛事密钥> <成绩码或成绩码文件>");
+        return 2;
+    };
+    let token = std::fs::read_to_string(token).unwrap_or_else(|_| token.clone());
+    match TournamentResult::verify(&token, key) {
+        Ok(result) => {
+            println!("✅ 成绩码有效");
+            println!("玩家: {}", result.player);
+            println!("种子: {}", result.seed);
+            println!("数值预设: {}", result.preset.label());
+            println!("难度: {}", result.difficulty.label());
+            if let Some(scenario) = &result.scenario {
+                println!("挑战剧本: {}", scenario);
+            }
+            println!("天数: {}", result.days);
+            println!("得分: {}", result.score);
+            if !result.banned.is_empty() {
+                let banned: Vec<String> = result.banned.iter().map(|id| id.to_string()).collect();
+                println!("禁用事件: {}", banned.join(" "));
+            }
+            println!("选择: {}", result.choices.join(" "));
+            0
+        }
+        Err(err) => {
+            println!("❌ {}", err);
+            1
+        }
+    }
+}
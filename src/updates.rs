@@ -0,0 +1,83 @@
+//! 版本更新检查与内置更新日志
+
+/// 当前版本号
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// 随程序打包的更新日志（Markdown）
+pub const CHANGELOG: &str = include_str!("../CHANGELOG.md");
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use native::{Release, UpdateChecker};
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use std::sync::mpsc::{self, Receiver};
+
+    /// 发布信息接口（GitHub 最新 Release）
+    const RELEASE_ENDPOINT: &str = "https://api.github.com/repos/Runbinlin/jbn_daily/releases/latest";
+
+    /// 比当前版本新的发布
+    #[derive(Debug, Clone)]
+    pub struct Release {
+        pub version: String,
+        pub url: String,
+    }
+
+    /// 后台线程里检查一次更新，界面每帧轮询结果
+    pub struct UpdateChecker {
+        receiver: Receiver<Option<Release>>,
+    }
+
+    impl UpdateChecker {
+        /// 开始检查；`endpoint` 为空时使用默认的发布信息接口
+        pub fn spawn(endpoint: Option<String>) -> Self {
+            let (sender, receiver) = mpsc::channel();
+            let endpoint = endpoint.unwrap_or_else(|| RELEASE_ENDPOINT.to_string());
+            std::thread::spawn(move || {
+                // 网络错误、限流等情况一律静默忽略，不打扰玩家
+                let _ = sender.send(fetch_latest(&endpoint).ok().flatten());
+            });
+            UpdateChecker { receiver }
+        }
+
+        /// 检查结果到达时返回 `Some(结果)`，尚未完成返回 `None`
+        pub fn poll(&self) -> Option<Option<Release>> {
+            self.receiver.try_recv().ok()
+        }
+    }
+
+    /// 把 "v1.2.3" / "1.2" 之类的版本号解析为数字序列，无法解析的部分记为 0
+    fn parse_version(version: &str) -> Vec<u32> {
+        version
+            .trim()
+            .trim_start_matches(['v', 'V'])
+            .split(['.', '-'])
+            .take(3)
+            .map(|part| part.parse().unwrap_or(0))
+            .collect()
+    }
+
+    /// `latest` 是否比 `current` 新
+    fn is_newer(latest: &str, current: &str) -> bool {
+        parse_version(latest) > parse_version(current)
+    }
+
+    fn fetch_latest(endpoint: &str) -> Result<Option<Release>, String> {
+        let body = ureq::get(endpoint)
+            .set("User-Agent", "xiuxian_game")
+            .timeout(std::time::Duration::from_secs(10))
+            .call()
+            .map_err(|e| e.to_string())?
+            .into_string()
+            .map_err(|e| e.to_string())?;
+        let json: serde_json::Value = serde_json::from_str(&body).map_err(|e| e.to_string())?;
+        let version = json["tag_name"].as_str().ok_or("缺少 tag_name")?;
+        if !is_newer(version, super::CURRENT_VERSION) {
+            return Ok(None);
+        }
+        Ok(Some(Release {
+            version: version.to_string(),
+            url: json["html_url"].as_str().unwrap_or_default().to_string(),
+        }))
+    }
+}
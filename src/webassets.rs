@@ -0,0 +1,148 @@
+//! 网页端资源分包：中文字体和内容数据不再编进 wasm，启动后并行下载并显示进度，
+//! 全部到齐后再装上字体、装入内容。下载地址相对页面，build_web.sh 负责把文件放到 web/ 下。
+//! 进度界面出现时中文字体还没到，所以这里的提示文字（含错误信息）都只用 ASCII。
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use eframe::egui;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+use xiuxian_core::content::ContentFile;
+
+/// 中文字体的下载地址
+const FONT_URL: &str = "./fonts/NotoSansSC-Regular.ttf";
+
+/// 下载的是什么
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetKind {
+    Font,
+    Content(ContentFile),
+}
+
+/// 一个资源的下载进度
+#[derive(Debug)]
+pub struct Asset {
+    pub kind: AssetKind,
+    pub url: String,
+    pub loaded: u64,          // 已下载字节数
+    pub total: Option<u64>,   // 服务器给了 Content-Length 时的总字节数
+    pub bytes: Option<Vec<u8>>,
+    pub error: Option<String>,
+}
+
+/// 全部资源的下载器（wasm 单线程，用 Rc 在下载任务和界面之间共享）
+pub struct AssetLoader {
+    assets: Rc<RefCell<Vec<Asset>>>,
+}
+
+impl AssetLoader {
+    /// 开始下载字体和全部内容文件，每收到一块数据就请求重绘
+    pub fn start(ctx: &egui::Context) -> Self {
+        let kinds = std::iter::once(AssetKind::Font).chain(ContentFile::ALL.into_iter().map(AssetKind::Content));
+        let assets: Vec<Asset> = kinds
+            .map(|kind| Asset {
+                kind,
+                url: match kind {
+                    AssetKind::Font => FONT_URL.to_string(),
+                    AssetKind::Content(file) => format!("./content/{}", file.file_name()),
+                },
+                loaded: 0,
+                total: None,
+                bytes: None,
+                error: None,
+            })
+            .collect();
+        let loader = Self {
+            assets: Rc::new(RefCell::new(assets)),
+        };
+        for index in 0..loader.assets.borrow().len() {
+            let assets = Rc::clone(&loader.assets);
+            let ctx = ctx.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let url = assets.borrow()[index].url.clone();
+                let result = fetch(&url, |loaded, total| {
+                    let mut assets = assets.borrow_mut();
+                    assets[index].loaded = loaded;
+                    assets[index].total = total;
+                    ctx.request_repaint();
+                })
+                .await;
+                let mut assets = assets.borrow_mut();
+                match result {
+                    Ok(bytes) => assets[index].bytes = Some(bytes),
+                    Err(err) => assets[index].error = Some(format!("{}: {}", url, err)),
+                }
+                ctx.request_repaint();
+            });
+        }
+        loader
+    }
+
+    /// 各资源的下载进度：(地址, 已下载, 总大小)
+    pub fn progress(&self) -> Vec<(String, u64, Option<u64>)> {
+        self.assets
+            .borrow()
+            .iter()
+            .map(|asset| (asset.url.clone(), asset.loaded, asset.total))
+            .collect()
+    }
+
+    /// 第一个下载失败的资源
+    pub fn error(&self) -> Option<String> {
+        self.assets.borrow().iter().find_map(|asset| asset.error.clone())
+    }
+
+    pub fn finished(&self) -> bool {
+        self.assets.borrow().iter().all(|asset| asset.bytes.is_some())
+    }
+
+    /// 下载完成后取出全部资源
+    pub fn take(&self) -> Vec<(AssetKind, Vec<u8>)> {
+        self.assets
+            .borrow_mut()
+            .iter_mut()
+            .filter_map(|asset| Some((asset.kind, asset.bytes.take()?)))
+            .collect()
+    }
+}
+
+fn describe(err: JsValue) -> String {
+    err.as_string().unwrap_or_else(|| format!("{:?}", err))
+}
+
+/// 流式下载一个文件，边下边回报进度
+pub async fn fetch(url: &str, mut on_progress: impl FnMut(u64, Option<u64>)) -> Result<Vec<u8>, String> {
+    let window = web_sys::window().ok_or("no window")?;
+    let response: web_sys::Response = JsFuture::from(window.fetch_with_str(url))
+        .await
+        .map_err(describe)?
+        .dyn_into()
+        .map_err(describe)?;
+    if !response.ok() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    let total = response
+        .headers()
+        .get("Content-Length")
+        .ok()
+        .flatten()
+        .and_then(|length| length.parse().ok());
+    let Some(body) = response.body() else {
+        return Ok(Vec::new());
+    };
+    let reader: web_sys::ReadableStreamDefaultReader = body.get_reader().unchecked_into();
+    let mut bytes = Vec::with_capacity(total.unwrap_or(0) as usize);
+    loop {
+        let chunk = JsFuture::from(reader.read()).await.map_err(describe)?;
+        let done = js_sys::Reflect::get(&chunk, &"done".into()).map_err(describe)?;
+        if done.as_bool().unwrap_or(true) {
+            break;
+        }
+        let value = js_sys::Reflect::get(&chunk, &"value".into()).map_err(describe)?;
+        bytes.extend(js_sys::Uint8Array::new(&value).to_vec());
+        on_progress(bytes.len() as u64, total);
+    }
+    Ok(bytes)
+}
@@ -0,0 +1,408 @@
+use eframe::egui::text::{LayoutJob, TextFormat};
+use eframe::egui::{self, Color32, Pos2, RichText, Sense, Shape, Stroke, TextStyle, Vec2};
+
+use crate::ending::EndingCard;
+use crate::message::{ResultMessage, Style};
+use crate::theme::{self, Palette};
+
+/// 迷你趋势线（用于状态栏）；`ghost` 为上一局最佳成绩同期的数值，以淡色叠加
+pub fn sparkline(ui: &mut egui::Ui, values: &[i32], ghost: &[i32], color: Color32) -> egui::Response {
+    let size = Vec2::new(64.0, ui.spacing().interact_size.y * 0.7);
+    let (rect, response) = ui.allocate_exact_size(size, Sense::hover());
+
+    if values.len() < 2 || !ui.is_rect_visible(rect) {
+        return response;
+    }
+
+    let all = values.iter().chain(ghost);
+    let min = *all.clone().min().unwrap_or(&0);
+    let max = *all.max().unwrap_or(&0);
+    let span = (max - min).max(1) as f32;
+    let step = rect.width() / (values.len() - 1) as f32;
+
+    let to_points = |series: &[i32]| -> Vec<Pos2> {
+        series
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                let t = (*v - min) as f32 / span;
+                Pos2::new(rect.left() + step * i as f32, rect.bottom() - t * rect.height())
+            })
+            .collect()
+    };
+
+    let painter = ui.painter();
+    if ghost.len() >= 2 {
+        painter.add(Shape::line(
+            to_points(ghost),
+            Stroke::new(1.0, color.gamma_multiply(0.35)),
+        ));
+    }
+    let points = to_points(values);
+    let last = points[points.len() - 1];
+    painter.add(Shape::line(points, Stroke::new(1.5, color)));
+    painter.circle_filled(last, 2.0, color);
+
+    response
+}
+
+/// 按天的折线图：横轴为天数，可叠加多条同量纲的曲线；鼠标悬停时显示那一天的各项数值
+pub fn line_chart(ui: &mut egui::Ui, days: &[u32], series: &[(&str, Vec<i32>, Color32)], height: f32) -> egui::Response {
+    let size = Vec2::new(ui.available_width().min(420.0), height);
+    let (rect, response) = ui.allocate_exact_size(size, Sense::hover());
+    if days.len() < 2 || !ui.is_rect_visible(rect) {
+        return response;
+    }
+
+    let all = series.iter().flat_map(|(_, values, _)| values.iter().copied());
+    let min = all.clone().min().unwrap_or(0).min(0);
+    let max = all.max().unwrap_or(0).max(min + 1);
+    let span = (max - min) as f32;
+    let font = TextStyle::Small.resolve(ui.style());
+    let weak = ui.visuals().weak_text_color();
+    // 左边留出纵轴刻度，下面留出天数
+    let plot = egui::Rect::from_min_max(
+        rect.min + Vec2::new(36.0, 4.0),
+        rect.max - Vec2::new(4.0, font.size + 4.0),
+    );
+    let step = plot.width() / (days.len() - 1) as f32;
+    let x_of = |i: usize| plot.left() + step * i as f32;
+    let y_of = |v: i32| plot.bottom() - (v - min) as f32 / span * plot.height();
+
+    let painter = ui.painter();
+    painter.rect_stroke(plot, 0.0, ui.visuals().widgets.noninteractive.bg_stroke);
+    for (value, align) in [(max, egui::Align2::RIGHT_TOP), (min, egui::Align2::RIGHT_BOTTOM)] {
+        let pos = Pos2::new(plot.left() - 4.0, y_of(value));
+        painter.text(pos, align, value.to_string(), font.clone(), weak);
+    }
+    if min < 0 {
+        let zero = y_of(0);
+        painter.line_segment([Pos2::new(plot.left(), zero), Pos2::new(plot.right(), zero)], Stroke::new(0.5, weak));
+    }
+    for (i, align) in [(0, egui::Align2::LEFT_TOP), (days.len() - 1, egui::Align2::RIGHT_TOP)] {
+        let pos = Pos2::new(x_of(i), plot.bottom() + 2.0);
+        painter.text(pos, align, format!("第{}天", days[i]), font.clone(), weak);
+    }
+    for (_, values, color) in series {
+        let points: Vec<Pos2> = values.iter().enumerate().map(|(i, v)| Pos2::new(x_of(i), y_of(*v))).collect();
+        painter.add(Shape::line(points, Stroke::new(1.5, *color)));
+    }
+
+    let Some(pointer) = response.hover_pos().filter(|pos| plot.contains(*pos)) else {
+        return response;
+    };
+    let index = (((pointer.x - plot.left()) / step).round() as usize).min(days.len() - 1);
+    let x = x_of(index);
+    painter.line_segment([Pos2::new(x, plot.top()), Pos2::new(x, plot.bottom())], Stroke::new(0.5, weak));
+    let readout: Vec<String> = series
+        .iter()
+        .filter_map(|(name, values, _)| values.get(index).map(|v| format!("{} {}", name, v)))
+        .collect();
+    response.on_hover_text(format!("第{}天：{}", days[index], readout.join(" · ")))
+}
+
+/// 提示文字：普通模式悬停显示；大屏/手柄模式下改为长按显示（不依赖悬停）。
+/// 长按阈值与 egui 的最长点击时长一致，松开后不会被当成点击。
+pub fn tooltip(response: egui::Response, text: &str, hold_to_show: bool) -> egui::Response {
+    if !hold_to_show {
+        return response.on_hover_text(text);
+    }
+    let held_long_enough = response.is_pointer_button_down_on()
+        && response.ctx.input(|i| {
+            i.pointer
+                .press_start_time()
+                .is_some_and(|start| i.time - start > 0.8)
+        });
+    if held_long_enough {
+        egui::show_tooltip_at_pointer(&response.ctx, response.id.with("hold_tip"), |ui| {
+            ui.label(text);
+        });
+    }
+    response
+}
+
+/// 可折叠分区，折叠状态由调用方保存（便于持久化）
+pub fn collapsible_section(
+    ui: &mut egui::Ui,
+    title: &str,
+    collapsed: &mut bool,
+    add_contents: impl FnOnce(&mut egui::Ui),
+) {
+    let response = egui::CollapsingHeader::new(title)
+        .open(Some(!*collapsed))
+        .show(ui, add_contents);
+    if response.header_response.clicked() {
+        *collapsed = !*collapsed;
+    }
+}
+
+/// 事件选项卡片：图标 + 标题 + 一行摘要，整张卡片可点击
+pub fn option_card(
+    ui: &mut egui::Ui,
+    enabled: bool,
+    highlight: Option<Color32>,
+    icon: &str,
+    title: &str,
+    summary: &str,
+) -> egui::Response {
+    let frame = ui
+        .add_enabled_ui(enabled, |ui| {
+            egui::Frame::group(ui.style())
+                .inner_margin(8.0)
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width());
+                    ui.horizontal(|ui| {
+                        ui.label(RichText::new(icon).size(20.0));
+                        ui.vertical(|ui| {
+                            ui.strong(title);
+                            if !summary.is_empty() {
+                                ui.label(RichText::new(summary).small());
+                            }
+                        });
+                    });
+                })
+                .response
+        })
+        .inner;
+
+    let sense = if enabled { Sense::click() } else { Sense::hover() };
+    let response = ui.interact(frame.rect, frame.id.with("option_card"), sense);
+    if let Some(color) = highlight {
+        ui.painter()
+            .rect_stroke(frame.rect, 4.0, Stroke::new(2.0, color));
+    } else if enabled && response.hovered() {
+        ui.painter()
+            .rect_stroke(frame.rect, 4.0, ui.visuals().widgets.hovered.fg_stroke);
+    }
+    response
+}
+
+/// 新闻跑马灯：文字从右向左循环滚动；`animate` 为 false 时改为静态换行显示
+pub fn ticker(ui: &mut egui::Ui, headlines: &[String], color: Color32, animate: bool) {
+    let text = headlines.join("    ｜    ");
+    if !animate {
+        ui.add(egui::Label::new(RichText::new(text).color(color)).wrap(true));
+        return;
+    }
+
+    const SPEED: f32 = 60.0; // 每秒滚动的像素
+    const GAP: f32 = 80.0;
+    let font = egui::TextStyle::Body.resolve(ui.style());
+    let galley = ui.painter().layout_no_wrap(text, font, color);
+    let size = Vec2::new(ui.available_width(), galley.size().y);
+    let (rect, _) = ui.allocate_exact_size(size, Sense::hover());
+    if !ui.is_rect_visible(rect) {
+        return;
+    }
+
+    let cycle = galley.size().x + GAP;
+    let time = ui.input(|i| i.time) as f32;
+    let offset = (time * SPEED) % cycle;
+    let painter = ui.painter_at(rect);
+    let mut x = rect.left() - offset;
+    while x < rect.right() {
+        painter.galley(Pos2::new(x, rect.top()), galley.clone(), color);
+        x += cycle;
+    }
+    ui.ctx().request_repaint();
+}
+
+/// 占比条形图：每行一个标签、一根按比例填充的条和百分比
+pub fn share_bars(ui: &mut egui::Ui, rows: &[(String, f32, Color32)]) {
+    let bar_width = 120.0;
+    let bar_height = ui.spacing().interact_size.y * 0.6;
+    egui::Grid::new(ui.next_auto_id()).num_columns(3).show(ui, |ui| {
+        for (label, share, color) in rows {
+            ui.label(label);
+            let (rect, _) = ui.allocate_exact_size(Vec2::new(bar_width, bar_height), Sense::hover());
+            let painter = ui.painter();
+            painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+            let mut filled = rect;
+            filled.set_width(bar_width * share.clamp(0.0, 1.0));
+            painter.rect_filled(filled, 2.0, *color);
+            painter.rect_stroke(rect, 2.0, ui.visuals().widgets.noninteractive.bg_stroke);
+            ui.label(format!("{:.0}%", share * 100.0));
+            ui.end_row();
+        }
+    });
+}
+
+/// 按名字生成的像素头像（5×5 左右对称色块，同一个名字在各平台上总是同一张脸）
+pub fn identicon(ui: &mut egui::Ui, name: &str, size: f32) -> egui::Response {
+    let (rect, response) = ui.allocate_exact_size(Vec2::splat(size), Sense::hover());
+    if !ui.is_rect_visible(rect) {
+        return response;
+    }
+    // FNV-1a：不依赖标准库哈希的实现细节，保证跨平台稳定
+    let hash = name
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |h, b| (h ^ b as u64).wrapping_mul(0x0100_0000_01b3));
+    let hue = (hash >> 40) as f32 / (1u64 << 24) as f32;
+    let color: Color32 = egui::ecolor::Hsva::new(hue, 0.55, 0.8, 1.0).into();
+
+    let painter = ui.painter();
+    painter.rect_filled(rect, size * 0.15, ui.visuals().extreme_bg_color);
+    let cell = size / 6.0;
+    let origin = rect.min + Vec2::splat(cell / 2.0);
+    for row in 0..5 {
+        for col in 0..3 {
+            if (hash >> (row * 3 + col)) & 1 == 0 {
+                continue;
+            }
+            for x in [col, 4 - col] {
+                let min = origin + Vec2::new(x as f32 * cell, row as f32 * cell);
+                painter.rect_filled(egui::Rect::from_min_size(min, Vec2::splat(cell)), 0.0, color);
+            }
+        }
+    }
+    response
+}
+
+/// 平面图：`zones` 按两列排成可点击的房间（标签, 悬停说明），`selected` 的那间高亮；
+/// `enabled` 为 false 时只能看不能点。返回点了第几间
+pub fn zone_map(
+    ui: &mut egui::Ui,
+    zones: &[(String, &str)],
+    selected: Option<usize>,
+    enabled: bool,
+    palette: &Palette,
+) -> Option<usize> {
+    let columns = 2;
+    let rows = zones.len().div_ceil(columns);
+    let width = ui.available_width().min(320.0);
+    let cell = Vec2::new(width / columns as f32, ui.spacing().interact_size.y * 2.0);
+    let sense = if enabled { Sense::click() } else { Sense::hover() };
+    let (rect, _) = ui.allocate_exact_size(Vec2::new(width, cell.y * rows as f32), Sense::hover());
+    let mut clicked = None;
+    for (index, (label, hint)) in zones.iter().enumerate() {
+        let min = rect.min + Vec2::new((index % columns) as f32 * cell.x, (index / columns) as f32 * cell.y);
+        let room = egui::Rect::from_min_size(min, cell).shrink(2.0);
+        let response = ui.interact(room, ui.id().with(("zone", index)), sense).on_hover_text(*hint);
+        let fill = if selected == Some(index) {
+            palette.highlight.gamma_multiply(0.35)
+        } else if response.hovered() && enabled {
+            ui.visuals().widgets.hovered.bg_fill
+        } else {
+            ui.visuals().extreme_bg_color
+        };
+        let painter = ui.painter();
+        painter.rect_filled(room, 4.0, fill);
+        painter.rect_stroke(room, 4.0, ui.visuals().widgets.noninteractive.bg_stroke);
+        let color = if enabled || selected == Some(index) { palette.text } else { palette.text.gamma_multiply(0.5) };
+        painter.text(room.center(), egui::Align2::CENTER_CENTER, label, TextStyle::Body.resolve(ui.style()), color);
+        if response.clicked() {
+            clicked = Some(index);
+        }
+    }
+    clicked
+}
+
+/// 头像：有图片资源时显示图片，没有或还在加载时用像素头像占位
+pub fn portrait(ui: &mut egui::Ui, texture: Option<egui::TextureHandle>, name: &str, size: f32) -> egui::Response {
+    match texture {
+        Some(texture) => ui.add(
+            egui::Image::new((texture.id(), Vec2::splat(size)))
+                .rounding(size * 0.15)
+                .sense(Sense::hover()),
+        ),
+        None => identicon(ui, name, size),
+    }
+}
+
+/// 结果提示：各片段按样式拼成一段富文本（关掉表情符号时去掉文字里的 emoji）
+pub fn result_message(ui: &mut egui::Ui, message: &ResultMessage, palette: &Palette, show_emoji: bool) -> egui::Response {
+    let body = TextStyle::Body.resolve(ui.style());
+    let visuals = ui.visuals();
+    let mut job = LayoutJob::default();
+    for segment in message.segments() {
+        let (font_id, color) = match segment.style {
+            Style::正文 | Style::剧情 => (body.clone(), visuals.text_color()),
+            Style::标题 => (TextStyle::Heading.resolve(ui.style()), visuals.strong_text_color()),
+            Style::提示 => (body.clone(), visuals.weak_text_color()),
+            Style::警告 => (body.clone(), palette.negative),
+            Style::喜报 => (body.clone(), palette.highlight),
+        };
+        job.append(&theme::strip_emoji(&segment.text, show_emoji), 0.0, TextFormat::simple(font_id, color));
+    }
+    job.wrap.max_width = ui.available_width();
+    ui.label(job)
+}
+
+/// 结局总结卡片：结局标题与文案、关键数字、主要倾向和里程碑，排成一张方便截图的卡片
+pub fn ending_card(ui: &mut egui::Ui, card: &EndingCard, palette: &Palette) -> egui::Response {
+    egui::Frame::group(ui.style())
+        .fill(palette.background)
+        .stroke(Stroke::new(2.0, palette.highlight))
+        .inner_margin(16.0)
+        .rounding(8.0)
+        .show(ui, |ui| {
+            ui.set_max_width(420.0);
+            ui.vertical_centered(|ui| {
+                ui.label(RichText::new(card.ending.icon()).size(40.0));
+                ui.label(RichText::new(card.ending.title()).heading().strong().color(palette.highlight));
+                ui.weak(format!("{} · 第{}天 · {}", card.name, card.days, card.realm));
+            });
+            ui.add_space(6.0);
+            ui.label(RichText::new(&card.description).color(palette.text));
+            ui.add_space(6.0);
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(format!("技能点 {}", card.skills)).color(palette.skill));
+                ui.label(RichText::new(format!("压力 {}", card.pressure)).color(palette.pressure));
+                ui.label(RichText::new(format!("存款 {} 元", card.savings)).color(palette.text));
+            });
+            if let Some((archetype, share)) = card.persona {
+                ui.label(format!("主要倾向：{}（{:.0}%）", archetype.persona(), share * 100.0));
+            }
+            ui.add(
+                egui::ProgressBar::new(card.completion())
+                    .text(format!("里程碑完成度 {:.0}%", card.completion() * 100.0)),
+            );
+            ui.horizontal_wrapped(|ui| {
+                for (milestone, reached) in &card.milestones {
+                    let text = RichText::new(format!("{} {}", if *reached { "✔" } else { "✖" }, milestone.label())).small();
+                    ui.label(if *reached { text.color(palette.positive) } else { text.weak() });
+                }
+            });
+        })
+        .response
+}
+
+/// 简易 Markdown 渲染：支持标题、列表、行内代码（用于更新日志）
+pub fn markdown(ui: &mut egui::Ui, text: &str) {
+    for line in text.lines() {
+        let line = line.trim_end();
+        if let Some(title) = line.strip_prefix("# ") {
+            ui.heading(title);
+        } else if let Some(title) = line.strip_prefix("## ") {
+            ui.add_space(6.0);
+            ui.label(RichText::new(title).strong().size(18.0));
+        } else if let Some(title) = line.strip_prefix("### ") {
+            ui.add_space(4.0);
+            ui.strong(title);
+        } else if let Some(item) = line.strip_prefix("- ") {
+            ui.horizontal_wrapped(|ui| {
+                ui.label("•");
+                inline_code(ui, item);
+            });
+        } else if line.is_empty() {
+            ui.add_space(4.0);
+        } else {
+            ui.horizontal_wrapped(|ui| inline_code(ui, line));
+        }
+    }
+}
+
+/// 按反引号切分，奇数段以代码样式显示
+fn inline_code(ui: &mut egui::Ui, text: &str) {
+    ui.spacing_mut().item_spacing.x = 0.0;
+    for (i, part) in text.split('`').enumerate() {
+        if part.is_empty() {
+            continue;
+        }
+        if i % 2 == 1 {
+            ui.code(part);
+        } else {
+            ui.label(part);
+        }
+    }
+}